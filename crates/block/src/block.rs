@@ -4,6 +4,7 @@
 use std::fmt::{self, Debug};
 
 use bulldag::vertex::Vertex;
+use primitives::Round;
 use reward::reward::Reward;
 #[cfg(mainnet)]
 use reward::reward::GENESIS_REWARD;
@@ -11,7 +12,14 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(mainnet)]
 use crate::genesis;
-use crate::{header::BlockHeader, ConvergenceBlock, GenesisBlock, ProposalBlock};
+use crate::{
+    header::BlockHeader,
+    BlockHash,
+    Certificate,
+    ConvergenceBlock,
+    GenesisBlock,
+    ProposalBlock,
+};
 
 pub trait InnerBlock: std::fmt::Debug + Send {
     type Header;
@@ -48,6 +56,51 @@ impl Block {
         matches!(self, Block::Genesis { .. })
     }
 
+    pub fn hash(&self) -> BlockHash {
+        match self {
+            Block::Convergence { block } => block.hash.clone(),
+            Block::Proposal { block } => block.hash.clone(),
+            Block::Genesis { block } => block.hash.clone(),
+        }
+    }
+
+    /// Checks that the hash of the block matches its contents.
+    pub fn verify_hash(&self) -> bool {
+        match self {
+            Block::Convergence { block } => block.verify_hash(),
+            Block::Proposal { block } => block.hash == block.compute_hash(),
+            Block::Genesis { block } => block.verify_hash(),
+        }
+    }
+
+    pub fn round(&self) -> Round {
+        match self {
+            Block::Convergence { block } => block.header.round,
+            Block::Proposal { block } => block.round,
+            Block::Genesis { block } => block.header.round,
+        }
+    }
+
+    /// Returns the hashes of the blocks that have to be part of the DAG before
+    /// this block can be appended to it. Genesis blocks have none.
+    pub fn parent_hashes(&self) -> Vec<BlockHash> {
+        match self {
+            Block::Convergence { block } => block.get_ref_hashes(),
+            Block::Proposal { block } => vec![block.ref_block.clone()],
+            Block::Genesis { .. } => vec![],
+        }
+    }
+
+    /// Returns the certificate of a convergence or genesis block, if it has
+    /// one.
+    pub fn certificate(&self) -> Option<&Certificate> {
+        match self {
+            Block::Convergence { block } => block.certificate.as_ref(),
+            Block::Proposal { .. } => None,
+            Block::Genesis { block } => block.certificate.as_ref(),
+        }
+    }
+
     pub fn size(&self) -> usize {
         match self {
             Block::Convergence { block } => block
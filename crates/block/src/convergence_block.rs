@@ -4,6 +4,7 @@ use reward::reward::Reward;
 use reward::reward::GENESIS_REWARD;
 use ritelinked::{LinkedHashMap, LinkedHashSet};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use vrrb_core::{
     claim::Claim,
     txn::{TransactionDigest, Txn},
@@ -18,6 +19,8 @@ use crate::{
     Certificate,
     ConsolidatedClaims,
     ConsolidatedTxns,
+    EvidenceList,
+    StakeList,
 };
 
 pub struct MineArgs<'a> {
@@ -45,6 +48,12 @@ pub struct ConvergenceBlock {
     pub claims: ConsolidatedClaims,
     pub hash: BlockHash,
     pub certificate: Option<Certificate>,
+    /// Certified misbehaviour whose slashes apply once the block is committed
+    #[serde(default)]
+    pub evidence: EvidenceList,
+    /// Certified stakes that apply once the block is committed
+    #[serde(default)]
+    pub stakes: StakeList,
 }
 
 impl ConvergenceBlock {
@@ -55,4 +64,36 @@ impl ConvergenceBlock {
     pub fn txn_id_set(&self) -> LinkedHashSet<&TransactionDigest> {
         self.txns.iter().flat_map(|(_, set)| set).collect()
     }
+
+    /// Checks that the hash of the block was computed from its header, and
+    /// that the header commits to the transactions and claims of the block.
+    pub fn verify_hash(&self) -> bool {
+        self.hash == self.header.hash()
+            && self.header.txn_hash == txns_hash(&self.txns)
+            && self.header.claim_list_hash == claims_hash(&self.claims)
+    }
+}
+
+/// Hashes and returns a hexadecimal string representation of the hash of
+/// the consolidated `Txn`s of a `ConvergenceBlock`
+pub fn txns_hash(txns: &ConsolidatedTxns) -> String {
+    let mut txn_hasher = Sha256::new();
+
+    if let Ok(serialized_txns) = serde_json::to_string(txns) {
+        txn_hasher.update(serialized_txns.as_bytes());
+    }
+
+    format!("{:x}", txn_hasher.finalize())
+}
+
+/// Hashes and returns a hexadecimal string representation of the hash of
+/// the consolidated `Claim`s of a `ConvergenceBlock`
+pub fn claims_hash(claims: &ConsolidatedClaims) -> String {
+    let mut claim_hasher = Sha256::new();
+
+    if let Ok(serialized_claims) = serde_json::to_string(claims) {
+        claim_hasher.update(serialized_claims.as_bytes());
+    }
+
+    format!("{:x}", claim_hasher.finalize())
 }
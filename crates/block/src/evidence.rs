@@ -0,0 +1,198 @@
+use hbbft::crypto::{PublicKeySet, Signature, SignatureShare, SIG_SIZE};
+use primitives::{Address, NodeId, NodeIdx, RawSignature};
+use serde::{Deserialize, Serialize};
+use utils::hash_data;
+use vrrb_core::{
+    keypair::{MinerPk, MinerSk},
+    staking::{Stake, StakeUpdate},
+};
+
+use crate::{header::BlockHeader, Certificate, ProposalBlock, QuorumPubkey};
+
+/// Percentage of its stake a claim loses for proposing two different blocks
+/// for the same round.
+pub const PROPOSAL_EQUIVOCATION_SLASH_PERCENTAGE: u8 = 10;
+
+/// Percentage of its stake a claim loses for signing two different
+/// convergence blocks for the same round, as it puts finality at risk.
+pub const BLOCK_SIGNATURE_EQUIVOCATION_SLASH_PERCENTAGE: u8 = 20;
+
+pub type EvidenceHash = String;
+pub type EvidenceList = Vec<Evidence>;
+
+/// Partial signature of a harvester over the hash of a convergence block and
+/// the state root it was signed on.
+#[derive(Clone, Debug, Serialize, Deserialize, Hash, Eq, PartialEq)]
+pub struct SignedBlockHeader {
+    pub header: BlockHeader,
+    #[serde(default)]
+    pub root_hash: String,
+    pub signature: RawSignature,
+}
+
+/// Proof that a node misbehaved while taking part in consensus.
+#[derive(Clone, Debug, Serialize, Deserialize, Hash, Eq, PartialEq)]
+pub enum Misbehaviour {
+    /// The same claim signed two different proposal blocks for the same
+    /// round.
+    ProposalEquivocation {
+        first: Box<ProposalBlock>,
+        second: Box<ProposalBlock>,
+    },
+    /// The same harvester partially signed two different convergence blocks
+    /// for the same round. Signature shares are deterministic, so conflicting
+    /// shares can only be told apart by the blocks they were produced for.
+    BlockSignatureEquivocation {
+        signer_id: NodeId,
+        signer_idx: NodeIdx,
+        first: Box<SignedBlockHeader>,
+        second: Box<SignedBlockHeader>,
+    },
+}
+
+impl Misbehaviour {
+    pub fn round(&self) -> u128 {
+        match self {
+            Misbehaviour::ProposalEquivocation { first, .. } => first.round,
+            Misbehaviour::BlockSignatureEquivocation { first, .. } => first.header.round,
+        }
+    }
+
+    /// Returns the percentage of the offender's stake to slash.
+    pub fn slash_percentage(&self) -> u8 {
+        match self {
+            Misbehaviour::ProposalEquivocation { .. } => PROPOSAL_EQUIVOCATION_SLASH_PERCENTAGE,
+            Misbehaviour::BlockSignatureEquivocation { .. } => {
+                BLOCK_SIGNATURE_EQUIVOCATION_SLASH_PERCENTAGE
+            },
+        }
+    }
+
+    /// Checks that the evidence proves the misbehaviour. Block signatures are
+    /// checked against the key set of the harvester quorum that produced
+    /// them, which proposal equivocations do not need.
+    pub fn verify(&self, harvester_public_key_set: &PublicKeySet) -> bool {
+        match self {
+            Misbehaviour::ProposalEquivocation { first, second } => {
+                first.round == second.round
+                    && first.from.public_key == second.from.public_key
+                    && first.payload() != second.payload()
+                    && first.verify_signature()
+                    && second.verify_signature()
+            },
+            Misbehaviour::BlockSignatureEquivocation {
+                signer_idx,
+                first,
+                second,
+                ..
+            } => {
+                let public_key_share =
+                    harvester_public_key_set.public_key_share(*signer_idx as usize);
+
+                let is_valid = |signed_header: &SignedBlockHeader| {
+                    let signature_share =
+                        TryInto::<[u8; SIG_SIZE]>::try_into(signed_header.signature.as_slice())
+                            .ok()
+                            .and_then(|bytes| SignatureShare::from_bytes(bytes).ok());
+
+                    let payload = Certificate::payload(
+                        &signed_header.header.hash(),
+                        &signed_header.root_hash,
+                    );
+
+                    match signature_share {
+                        Some(signature_share) => public_key_share.verify(&signature_share, payload),
+                        None => false,
+                    }
+                };
+
+                first.header.round == second.header.round
+                    && first.header.hash() != second.header.hash()
+                    && is_valid(first)
+                    && is_valid(second)
+            },
+        }
+    }
+}
+
+/// Misbehaviour certified by a harvester quorum, along with the slash it
+/// warrants against the offender's claim.
+#[derive(Clone, Debug, Serialize, Deserialize, Hash, Eq, PartialEq)]
+pub struct Evidence {
+    pub misbehaviour: Misbehaviour,
+    /// Address of the claim to slash
+    pub offender: Address,
+    pub quorum_pubkey: QuorumPubkey,
+    pub signature: String,
+    pub slash: Stake,
+}
+
+impl Evidence {
+    /// Returns the payload signed by the quorum certifying the evidence.
+    pub fn payload(misbehaviour: &Misbehaviour, offender: &Address) -> Vec<u8> {
+        hash_data!(misbehaviour, offender).to_vec()
+    }
+
+    /// Returns the hash identifying the given misbehaviour of the given
+    /// offender.
+    pub fn hash_of(misbehaviour: &Misbehaviour, offender: &Address) -> EvidenceHash {
+        hex::encode(Self::payload(misbehaviour, offender))
+    }
+
+    /// Wraps misbehaviour certified by a quorum along with a slash of the
+    /// offender's claim, signed with the given keys and backed by the
+    /// quorum's signature.
+    pub fn new(
+        misbehaviour: Misbehaviour,
+        offender: Address,
+        quorum_pubkey: QuorumPubkey,
+        signature: RawSignature,
+        secret_key: MinerSk,
+        public_key: MinerPk,
+    ) -> Option<Self> {
+        let amount = StakeUpdate::Slash(misbehaviour.slash_percentage());
+        let mut slash = Stake::new(amount, secret_key, public_key, offender.clone(), None)?;
+
+        slash
+            .certify((signature.clone(), Self::payload(&misbehaviour, &offender)))
+            .ok()?;
+
+        Some(Self {
+            misbehaviour,
+            offender,
+            quorum_pubkey,
+            signature: hex::encode(signature),
+            slash,
+        })
+    }
+
+    pub fn hash(&self) -> EvidenceHash {
+        Self::hash_of(&self.misbehaviour, &self.offender)
+    }
+
+    /// Checks that the evidence was certified by the harvester quorum with the
+    /// given key set, that the misbehaviour it certifies is proven, and that
+    /// its slash is the one the misbehaviour warrants.
+    pub fn verify(&self, harvester_public_key_set: &PublicKeySet) -> bool {
+        let payload = Self::payload(&self.misbehaviour, &self.offender);
+        let public_key = harvester_public_key_set.public_key();
+
+        if self.quorum_pubkey != hex::encode(public_key.to_bytes()) {
+            return false;
+        }
+
+        let raw_signature = hex::decode(&self.signature).unwrap_or_default();
+        let is_certified = TryInto::<[u8; SIG_SIZE]>::try_into(raw_signature.as_slice())
+            .ok()
+            .and_then(|bytes| Signature::from_bytes(bytes).ok())
+            .map_or(false, |signature| public_key.verify(&signature, &payload));
+
+        is_certified
+            && self.misbehaviour.verify(harvester_public_key_set)
+            && self.slash.verify().is_ok()
+            && self.slash.get_amount() == StakeUpdate::Slash(self.misbehaviour.slash_percentage())
+            && self.slash.get_sender() == self.offender
+            && self.slash.get_receiver() == self.offender
+            && self.slash.get_certificate() == Some((raw_signature, payload))
+    }
+}
@@ -15,3 +15,10 @@ pub struct GenesisBlock {
     pub hash: BlockHash,
     pub certificate: Option<Certificate>,
 }
+
+impl GenesisBlock {
+    /// Checks that the hash of the block was computed from its header.
+    pub fn verify_hash(&self) -> bool {
+        self.hash == self.header.hash()
+    }
+}
@@ -11,7 +11,7 @@ use utils::{create_payload, hash_data};
 use vrrb_core::claim::Claim;
 use vrrb_vrf::{vrng::VRNG, vvrf::VVRF};
 
-use crate::{block::Block, InnerBlock, NextEpochAdjustment};
+use crate::{block::Block, BlockHash, InnerBlock, NextEpochAdjustment, EPOCH_BLOCK};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Hash, PartialEq, Eq)]
 pub struct BlockHeader {
@@ -191,6 +191,31 @@ impl BlockHeader {
         Some(block_header)
     }
 
+    /// Returns whether this is the header of the last block of its epoch.
+    pub fn is_epoch_final_block(&self) -> bool {
+        (self.block_height + 1) % EPOCH_BLOCK as u128 == 0
+    }
+
+    /// Returns the hash of the block this header belongs to.
+    pub fn hash(&self) -> BlockHash {
+        let block_hash = hash_data!(
+            self.ref_hashes,
+            self.round,
+            self.block_seed,
+            self.next_block_seed,
+            self.block_height,
+            self.timestamp,
+            self.txn_hash,
+            self.miner_claim,
+            self.claim_list_hash,
+            self.block_reward,
+            self.next_block_reward,
+            self.miner_signature
+        );
+
+        format!("{block_hash:x}")
+    }
+
     pub fn get_payload(&self) -> Message {
         create_payload!(
             self.ref_hashes,
@@ -1,5 +1,6 @@
 pub mod block;
 pub mod convergence_block;
+pub mod evidence;
 pub mod genesis;
 pub mod header;
 pub mod invalid;
@@ -11,6 +12,7 @@ mod types;
 pub use crate::{
     block::*,
     convergence_block::*,
+    evidence::*,
     genesis::*,
     proposal_block::*,
     types::*,
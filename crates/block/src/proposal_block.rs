@@ -103,6 +103,42 @@ impl ProposalBlock {
         self.round == round
     }
 
+    /// Returns the payload signed by the harvester that proposed the block.
+    pub fn payload(&self) -> Vec<u8> {
+        let hashable_txns = self.get_hashable_txns();
+        hash_data!(
+            self.round,
+            self.epoch,
+            hashable_txns,
+            self.claims,
+            self.from
+        )
+        .to_vec()
+    }
+
+    /// Recomputes the hash of the block from its contents.
+    pub fn compute_hash(&self) -> BlockHash {
+        let hashable_txns = self.get_hashable_txns();
+        hex::encode(hash_data!(
+            self.round,
+            self.epoch,
+            hashable_txns,
+            self.claims,
+            self.from,
+            self.signature
+        ))
+    }
+
+    /// Checks that the block was signed by the claim it was proposed from.
+    pub fn verify_signature(&self) -> bool {
+        Keypair::verify_ecdsa_sign(
+            self.signature.clone(),
+            &self.payload(),
+            self.from.public_key.serialize().to_vec(),
+        )
+        .is_ok()
+    }
+
     pub fn decode_signature_share(&self) -> Result<[u8; 96], FromHexError> {
         let byte_vec = hex::decode(&self.signature)?;
 
@@ -7,8 +7,9 @@ use std::{
     hash::{Hash, Hasher},
 };
 
+use hbbft::crypto::{PublicKey, PublicKeySet, Signature, PK_SIZE, SIG_SIZE};
 use hex::FromHexError;
-use primitives::RawSignature;
+use primitives::{Epoch, RawSignature};
 #[cfg(mainnet)]
 use reward::reward::GENESIS_REWARD;
 use ritelinked::{LinkedHashMap, LinkedHashSet};
@@ -16,6 +17,7 @@ use serde::{Deserialize, Serialize};
 use tokio::task::JoinHandle;
 use vrrb_core::{
     claim::Claim,
+    staking::Stake,
     txn::{QuorumCertifiedTxn, TransactionDigest, Txn},
 };
 
@@ -39,7 +41,9 @@ pub type BlockHash = String;
 pub type QuorumId = String;
 pub type QuorumPubkey = String;
 pub type QuorumPubkeys = LinkedHashMap<QuorumId, QuorumPubkey>;
+pub type QuorumHandOffs = LinkedHashMap<QuorumId, QuorumHandOff>;
 pub type ConflictList = HashMap<TransactionDigest, Conflict>;
+pub type StakeList = Vec<Stake>;
 pub type ResolvedConflicts = Vec<JoinHandle<Result<Conflict, Box<dyn Error>>>>;
 
 #[derive(Clone, Debug, Serialize, Deserialize, Hash, Eq, PartialEq)]
@@ -47,11 +51,28 @@ pub type ResolvedConflicts = Vec<JoinHandle<Result<Conflict, Box<dyn Error>>>>;
 pub struct Certificate {
     pub signature: String,
     pub inauguration: Option<QuorumPubkeys>,
+    /// Proofs that the outgoing quorums approved the keys in `inauguration`
+    pub hand_offs: Option<QuorumHandOffs>,
+    /// Hex encoded root of the state the block was certified on, i.e. the
+    /// state committed by the block before it
     pub root_hash: String,
     pub next_root_hash: String,
     pub block_hash: String,
 }
 
+/// Proof that an outgoing quorum handed its duties over to the quorum
+/// replacing it, in the form of a threshold signature of the outgoing quorum
+/// over the incoming quorum's group public key.
+#[derive(Clone, Debug, Serialize, Deserialize, Hash, Eq, PartialEq)]
+pub struct QuorumHandOff {
+    pub quorum_id: QuorumId,
+    /// Epoch the incoming quorum takes over at
+    pub epoch: Epoch,
+    pub outgoing_pubkey: QuorumPubkey,
+    pub incoming_pubkey: QuorumPubkey,
+    pub signature: String,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 #[repr(C)]
 pub struct Conflict {
@@ -93,4 +114,87 @@ impl Certificate {
         let signature = hex::decode(self.signature.clone())?;
         Ok(signature)
     }
+
+    /// Returns the payload signed by the harvester quorum, which binds the
+    /// hash of a block to the root of the state it was certified on.
+    pub fn payload(block_hash: &str, root_hash: &str) -> Vec<u8> {
+        format!("{block_hash}:{root_hash}").into_bytes()
+    }
+
+    /// Checks that the certificate is a threshold signature of the given
+    /// harvester quorum over the hash of the block it certifies and the
+    /// state root it carries.
+    pub fn verify(&self, harvester_public_key_set: &PublicKeySet) -> bool {
+        self.verify_with_pubkey(&harvester_public_key_set.public_key())
+    }
+
+    /// Checks that the certificate is a threshold signature over the hash of
+    /// the block it certifies and the state root it carries, by the
+    /// harvester quorum with the given group public key.
+    pub fn verify_with_pubkey(&self, harvester_public_key: &PublicKey) -> bool {
+        let signature = hex::decode(&self.signature)
+            .ok()
+            .and_then(|bytes| TryInto::<[u8; SIG_SIZE]>::try_into(bytes).ok())
+            .and_then(|bytes| Signature::from_bytes(bytes).ok());
+
+        match signature {
+            Some(signature) => harvester_public_key
+                .verify(&signature, Self::payload(&self.block_hash, &self.root_hash)),
+            None => false,
+        }
+    }
+
+    /// Records the given hand-offs, inaugurating the group public keys of
+    /// the incoming quorums.
+    pub fn inaugurate(&mut self, hand_offs: QuorumHandOffs) {
+        let inauguration = hand_offs
+            .iter()
+            .map(|(quorum_id, hand_off)| (quorum_id.clone(), hand_off.incoming_pubkey.clone()))
+            .collect();
+
+        self.inauguration = Some(inauguration);
+        self.hand_offs = Some(hand_offs);
+    }
+}
+
+impl QuorumHandOff {
+    /// Returns the payload signed by the outgoing quorum.
+    pub fn payload(
+        quorum_id: &QuorumId,
+        epoch: Epoch,
+        outgoing_pubkey: &QuorumPubkey,
+        incoming_pubkey: &QuorumPubkey,
+    ) -> Vec<u8> {
+        format!("{quorum_id}:{epoch}:{outgoing_pubkey}:{incoming_pubkey}").into_bytes()
+    }
+
+    /// Checks that the hand-off was signed by the outgoing quorum.
+    pub fn verify(&self) -> bool {
+        let payload = Self::payload(
+            &self.quorum_id,
+            self.epoch,
+            &self.outgoing_pubkey,
+            &self.incoming_pubkey,
+        );
+
+        let public_key = decode_quorum_pubkey(&self.outgoing_pubkey);
+
+        let signature = hex::decode(&self.signature)
+            .ok()
+            .and_then(|bytes| TryInto::<[u8; SIG_SIZE]>::try_into(bytes).ok())
+            .and_then(|bytes| Signature::from_bytes(bytes).ok());
+
+        match (public_key, signature) {
+            (Some(public_key), Some(signature)) => public_key.verify(&signature, payload),
+            _ => false,
+        }
+    }
+}
+
+/// Decodes the hex encoded group public key of a quorum.
+pub fn decode_quorum_pubkey(quorum_pubkey: &QuorumPubkey) -> Option<PublicKey> {
+    hex::decode(quorum_pubkey)
+        .ok()
+        .and_then(|bytes| TryInto::<[u8; PK_SIZE]>::try_into(bytes).ok())
+        .and_then(|bytes| PublicKey::from_bytes(bytes).ok())
 }
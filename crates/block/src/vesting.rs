@@ -1,8 +1,16 @@
+use std::path::Path;
+
+use primitives::Address;
 use ritelinked::LinkedHashMap;
-use vrrb_core::txn::Txn;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use vrrb_core::{
+    account::{Account, AccountField},
+    vesting::{VestingSchedule, SECONDS_PER_YEAR},
+};
 
 // 50% after one year, then monthly for 12 months
-const EMPLOYEE_VESTING: VestingConfig = VestingConfig {
+pub const EMPLOYEE_VESTING: VestingConfig = VestingConfig {
     cliff_fraction: 0.5f64,
     cliff_years: 1f64,
     unlocks: 12,
@@ -10,23 +18,26 @@ const EMPLOYEE_VESTING: VestingConfig = VestingConfig {
 };
 
 // 25% after half year, then monthly for 18  months
-const INVESTOR_VESTING: VestingConfig = VestingConfig {
+pub const INVESTOR_VESTING: VestingConfig = VestingConfig {
     cliff_fraction: 0.25f64,
     cliff_years: 0.75f64,
     unlocks: 18,
     unlock_years: 1.5f64,
 };
 
-const EMPLOYEESS: [&str; 2] = [
-    "6pME5t7fLGubJjcn4L4ncN7DZs7tWPHU4FoYV4Cv3vGa",
-    "3ki3QpPM2cGE3X5MZm6L4NcMdrp7R9vVeE2PEE427uqa",
-];
+#[derive(Debug, Error)]
+pub enum VestingError {
+    #[error("unable to read genesis file: {0}")]
+    Io(#[from] std::io::Error),
 
-const INVESTORS: [&str; 2] = [
-    "C5dz418Wf5cKeGKCUBN7AUTRcGK9wcRknEfVbjSyAMZm",
-    "5TAgthC5PLYBP3JSvjjfnd1jkY1VLrC78p4T4MucHFE",
-];
+    #[error("invalid genesis file: {0}")]
+    InvalidGenesisFile(#[from] serde_json::Error),
 
+    #[error("invalid allocation of {0}: {1}")]
+    InvalidAllocation(Address, String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VestingConfig {
     pub cliff_fraction: f64,
     pub cliff_years: f64,
@@ -34,22 +45,170 @@ pub struct VestingConfig {
     pub unlock_years: f64,
 }
 
-#[allow(clippy::diverging_sub_expression)]
-pub fn create_vesting(_target: &str, _config: VestingConfig) -> (String, Txn) {
-    todo!()
+impl VestingConfig {
+    /// Produces the schedule along which `amount`, granted at the given
+    /// time, unlocks.
+    pub fn schedule(&self, amount: u128, start_timestamp: i64) -> VestingSchedule {
+        let cliff_seconds = self.cliff_years * SECONDS_PER_YEAR as f64;
+        let unlock_seconds = self.unlock_years * SECONDS_PER_YEAR as f64;
+        let cliff_amount = (amount as f64 * self.cliff_fraction) as u128;
+
+        VestingSchedule {
+            amount,
+            cliff_timestamp: start_timestamp + cliff_seconds as i64,
+            cliff_amount: cliff_amount.min(amount),
+            unlocks: self.unlocks as u32,
+            unlock_interval: (unlock_seconds / self.unlocks.max(1) as f64) as i64,
+        }
+    }
+}
+
+/// How the funds of a genesis allocation unlock.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VestingPlan {
+    Employee,
+    Investor,
+    Custom(VestingConfig),
 }
 
-pub fn generate_genesis_txns() -> LinkedHashMap<String, Txn> {
-    let mut genesis_txns: LinkedHashMap<String, Txn> = LinkedHashMap::new();
-    for employee in EMPLOYEESS {
-        let vesting_txn = create_vesting(employee, EMPLOYEE_VESTING);
-        genesis_txns.insert(vesting_txn.0, vesting_txn.1);
+impl VestingPlan {
+    pub fn config(&self) -> &VestingConfig {
+        match self {
+            VestingPlan::Employee => &EMPLOYEE_VESTING,
+            VestingPlan::Investor => &INVESTOR_VESTING,
+            VestingPlan::Custom(config) => config,
+        }
     }
+}
+
+/// Funds granted to an address at genesis. They are spendable right away
+/// unless a vesting plan is given.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenesisAllocation {
+    pub address: Address,
+    pub amount: u128,
+    #[serde(default)]
+    pub vesting: Option<VestingPlan>,
+}
+
+/// The allocation table of a network's genesis, as read from its genesis
+/// file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GenesisAllocations {
+    /// Unix timestamp, in seconds, vesting schedules start at
+    pub genesis_timestamp: i64,
+    pub allocations: Vec<GenesisAllocation>,
+}
 
-    for investor in INVESTORS {
-        let vesting_txn = create_vesting(investor, INVESTOR_VESTING);
-        genesis_txns.insert(vesting_txn.0, vesting_txn.1);
+impl GenesisAllocations {
+    /// Reads the allocation table from the JSON genesis file at `path`.
+    pub fn from_file(path: &Path) -> Result<Self, VestingError> {
+        let contents = std::fs::read_to_string(path)?;
+
+        Ok(serde_json::from_str(&contents)?)
     }
+}
 
-    genesis_txns
+/// Creates the genesis account of the given allocation, whose funds are
+/// locked along the allocation's vesting plan, starting at the given time.
+pub fn create_vesting(
+    allocation: &GenesisAllocation,
+    genesis_timestamp: i64,
+) -> Result<Account, VestingError> {
+    let mut account = Account::new(allocation.address.public_key());
+
+    account
+        .update_field(AccountField::Credits(allocation.amount))
+        .map_err(|err| {
+            VestingError::InvalidAllocation(allocation.address.clone(), err.to_string())
+        })?;
+
+    let vesting = allocation
+        .vesting
+        .as_ref()
+        .map(|plan| plan.config().schedule(allocation.amount, genesis_timestamp));
+
+    account.set_vesting(vesting);
+
+    Ok(account)
+}
+
+/// Creates the genesis account of every allocation. Every address can only be
+/// allocated funds once.
+pub fn generate_genesis_accounts(
+    genesis: &GenesisAllocations,
+) -> Result<LinkedHashMap<Address, Account>, VestingError> {
+    let mut genesis_accounts = LinkedHashMap::new();
+
+    for allocation in genesis.allocations.iter() {
+        if genesis_accounts.contains_key(&allocation.address) {
+            return Err(VestingError::InvalidAllocation(
+                allocation.address.clone(),
+                "address is allocated more than once".to_string(),
+            ));
+        }
+
+        let account = create_vesting(allocation, genesis.genesis_timestamp)?;
+        genesis_accounts.insert(allocation.address.clone(), account);
+    }
+
+    Ok(genesis_accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use vrrb_core::keypair::Keypair;
+
+    use super::*;
+
+    fn random_address() -> Address {
+        Address::new(Keypair::random().miner_kp.1)
+    }
+
+    #[test]
+    fn genesis_allocations_are_locked_along_their_vesting_plan() {
+        let employee = random_address();
+        let investor = random_address();
+        let faucet = random_address();
+
+        let genesis_file = serde_json::json!({
+            "genesis_timestamp": 0,
+            "allocations": [
+                { "address": employee, "amount": 1_200, "vesting": "employee" },
+                { "address": investor, "amount": 1_800, "vesting": "investor" },
+                { "address": faucet, "amount": 500 },
+            ]
+        });
+
+        let path = std::env::temp_dir().join(format!("genesis-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(&path, genesis_file.to_string()).unwrap();
+
+        let genesis = GenesisAllocations::from_file(&path).unwrap();
+        let accounts = generate_genesis_accounts(&genesis).unwrap();
+
+        let employee_account = accounts.get(&employee).unwrap();
+        assert_eq!(employee_account.credits(), 1_200);
+        assert_eq!(employee_account.spendable_balance(0), 0);
+        assert_eq!(employee_account.spendable_balance(SECONDS_PER_YEAR), 600);
+        assert_eq!(
+            employee_account.spendable_balance(2 * SECONDS_PER_YEAR),
+            1_200
+        );
+
+        let investor_account = accounts.get(&investor).unwrap();
+        assert_eq!(investor_account.locked_balance(0), 1_800);
+        assert_eq!(
+            investor_account.locked_balance(SECONDS_PER_YEAR * 3 / 4),
+            1_350
+        );
+
+        assert_eq!(accounts.get(&faucet).unwrap().spendable_balance(0), 500);
+
+        let mut duplicated = genesis;
+        duplicated
+            .allocations
+            .push(duplicated.allocations[0].clone());
+        assert!(generate_genesis_accounts(&duplicated).is_err());
+    }
 }
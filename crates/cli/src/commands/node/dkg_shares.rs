@@ -0,0 +1,101 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use dkg_engine::share_store::{DkgShareStore, QuorumId, StoredDkgShares};
+use primitives::{Epoch, NodeId, DEFAULT_VRRB_DATA_DIR_PATH};
+use serde::Serialize;
+use vrrb_config::ThresholdConfig;
+
+use crate::result::{CliError, Result};
+
+#[derive(Debug, Subcommand)]
+pub enum DkgSharesCmd {
+    /// Prints the DKG shares stored by the node. Secret key shares are never
+    /// printed
+    Inspect {
+        /// Only print the shares of the given quorum
+        #[clap(long, value_parser)]
+        quorum_id: Option<QuorumId>,
+    },
+
+    /// Deletes the DKG shares stored by the node, forcing a new DKG run on
+    /// its next start
+    Wipe {
+        /// Only delete the shares of the given quorum
+        #[clap(long, value_parser)]
+        quorum_id: Option<QuorumId>,
+    },
+}
+
+#[derive(Parser, Debug)]
+pub struct DkgSharesOpts {
+    /// Data directory of the node the shares belong to
+    #[clap(long, value_parser, default_value = DEFAULT_VRRB_DATA_DIR_PATH)]
+    pub data_dir: PathBuf,
+
+    #[clap(subcommand)]
+    pub subcommand: DkgSharesCmd,
+}
+
+#[derive(Debug, Serialize)]
+struct DkgSharesInfo {
+    quorum_id: QuorumId,
+    epoch: Epoch,
+    members: Vec<NodeId>,
+    threshold_config: ThresholdConfig,
+    group_public_key: String,
+}
+
+impl From<StoredDkgShares> for DkgSharesInfo {
+    fn from(shares: StoredDkgShares) -> Self {
+        Self {
+            quorum_id: shares.tag.quorum_id,
+            epoch: shares.tag.epoch,
+            members: shares.tag.members.into_iter().collect(),
+            threshold_config: shares.threshold_config,
+            group_public_key: hex::encode(shares.public_key_set.public_key().to_bytes()),
+        }
+    }
+}
+
+pub async fn exec_dkg_shares(opts: DkgSharesOpts) -> Result<()> {
+    let store = DkgShareStore::new(&opts.data_dir);
+
+    match opts.subcommand {
+        DkgSharesCmd::Inspect { quorum_id } => {
+            let entries = match quorum_id {
+                Some(quorum_id) => store
+                    .load(&quorum_id)
+                    .map_err(|err| CliError::Other(err.to_string()))?
+                    .into_iter()
+                    .collect(),
+                None => store
+                    .entries()
+                    .map_err(|err| CliError::Other(err.to_string()))?,
+            };
+
+            let entries: Vec<DkgSharesInfo> =
+                entries.into_iter().map(DkgSharesInfo::from).collect();
+
+            let entries = serde_json::to_string_pretty(&entries)
+                .map_err(|err| CliError::Other(format!("unable to serialize DKG shares: {err}")))?;
+
+            println!("{entries}");
+        },
+        DkgSharesCmd::Wipe { quorum_id } => {
+            let removed = match quorum_id {
+                Some(quorum_id) => store
+                    .remove(&quorum_id)
+                    .map(usize::from)
+                    .map_err(|err| CliError::Other(err.to_string()))?,
+                None => store
+                    .clear()
+                    .map_err(|err| CliError::Other(err.to_string()))?,
+            };
+
+            println!("Deleted the DKG shares of {removed} quorum(s)");
+        },
+    }
+
+    Ok(())
+}
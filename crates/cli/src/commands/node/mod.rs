@@ -1,7 +1,9 @@
+mod dkg_shares;
 mod info;
 mod run;
 
 use clap::{Parser, Subcommand};
+pub use dkg_shares::*;
 pub use info::*;
 pub use run::*;
 
@@ -17,6 +19,9 @@ pub enum NodeCmd {
 
     /// Stops any node currrently running in dettached mode
     Stop,
+
+    /// Inspect or wipe the DKG key shares persisted by a node
+    DkgShares(DkgSharesOpts),
 }
 
 #[derive(Parser, Debug)]
@@ -31,6 +36,7 @@ pub async fn exec(args: NodeOpts) -> Result<()> {
     match sub_cmd {
         NodeCmd::Run(opts) => run(*opts).await,
         NodeCmd::Info => Ok(()),
+        NodeCmd::DkgShares(opts) => exec_dkg_shares(opts).await,
         _ => Err(CliError::InvalidCommand(format!("{sub_cmd:?}"))),
     }
 }
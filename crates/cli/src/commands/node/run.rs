@@ -141,6 +141,9 @@ impl From<RunOpts> for NodeConfig {
             bootstrap_quorum_config: default_node_config.bootstrap_quorum_config,
             quorum_config: default_node_config.quorum_config,
             enable_block_indexing: default_node_config.enable_block_indexing,
+            unbonding_epochs: default_node_config.unbonding_epochs,
+            genesis_file: default_node_config.genesis_file,
+            sync_mode: default_node_config.sync_mode,
             threshold_config: default_node_config.threshold_config,
         }
     }
@@ -308,7 +311,8 @@ async fn run_blocking(node_config: NodeConfig) -> Result<()> {
         vrrbdb_config.with_path(node_config.db_path().to_path_buf());
     }
 
-    let database = storage::vrrbdb::VrrbDb::new(vrrbdb_config);
+    let database = storage::vrrbdb::VrrbDb::new(vrrbdb_config)
+        .map_err(|err| CliError::Other(format!("failed to open database: {err}")))?;
 
     let node_start_args = StartArgs::new(node_config, database);
 
@@ -1,5 +1,6 @@
 use std::{
     collections::{BTreeMap, HashMap},
+    path::PathBuf,
     sync::Arc,
 };
 
@@ -11,7 +12,12 @@ use primitives::{NodeId, NodeIdx, NodeType};
 use rand::rngs::OsRng;
 use vrrb_config::ThresholdConfig;
 
-use crate::{prelude::DkgGenerator, DkgError, Result};
+use crate::{
+    prelude::DkgGenerator,
+    share_store::{DkgShareStore, DkgShareTag, StoredDkgShares},
+    DkgError,
+    Result,
+};
 
 pub type SenderId = NodeId;
 
@@ -45,6 +51,9 @@ pub struct DkgEngine {
 
     /// Harvester Distributed  Group public key
     pub harvester_public_key: Option<PublicKey>,
+
+    /// Where the output of the DKG run is persisted, if anywhere
+    pub persistence: Option<DkgPersistenceConfig>,
 }
 
 /// `DkgState` is a struct that contains all the state that is needed to run the
@@ -91,11 +100,23 @@ pub struct DkgEngineConfig {
     pub node_type: NodeType,
     pub secret_key: SecretKey,
     pub threshold_config: vrrb_config::ThresholdConfig,
+    pub persistence: Option<DkgPersistenceConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DkgPersistenceConfig {
+    /// The node's data directory
+    pub data_dir: PathBuf,
+    pub tag: DkgShareTag,
 }
 
 impl DkgEngine {
-    pub fn new(config: DkgEngineConfig) -> DkgEngine {
-        DkgEngine {
+    /// Creates a new engine, restoring the output of an earlier DKG run if one
+    /// was persisted for the same epoch and quorum membership. Fails if the
+    /// persisted shares can not be read, in which case they have to be
+    /// cleared before the quorum can run DKG again.
+    pub fn new(config: DkgEngineConfig) -> Result<DkgEngine> {
+        let mut engine = DkgEngine {
             node_id: config.node_id,
             node_type: config.node_type,
             secret_key: config.secret_key,
@@ -110,7 +131,12 @@ impl DkgEngine {
                 random_number_gen: None,
             },
             harvester_public_key: None,
-        }
+            persistence: config.persistence,
+        };
+
+        engine.reload_key_sets()?;
+
+        Ok(engine)
     }
 
     pub fn add_peer_public_key(&mut self, node_id: NodeId, public_key: PublicKey) {
@@ -125,6 +151,81 @@ impl DkgEngine {
         self.node_id.clone()
     }
 
+    /// Restores the key sets persisted by an earlier DKG run, provided the
+    /// epoch, the quorum's membership and threshold did not change since.
+    /// Shares persisted for an earlier epoch or another membership are stale
+    /// and get deleted. Returns whether anything was restored.
+    pub fn reload_key_sets(&mut self) -> Result<bool> {
+        let Some(persistence) = self.persistence.as_ref() else {
+            return Ok(false);
+        };
+
+        let store = DkgShareStore::new(&persistence.data_dir);
+        let Some(shares) = store.load(&persistence.tag.quorum_id)? else {
+            return Ok(false);
+        };
+
+        // NOTE: shares of a later epoch are kept, the node may just be behind
+        if shares.tag.epoch > persistence.tag.epoch {
+            return Ok(false);
+        }
+
+        if shares.tag != persistence.tag || shares.threshold_config != self.threshold_config {
+            store.remove(&persistence.tag.quorum_id)?;
+            return Ok(false);
+        }
+
+        let secret_key_share = shares.secret_key_share(&self.secret_key)?;
+
+        self.dkg_state.peer_public_keys = shares.peer_public_keys;
+        self.dkg_state.public_key_set = Some(shares.public_key_set);
+        self.dkg_state.secret_key_share = Some(secret_key_share);
+
+        Ok(true)
+    }
+
+    /// Persists the key sets generated by the DKG run, encrypting this node's
+    /// secret key share at rest.
+    pub fn persist_key_sets(&self) -> Result<()> {
+        let Some(persistence) = self.persistence.as_ref() else {
+            return Ok(());
+        };
+
+        let (Some(public_key_set), Some(secret_key_share)) = (
+            self.dkg_state.public_key_set.clone(),
+            self.dkg_state.secret_key_share.as_ref(),
+        ) else {
+            return Err(DkgError::ShareStore(
+                "key sets have not been generated yet".to_string(),
+            ));
+        };
+
+        let shares = StoredDkgShares::new(
+            &self.secret_key,
+            persistence.tag.clone(),
+            self.threshold_config.clone(),
+            self.dkg_state.peer_public_keys.clone(),
+            public_key_set,
+            secret_key_share,
+        )?;
+
+        DkgShareStore::new(&persistence.data_dir).save(&shares)
+    }
+
+    /// Clears the state of the DKG along with the key sets persisted for it,
+    /// so the quorum can run DKG again.
+    pub fn discard_key_sets(&mut self) -> Result<()> {
+        self.clear_state();
+
+        let Some(persistence) = self.persistence.as_ref() else {
+            return Ok(());
+        };
+
+        DkgShareStore::new(&persistence.data_dir).remove(&persistence.tag.quorum_id)?;
+
+        Ok(())
+    }
+
     /// It clears the state of the DKG. it happens during change of Epoch
     pub fn clear_state(&mut self) {
         self.dkg_state.part_message_store.clear();
@@ -155,7 +256,11 @@ impl DkgGenerator for DkgEngine {
             return Err(DkgError::NotEnoughPeerPublicKeys);
         }
 
-        if self.node_type != NodeType::MasterNode {
+        // NOTE: quorums are made up of validators, which full nodes can act as
+        if !matches!(
+            self.node_type,
+            NodeType::MasterNode | NodeType::Validator | NodeType::Full
+        ) {
             return Err(DkgError::InvalidNode);
         }
 
@@ -1,6 +1,7 @@
 pub mod dkg;
 pub mod engine;
 pub mod result;
+pub mod share_store;
 pub mod test_utils;
 
 pub use crate::result::*;
@@ -8,14 +9,19 @@ pub use crate::result::*;
 pub mod prelude {
     pub use crate::dkg::*;
     pub use crate::engine::*;
+    pub use crate::share_store::*;
 }
 
 #[cfg(test)]
 mod tests {
     use std::{borrow::BorrowMut, collections::HashMap};
 
-    use hbbft::sync_key_gen::Ack;
+    use hbbft::{
+        crypto::{SecretKey, SecretKeySet},
+        sync_key_gen::Ack,
+    };
     use primitives::{NodeId, NodeType};
+    use vrrb_config::ThresholdConfig;
     use vrrb_core::is_enum_variant;
 
     use crate::dkg::DkgGenerator;
@@ -245,6 +251,95 @@ mod tests {
         assert!(dkg_engine_node1.dkg_state.secret_key_share.is_some());
     }
 
+    #[test]
+    fn key_sets_are_reloaded_while_quorum_membership_is_unchanged() {
+        let mut rng = rand::rngs::OsRng::new().unwrap();
+        let secret_key_set = SecretKeySet::random(1, &mut rng);
+        let secret_key = SecretKey::random();
+
+        let tag = DkgShareTag {
+            quorum_id: String::from("harvester"),
+            epoch: 1,
+            members: (0..4).map(|i| format!("node-{i}")).collect(),
+        };
+
+        let config = DkgEngineConfig {
+            node_id: String::from("node-0"),
+            node_idx: 0,
+            node_type: NodeType::Validator,
+            secret_key: secret_key.clone(),
+            threshold_config: ThresholdConfig {
+                upper_bound: 4,
+                threshold: 1,
+            },
+            persistence: Some(DkgPersistenceConfig {
+                data_dir: std::env::temp_dir().join(vrrb_core::helpers::generate_random_string()),
+                tag: tag.clone(),
+            }),
+        };
+
+        let mut dkg_engine = DkgEngine::new(config.clone()).unwrap();
+        assert!(dkg_engine.dkg_state.secret_key_share.is_none());
+
+        dkg_engine.dkg_state.public_key_set = Some(secret_key_set.public_keys());
+        dkg_engine.dkg_state.secret_key_share = Some(secret_key_set.secret_key_share(0));
+        dkg_engine.persist_key_sets().unwrap();
+
+        let restarted_dkg_engine = DkgEngine::new(config.clone()).unwrap();
+        assert_eq!(
+            restarted_dkg_engine.dkg_state.public_key_set,
+            Some(secret_key_set.public_keys())
+        );
+        assert_eq!(
+            restarted_dkg_engine.dkg_state.secret_key_share,
+            Some(secret_key_set.secret_key_share(0))
+        );
+
+        let mut earlier_epoch_config = config.clone();
+        if let Some(persistence) = earlier_epoch_config.persistence.as_mut() {
+            persistence.tag.epoch = 0;
+        }
+
+        let dkg_engine = DkgEngine::new(earlier_epoch_config).unwrap();
+        assert!(dkg_engine.dkg_state.public_key_set.is_none());
+
+        let mut other_node_config = config.clone();
+        other_node_config.secret_key = SecretKey::random();
+
+        assert!(is_enum_variant!(
+            DkgEngine::new(other_node_config),
+            Err(DkgError::ShareStore { .. })
+        ));
+
+        let mut changed_membership_config = config.clone();
+        if let Some(persistence) = changed_membership_config.persistence.as_mut() {
+            persistence.tag.members.insert(String::from("node-4"));
+        }
+
+        let dkg_engine = DkgEngine::new(changed_membership_config).unwrap();
+        assert!(dkg_engine.dkg_state.public_key_set.is_none());
+
+        // NOTE: shares of the earlier membership were stale and got deleted
+        let dkg_engine = DkgEngine::new(config).unwrap();
+        assert!(dkg_engine.dkg_state.public_key_set.is_none());
+    }
+
+    #[test]
+    fn share_store_rejects_quorum_ids_that_are_not_plain_names() {
+        let store = DkgShareStore::new(
+            &std::env::temp_dir().join(vrrb_core::helpers::generate_random_string()),
+        );
+
+        for quorum_id in ["", "..", "../harvester", "dkg/harvester", "C:\\harvester"] {
+            assert!(is_enum_variant!(
+                store.load(&quorum_id.to_string()),
+                Err(DkgError::ShareStore { .. })
+            ));
+        }
+
+        assert!(store.load(&String::from("harvester")).unwrap().is_none());
+    }
+
     fn add_part_commitment_to_node_dkg_state(
         dkg_engine_node1: &mut DkgEngine,
         dkg_engine_node2: &mut DkgEngine,
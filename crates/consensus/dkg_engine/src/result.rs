@@ -40,6 +40,8 @@ pub enum DkgError {
     InvalidNode,
     #[error("All participants of Quorum need to actively participate in DKG")]
     ObserverNotAllowed,
+    #[error("Failed to persist or restore DKG shares: {0}")]
+    ShareStore(String),
     #[error("Unknown Error: {0}")]
     Unknown(String),
 }
@@ -0,0 +1,245 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use hbbft::crypto::{
+    serde_impl::SerdeSecret,
+    PublicKey,
+    PublicKeySet,
+    SecretKey,
+    SecretKeyShare,
+};
+use primitives::{Epoch, NodeId};
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN},
+    digest::{digest, SHA256},
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{Deserialize, Serialize};
+use vrrb_config::ThresholdConfig;
+
+use crate::{DkgError, Result};
+
+/// Directory, relative to the node's data directory, where DKG shares are
+/// stored.
+pub const DKG_SHARES_DIR: &str = "dkg";
+
+const DKG_SHARES_FILE_EXTENSION: &str = "shares";
+
+/// Domain separator used when deriving the key that encrypts secret key
+/// shares from the node's validator secret key.
+const DKG_SHARES_KEY_DOMAIN: &[u8] = b"vrrb-dkg-shares";
+
+pub type QuorumId = String;
+
+/// Identifies the quorum a set of DKG shares was generated for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DkgShareTag {
+    pub quorum_id: QuorumId,
+    pub epoch: Epoch,
+    pub members: BTreeSet<NodeId>,
+}
+
+/// Output of a completed DKG run as stored on disk. Only the secret key
+/// share is encrypted, everything else is public to the quorum anyway.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredDkgShares {
+    pub tag: DkgShareTag,
+    pub threshold_config: ThresholdConfig,
+    pub peer_public_keys: BTreeMap<NodeId, PublicKey>,
+    pub public_key_set: PublicKeySet,
+    nonce: [u8; NONCE_LEN],
+    encrypted_secret_key_share: Vec<u8>,
+}
+
+impl StoredDkgShares {
+    /// Encrypts the given secret key share with a key derived from the
+    /// node's validator secret key.
+    pub fn new(
+        secret_key: &SecretKey,
+        tag: DkgShareTag,
+        threshold_config: ThresholdConfig,
+        peer_public_keys: BTreeMap<NodeId, PublicKey>,
+        public_key_set: PublicKeySet,
+        secret_key_share: &SecretKeyShare,
+    ) -> Result<Self> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| DkgError::ShareStore("failed to generate a nonce".to_string()))?;
+
+        let mut encrypted_secret_key_share = bincode::serialize(&SerdeSecret(secret_key_share))
+            .map_err(|err| DkgError::ShareStore(err.to_string()))?;
+
+        let aad = bincode::serialize(&tag).map_err(|err| DkgError::ShareStore(err.to_string()))?;
+
+        encryption_key(secret_key)?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(aad),
+                &mut encrypted_secret_key_share,
+            )
+            .map_err(|_| DkgError::ShareStore("failed to encrypt secret key share".to_string()))?;
+
+        Ok(Self {
+            tag,
+            threshold_config,
+            peer_public_keys,
+            public_key_set,
+            nonce,
+            encrypted_secret_key_share,
+        })
+    }
+
+    /// Decrypts the secret key share, which only works with the validator
+    /// secret key of the node that stored it.
+    pub fn secret_key_share(&self, secret_key: &SecretKey) -> Result<SecretKeyShare> {
+        let mut secret_key_share = self.encrypted_secret_key_share.clone();
+
+        let aad =
+            bincode::serialize(&self.tag).map_err(|err| DkgError::ShareStore(err.to_string()))?;
+
+        let secret_key_share = encryption_key(secret_key)?
+            .open_in_place(
+                Nonce::assume_unique_for_key(self.nonce),
+                Aad::from(aad),
+                &mut secret_key_share,
+            )
+            .map_err(|_| DkgError::ShareStore("failed to decrypt secret key share".to_string()))?;
+
+        bincode::deserialize::<SerdeSecret<SecretKeyShare>>(secret_key_share)
+            .map(|secret_key_share| secret_key_share.0)
+            .map_err(|err| DkgError::ShareStore(err.to_string()))
+    }
+}
+
+fn encryption_key(secret_key: &SecretKey) -> Result<LessSafeKey> {
+    let secret_key = bincode::serialize(&SerdeSecret(secret_key))
+        .map_err(|err| DkgError::ShareStore(err.to_string()))?;
+
+    let key_material = [DKG_SHARES_KEY_DOMAIN, secret_key.as_slice()].concat();
+    let key = digest(&SHA256, &key_material);
+
+    UnboundKey::new(&CHACHA20_POLY1305, key.as_ref())
+        .map(LessSafeKey::new)
+        .map_err(|_| DkgError::ShareStore("failed to derive encryption key".to_string()))
+}
+
+/// Persists the output of completed DKG runs, one file per quorum, under the
+/// node's data directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DkgShareStore {
+    dir: PathBuf,
+}
+
+impl DkgShareStore {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join(DKG_SHARES_DIR),
+        }
+    }
+
+    /// Returns the path of the file holding the shares of the given quorum.
+    /// Quorum ids are only used as plain file names, so ids that could point
+    /// outside of the store's directory are rejected.
+    fn path(&self, quorum_id: &QuorumId) -> Result<PathBuf> {
+        let is_plain_name = !quorum_id.is_empty()
+            && quorum_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+        if !is_plain_name {
+            return Err(DkgError::ShareStore(format!(
+                "invalid quorum id: {quorum_id:?}"
+            )));
+        }
+
+        Ok(self
+            .dir
+            .join(quorum_id)
+            .with_extension(DKG_SHARES_FILE_EXTENSION))
+    }
+
+    /// Stores the given shares, replacing any shares stored earlier for the
+    /// same quorum.
+    pub fn save(&self, shares: &StoredDkgShares) -> Result<()> {
+        let path = self.path(&shares.tag.quorum_id)?;
+
+        fs::create_dir_all(&self.dir).map_err(|err| DkgError::ShareStore(err.to_string()))?;
+
+        let bytes =
+            bincode::serialize(shares).map_err(|err| DkgError::ShareStore(err.to_string()))?;
+
+        fs::write(path, bytes).map_err(|err| DkgError::ShareStore(err.to_string()))
+    }
+
+    /// Returns the shares stored for the given quorum, if any.
+    pub fn load(&self, quorum_id: &QuorumId) -> Result<Option<StoredDkgShares>> {
+        let path = self.path(quorum_id)?;
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let bytes = fs::read(path).map_err(|err| DkgError::ShareStore(err.to_string()))?;
+
+        bincode::deserialize(&bytes)
+            .map(Some)
+            .map_err(|err| DkgError::ShareStore(err.to_string()))
+    }
+
+    /// Returns the shares stored for every quorum.
+    pub fn entries(&self) -> Result<Vec<StoredDkgShares>> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut entries = vec![];
+
+        for entry in fs::read_dir(&self.dir).map_err(|err| DkgError::ShareStore(err.to_string()))? {
+            let path = entry
+                .map_err(|err| DkgError::ShareStore(err.to_string()))?
+                .path();
+
+            if path.extension().and_then(|ext| ext.to_str()) != Some(DKG_SHARES_FILE_EXTENSION) {
+                continue;
+            }
+
+            let bytes = fs::read(path).map_err(|err| DkgError::ShareStore(err.to_string()))?;
+            let shares = bincode::deserialize(&bytes)
+                .map_err(|err| DkgError::ShareStore(err.to_string()))?;
+
+            entries.push(shares);
+        }
+
+        Ok(entries)
+    }
+
+    /// Deletes the shares stored for the given quorum, returning whether
+    /// there were any.
+    pub fn remove(&self, quorum_id: &QuorumId) -> Result<bool> {
+        let path = self.path(quorum_id)?;
+
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        fs::remove_file(path).map_err(|err| DkgError::ShareStore(err.to_string()))?;
+
+        Ok(true)
+    }
+
+    /// Deletes the shares stored for every quorum, returning how many were
+    /// deleted.
+    pub fn clear(&self) -> Result<usize> {
+        let entries = self.entries()?;
+
+        for shares in entries.iter() {
+            self.remove(&shares.tag.quorum_id)?;
+        }
+
+        Ok(entries.len())
+    }
+}
@@ -67,6 +67,7 @@ pub async fn generate_dkg_engines(total_nodes: u16, node_type: NodeType) -> Vec<
                 random_number_gen: None,
             },
             harvester_public_key: None,
+            persistence: None,
         });
     }
     dkg_instances
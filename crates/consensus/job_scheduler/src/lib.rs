@@ -1,4 +1,4 @@
-use std::{cmp::Ordering, sync::Arc};
+use std::{cmp::Ordering, collections::HashMap, sync::Arc};
 
 use job_pool::{
     builder::PoolBuilder,
@@ -6,7 +6,7 @@ use job_pool::{
 };
 use once_cell::sync::Lazy;
 use primitives::PeerId as PeerID;
-use vrrb_core::cache::Cache;
+use vrrb_core::{cache::Cache, reputation::PeerReputation};
 
 pub struct JobScheduler {
     local_peer_id: PeerID,
@@ -14,6 +14,7 @@ pub struct JobScheduler {
     remote_pool: JobPool,
     forwarding_pool: JobPool,
     peers_back_pressure: Cache<PeerID, f32>,
+    peers_reputation: HashMap<PeerID, f64>,
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +64,21 @@ impl JobScheduler {
         self.peers_back_pressure.push(peer_id, back_pressure);
     }
 
+    /// Sets the reputation score of a peer. Peers that are not trusted are
+    /// left out of back pressure calculations, while the back pressure of
+    /// the rest is scaled up the less they are trusted, so that work is
+    /// delegated to trusted peers first.
+    pub fn set_peer_reputation(&mut self, peer_id: PeerID, reputation: &PeerReputation) {
+        self.peers_reputation.insert(peer_id, reputation.score());
+    }
+
+    fn peer_reputation_score(&self, peer_id: &PeerID) -> f64 {
+        self.peers_reputation
+            .get(peer_id)
+            .copied()
+            .unwrap_or_else(|| PeerReputation::default().score())
+    }
+
     /// > The function creates a new JobScheduler object with three thread
     /// > pools, one for local jobs,
     /// one for remote jobs, and one for forwarding jobs
@@ -105,6 +121,7 @@ impl JobScheduler {
             .stack_size(2 * 1024 * 1024)
             .build(),
             peers_back_pressure: Cache::new(1000, 50000),
+            peers_reputation: HashMap::new(),
         }
     }
 
@@ -160,7 +177,16 @@ impl JobScheduler {
             1.0 + local_backpressure.log10(),
         )];
         for data in self.peers_back_pressure.cache.to_owned().iter() {
-            back_pressure_list.push(BackPressure::new(data.0.clone(), 1.0 + data.1.log10()));
+            let reputation_score = self.peer_reputation_score(data.0);
+            if reputation_score < PeerReputation::MIN_TRUSTED_SCORE {
+                continue;
+            }
+            // Unobserved peers score 0.5, so their back pressure is left as is
+            let back_pressure = data.1 * (0.5 / reputation_score) as f32;
+            back_pressure_list.push(BackPressure::new(
+                data.0.clone(),
+                1.0 + back_pressure.log10(),
+            ));
         }
         let mut log_normalized_backpressure =
             BackPressure::log_normalized_backpressure(back_pressure_list);
@@ -204,6 +230,7 @@ mod tests {
     };
 
     use rand::Rng;
+    use vrrb_core::reputation::PeerBehaviour;
 
     use super::*;
 
@@ -253,4 +280,32 @@ mod tests {
             }
         });
     }
+
+    #[test]
+    fn untrusted_peers_are_excluded_from_back_pressure() {
+        let mut job_scheduler = JobScheduler::new(vec![2u8]);
+        for i in 0..4u8 {
+            job_scheduler
+                .peers_back_pressure
+                .push(vec![i], 100.0 * (i + 1) as f32);
+        }
+
+        let mut untrusted = PeerReputation::default();
+        (0..10).for_each(|_| untrusted.record(PeerBehaviour::ClaimAbandoned));
+        job_scheduler.set_peer_reputation(vec![0u8], &untrusted);
+
+        // Keeps the local pool busy so the local back pressure is positive
+        for _ in 0..4 {
+            job_scheduler
+                .local_pool
+                .run_async_job(async { thread::sleep(Duration::from_millis(500)) });
+        }
+
+        let (_, back_pressure_list) = job_scheduler.calculate_back_pressure();
+
+        assert!(!back_pressure_list.is_empty());
+        assert!(back_pressure_list
+            .iter()
+            .all(|back_pressure| back_pressure.peer_id != vec![0u8]));
+    }
 }
@@ -11,7 +11,12 @@ mod tests {
 
     use primitives::Address;
     use sha256::digest;
-    use vrrb_core::{claim::Claim, keypair::KeyPair};
+    use vrrb_config::ThresholdConfig;
+    use vrrb_core::{
+        claim::Claim,
+        keypair::KeyPair,
+        staking::{Stake, StakeUpdate},
+    };
 
     use crate::{election::Election, quorum::Quorum};
 
@@ -55,7 +60,7 @@ mod tests {
         let payload1 = (10, hash);
 
         if let Ok(seed) = Quorum::generate_seed(payload1, keypair.clone()) {
-            if let Ok(mut quorum) = Quorum::new(seed, 11) {
+            if let Ok(mut quorum) = Quorum::new(seed, 11, &ThresholdConfig::default()) {
                 assert!(quorum.run_election(dummy_claims).is_err());
             };
         }
@@ -164,7 +169,7 @@ mod tests {
         let seed = Quorum::generate_seed(payload1, keypair.clone());
 
         if let Ok(seed) = seed {
-            assert!(Quorum::new(seed, 0).is_err());
+            assert!(Quorum::new(seed, 0, &ThresholdConfig::default()).is_err());
         }
     }
 
@@ -200,7 +205,7 @@ mod tests {
         let payload1 = (10, hash);
 
         if let Ok(seed) = Quorum::generate_seed(payload1, keypair.clone()) {
-            assert!(Quorum::new(seed, 0).is_err());
+            assert!(Quorum::new(seed, 0, &ThresholdConfig::default()).is_err());
         }
     }
 
@@ -236,7 +241,7 @@ mod tests {
         let payload1 = (10, hash);
 
         if let Ok(seed) = Quorum::generate_seed(payload1, keypair.clone()) {
-            if let Ok(mut quorum) = Quorum::new(seed, 11) {
+            if let Ok(mut quorum) = Quorum::new(seed, 11, &ThresholdConfig::default()) {
                 if quorum.run_election(dummy_claims.clone()).is_ok() {
                     assert!(quorum.master_pubkeys.len() == 13);
                 }
@@ -281,8 +286,8 @@ mod tests {
 
         if let Ok(seed1) = Quorum::generate_seed(payload.clone(), keypair.clone()) {
             if let Ok(seed2) = Quorum::generate_seed(payload.clone(), keypair.clone()) {
-                if let Ok(mut quorum1) = Quorum::new(seed1, 11) {
-                    if let Ok(mut quorum2) = Quorum::new(seed2, 11) {
+                if let Ok(mut quorum1) = Quorum::new(seed1, 11, &ThresholdConfig::default()) {
+                    if let Ok(mut quorum2) = Quorum::new(seed2, 11, &ThresholdConfig::default()) {
                         if let Ok(q1) = quorum1.run_election(dummy_claims1) {
                             if let Ok(q2) = quorum2.run_election(dummy_claims2) {
                                 assert!(q1.master_pubkeys == q2.master_pubkeys);
@@ -293,4 +298,92 @@ mod tests {
             }
         }
     }
+
+    fn staked_claim(amount: u128) -> Claim {
+        let keypair = KeyPair::random();
+        let public_key = keypair.get_miner_public_key().clone();
+        let address = Address::new(public_key);
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key.clone(),
+            ip_address,
+            keypair.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let mut claim = Claim::new(public_key, address.clone(), ip_address, signature).unwrap();
+
+        let mut stake = Stake::new(
+            StakeUpdate::Add(amount),
+            keypair.miner_kp.0.clone(),
+            keypair.miner_kp.1.clone(),
+            address,
+            None,
+        )
+        .unwrap();
+        stake.certify((vec![0; 96], vec![0; 96])).unwrap();
+        claim.update_stake(stake).unwrap();
+
+        claim
+    }
+
+    #[test]
+    fn claims_below_minimum_stake_are_not_eligible() {
+        let threshold_config = ThresholdConfig {
+            upper_bound: 3,
+            threshold: 1,
+        };
+
+        let mut claims: Vec<Claim> = (0..3).map(|_| staked_claim(Stake::MIN)).collect();
+        claims.push(staked_claim(Stake::MIN - 1));
+
+        let quorum = Quorum::new(u32::MAX as u64 + 1, 11, &threshold_config).unwrap();
+        assert_eq!(quorum.get_eligible_claims(claims.clone()).unwrap().len(), 3);
+
+        claims.pop();
+        claims.pop();
+        assert!(quorum.get_eligible_claims(claims).is_err());
+    }
+
+    #[test]
+    fn stake_weighted_elections_are_reproducible_and_auditable() {
+        let threshold_config = ThresholdConfig {
+            upper_bound: 4,
+            threshold: 2,
+        };
+
+        let claims: Vec<Claim> = (0..10)
+            .map(|i| staked_claim(Stake::MIN + i * 20_000))
+            .collect();
+
+        let seed = u32::MAX as u64 + 7;
+
+        let mut quorum1 = Quorum::new(seed, 11, &threshold_config).unwrap();
+        let mut reversed_claims = claims.clone();
+        reversed_claims.reverse();
+        let mut quorum2 = Quorum::new(seed, 11, &threshold_config).unwrap();
+
+        quorum1.run_election(claims).unwrap();
+        quorum2.run_election(reversed_claims).unwrap();
+
+        assert_eq!(quorum1.master_pubkeys, quorum2.master_pubkeys);
+        assert_eq!(quorum1.election_results, quorum2.election_results);
+
+        assert_eq!(quorum1.master_pubkeys.len(), 6);
+        assert_eq!(quorum1.election_results.len(), 10);
+
+        quorum1
+            .election_results
+            .iter()
+            .enumerate()
+            .for_each(|(rank, entry)| {
+                assert_eq!(entry.rank, rank);
+                assert_eq!(entry.elected, rank < 6);
+                assert!(entry.weight >= Stake::MIN && entry.weight <= Stake::MAX);
+            });
+
+        assert!(quorum1
+            .election_results
+            .windows(2)
+            .all(|entries| entries[0].score <= entries[1].score));
+    }
 }
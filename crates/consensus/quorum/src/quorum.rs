@@ -3,10 +3,8 @@ use std::collections::BTreeMap;
 use ethereum_types::U256;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use vrrb_core::{
-    claim::{Claim, Eligibility},
-    keypair::KeyPair,
-};
+use vrrb_config::ThresholdConfig;
+use vrrb_core::{claim::Claim, keypair::KeyPair, staking::Stake};
 use vrrb_vrf::{vrng::VRNG, vvrf::VVRF};
 
 use crate::election::Election;
@@ -39,6 +37,27 @@ pub struct Quorum {
     pub master_pubkeys: Vec<String>,
     pub quorum_pk: String,
     pub election_block_height: u128,
+    /// Minimum number of members an elected quorum must have
+    pub min_quorum_size: usize,
+    /// Every eligible claim ranked by the last election run, elected or not
+    pub election_results: Vec<ElectionEntry>,
+}
+
+/// The standing of a single claim within an election. Entries are kept on the
+/// elected `Quorum` so any node can audit the outcome and reproduce it from
+/// the same seed and claims.
+#[derive(Debug, Clone, Serialize, Deserialize, Hash, Eq, PartialEq)]
+pub struct ElectionEntry {
+    pub public_key: String,
+    pub claim_hash: U256,
+    /// The claim's stake, capped at `Stake::MAX`
+    pub weight: u128,
+    /// The claim's election result divided by its weight, lower scores rank
+    /// higher
+    pub score: U256,
+    /// Position of the claim within the election, starting at 0
+    pub rank: usize,
+    pub elected: bool,
 }
 
 ///generic types from Election trait defined here for Quorums
@@ -82,7 +101,7 @@ impl Election for Quorum {
             return Err(QuorumError::InvalidChildBlockError);
         }
 
-        let eligible_claims = match Quorum::get_eligible_claims(ballot) {
+        let eligible_claims = match self.get_eligible_claims(ballot) {
             Ok(eligible_claims) => eligible_claims,
             Err(e) => return Err(e),
         };
@@ -97,9 +116,13 @@ impl Election for Quorum {
 }
 
 impl Quorum {
-    /// Makes a new Quorum and initializes seed, child block height, and child
-    /// block timestamp
-    pub fn new(seed: u64, height: u128) -> Result<Quorum, QuorumError> {
+    /// Makes a new Quorum and initializes seed, child block height, and the
+    /// minimum quorum size, which is the number of nodes taking part in DKG
+    pub fn new(
+        seed: u64,
+        height: u128,
+        threshold_config: &ThresholdConfig,
+    ) -> Result<Quorum, QuorumError> {
         if !Quorum::check_validity(height) {
             Err(QuorumError::InvalidChildBlockError)
         } else {
@@ -108,6 +131,8 @@ impl Quorum {
                 master_pubkeys: Vec::new(),
                 quorum_pk: String::new(),
                 election_block_height: height,
+                min_quorum_size: (threshold_config.upper_bound as usize).max(1),
+                election_results: Vec::new(),
             })
         }
     }
@@ -118,65 +143,81 @@ impl Quorum {
         height > 0
     }
 
-    ///gets all claims that belong to eligible nodes (master nodes)
-    /// needs to be modifed as claim field eligible:  bool needs to become a uX
-    /// of staked amt
-    pub fn get_eligible_claims(claims: Vec<Claim>) -> Result<Vec<Claim>, QuorumError> {
-        let mut eligible_claims = Vec::<Claim>::new();
-        claims
+    /// Returns the weight a claim carries within elections, which is its own
+    /// and delegated stake capped at `Stake::MAX`, or `None` if less than
+    /// `Stake::MIN` is staked
+    pub fn election_weight(claim: &Claim) -> Option<u128> {
+        let stake = claim.get_total_stake();
+        if stake < Stake::MIN {
+            return None;
+        }
+
+        Some(stake.min(Stake::MAX))
+    }
+
+    /// Gets all claims that have staked enough to take part in elections,
+    /// ordered by claim hash. Claims sharing a hash only get a single ballot.
+    pub fn get_eligible_claims(&self, claims: Vec<Claim>) -> Result<Vec<Claim>, QuorumError> {
+        let eligible_claims: BTreeMap<U256, Claim> = claims
             .into_iter()
-            .filter(|claim| {
-                claim.eligibility == Eligibility::Harvester
-                    && claim.eligibility == Eligibility::Farmer
-            })
-            .for_each(|claim| {
-                eligible_claims.push(claim);
-            });
+            .filter(|claim| Quorum::election_weight(claim).is_some())
+            .map(|claim| (claim.hash, claim))
+            .collect();
 
-        if eligible_claims.len() < 20 {
+        if eligible_claims.len() < self.min_quorum_size {
             return Err(QuorumError::InsufficientNodesError);
         }
 
-        let eligible_claims = eligible_claims;
-
-        Ok(eligible_claims)
+        Ok(eligible_claims.into_values().collect())
     }
 
-    /// Gets the final quorum by getting 51% of master nodes with lowest pointer
-    /// sums
+    /// Gets the final quorum by ranking claims by their election result
+    /// divided by their weight and electing the 51% with the lowest scores,
+    /// or at least `min_quorum_size` of them
     pub fn get_final_quorum(&mut self, claims: Vec<Claim>) -> Result<&Quorum, QuorumError> {
         if self.quorum_seed == 0 {
             return Err(QuorumError::NoSeedError);
         }
 
-        let num_claims = ((claims.len() as f32) * 0.51).ceil() as usize;
-
-        let election_results: BTreeMap<U256, Claim> = claims
-            .iter()
-            .map(|claim| (claim.get_election_result(self.quorum_seed), claim.clone()))
+        let mut ranked_claims: Vec<(U256, u128, Claim)> = claims
+            .into_iter()
+            .filter_map(|claim| {
+                let weight = Quorum::election_weight(&claim)?;
+                let score = claim.get_election_result(self.quorum_seed) / U256::from(weight);
+                Some((score, weight, claim))
+            })
             .collect();
 
-        if election_results.len() < (((claims.len() as f32) * 0.65).ceil() as usize) {
-            return Err(QuorumError::InvalidPointerSumError(claims));
+        let num_claims =
+            (((ranked_claims.len() as f32) * 0.51).ceil() as usize).max(self.min_quorum_size);
+
+        if ranked_claims.len() < num_claims {
+            return Err(QuorumError::InsufficientNodesError);
         }
 
-        let pubkeys: Vec<String> = election_results
-            .values()
-            .map(|claim| claim.public_key.clone().to_string())
-            .take(num_claims)
-            .collect();
+        // Ties are broken by claim hash so every node ranks claims identically
+        ranked_claims.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.2.hash.cmp(&b.2.hash)));
 
-        let final_pubkeys = Vec::from_iter(pubkeys[0..num_claims].iter().cloned());
+        self.election_results = ranked_claims
+            .into_iter()
+            .enumerate()
+            .map(|(rank, (score, weight, claim))| ElectionEntry {
+                public_key: claim.public_key.to_string(),
+                claim_hash: claim.hash,
+                weight,
+                score,
+                rank,
+                elected: rank < num_claims,
+            })
+            .collect();
 
-        self.master_pubkeys = final_pubkeys;
+        self.master_pubkeys = self
+            .election_results
+            .iter()
+            .filter(|entry| entry.elected)
+            .map(|entry| entry.public_key.clone())
+            .collect();
 
         Ok(self)
     }
-
-    pub fn get_trusted_peers(&mut self, _claims: Vec<Claim>) -> Self {
-        //get the weighted value hashmap
-        //calcualte all the t values
-
-        todo!();
-    }
 }
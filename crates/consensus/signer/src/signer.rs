@@ -35,7 +35,7 @@ pub trait Signer {
     ) -> SignerResult<bool>;
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct SignatureProvider {
     pub dkg_state: Arc<RwLock<DkgState>>,
     pub quorum_config: ThresholdConfig,
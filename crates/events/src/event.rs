@@ -6,14 +6,28 @@ use block::{
     BlockHash,
     Certificate,
     ConvergenceBlock,
+    Evidence,
     ProposalBlock,
     RefHash,
 };
-use primitives::{Address, Epoch, NodeId, NodeIdx, PublicKeyShareVec, RawSignature, Round, Seed};
+use mempool::TxnRecord;
+use primitives::{
+    Address,
+    Epoch,
+    NodeId,
+    NodeIdx,
+    PublicKeyShareVec,
+    QuorumKind,
+    RawSignature,
+    Round,
+    Seed,
+};
 use serde::{Deserialize, Serialize};
 use vrrb_config::QuorumMembershipConfig;
 use vrrb_core::{
     claim::Claim,
+    reputation::PeerBehaviour,
+    staking::Stake,
     txn::{TransactionDigest, Txn},
 };
 
@@ -25,6 +39,7 @@ pub type HeaderBytes = Vec<u8>;
 pub type ConflictBytes = Vec<u8>;
 pub type MinerClaim = Claim;
 pub type Count = usize;
+pub type DkgPhaseToken = u64;
 
 #[derive(Default, Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -91,12 +106,39 @@ pub enum Event {
     /// be invalid.
     ClaimAbandoned(NodeId, Claim),
 
+    /// `PeerBehaviourObserved(NodeId, PeerBehaviour)` reports a behaviour
+    /// observed from a peer while it took part in consensus, which is
+    /// recorded into the peer's reputation.
+    PeerBehaviourObserved(NodeId, PeerBehaviour),
+
     /// A peer joined the network, should be added to the node's peer list
     PeerJoined(PeerData),
 
     /// A peer joined the network and was added to the node's peer list
     NodeAddedToPeerList(PeerData),
 
+    /// A peer signed its intent to leave the network, should be removed from
+    /// the node's peer list once the notice is authenticated
+    PeerUnregistered(SignedPeerLeave),
+
+    /// A peer left the network or turned unresponsive and was removed from the
+    /// node's peer list
+    NodeRemovedFromPeerList(PeerData),
+
+    /// `PingIntervalElapsed` asks the network module to check the liveness of
+    /// every known peer.
+    PingIntervalElapsed,
+
+    /// `NetworkReady(Vec<PeerData>)` is emitted once enough peers joined the
+    /// network during bootstrap peer discovery for the first quorums to be
+    /// formed. It carries every peer that joined.
+    NetworkReady(Vec<PeerData>),
+
+    /// `NetworkReadinessTimedOut` is emitted once bootstrap peer discovery
+    /// went on for too long, so the members that are still missing can be
+    /// reported.
+    NetworkReadinessTimedOut,
+
     /// `CreateAccountRequested((Address, AccountBytes))` is triggered when
     /// request for Account creation on the chain has been requested.
     CreateAccountRequested((Address, AccountBytes)),
@@ -121,6 +163,131 @@ pub enum Event {
 
     DkgProtocolInitiated,
 
+    /// `DkgMessageCreated(SignedDkgMessage, Vec<SocketAddr>)` asks the
+    /// network module to send a DKG message to the given quorum peers.
+    DkgMessageCreated(SignedDkgMessage, Vec<SocketAddr>),
+
+    /// `DkgMessageReceived(SignedDkgMessage)` carries a DKG message sent by
+    /// another quorum peer.
+    DkgMessageReceived(SignedDkgMessage),
+
+    /// `DkgPhaseTimedOut(QuorumKind, DkgPhaseToken)` is emitted once a phase
+    /// of the DKG run of the given quorum went on for too long, so it can be
+    /// retried or abandoned.
+    DkgPhaseTimedOut(QuorumKind, DkgPhaseToken),
+
+    /// `QuorumHandOffMessageCreated(QuorumHandOffMessage, Vec<SocketAddr>)`
+    /// asks the network module to send a message of a quorum key hand-off to
+    /// the quorum members listening at the given addresses.
+    QuorumHandOffMessageCreated(QuorumHandOffMessage, Vec<SocketAddr>),
+
+    /// `QuorumHandOffMessageReceived(QuorumHandOffMessage)` carries a message
+    /// of a quorum key hand-off broadcast by another node.
+    QuorumHandOffMessageReceived(QuorumHandOffMessage),
+
+    /// `Farm` asks a farmer to vote on the next batch of transactions pending
+    /// in its mempool.
+    Farm,
+
+    /// `FarmIntervalElapsed` lets a farmer know the transactions that arrived
+    /// since it last farmed can be voted on as a batch.
+    FarmIntervalElapsed,
+
+    /// `ProcessedVotes(JobResult)` carries the votes the job scheduler cast on
+    /// a batch of farmed transactions.
+    ProcessedVotes(JobResult),
+
+    /// `TxnForwardReceived(SignedTxnForward)` carries a transaction a peer
+    /// forwarded, whose signature the network module checks before handing
+    /// it over to the state manager.
+    TxnForwardReceived(SignedTxnForward),
+
+    /// `ForwardedTxnReceived(Option<NodeId>, Txn)` carries a forwarded
+    /// transaction along with the id of the peer that forwarded it, if its
+    /// signature could be checked. It still has to make it through the gossip
+    /// filter before it's added to the mempool.
+    ForwardedTxnReceived(Option<NodeId>, Txn),
+
+    /// `TxnForwarded(TxnRecord, Vec<SocketAddr>)` asks the network module to
+    /// hand a transaction over to the farmer quorum at the given addresses,
+    /// which is the one supposed to vote on it.
+    TxnForwarded(TxnRecord, Vec<SocketAddr>),
+
+    /// `VoteCreated(Vote)` asks the network module to broadcast a farmer's
+    /// vote on a transaction to the harvesters.
+    VoteCreated(Vote),
+
+    /// `VoteReceived(Vote)` carries a vote broadcast by a farmer.
+    VoteReceived(Vote),
+
+    /// `FarmerQuorumKeySetCreated(FarmerQuorumKeySet)` asks the network
+    /// module to broadcast the keys of the local farmer quorum.
+    FarmerQuorumKeySetCreated(FarmerQuorumKeySet),
+
+    /// `FarmerQuorumKeySetReceived(FarmerQuorumKeySet)` carries the keys of a
+    /// farmer quorum broadcast by one of its members.
+    FarmerQuorumKeySetReceived(FarmerQuorumKeySet),
+
+    /// `EvidenceMessageCreated(EvidenceMessage)` asks the network module to
+    /// broadcast a message of the certification of misbehaviour evidence.
+    EvidenceMessageCreated(EvidenceMessage),
+
+    /// `EvidenceMessageReceived(EvidenceMessage)` carries a message of the
+    /// certification of misbehaviour evidence broadcast by another node.
+    EvidenceMessageReceived(EvidenceMessage),
+
+    /// `EvidenceCertified(Evidence)` is emitted once evidence of misbehaviour
+    /// was certified, so it can be stored and included in the next
+    /// convergence block.
+    EvidenceCertified(Evidence),
+
+    /// `NewStakeCreated(Stake)` is triggered when a new stake is received from
+    /// the rpc node and needs to be certified by the harvester quorum.
+    NewStakeCreated(Stake),
+
+    /// `StakeMessageCreated(StakeMessage)` asks the network module to
+    /// broadcast a message of the certification of a stake.
+    StakeMessageCreated(StakeMessage),
+
+    /// `StakeMessageReceived(StakeMessage)` carries a message of the
+    /// certification of a stake broadcast by another node.
+    StakeMessageReceived(StakeMessage),
+
+    /// `StakeCertified(Stake)` is emitted once a stake was certified, so it
+    /// can be included in the next convergence block, which applies it to
+    /// the claim it targets once committed.
+    StakeCertified(Stake),
+
+    /// `SyncRequested` asks the node to catch up with the chain from its
+    /// peers, once it joined the network.
+    SyncRequested,
+
+    /// `SyncMessageCreated(SyncMessage, Option<SocketAddr>)` asks the network
+    /// module to send a sync message to the peer at the given address, or to
+    /// the closest peers if none is given.
+    SyncMessageCreated(SyncMessage, Option<SocketAddr>),
+
+    /// `SyncMessageReceived(SyncMessage, SocketAddr)` carries a sync message
+    /// sent by the peer listening at the given address.
+    SyncMessageReceived(SyncMessage, SocketAddr),
+
+    /// `StateSnapshotCertified(StateSnapshot, String, Epoch)` is emitted once
+    /// a state snapshot was fetched at the block a certified block builds on,
+    /// so it can be loaded into state and checked against the hex encoded
+    /// state root the certified block was certified on. The epoch is the one
+    /// of the certified block.
+    StateSnapshotCertified(StateSnapshot, String, Epoch),
+
+    /// `StateSnapshotLoaded(BlockHash)` is emitted once the state snapshot
+    /// taken at the given block matched its certified state root and was
+    /// loaded into state.
+    StateSnapshotLoaded(BlockHash),
+
+    /// `StateSnapshotRejected(BlockHash)` is emitted when the state snapshot
+    /// taken at the given block could not be loaded into state, e.g. because
+    /// it doesn't match its certified state root.
+    StateSnapshotRejected(BlockHash),
+
     // TODO: refactor all the events below
     // ==========================================================================
     ///
@@ -130,6 +297,11 @@ pub enum Event {
     /// after a last new convergence block has been certified .
     UpdateState(BlockHash),
 
+    /// `AccountsUpdated(Vec<Address>)` is emitted once the state has been
+    /// updated to a new block and carries the addresses of every account
+    /// the block changed.
+    AccountsUpdated(Vec<Address>),
+
     /// `MineProposalBlock` is an event that triggers the mining of a proposal
     /// block by a farmer node after every `X` seconds. The proposal block
     /// contains a list of transactions that have been validated and certified
@@ -167,8 +339,15 @@ pub enum Event {
     PeerConvergenceBlockSign(NodeIdx, BlockHash, PublicKeyShareVec, RawSignature),
 
     /// `SendPeerConvergenceBlockSign` is an event that triggers the sharing of
-    /// a convergence block partial signature with other peers.
-    SendPeerConvergenceBlockSign(NodeIdx, BlockHash, PublicKeyShareVec, RawSignature),
+    /// a convergence block partial signature with the other harvesters,
+    /// listening at the given addresses.
+    SendPeerConvergenceBlockSign(
+        NodeIdx,
+        BlockHash,
+        PublicKeyShareVec,
+        RawSignature,
+        Vec<SocketAddr>,
+    ),
 
     /// `SendBlockCertificate(Certificate)` is an event that triggers the
     /// sending of a `Certificate` object representing a proof that a block
@@ -200,6 +379,80 @@ pub enum Event {
     Ping(NodeId),
 }
 
+impl Event {
+    /// Returns the name of the event's variant, e.g. `TxnAddedToMempool`.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            Event::NoOp => "NoOp",
+            Event::Stop => "Stop",
+            Event::NewTxnCreated(..) => "NewTxnCreated",
+            Event::TxnValidated(..) => "TxnValidated",
+            Event::TxnAddedToMempool(..) => "TxnAddedToMempool",
+            Event::MempoolSizeThesholdReached { .. } => "MempoolSizeThesholdReached",
+            Event::BlockReceived(..) => "BlockReceived",
+            Event::BlockConfirmed(..) => "BlockConfirmed",
+            Event::ClaimCreated(..) => "ClaimCreated",
+            Event::ClaimReceived(..) => "ClaimReceived",
+            Event::ClaimAbandoned(..) => "ClaimAbandoned",
+            Event::PeerBehaviourObserved(..) => "PeerBehaviourObserved",
+            Event::PeerJoined(..) => "PeerJoined",
+            Event::NodeAddedToPeerList(..) => "NodeAddedToPeerList",
+            Event::PeerUnregistered(..) => "PeerUnregistered",
+            Event::NodeRemovedFromPeerList(..) => "NodeRemovedFromPeerList",
+            Event::PingIntervalElapsed => "PingIntervalElapsed",
+            Event::NetworkReady(..) => "NetworkReady",
+            Event::NetworkReadinessTimedOut => "NetworkReadinessTimedOut",
+            Event::CreateAccountRequested(..) => "CreateAccountRequested",
+            Event::AccountUpdateRequested(..) => "AccountUpdateRequested",
+            Event::PeerSyncFailed(..) => "PeerSyncFailed",
+            Event::BlockCreated(..) => "BlockCreated",
+            Event::QuorumMembershipAssigmentCreated(..) => "QuorumMembershipAssigmentCreated",
+            Event::DkgProtocolInitiated => "DkgProtocolInitiated",
+            Event::DkgMessageCreated(..) => "DkgMessageCreated",
+            Event::DkgMessageReceived(..) => "DkgMessageReceived",
+            Event::DkgPhaseTimedOut(..) => "DkgPhaseTimedOut",
+            Event::QuorumHandOffMessageCreated(..) => "QuorumHandOffMessageCreated",
+            Event::QuorumHandOffMessageReceived(..) => "QuorumHandOffMessageReceived",
+            Event::Farm => "Farm",
+            Event::FarmIntervalElapsed => "FarmIntervalElapsed",
+            Event::ProcessedVotes(..) => "ProcessedVotes",
+            Event::TxnForwardReceived(..) => "TxnForwardReceived",
+            Event::ForwardedTxnReceived(..) => "ForwardedTxnReceived",
+            Event::TxnForwarded(..) => "TxnForwarded",
+            Event::VoteCreated(..) => "VoteCreated",
+            Event::VoteReceived(..) => "VoteReceived",
+            Event::FarmerQuorumKeySetCreated(..) => "FarmerQuorumKeySetCreated",
+            Event::FarmerQuorumKeySetReceived(..) => "FarmerQuorumKeySetReceived",
+            Event::EvidenceMessageCreated(..) => "EvidenceMessageCreated",
+            Event::EvidenceMessageReceived(..) => "EvidenceMessageReceived",
+            Event::EvidenceCertified(..) => "EvidenceCertified",
+            Event::NewStakeCreated(..) => "NewStakeCreated",
+            Event::StakeMessageCreated(..) => "StakeMessageCreated",
+            Event::StakeMessageReceived(..) => "StakeMessageReceived",
+            Event::StakeCertified(..) => "StakeCertified",
+            Event::SyncRequested => "SyncRequested",
+            Event::SyncMessageCreated(..) => "SyncMessageCreated",
+            Event::SyncMessageReceived(..) => "SyncMessageReceived",
+            Event::StateSnapshotCertified(..) => "StateSnapshotCertified",
+            Event::StateSnapshotLoaded(..) => "StateSnapshotLoaded",
+            Event::StateSnapshotRejected(..) => "StateSnapshotRejected",
+            Event::UpdateState(..) => "UpdateState",
+            Event::AccountsUpdated(..) => "AccountsUpdated",
+            Event::MineProposalBlock(..) => "MineProposalBlock",
+            Event::ConvergenceBlockPartialSign(..) => "ConvergenceBlockPartialSign",
+            Event::CheckConflictResolution(..) => "CheckConflictResolution",
+            Event::SignConvergenceBlock(..) => "SignConvergenceBlock",
+            Event::PeerConvergenceBlockSign(..) => "PeerConvergenceBlockSign",
+            Event::SendPeerConvergenceBlockSign(..) => "SendPeerConvergenceBlockSign",
+            Event::SendBlockCertificate(..) => "SendBlockCertificate",
+            Event::BlockCertificate(..) => "BlockCertificate",
+            Event::PrecheckConvergenceBlock(..) => "PrecheckConvergenceBlock",
+            Event::HarvesterPublicKey(..) => "HarvesterPublicKey",
+            Event::Ping(..) => "Ping",
+        }
+    }
+}
+
 impl From<&theater::Message> for Event {
     fn from(msg: &theater::Message) -> Self {
         serde_json::from_slice(&msg.data).unwrap_or_default()
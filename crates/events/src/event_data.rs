@@ -1,13 +1,30 @@
-use std::net::SocketAddr;
+use std::{
+    hash::{Hash, Hasher},
+    net::SocketAddr,
+};
 
-use block::BlockHash;
-use hbbft::crypto::PublicKeyShare;
+use block::{Block, BlockHash, Evidence, Misbehaviour, QuorumHandOff, QuorumPubkey};
+use hbbft::crypto::{
+    PublicKey,
+    PublicKeySet,
+    PublicKeyShare,
+    SecretKey,
+    Signature,
+    SignatureShare,
+    SIG_SIZE,
+};
+use mempool::TxnRecord;
 use primitives::{
-    ByteVec, FarmerId, FarmerQuorumThreshold, IsTxnValid, KademliaPeerId, NodeId, NodeIdx,
-    NodeType, QuorumKind, RawSignature,
+    Address, ByteVec, Epoch, FarmerId, FarmerQuorumThreshold, IsTxnValid, KademliaPeerId, NodeId, NodeIdx,
+    NodeType, QuorumKind, RawSignature, Round,
 };
 use serde::{Deserialize, Serialize};
-use vrrb_core::txn::{TransactionDigest, Txn};
+use vrrb_core::{
+    account::Account,
+    keypair::Keypair,
+    staking::Stake,
+    txn::{TransactionDigest, Txn},
+};
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PeerData {
@@ -17,6 +34,8 @@ pub struct PeerData {
     pub udp_gossip_addr: SocketAddr,
     pub raptorq_gossip_addr: SocketAddr,
     pub kademlia_liveness_addr: SocketAddr,
+    /// Key the peer signs its messages with while taking part in DKG
+    pub validator_public_key: PublicKey,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
@@ -37,6 +56,8 @@ pub struct Vote {
     /// The identity of the voter.
     pub farmer_id: Vec<u8>,
     pub farmer_node_id: NodeIdx,
+    /// Address credited with the voter's share of the validator fees.
+    pub farmer_address: Address,
     /// Partial Signature
     pub signature: RawSignature,
     pub txn: Txn,
@@ -108,3 +129,348 @@ pub struct AssignedQuorumMembership {
     pub quorum_kind: QuorumKind,
     pub peers: Vec<PeerData>,
 }
+
+/// Messages exchanged by the members of a quorum while running DKG. Each
+/// member first shares its validator public key, then its `Part` commitment
+/// once every member's key is known, and finally an `Ack` for every `Part`
+/// it received.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub enum DkgMessage {
+    PublicKeyShared {
+        quorum_kind: QuorumKind,
+        public_key: ByteVec,
+    },
+    PartCommitmentCreated {
+        quorum_kind: QuorumKind,
+        part: ByteVec,
+    },
+    PartCommitmentAcknowledged {
+        quorum_kind: QuorumKind,
+        part_sender_id: NodeId,
+        ack: ByteVec,
+    },
+}
+
+impl DkgMessage {
+    pub fn quorum_kind(&self) -> &QuorumKind {
+        match self {
+            DkgMessage::PublicKeyShared { quorum_kind, .. } => quorum_kind,
+            DkgMessage::PartCommitmentCreated { quorum_kind, .. } => quorum_kind,
+            DkgMessage::PartCommitmentAcknowledged { quorum_kind, .. } => quorum_kind,
+        }
+    }
+}
+
+/// A `DkgMessage` signed with the validator key of the quorum member that
+/// sent it, so members can't be impersonated during a DKG run.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct SignedDkgMessage {
+    pub sender_id: NodeId,
+    pub message: DkgMessage,
+    pub signature: RawSignature,
+}
+
+impl SignedDkgMessage {
+    pub fn new(sender_id: NodeId, message: DkgMessage, secret_key: &SecretKey) -> Self {
+        let signature = secret_key
+            .sign(Self::payload(&sender_id, &message))
+            .to_bytes()
+            .to_vec();
+
+        Self {
+            sender_id,
+            message,
+            signature,
+        }
+    }
+
+    /// Returns the payload signed by the sender.
+    pub fn payload(sender_id: &NodeId, message: &DkgMessage) -> Vec<u8> {
+        // NOTE: serializing strings, byte vectors and enums can not fail
+        bincode::serialize(&(sender_id, message)).unwrap_or_default()
+    }
+
+    /// Checks that the message was signed with the given validator key.
+    pub fn verify(&self, public_key: &PublicKey) -> bool {
+        TryInto::<[u8; SIG_SIZE]>::try_into(self.signature.clone())
+            .ok()
+            .and_then(|bytes| Signature::from_bytes(bytes).ok())
+            .map(|signature| {
+                public_key.verify(&signature, Self::payload(&self.sender_id, &self.message))
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// Notice a peer signs with its validator key as it leaves the network, so
+/// peers can't be evicted on someone else's behalf.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct SignedPeerLeave {
+    pub node_id: NodeId,
+    pub signature: RawSignature,
+}
+
+impl SignedPeerLeave {
+    pub fn new(node_id: NodeId, secret_key: &SecretKey) -> Self {
+        let signature = secret_key.sign(Self::payload(&node_id)).to_bytes().to_vec();
+
+        Self { node_id, signature }
+    }
+
+    /// Returns the payload signed by the leaving peer.
+    pub fn payload(node_id: &NodeId) -> Vec<u8> {
+        format!("leave:{node_id}").into_bytes()
+    }
+
+    /// Checks that the notice was signed with the given validator key.
+    pub fn verify(&self, public_key: &PublicKey) -> bool {
+        TryInto::<[u8; SIG_SIZE]>::try_into(self.signature.clone())
+            .ok()
+            .and_then(|bytes| Signature::from_bytes(bytes).ok())
+            .map(|signature| public_key.verify(&signature, Self::payload(&self.node_id)))
+            .unwrap_or(false)
+    }
+}
+
+/// A transaction a peer forwarded to the farmer quorum owning it, signed with
+/// the validator key of the peer so its forwarding rate can be limited.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct SignedTxnForward {
+    pub sender_id: NodeId,
+    pub txn_record: TxnRecord,
+    pub signature: RawSignature,
+}
+
+impl SignedTxnForward {
+    pub fn new(sender_id: NodeId, txn_record: TxnRecord, secret_key: &SecretKey) -> Self {
+        let payload = Self::payload(&sender_id, &txn_record.txn.id());
+        let signature = secret_key.sign(payload).to_bytes().to_vec();
+
+        Self {
+            sender_id,
+            txn_record,
+            signature,
+        }
+    }
+
+    /// Returns the payload signed by the forwarding peer.
+    pub fn payload(sender_id: &NodeId, txn_id: &TransactionDigest) -> Vec<u8> {
+        format!("forward:{sender_id}:{txn_id}").into_bytes()
+    }
+
+    /// Checks that the transaction was forwarded by the holder of the given
+    /// validator key.
+    pub fn verify(&self, public_key: &PublicKey) -> bool {
+        let payload = Self::payload(&self.sender_id, &self.txn_record.txn.id());
+
+        TryInto::<[u8; SIG_SIZE]>::try_into(self.signature.clone())
+            .ok()
+            .and_then(|bytes| Signature::from_bytes(bytes).ok())
+            .map(|signature| public_key.verify(&signature, payload))
+            .unwrap_or(false)
+    }
+}
+
+/// Messages exchanged while an outgoing quorum hands its duties over to the
+/// quorum replacing it. The incoming quorum asks for its group public key to
+/// be certified, the members of the outgoing quorum sign it, and the first
+/// member to gather enough signatures shares the certified hand-off.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub enum QuorumHandOffMessage {
+    Requested {
+        quorum_kind: QuorumKind,
+        epoch: Epoch,
+        incoming_pubkey: QuorumPubkey,
+    },
+    Signed {
+        quorum_kind: QuorumKind,
+        epoch: Epoch,
+        outgoing_pubkey: QuorumPubkey,
+        incoming_pubkey: QuorumPubkey,
+        signer_idx: NodeIdx,
+        signature: RawSignature,
+    },
+    Certified {
+        quorum_kind: QuorumKind,
+        hand_off: QuorumHandOff,
+    },
+}
+
+impl QuorumHandOffMessage {
+    pub fn quorum_kind(&self) -> &QuorumKind {
+        match self {
+            QuorumHandOffMessage::Requested { quorum_kind, .. } => quorum_kind,
+            QuorumHandOffMessage::Signed { quorum_kind, .. } => quorum_kind,
+            QuorumHandOffMessage::Certified { quorum_kind, .. } => quorum_kind,
+        }
+    }
+}
+
+/// Messages exchanged while the harvester quorum certifies evidence of
+/// misbehaviour. The member that detected the misbehaviour shares it, every
+/// member checks and signs it, and the first member to gather enough
+/// signatures shares the certified evidence.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub enum EvidenceMessage {
+    Detected {
+        misbehaviour: Misbehaviour,
+        offender: Address,
+    },
+    Signed {
+        misbehaviour: Misbehaviour,
+        offender: Address,
+        signer_idx: NodeIdx,
+        signature: RawSignature,
+    },
+    Certified(Evidence),
+}
+
+/// Messages exchanged while the harvester quorum certifies a stake. The node
+/// the stake was submitted to shares it, every member checks and signs it,
+/// and the first member to gather enough signatures shares the certified
+/// stake.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub enum StakeMessage {
+    Submitted(Stake),
+    Signed {
+        stake: Stake,
+        signer_idx: NodeIdx,
+        signature: RawSignature,
+    },
+    Certified(Stake),
+}
+
+/// Group keys and addresses of a farmer quorum, shared by each of its members
+/// once its DKG run completed so transactions can be routed to it and
+/// harvesters can aggregate the votes of its members.
+///
+/// A member vouches for the keys with its share of them and with the key of
+/// its claim, so nodes only accept keys enough elected members generated.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub struct FarmerQuorumKeySet {
+    pub public_key_set: PublicKeySet,
+    pub peers: Vec<SocketAddr>,
+    pub member_id: NodeId,
+    pub member_idx: NodeIdx,
+    /// Signature of the member's share of the keys
+    pub share_signature: RawSignature,
+    /// Signature of the key of the member's claim
+    pub claim_signature: String,
+}
+
+impl FarmerQuorumKeySet {
+    /// Returns the payload members sign when vouching for the keys.
+    pub fn payload(
+        public_key_set: &PublicKeySet,
+        peers: &[SocketAddr],
+        member_id: &NodeId,
+        member_idx: NodeIdx,
+    ) -> Vec<u8> {
+        // NOTE: serializing keys, addresses and strings can not fail
+        bincode::serialize(&(public_key_set, peers, member_id, member_idx)).unwrap_or_default()
+    }
+
+    /// Checks that the keys were vouched for by the holder of the given share
+    /// of them and of the given claim key.
+    pub fn verify(&self, claim_public_key: &secp256k1::PublicKey) -> bool {
+        let payload = Self::payload(
+            &self.public_key_set,
+            &self.peers,
+            &self.member_id,
+            self.member_idx,
+        );
+
+        let is_share_signature_valid =
+            TryInto::<[u8; SIG_SIZE]>::try_into(self.share_signature.as_slice())
+                .ok()
+                .and_then(|bytes| SignatureShare::from_bytes(bytes).ok())
+                .map(|signature_share| {
+                    self.public_key_set
+                        .public_key_share(self.member_idx as usize)
+                        .verify(&signature_share, &payload)
+                })
+                .unwrap_or(false);
+
+        is_share_signature_valid
+            && Keypair::verify_ecdsa_sign(
+                self.claim_signature.clone(),
+                &payload,
+                claim_public_key.serialize().to_vec(),
+            )
+            .is_ok()
+    }
+}
+
+/// Blocks a syncing node asks its peers for.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub enum BlockRequest {
+    /// The blocks with the given hashes
+    ByHash(Vec<BlockHash>),
+    /// The blocks of every round from `from_round` on, up to `to_round` if
+    /// given
+    ByRange {
+        from_round: Round,
+        to_round: Option<Round>,
+    },
+    /// The latest certified convergence block
+    Latest,
+}
+
+/// Accounts within state right after the block with the given hash was
+/// committed. Snapshots are provided in pages of accounts ordered by their key
+/// within the state trie, each starting right after the `after` address.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+pub struct StateSnapshot {
+    pub block_hash: BlockHash,
+    pub after: Option<Address>,
+    pub accounts: Vec<(Address, Account)>,
+    /// Whether no accounts are left after this page
+    pub is_last: bool,
+}
+
+// NOTE: accounts can't be hashed, so snapshots are told apart by their block
+// and the addresses they hold
+impl Hash for StateSnapshot {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.block_hash.hash(state);
+        self.after.hash(state);
+        self.accounts
+            .iter()
+            .for_each(|(address, _)| address.hash(state));
+    }
+}
+
+/// Messages exchanged while a node joining or restarting catches up with the
+/// chain. The syncing node asks its peers for blocks and, when fast syncing,
+/// for a snapshot of the state a certified block was certified on, which
+/// they provide. Provided blocks come along with the request they answer.
+/// Nodes that don't hold the harvester keys themselves ask for the group
+/// public key of the genesis harvester quorum along with the hand-offs to the
+/// quorums that replaced it, so they can check block certificates.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone)]
+pub enum SyncMessage {
+    BlocksRequested(BlockRequest),
+    BlocksProvided(BlockRequest, Vec<Block>),
+    StateSnapshotRequested(BlockHash, Option<Address>),
+    StateSnapshotProvided(StateSnapshot),
+    HarvesterKeysRequested,
+    HarvesterKeysProvided(QuorumPubkey, Vec<QuorumHandOff>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leave_notices_are_authenticated_with_the_key_the_peer_joined_with() {
+        let node_id = "node-1".to_string();
+        let secret_key = SecretKey::random();
+        let joined_with = secret_key.public_key();
+
+        let signed_leave = SignedPeerLeave::new(node_id.clone(), &secret_key);
+        assert!(signed_leave.verify(&joined_with));
+
+        let forged_leave = SignedPeerLeave::new(node_id, &SecretKey::random());
+        assert!(!forged_leave.verify(&joined_with));
+    }
+}
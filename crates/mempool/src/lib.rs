@@ -44,7 +44,7 @@ mod tests {
         txn::{NewTxnArgs, Txn},
     };
 
-    use crate::mempool::{LeftRightMempool, TxnRecord, TxnStatus};
+    use crate::mempool::{LeftRightMempool, TxnFilter, TxnRecord, TxnStatus};
 
     fn mock_txn_signature() -> Signature {
         ecdsa::Signature::from_compact(&[
@@ -534,4 +534,107 @@ mod tests {
                 handle.join().unwrap();
             });
     }
+
+    #[test]
+    fn pages_through_filtered_txns_in_digest_order() {
+        let sender_keypair = KeyPair::random();
+        let sender_address = Address::new(sender_keypair.get_miner_public_key().clone());
+        let other_keypair = KeyPair::random();
+
+        let mut mpooldb = LeftRightMempool::new();
+
+        for nonce in 0..5 {
+            let txn = Txn::new(NewTxnArgs {
+                timestamp: 0,
+                sender_address: sender_address.clone(),
+                sender_public_key: sender_keypair.get_miner_public_key().clone(),
+                receiver_address: Address::new(other_keypair.get_miner_public_key().clone()),
+                token: None,
+                amount: 0,
+                validators: Some(HashMap::<String, bool>::new()),
+                nonce,
+                signature: mock_txn_signature(),
+            });
+
+            mpooldb.insert(txn).unwrap();
+        }
+
+        let txn = Txn::new(NewTxnArgs {
+            timestamp: 0,
+            sender_address: Address::new(other_keypair.get_miner_public_key().clone()),
+            sender_public_key: other_keypair.get_miner_public_key().clone(),
+            receiver_address: sender_address.clone(),
+            token: None,
+            amount: 0,
+            validators: Some(HashMap::<String, bool>::new()),
+            nonce: 0,
+            signature: mock_txn_signature(),
+        });
+
+        mpooldb.insert(txn).unwrap();
+
+        let factory = mpooldb.factory();
+        let filter = TxnFilter {
+            sender: Some(sender_address),
+            ..Default::default()
+        };
+
+        let first_page = factory.page(None, 3, &filter);
+        assert_eq!(first_page.len(), 3);
+
+        let last_digest = first_page.last().map(|record| record.txn_id.clone());
+        let second_page = factory.page(last_digest.as_ref(), 3, &filter);
+        assert_eq!(second_page.len(), 2);
+
+        let digests: Vec<_> = first_page
+            .iter()
+            .chain(second_page.iter())
+            .map(|record| record.txn_id.clone())
+            .collect();
+
+        let mut sorted_digests = digests.clone();
+        sorted_digests.sort();
+
+        assert_eq!(digests, sorted_digests);
+        assert_eq!(factory.page(None, 10, &TxnFilter::default()).len(), 6);
+    }
+
+    #[test]
+    fn batches_leave_out_skipped_transactions() {
+        let sender_keypair = KeyPair::random();
+        let sender_address = Address::new(sender_keypair.get_miner_public_key().clone());
+        let receiver_address = Address::new(KeyPair::random().get_miner_public_key().clone());
+
+        let mut mpooldb = LeftRightMempool::new();
+
+        for nonce in 0..5 {
+            let txn = Txn::new(NewTxnArgs {
+                timestamp: 0,
+                sender_address: sender_address.clone(),
+                sender_public_key: sender_keypair.get_miner_public_key().clone(),
+                receiver_address: receiver_address.clone(),
+                token: None,
+                amount: 0,
+                validators: Some(HashMap::<String, bool>::new()),
+                nonce,
+                signature: mock_txn_signature(),
+            });
+
+            mpooldb.insert(txn).unwrap();
+        }
+
+        let factory = mpooldb.factory();
+        let first_batch = factory.batch(3, |_| false);
+        assert_eq!(first_batch.len(), 3);
+        assert!(first_batch
+            .iter()
+            .all(|(txn_id, _)| factory.contains(txn_id)));
+
+        let farmed: Vec<_> = first_batch.into_iter().map(|(txn_id, _)| txn_id).collect();
+        let second_batch = factory.batch(3, |txn_id| farmed.contains(txn_id));
+        assert_eq!(second_batch.len(), 2);
+        assert!(second_batch
+            .iter()
+            .all(|(txn_id, _)| !farmed.contains(txn_id)));
+    }
 }
@@ -1,14 +1,16 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     hash::Hash,
+    ops::Bound,
     result::Result as StdResult,
 };
 
 use fxhash::FxBuildHasher;
 use indexmap::IndexMap;
 use left_right::{Absorb, ReadHandle, ReadHandleFactory, WriteHandle};
+use primitives::Address;
 use serde::{Deserialize, Serialize};
-use vrrb_core::txn::{TransactionDigest, TxTimestamp, Txn};
+use vrrb_core::txn::{Token, TransactionDigest, TxTimestamp, Txn};
 
 use super::error::MempoolError;
 
@@ -50,6 +52,31 @@ impl TxnRecord {
 
 pub type PoolType = IndexMap<TransactionDigest, TxnRecord, FxBuildHasher>;
 
+/// Criteria a mempool entry must match to be returned by
+/// `MempoolReadHandleFactory::page`. Unset fields match every entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxnFilter {
+    pub sender: Option<Address>,
+    pub receiver: Option<Address>,
+    pub token: Option<Token>,
+}
+
+impl TxnFilter {
+    pub fn matches(&self, txn: &Txn) -> bool {
+        self.sender
+            .as_ref()
+            .map_or(true, |sender| sender == &txn.sender_address())
+            && self
+                .receiver
+                .as_ref()
+                .map_or(true, |receiver| receiver == &txn.receiver_address())
+            && self
+                .token
+                .as_ref()
+                .map_or(true, |token| token == &txn.token())
+    }
+}
+
 #[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TxnStatus {
     #[default]
@@ -63,6 +90,9 @@ pub enum TxnStatus {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Mempool {
     pool: PoolType,
+    /// Digests of the records within the pool, in order, used to page
+    /// through it.
+    digests: BTreeSet<TransactionDigest>,
 }
 
 pub const DEFAULT_INITIAL_MEMPOOL_CAPACITY: usize = 10000;
@@ -74,6 +104,7 @@ impl Default for Mempool {
                 DEFAULT_INITIAL_MEMPOOL_CAPACITY,
                 <_>::default(),
             ),
+            digests: BTreeSet::new(),
         }
     }
 }
@@ -98,9 +129,11 @@ impl Absorb<MempoolOp> for Mempool {
     fn absorb_first(&mut self, op: &mut MempoolOp, _: &Self) {
         match op {
             MempoolOp::Add(record) => {
+                self.digests.insert(record.txn_id.clone());
                 self.pool.insert(record.txn_id.clone(), *record.clone());
             },
             MempoolOp::Remove(id) => {
+                self.digests.remove(id);
                 self.pool.remove(id);
             },
         }
@@ -401,4 +434,63 @@ impl MempoolReadHandleFactory {
         }
         None
     }
+
+    /// Returns whether the transaction with the given digest is pending,
+    /// without cloning the pool.
+    pub fn contains(&self, digest: &TransactionDigest) -> bool {
+        self.factory
+            .handle()
+            .enter()
+            .map_or(false, |guard| guard.pool.contains_key(digest))
+    }
+
+    /// Returns up to `limit` records ordered by their digest, leaving out the
+    /// ones `skip` returns true for. Only the returned records are cloned.
+    pub fn batch(
+        &self,
+        limit: usize,
+        skip: impl Fn(&TransactionDigest) -> bool,
+    ) -> Vec<(TransactionDigest, TxnRecord)> {
+        let handle = self.factory.handle();
+        let guard = match handle.enter() {
+            Some(guard) => guard,
+            None => return Vec::new(),
+        };
+
+        guard
+            .digests
+            .iter()
+            .filter(|digest| !skip(digest))
+            .filter_map(|digest| guard.pool.get(digest))
+            .take(limit)
+            .map(|record| (record.txn_id.clone(), record.clone()))
+            .collect()
+    }
+
+    /// Returns up to `limit` records matching `filter`, ordered by their
+    /// digest and starting right after the `after` digest. Only the returned
+    /// records are cloned out of the pool, which is read from the cursor on.
+    pub fn page(
+        &self,
+        after: Option<&TransactionDigest>,
+        limit: usize,
+        filter: &TxnFilter,
+    ) -> Vec<TxnRecord> {
+        let handle = self.factory.handle();
+        let guard = match handle.enter() {
+            Some(guard) => guard,
+            None => return Vec::new(),
+        };
+
+        let start = after.map_or(Bound::Unbounded, Bound::Excluded);
+
+        guard
+            .digests
+            .range((start, Bound::Unbounded))
+            .filter_map(|digest| guard.pool.get(digest))
+            .filter(|record| filter.matches(&record.txn))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
 }
@@ -14,11 +14,14 @@ use block::{
     ConsolidatedClaims,
     ConsolidatedTxns,
     ConvergenceBlock,
+    Evidence,
+    EvidenceList,
     GenesisBlock,
     InnerBlock,
     ProposalBlock,
     QuorumCertifiedTxnList,
     RefHash,
+    StakeList,
 };
 use bulldag::graph::BullDag;
 use ethereum_types::U256;
@@ -30,11 +33,11 @@ use secp256k1::{
     Message,
 };
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use utils::{create_payload, hash_data};
 use vrrb_core::{
     claim::{Claim, ClaimError},
     keypair::{MinerPk, MinerSk},
+    staking::Stake,
 };
 
 use crate::{block_builder::BlockBuilder, result::MinerError};
@@ -99,7 +102,7 @@ pub struct MinerConfig {
 /// use vrrb_core::{claim::Claim, keypair::{MinerPk, MinerSk}};
 /// use primitives::Address;
 /// use miner::{conflict_resolver::Resolver, block_builder::BlockBuilder, miner::MinerStatus};
-/// use block::{Block, ConvergenceBlock, header::BlockHeader, InnerBlock};
+/// use block::{Block, ConvergenceBlock, EvidenceList, header::BlockHeader, InnerBlock, StakeList};
 /// use reward::reward::Reward;
 /// use std::sync::{Arc, RwLock};
 /// use bulldag::graph::BullDag;
@@ -115,6 +118,8 @@ pub struct MinerConfig {
 ///     pub last_block: Option<Arc<dyn InnerBlock<Header = BlockHeader, RewardType = Reward>>>,
 ///     pub status: MinerStatus,
 ///     pub next_epoch_adjustment: i128,
+///     pub pending_evidence: EvidenceList,
+///     pub pending_stakes: StakeList,
 /// }
 #[derive(Debug, Clone)]
 pub struct Miner {
@@ -127,6 +132,10 @@ pub struct Miner {
     pub last_block: Option<Arc<dyn InnerBlock<Header = BlockHeader, RewardType = Reward>>>,
     pub status: MinerStatus,
     pub next_epoch_adjustment: i128,
+    /// Certified evidence waiting to be included in a convergence block
+    pub pending_evidence: EvidenceList,
+    /// Certified stakes waiting to be included in a convergence block
+    pub pending_stakes: StakeList,
 }
 
 pub type Result<T> = std::result::Result<T, MinerError>;
@@ -198,6 +207,8 @@ impl Miner {
             last_block: None,
             status: MinerStatus::Waiting,
             next_epoch_adjustment: 0,
+            pending_evidence: vec![],
+            pending_stakes: vec![],
         })
     }
 
@@ -287,6 +298,28 @@ impl Miner {
         self.status = status;
     }
 
+    /// Queues certified evidence for inclusion in the next convergence block
+    /// the local `Miner` builds.
+    pub fn add_evidence(&mut self, evidence: Evidence) {
+        let evidence_hash = evidence.hash();
+
+        if !self
+            .pending_evidence
+            .iter()
+            .any(|pending| pending.hash() == evidence_hash)
+        {
+            self.pending_evidence.push(evidence);
+        }
+    }
+
+    /// Queues a certified stake for inclusion in the next convergence block
+    /// the local `Miner` builds.
+    pub fn add_stake(&mut self, stake: Stake) {
+        if !self.pending_stakes.contains(&stake) {
+            self.pending_stakes.push(stake);
+        }
+    }
+
     /// Builds a convergence block using the `<Miner as BlockBuilder>::build()`
     /// method.
     pub fn mine_convergence_block(&self) -> Option<ConvergenceBlock> {
@@ -356,14 +389,9 @@ impl Miner {
         let mut claims = LinkedHashMap::new();
         claims.insert(claim.hash, claim);
 
-        #[cfg(mainnet)]
-        let txns = genesis::generate_genesis_txns();
-
-        // TODO: Genesis block on local/testnet should generate either a
-        // faucet for tokens, or fill some initial accounts so that testing
-        // can be executed
-
-        #[cfg(not(mainnet))]
+        // NOTE: genesis allocations are not transactions, they are written
+        // straight into the state from the genesis file, see
+        // `block::vesting::GenesisAllocations`
         let txns = LinkedHashMap::new();
         let header = header;
 
@@ -416,31 +444,13 @@ impl Miner {
     /// Hashes and returns a hexadecimal string representation of the hash of
     /// the consolidated `Txn`s
     pub(crate) fn get_txn_hash(&self, txns: &ConsolidatedTxns) -> String {
-        let mut txn_hasher = Sha256::new();
-
-        let txns_hash = {
-            if let Ok(serialized_txns) = serde_json::to_string(txns) {
-                txn_hasher.update(serialized_txns.as_bytes());
-            }
-            txn_hasher.finalize()
-        };
-
-        format!("{txns_hash:x}")
+        block::txns_hash(txns)
     }
 
     /// Hashes and returns a hexadecimal string representation of the hash of
     /// the consolidated `Claim`s
     pub(crate) fn get_claim_hash(&self, claims: &ConsolidatedClaims) -> String {
-        let mut claim_hasher = Sha256::new();
-
-        let claims_hash = {
-            if let Ok(serialized_claims) = serde_json::to_string(claims) {
-                claim_hasher.update(serialized_claims.as_bytes());
-            }
-            claim_hasher.finalize()
-        };
-
-        format!("{claims_hash:x}")
+        block::claims_hash(claims)
     }
 
     /// Builds a `BlockHeader` for the `ConvergenceBlock` being mined.
@@ -494,22 +504,7 @@ impl Miner {
     /// Hashes the current `ConvergenceBlock` being mined using
     /// the fields from the `BlockHeader`
     pub(crate) fn hash_block(&self, header: &BlockHeader) -> String {
-        let block_hash = hash_data!(
-            header.ref_hashes,
-            header.round,
-            header.block_seed,
-            header.next_block_seed,
-            header.block_height,
-            header.timestamp,
-            header.txn_hash,
-            header.miner_claim,
-            header.claim_list_hash,
-            header.block_reward,
-            header.next_block_reward,
-            header.miner_signature
-        );
-
-        format!("{block_hash:x}")
+        header.hash()
     }
 
     /// Gets the current election `seed` from the
@@ -9,6 +9,7 @@ use block::{
     Conflict,
     ConflictList,
     ConvergenceBlock,
+    EvidenceHash,
     InnerBlock,
     ProposalBlock,
     RefHash,
@@ -35,6 +36,25 @@ impl BlockBuilder for Miner {
         last_block: Option<Arc<dyn InnerBlock<Header = BlockHeader, RewardType = Reward>>>,
         adjustment: &i128,
     ) {
+        // NOTE: evidence and stakes included in the last block do not need to be
+        // included again
+        if let Some(block) = last_block
+            .as_ref()
+            .and_then(|block| block.as_static_convergence())
+        {
+            let included: HashSet<EvidenceHash> = block
+                .evidence
+                .iter()
+                .map(|evidence| evidence.hash())
+                .collect();
+
+            self.pending_evidence
+                .retain(|evidence| !included.contains(&evidence.hash()));
+
+            self.pending_stakes
+                .retain(|stake| !block.stakes.contains(stake));
+        }
+
         self.last_block = last_block;
         self.next_epoch_adjustment = *adjustment;
     }
@@ -58,6 +78,8 @@ impl BlockBuilder for Miner {
                 claims,
                 hash,
                 certificate: None,
+                evidence: self.pending_evidence.clone(),
+                stakes: self.pending_stakes.clone(),
             })
         } else {
             None
@@ -4,9 +4,10 @@ use events::{Event, EventPublisher, EventSubscriber};
 use mempool::MempoolReadHandleFactory;
 use storage::vrrbdb::VrrbDbReadHandle;
 use telemetry::info;
-use tokio::task::JoinHandle;
+use tokio::{sync::broadcast::error::RecvError, task::JoinHandle};
 use vrrb_config::NodeConfig;
-use vrrb_rpc::rpc::{JsonRpcServer, JsonRpcServerConfig};
+use vrrb_grpc::{GrpcServer, GrpcServerConfig};
+use vrrb_rpc::rpc::{JsonRpcServer, JsonRpcServerConfig, RpcSubscriptions};
 
 use crate::result::{NodeError, Result};
 
@@ -16,13 +17,20 @@ pub async fn setup_rpc_api_server(
     vrrbdb_read_handle: VrrbDbReadHandle,
     mempool_read_handle_factory: MempoolReadHandleFactory,
     mut jsonrpc_events_rx: EventSubscriber,
+    subscription_events_rx: EventSubscriber,
 ) -> Result<(JoinHandle<Result<()>>, SocketAddr)> {
+    let subscriptions = RpcSubscriptions::new(
+        vrrbdb_read_handle.clone(),
+        mempool_read_handle_factory.clone(),
+    );
+
     let jsonrpc_server_config = JsonRpcServerConfig {
         address: config.jsonrpc_server_address,
         node_type: config.node_type,
         events_tx,
         vrrbdb_read_handle,
         mempool_read_handle_factory,
+        subscriptions: subscriptions.clone(),
     };
 
     let (jsonrpc_server_handle, resolved_jsonrpc_server_addr) =
@@ -30,9 +38,17 @@ pub async fn setup_rpc_api_server(
             .await
             .map_err(|err| NodeError::Other(format!("unable to start JSON-RPC server: {err}")))?;
 
+    tokio::spawn(feed_subscriptions(subscription_events_rx, subscriptions));
+
     let jsonrpc_server_handle = tokio::spawn(async move {
-        if let Ok(evt) = jsonrpc_events_rx.recv().await {
-            if let Event::Stop = evt.into() {
+        loop {
+            let event: Event = match jsonrpc_events_rx.recv().await {
+                Ok(evt) => evt.into(),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            };
+
+            if let Event::Stop = event {
                 jsonrpc_server_handle.stop().map_err(|err| {
                     NodeError::Other(format!("JSON-RPC event has stopped: {err}"))
                 })?;
@@ -52,3 +68,120 @@ pub async fn setup_rpc_api_server(
 
     Ok((jsonrpc_server_handle, resolved_jsonrpc_server_addr))
 }
+
+/// Publishes the events the subscriptions are interested in until the node
+/// stops. The subscriber has to be on the topic blocks, mempool and account
+/// events are sent to, which is the router's default one.
+async fn feed_subscriptions(mut events_rx: EventSubscriber, subscriptions: RpcSubscriptions) {
+    loop {
+        let event: Event = match events_rx.recv().await {
+            Ok(evt) => evt.into(),
+            Err(RecvError::Lagged(missed)) => {
+                subscriptions.report_lag(missed);
+                continue;
+            },
+            Err(RecvError::Closed) => break,
+        };
+
+        if let Event::Stop = event {
+            break;
+        }
+
+        subscriptions.publish(&event);
+    }
+}
+
+pub async fn setup_grpc_api_server(
+    config: &NodeConfig,
+    events_tx: EventPublisher,
+    vrrbdb_read_handle: VrrbDbReadHandle,
+    mempool_read_handle_factory: MempoolReadHandleFactory,
+    mut grpc_events_rx: EventSubscriber,
+) -> Result<(JoinHandle<Result<()>>, SocketAddr)> {
+    let grpc_server_config = GrpcServerConfig {
+        address: config.grpc_server_address,
+        node_id: config.id.clone(),
+        node_type: config.node_type,
+        vrrbdb_read_handle,
+        mempool_read_handle_factory,
+        events_tx,
+    };
+
+    let stop_signal = async move {
+        loop {
+            let event: Event = match grpc_events_rx.recv().await {
+                Ok(evt) => evt.into(),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            };
+
+            if let Event::Stop = event {
+                break;
+            }
+        }
+    };
+
+    let (grpc_server_handle, resolved_grpc_server_addr) =
+        GrpcServer::run(&grpc_server_config, stop_signal)
+            .await
+            .map_err(|err| NodeError::Other(format!("unable to start gRPC server: {err}")))?;
+
+    let grpc_server_handle = tokio::spawn(async move {
+        grpc_server_handle.await??;
+
+        Ok(())
+    });
+
+    info!("gRPC server started at {}", resolved_grpc_server_addr);
+
+    Ok((grpc_server_handle, resolved_grpc_server_addr))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use block::Block;
+    use events::{EventRouter, DEFAULT_BUFFER};
+    use mempool::LeftRightMempool;
+    use serial_test::serial;
+    use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
+    use tokio::{sync::mpsc::channel, time::timeout};
+    use vrrb_rpc::rpc::Notification;
+
+    use super::*;
+    use crate::test_utils::produce_genesis_block;
+
+    #[tokio::test]
+    #[serial]
+    async fn subscriptions_are_notified_of_blocks_sent_through_the_router() {
+        let (events_tx, mut events_rx) = channel(DEFAULT_BUFFER);
+        let mut router = EventRouter::new();
+        let subscription_events_rx = router.subscribe(None).unwrap();
+
+        tokio::spawn(async move { router.start(&mut events_rx).await });
+
+        let db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+        let mempool = LeftRightMempool::default();
+        let subscriptions = RpcSubscriptions::new(db.read_handle(), mempool.factory());
+        let mut new_blocks = subscriptions.subscribe_new_blocks();
+
+        tokio::spawn(feed_subscriptions(subscription_events_rx, subscriptions));
+
+        let block = Block::Genesis {
+            block: produce_genesis_block(),
+        };
+
+        events_tx
+            .send(Event::BlockCreated(block.clone()).into())
+            .await
+            .unwrap();
+
+        let notification = timeout(Duration::from_secs(1), new_blocks.recv())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(matches!(notification, Notification::Value(received) if received == block));
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use block::BlockHash;
+use primitives::{NodeIdx, RawSignature};
+use vrrb_core::reputation::PeerBehaviour;
+
+/// Number of convergence blocks the signers of are remembered for.
+pub const SIGNATURE_ATTENDANCE_BLOCKS: usize = 16;
+
+/// Keeps track of which harvesters delivered their partial signature of
+/// recent convergence blocks, so that signatures arriving after a block got
+/// certified, and those that never arrived, can be told apart from the ones
+/// delivered on time. Signatures are kept until the block is certified, so
+/// the certificate can be generated from them.
+#[derive(Debug, Default)]
+pub struct SignatureAttendance {
+    blocks: HashMap<BlockHash, BlockAttendance>,
+    /// Blocks in the order they were first seen, oldest first
+    order: VecDeque<BlockHash>,
+}
+
+#[derive(Debug, Default)]
+struct BlockAttendance {
+    signers: HashSet<NodeIdx>,
+    signatures: BTreeMap<NodeIdx, RawSignature>,
+    certified: bool,
+}
+
+impl SignatureAttendance {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn block_mut(&mut self, block_hash: &BlockHash) -> &mut BlockAttendance {
+        if !self.blocks.contains_key(block_hash) {
+            if self.order.len() >= SIGNATURE_ATTENDANCE_BLOCKS {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.blocks.remove(&oldest);
+                }
+            }
+
+            self.order.push_back(block_hash.clone());
+        }
+
+        self.blocks.entry(block_hash.clone()).or_default()
+    }
+
+    /// Records the partial signature of a harvester over a block, returning
+    /// the behaviour it amounts to, or `None` if the harvester already
+    /// delivered one for the block.
+    pub fn observe_signature(
+        &mut self,
+        block_hash: &BlockHash,
+        signer_idx: NodeIdx,
+        signature: RawSignature,
+    ) -> Option<PeerBehaviour> {
+        let block = self.block_mut(block_hash);
+        if !block.signers.insert(signer_idx) {
+            return None;
+        }
+
+        if !block.certified {
+            block.signatures.insert(signer_idx, signature);
+        }
+
+        Some(PeerBehaviour::PartialSignatureDelivered {
+            on_time: !block.certified,
+        })
+    }
+
+    /// Marks a block as certified, returning the members of the quorum that
+    /// did not deliver their partial signature of it in time. Certificates
+    /// seen more than once only count the first time.
+    pub fn observe_certificate(
+        &mut self,
+        block_hash: &BlockHash,
+        members: impl IntoIterator<Item = NodeIdx>,
+    ) -> Vec<NodeIdx> {
+        let block = self.block_mut(block_hash);
+        if block.certified {
+            return Vec::new();
+        }
+
+        block.certified = true;
+        block.signatures.clear();
+
+        members
+            .into_iter()
+            .filter(|member| !block.signers.contains(member))
+            .collect()
+    }
+
+    /// Returns the partial signatures delivered for a block that was not
+    /// certified yet.
+    pub fn signature_shares(&self, block_hash: &BlockHash) -> BTreeMap<NodeIdx, RawSignature> {
+        self.blocks
+            .get(block_hash)
+            .map(|block| block.signatures.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn late_and_missing_signatures_are_told_apart() {
+        let mut attendance = SignatureAttendance::new();
+        let block_hash = "block".to_string();
+
+        assert_eq!(
+            attendance.observe_signature(&block_hash, 0, vec![0]),
+            Some(PeerBehaviour::PartialSignatureDelivered { on_time: true })
+        );
+        assert_eq!(attendance.observe_signature(&block_hash, 0, vec![0]), None);
+        assert_eq!(attendance.signature_shares(&block_hash).len(), 1);
+
+        assert_eq!(
+            attendance.observe_certificate(&block_hash, 0..3),
+            vec![1, 2]
+        );
+        assert!(attendance.observe_certificate(&block_hash, 0..3).is_empty());
+        assert!(attendance.signature_shares(&block_hash).is_empty());
+
+        assert_eq!(
+            attendance.observe_signature(&block_hash, 1, vec![1]),
+            Some(PeerBehaviour::PartialSignatureDelivered { on_time: false })
+        );
+    }
+
+    #[test]
+    fn only_recent_blocks_are_remembered() {
+        let mut attendance = SignatureAttendance::new();
+
+        for block in 0..=SIGNATURE_ATTENDANCE_BLOCKS {
+            attendance.observe_signature(&block.to_string(), 0, vec![0]);
+        }
+
+        assert_eq!(attendance.blocks.len(), SIGNATURE_ATTENDANCE_BLOCKS);
+        assert!(!attendance.blocks.contains_key("0"));
+    }
+}
@@ -1,11 +1,13 @@
 use async_trait::async_trait;
+use crossbeam_channel::Sender;
 use events::{EventPublisher, EventSubscriber};
+use mempool::MempoolReadHandleFactory;
 use storage::vrrbdb::VrrbDbReadHandle;
 use theater::{Actor, ActorImpl};
 use vrrb_config::NodeConfig;
 
 use crate::{
-    consensus::{ConsensusModule, ConsensusModuleConfig},
+    consensus::{ConsensusModule, ConsensusModuleConfig, Job},
     NodeError,
     RuntimeComponent,
     RuntimeComponentHandle,
@@ -15,7 +17,9 @@ use crate::{
 pub struct ConsensusModuleComponentConfig {
     pub events_tx: EventPublisher,
     pub vrrbdb_read_handle: VrrbDbReadHandle,
+    pub mempool_read_handle_factory: MempoolReadHandleFactory,
     pub consensus_events_rx: EventSubscriber,
+    pub sync_jobs_sender: Sender<Job>,
     pub node_config: NodeConfig,
 }
 
@@ -27,7 +31,13 @@ impl RuntimeComponent<ConsensusModuleComponentConfig, ()> for ConsensusModule {
         let module = ConsensusModule::new(ConsensusModuleConfig {
             events_tx: args.events_tx,
             vrrbdb_read_handle: args.vrrbdb_read_handle,
+            mempool_read_handle_factory: args.mempool_read_handle_factory,
+            sync_jobs_sender: args.sync_jobs_sender,
             keypair: args.node_config.keypair,
+            node_id: args.node_config.id,
+            node_type: args.node_config.node_type,
+            threshold_config: args.node_config.threshold_config,
+            data_dir: args.node_config.data_dir,
         });
 
         let mut consensus_events_rx = args.consensus_events_rx;
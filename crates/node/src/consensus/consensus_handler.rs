@@ -1,9 +1,9 @@
 use std::collections::HashSet;
 
 use async_trait::async_trait;
-use events::{Event, EventMessage, EventPublisher, EventSubscriber, Vote};
+use events::{Event, EventMessage, EventPublisher, EventSubscriber, JobResult, Vote};
 use telemetry::info;
-use theater::{Actor, ActorId, ActorImpl, ActorLabel, ActorState, Handler};
+use theater::{Actor, ActorId, ActorImpl, ActorLabel, ActorState, Handler, TheaterError};
 
 use crate::consensus::ConsensusModule;
 
@@ -38,6 +38,89 @@ impl Handler<EventMessage> for ConsensusModule {
             Event::Stop => {
                 return Ok(ActorState::Stopped);
             },
+            Event::QuorumMembershipAssigmentCreated(assigned_membership) => {
+                self.handle_quorum_membership_assigment_created(assigned_membership);
+            },
+            Event::NodeRemovedFromPeerList(peer_data) => {
+                self.handle_node_removed_from_peer_list(&peer_data.node_id);
+            },
+            Event::DkgProtocolInitiated => {
+                self.handle_dkg_protocol_initiated()
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+            Event::DkgMessageReceived(dkg_message) => {
+                // NOTE: a faulty peer should not be able to stop the module
+                if let Err(err) = self.handle_dkg_message_received(dkg_message).await {
+                    telemetry::error!("failed to handle DKG message: {}", err);
+                }
+            },
+            Event::DkgPhaseTimedOut(quorum_kind, phase_token) => {
+                self.handle_dkg_phase_timed_out(quorum_kind, phase_token)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+            Event::QuorumHandOffMessageReceived(message) => {
+                if let Err(err) = self.handle_quorum_hand_off_message(message).await {
+                    telemetry::error!("failed to handle quorum hand-off message: {}", err);
+                }
+            },
+            Event::Farm => {
+                self.handle_farm();
+            },
+            Event::FarmIntervalElapsed => {
+                self.handle_farm_interval_elapsed()
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+            Event::ProcessedVotes(JobResult::Votes((votes, _))) => {
+                if let Err(err) = self.handle_processed_votes(votes).await {
+                    telemetry::error!("failed to handle processed votes: {}", err);
+                }
+            },
+            Event::VoteReceived(vote) => {
+                if let Err(err) = self.handle_vote_received(vote) {
+                    telemetry::error!("failed to handle vote: {}", err);
+                }
+            },
+            Event::FarmerQuorumKeySetReceived(key_set) => {
+                if let Err(err) = self.handle_farmer_quorum_key_set_received(key_set) {
+                    telemetry::error!("failed to handle farmer quorum keys: {}", err);
+                }
+            },
+            Event::BlockReceived(block) => {
+                if let Err(err) = self.handle_block_received(block).await {
+                    telemetry::error!("failed to check block for misbehaviour: {}", err);
+                }
+            },
+            Event::PeerConvergenceBlockSign(signer_idx, block_hash, _, signature) => {
+                if let Err(err) = self
+                    .handle_peer_convergence_block_sign(signer_idx, block_hash, signature)
+                    .await
+                {
+                    telemetry::error!("failed to check block signature for misbehaviour: {}", err);
+                }
+            },
+            Event::BlockCertificate(certificate) => {
+                if let Err(err) = self.handle_block_certificate(certificate).await {
+                    telemetry::error!("failed to check block signers: {}", err);
+                }
+            },
+            Event::EvidenceMessageReceived(message) => {
+                if let Err(err) = self.handle_evidence_message(message).await {
+                    telemetry::error!("failed to handle evidence message: {}", err);
+                }
+            },
+            Event::NewStakeCreated(stake) => {
+                if let Err(err) = self.submit_stake(stake).await {
+                    telemetry::error!("failed to submit stake: {}", err);
+                }
+            },
+            Event::StakeMessageReceived(message) => {
+                if let Err(err) = self.handle_stake_message(message).await {
+                    telemetry::error!("failed to handle stake message: {}", err);
+                }
+            },
             // // The above code is handling an event of type `Vote` in a Rust
             // // program. It checks the integrity of the vote by
             // // verifying that it comes from the actual voter and prevents
@@ -157,148 +240,6 @@ impl Handler<EventMessage> for ConsensusModule {
             //         ))
             //         .await;
             // },
-            // // it sends a job to sign the convergence block using the signature
-            // // provider
-            // Event::SignConvergenceBlock(block) => {
-            //     if let Some(sig_provider) = self.sig_provider.clone() {
-            //         let _ = self
-            //             .sync_jobs_sender
-            //             .send(Job::SignConvergenceBlock(sig_provider, block));
-            //     }
-            // },
-            //
-            // // Process the job result of signing convergence block and adds the
-            // // partial signature to the cache for certificate generation
-            // Event::ConvergenceBlockPartialSign(job_result) => {
-            //     if let JobResult::ConvergenceBlockPartialSign(
-            //         block_hash,
-            //         public_key_share,
-            //         partial_signature,
-            //     ) = job_result
-            //     {
-            //         if let Some(certificates_share) =
-            //             self.convergence_block_certificates.get(&block_hash)
-            //         {
-            //             let mut new_certificate_share = certificates_share.clone();
-            //             if let Ok(block_hash_bytes) = hex::decode(block_hash.clone()) {
-            //                 if let Ok(signature) =
-            //                     TryInto::<[u8; 96]>::try_into(partial_signature.clone())
-            //                 {
-            //                     if let Ok(signature_share) =
-            // SignatureShare::from_bytes(signature) {                         if
-            // public_key_share.verify(&signature_share, block_hash_bytes) {
-            // new_certificate_share.insert((
-            // self.harvester_id,                                 public_key_share,
-            //                                 partial_signature.clone(),
-            //                             ));
-            //                             self.convergence_block_certificates.push(
-            //                                 block_hash.clone(),
-            //                                 new_certificate_share.clone(),
-            //                             );
-            //                             if let Some(sig_provider) = self.sig_provider.as_ref() {
-            //                                 if new_certificate_share.len()
-            //                                     <= sig_provider.quorum_config.upper_bound as
-            // usize                                 {
-            //                                     self
-            //                                         .broadcast_events_tx
-            //                                         .send(EventMessage::new(
-            //                                             None,
-            //                                             Event::SendPeerConvergenceBlockSign(
-            //                                                 self.harvester_id,
-            //                                                 block_hash.clone(),
-            //                                                 public_key_share.to_bytes().to_vec(),
-            //                                                 partial_signature,
-            //                                             ),
-            //                                         ))
-            //                                         .await.map_err(|err|
-            // theater::TheaterError::Other(
-            // format!("failed to send peer convergence block sign: {err}")
-            // ))?;
-            //
-            //                                     self.generate_and_broadcast_certificate(
-            //                                         block_hash,
-            //                                         &new_certificate_share,
-            //                                         sig_provider,
-            //                                     )
-            //                                     .await?;
-            //                                 }
-            //                             }
-            //                         }
-            //                     }
-            //                 }
-            //             }
-            //         }
-            //     }
-            // },
-            // Event::PeerConvergenceBlockSign(
-            //     node_idx,
-            //     block_hash,
-            //     public_key_share_bytes,
-            //     partial_signature,
-            // ) => {
-            //     let mut pb_key_share = None;
-            //     let preliminary_check = TryInto::<[u8; 48]>::try_into(public_key_share_bytes)
-            //         .and_then(|public_key_share_bytes| {
-            //             PublicKeyShare::from_bytes(public_key_share_bytes).map_err(|e| {
-            //                 format!("Invalid Public Key, Expected 48byte array:
-            // {e}").into_bytes()             })
-            //         })
-            //         .and_then(|public_key_share| {
-            //             pb_key_share = Some(public_key_share);
-            //             TryInto::<[u8; 96]>::try_into(partial_signature.clone())
-            //                 .and_then(|signature_share_bytes| {
-            //                     SignatureShare::from_bytes(signature_share_bytes).map_err(|e| {
-            //                         format!("Invalid Signature, Expected 96byte array: {e}")
-            //                             .into_bytes()
-            //                     })
-            //                 })
-            //                 .and_then(|signature_share| {
-            //                     hex::decode(block_hash.clone())
-            //                         .map_err(|e| {
-            //                             format!(
-            //                                 "Invalid Hex Representation of Signature Share: {e}",
-            //                             )
-            //                             .into_bytes()
-            //                         })
-            //                         .and_then(|block_hash_bytes| {
-            //                             if public_key_share
-            //                                 .verify(&signature_share, block_hash_bytes)
-            //                             {
-            //                                 Ok(())
-            //                             } else {
-            //                                 Err("signature verification failed"
-            //                                     .to_string()
-            //                                     .into_bytes())
-            //                             }
-            //                         })
-            //                 })
-            //         });
-            //
-            //     if preliminary_check.is_ok() {
-            //         if let Some(certificates_share) =
-            //             self.convergence_block_certificates.get(&block_hash)
-            //         {
-            //             let mut new_certificate_share = certificates_share.clone();
-            //             if let Some(pb_key_share) = pb_key_share {
-            //                 new_certificate_share.insert((
-            //                     node_idx,
-            //                     pb_key_share,
-            //                     partial_signature,
-            //                 ));
-            //                 self.convergence_block_certificates
-            //                     .push(block_hash.clone(), new_certificate_share.clone());
-            //                 if let Some(sig_provider) = self.sig_provider.as_ref() {
-            //                     self.generate_and_broadcast_certificate(
-            //                         block_hash,
-            //                         &new_certificate_share,
-            //                         sig_provider,
-            //                     )
-            //                     .await?;
-            //                 }
-            //             }
-            //         }
-            //     }
-            // },
             // Event::PrecheckConvergenceBlock(block, last_confirmed_block_header) => {
             //     let claims = block.claims.clone();
             //     let txns = block.txns.clone();
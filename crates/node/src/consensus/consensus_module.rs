@@ -1,42 +1,112 @@
-use std::collections::HashSet;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::Instant,
+};
 
-use block::{Block, ProposalBlock, RefHash};
+use block::{
+    Block,
+    Certificate,
+    ConvergenceBlock,
+    Evidence,
+    EvidenceHash,
+    Misbehaviour,
+    ProposalBlock,
+    QuorumHandOff,
+    QuorumHandOffs,
+    RefHash,
+};
 use chrono::Duration;
-use events::{Event, EventMessage, EventPublisher, EventSubscriber, SyncPeerData, Vote};
-use hbbft::crypto::{PublicKeyShare, SecretKeyShare};
+use crossbeam_channel::Sender;
+use events::{
+    AssignedQuorumMembership,
+    DkgMessage,
+    DkgPhaseToken,
+    Event,
+    EventMessage,
+    EventPublisher,
+    EventSubscriber,
+    EvidenceMessage,
+    FarmerQuorumKeySet,
+    QuorumHandOffMessage,
+    SignedDkgMessage,
+    StakeMessage,
+    SyncPeerData,
+    Vote,
+};
+use hbbft::crypto::{PublicKeySet, SecretKeyShare};
 use laminar::{Packet, SocketEvent};
 use maglev::Maglev;
-use mempool::{TxnRecord, TxnStatus};
+use mempool::{MempoolReadHandleFactory, TxnRecord, TxnStatus};
 use primitives::{
+    Address,
     BlockHash,
     Epoch,
     FarmerQuorumThreshold,
     GroupPublicKey,
+    NodeId,
     NodeIdx,
+    NodeType,
     NodeTypeBytes,
     PKShareBytes,
     PayloadBytes,
+    QuorumKind,
     QuorumPublicKey,
     RawSignature,
     Round,
+    SignatureType,
+    GENESIS_EPOCH,
 };
 use ritelinked::LinkedHashMap;
 use serde::{Deserialize, Serialize};
-use signer::signer::SignatureProvider;
+use signer::signer::{SignatureProvider, Signer};
 use storage::vrrbdb::VrrbDbReadHandle;
 use telemetry::info;
 use theater::{Actor, ActorId, ActorState};
+use vrrb_config::ThresholdConfig;
 use vrrb_core::{
     bloom::Bloom,
     claim::Claim,
     keypair::Keypair,
+    reputation::PeerBehaviour,
+    staking::{Stake, StakeUpdate},
     txn::{QuorumCertifiedTxn, TransactionDigest, Txn},
 };
 
-use crate::{NodeError, RuntimeComponent, RuntimeComponentHandle};
+use crate::{
+    consensus::{
+        dkg_quorum_id,
+        quorum_pubkey,
+        signer_id,
+        signer_idx,
+        stake_hash,
+        DkgPhase,
+        DkgSession,
+        DkgSessionConfig,
+        DkgTimeoutOutcome,
+        EvidenceCollector,
+        Job,
+        MisbehaviourDetector,
+        QuorumHandOffCollector,
+        SignatureAttendance,
+        StakeCollector,
+        StakeHash,
+        VotePool,
+        DKG_PHASE_TIMEOUT,
+    },
+    NodeError,
+    RuntimeComponent,
+    RuntimeComponentHandle,
+};
 
 pub const PULL_TXN_BATCH_SIZE: usize = 100;
 
+/// How long a farmer lets transactions pile up in its mempool before voting
+/// on them as a batch.
+pub const FARM_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
 pub trait QuorumMember {}
 // TODO: Move this to primitives
 pub type QuorumId = String;
@@ -46,7 +116,13 @@ pub type QuorumPubkey = String;
 pub struct ConsensusModuleConfig {
     pub events_tx: EventPublisher,
     pub keypair: Keypair,
+    pub node_id: NodeId,
+    pub node_type: NodeType,
+    pub threshold_config: ThresholdConfig,
+    pub data_dir: PathBuf,
     pub vrrbdb_read_handle: VrrbDbReadHandle,
+    pub mempool_read_handle_factory: MempoolReadHandleFactory,
+    pub sync_jobs_sender: Sender<Job>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -85,9 +161,55 @@ pub struct ConsensusModule {
     pub(crate) status: ActorState,
     pub(crate) events_tx: EventPublisher,
     pub(crate) vrrbdb_read_handle: VrrbDbReadHandle,
+    pub(crate) mempool_read_handle_factory: MempoolReadHandleFactory,
+    /// Transactions of the mempool the local node already voted on or
+    /// forwarded to the farmer quorum owning them
+    pub(crate) farmed_txns: HashSet<TransactionDigest>,
+    /// Whether the next batch of mempool transactions is due to be farmed
+    pub(crate) farm_scheduled: bool,
+    /// Sends batches of transactions to the job scheduler to be voted on
+    pub(crate) sync_jobs_sender: Sender<Job>,
+    /// Keys and addresses of every known farmer quorum, by group public key
+    pub(crate) farmer_quorums: HashMap<QuorumPubkey, FarmerQuorumKeySet>,
+    /// Members that vouched for the keys of farmer quorums not accepted yet,
+    /// by group public key and index of their share of the keys
+    pub(crate) farmer_quorum_attestations: HashMap<QuorumPubkey, BTreeMap<NodeIdx, NodeId>>,
+    pub(crate) vote_pool: VotePool,
     pub(crate) quorum_certified_txns: Vec<QuorumCertifiedTxn>,
     pub(crate) keypair: Keypair,
     pub(crate) certified_txns_filter: Bloom,
+    pub(crate) node_id: NodeId,
+    pub(crate) node_type: NodeType,
+    pub(crate) threshold_config: ThresholdConfig,
+    pub(crate) data_dir: PathBuf,
+    pub(crate) quorum_memberships: HashMap<QuorumKind, AssignedQuorumMembership>,
+    pub(crate) dkg_sessions: HashMap<QuorumKind, DkgSession>,
+    /// DKG messages received before the local node started the matching run
+    pub(crate) pending_dkg_messages: Vec<SignedDkgMessage>,
+    pub(crate) sig_providers: HashMap<QuorumKind, SignatureProvider>,
+    /// Keys of quorums the local node was a member of, kept until they
+    /// certified the hand-off to the quorum replacing them
+    pub(crate) retiring_sig_providers: HashMap<QuorumKind, SignatureProvider>,
+    /// Memberships the retiring keys were generated by, whose members take
+    /// part in the hand-off
+    pub(crate) retiring_memberships: HashMap<QuorumKind, AssignedQuorumMembership>,
+    /// Group public keys the quorums hold the duties of the current epoch
+    /// with, as generated at genesis or handed off to since
+    pub(crate) certified_quorum_pubkeys: HashMap<QuorumKind, QuorumPubkey>,
+    pub(crate) hand_off_collectors: HashMap<QuorumKind, QuorumHandOffCollector>,
+    /// Hand-offs to record in the certificate of the epoch's final block
+    pub(crate) certified_hand_offs: QuorumHandOffs,
+    pub(crate) misbehaviour_detector: MisbehaviourDetector,
+    pub(crate) evidence_collectors: HashMap<EvidenceHash, EvidenceCollector>,
+    /// Evidence the harvester quorum already certified
+    pub(crate) certified_evidence: HashSet<EvidenceHash>,
+    pub(crate) stake_collectors: HashMap<StakeHash, StakeCollector>,
+    /// Stakes the harvester quorum already certified
+    pub(crate) certified_stakes: HashSet<StakeHash>,
+    pub(crate) signature_attendance: SignatureAttendance,
+    /// State root every recent convergence block is signed on, as committed
+    /// when the local harvester first signed the block
+    pub(crate) block_root_hashes: HashMap<BlockHash, String>,
     //
     // votes_pool: DashMap<(TransactionDigest, String), Vec<Vote>>,
     // group_public_key: GroupPublicKey,
@@ -139,12 +261,52 @@ impl ConsensusModule {
             status: ActorState::Stopped,
             events_tx: cfg.events_tx,
             vrrbdb_read_handle: cfg.vrrbdb_read_handle,
+            mempool_read_handle_factory: cfg.mempool_read_handle_factory,
+            farmed_txns: HashSet::new(),
+            farm_scheduled: false,
+            sync_jobs_sender: cfg.sync_jobs_sender,
+            farmer_quorums: HashMap::new(),
+            farmer_quorum_attestations: HashMap::new(),
+            vote_pool: VotePool::new(),
             quorum_certified_txns: vec![],
             keypair: cfg.keypair,
             certified_txns_filter: Bloom::new(10),
+            node_id: cfg.node_id,
+            node_type: cfg.node_type,
+            threshold_config: cfg.threshold_config,
+            data_dir: cfg.data_dir,
+            quorum_memberships: HashMap::new(),
+            dkg_sessions: HashMap::new(),
+            pending_dkg_messages: vec![],
+            sig_providers: HashMap::new(),
+            retiring_sig_providers: HashMap::new(),
+            retiring_memberships: HashMap::new(),
+            certified_quorum_pubkeys: HashMap::new(),
+            hand_off_collectors: HashMap::new(),
+            certified_hand_offs: QuorumHandOffs::new(),
+            misbehaviour_detector: MisbehaviourDetector::new(),
+            evidence_collectors: HashMap::new(),
+            certified_evidence: HashSet::new(),
+            stake_collectors: HashMap::new(),
+            certified_stakes: HashSet::new(),
+            signature_attendance: SignatureAttendance::new(),
+            block_root_hashes: HashMap::new(),
         }
     }
 
+    /// Returns the epoch of the most recently committed convergence block.
+    pub(crate) fn epoch(&self) -> crate::Result<Epoch> {
+        self.vrrbdb_read_handle
+            .last_committed_epoch()
+            .map_err(|err| NodeError::Other(err.to_string()))
+    }
+
+    /// Returns the signature provider backed by the keys generated by the
+    /// given quorum, once its DKG run completed.
+    pub fn sig_provider(&self, quorum_kind: &QuorumKind) -> Option<SignatureProvider> {
+        self.sig_providers.get(quorum_kind).cloned()
+    }
+
     async fn certify_block(&self) {
         //
     }
@@ -160,7 +322,11 @@ impl ConsensusModule {
         epoch: Epoch,
         claim: Claim,
     ) -> ProposalBlock {
-        let txns = self.quorum_certified_txns.iter().take(PULL_TXN_BATCH_SIZE);
+        // NOTE: certified txns leave the pool once they're proposed, so the next
+        // proposal picks up the ones that didn't fit in this batch
+        let batch_size = self.quorum_certified_txns.len().min(PULL_TXN_BATCH_SIZE);
+        let txns: Vec<QuorumCertifiedTxn> =
+            self.quorum_certified_txns.drain(..batch_size).collect();
 
         // NOTE: Read updated claims
         let claim_map = self.vrrbdb_read_handle.claim_store_values();
@@ -175,7 +341,7 @@ impl ConsensusModule {
                 if let Err(err) = self.certified_txns_filter.push(&txn.txn().id.to_string()) {
                     telemetry::error!("Error pushing txn to certified txns filter: {}", err);
                 }
-                (txn.txn().id(), txn.clone())
+                (txn.txn().id(), txn)
             })
             .collect();
 
@@ -279,203 +445,136 @@ impl ConsensusModule {
         //     }
     }
 
-    async fn broadcast_block_certificate(&self) {
-        //
-    }
-
-    fn generate_and_broadcast_certificate(
-        &self,
-        block_hash: BlockHash,
-        certificates_share: &HashSet<(NodeIdx, PublicKeyShare, RawSignature)>,
+    /// Certifies a convergence block once the local harvester holds enough
+    /// signature shares over it, and broadcasts the certificate.
+    async fn generate_and_broadcast_certificate(
+        &mut self,
+        block_hash: &block::BlockHash,
         sig_provider: &SignatureProvider,
-    ) -> Result<(), theater::TheaterError> {
-        todo!()
-        // if certificates_share.len() >= self.quorum_threshold {
-        //     //Generate a new certificate for the block
-        //     let mut sig_shares = BTreeMap::new();
-        //     certificates_share
-        //         .iter()
-        //         .for_each(|(node_idx, _, signature)| {
-        //             sig_shares.insert(*node_idx, signature.clone());
-        //         });
-        //     if let Ok(certificate) =
-        //         sig_provider.generate_quorum_signature(self.quorum_threshold
-        // as u16, sig_shares)     {
-        //         let certificate = Certificate {
-        //             signature: hex::encode(certificate),
-        //             inauguration: None,
-        //             root_hash: "".to_string(),
-        //             next_root_hash: "".to_string(),
-        //             block_hash,
-        //         };
-        //
-        //         self.broadcast_events_tx
-        //             .send(EventMessage::new(
-        //                 None,
-        //                 Event::SendBlockCertificate(certificate),
-        //             ))
-        //             .await
-        //             .map_err(|err| {
-        //                 theater::TheaterError::Other(format!(
-        //                     "failed to send block certificate: {err}"
-        //                 ))
-        //             })?
-        //     }
-        // }
-        // Ok(())
+    ) -> crate::Result<()> {
+        // NOTE: t + 1 signature shares are needed to produce a threshold signature
+        let quorum_threshold = sig_provider.quorum_config.threshold + 1;
+        let sig_shares = self.signature_attendance.signature_shares(block_hash);
+        if (sig_shares.len() as u16) < quorum_threshold {
+            return Ok(());
+        }
+
+        let Some(header) = self
+            .misbehaviour_detector
+            .convergence_block_header(block_hash)
+            .cloned()
+        else {
+            return Ok(());
+        };
+
+        let signature = sig_provider
+            .generate_quorum_signature(quorum_threshold, sig_shares)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let mut certificate = Certificate {
+            signature: hex::encode(signature),
+            inauguration: None,
+            hand_offs: None,
+            root_hash: self.block_root_hash(block_hash)?,
+            next_root_hash: "".to_string(),
+            block_hash: block_hash.clone(),
+        };
+
+        // NOTE: the quorums replacing the current ones are inaugurated by the
+        // certificate of the epoch's final block
+        if header.is_epoch_final_block() && !self.certified_hand_offs.is_empty() {
+            certificate.inaugurate(std::mem::take(&mut self.certified_hand_offs));
+        }
+
+        self.events_tx
+            .send(EventMessage::new(
+                Some("network-events".into()),
+                Event::SendBlockCertificate(certificate.clone()),
+            ))
+            .await
+            .map_err(NodeError::from)?;
+
+        self.events_tx
+            .send(Event::BlockCertificate(certificate.clone()).into())
+            .await
+            .map_err(NodeError::from)?;
+
+        self.handle_block_certificate(certificate).await
     }
 
-    async fn sign_convergence_block(&self) {
-        //     Event::SignConvergenceBlock(block) => {
-        //         if let Some(sig_provider) = self.sig_provider.clone() {
-        //             let _ = self
-        //                 .sync_jobs_sender
-        //                 .send(Job::SignConvergenceBlock(sig_provider,
-        // block));         }
-        //     },
-    }
-
-    async fn process_convergence_block_partial_signature(&self) {
-        //     // Process the job result of signing convergence block and adds
-        // the     // partial signature to the cache for certificate
-        // generation     Event::ConvergenceBlockPartialSign(job_result)
-        // => {         if let JobResult::ConvergenceBlockPartialSign(
-        //             block_hash,
-        //             public_key_share,
-        //             partial_signature,
-        //         ) = job_result
-        //         {
-        //             if let Some(certificates_share) =
-        //                 self.convergence_block_certificates.get(&block_hash)
-        //             {
-        //                 let mut new_certificate_share =
-        // certificates_share.clone();                 if let
-        // Ok(block_hash_bytes) = hex::decode(block_hash.clone()) {
-        //                     if let Ok(signature) =
-        //                         TryInto::<[u8;
-        // 96]>::try_into(partial_signature.clone())
-        // {                         if let Ok(signature_share) =
-        // SignatureShare::from_bytes(signature) {
-        // if public_key_share.verify(&signature_share, block_hash_bytes) {
-        //                                 new_certificate_share.insert((
-        //                                     self.harvester_id,
-        //                                     public_key_share,
-        //                                     partial_signature.clone(),
-        //                                 ));
-        //
-        // self.convergence_block_certificates.push(
-        // block_hash.clone(),
-        // new_certificate_share.clone(),
-        // );                                 if let Some(sig_provider)
-        // = self.sig_provider.as_ref() {
-        // if new_certificate_share.len()
-        // <= sig_provider.quorum_config.upper_bound as usize
-        //                                     {
-        //                                         self
-        //                                             .broadcast_events_tx
-        //                                             .send(EventMessage::new(
-        //                                                 None,
-        //
-        // Event::SendPeerConvergenceBlockSign(
-        // self.harvester_id,
-        // block_hash.clone(),
-        // public_key_share.to_bytes().to_vec(),
-        // partial_signature,
-        // ),                                             ))
-        //                                             .await.map_err(|err|
-        // theater::TheaterError::Other(
-        // format!("failed to send peer convergence block sign: {err}")
-        //                                             ))?;
-        //
-        //
-        // self.generate_and_broadcast_certificate(
-        // block_hash,
-        // &new_certificate_share,
-        // sig_provider,                                         )
-        //                                         .await?;
-        //                                     }
-        //                                 }
-        //                             }
-        //                         }
-        //                     }
-        //                 }
-        //             }
-        //         }
-        //     },
+    /// Signs a convergence block with the local harvester's key share and
+    /// sends the share to the rest of the harvester quorum.
+    async fn sign_convergence_block(&mut self, block: &ConvergenceBlock) -> crate::Result<()> {
+        let Some(sig_provider) = self.sig_provider(&QuorumKind::Harvester) else {
+            return Ok(());
+        };
+
+        let signer_idx = signer_idx(&sig_provider, &self.node_id)?;
+
+        // NOTE: the root is fixed once the block is signed, so blocks committed
+        // meanwhile don't change the payload the shares are checked against
+        let committed_root_hash = self.committed_root_hash()?;
+        self.block_root_hashes.retain(|block_hash, _| {
+            self.misbehaviour_detector
+                .convergence_block_header(block_hash)
+                .is_some()
+        });
+
+        let root_hash = self
+            .block_root_hashes
+            .entry(block.hash.clone())
+            .or_insert(committed_root_hash)
+            .clone();
+
+        let payload = Certificate::payload(&block.hash, &root_hash);
+
+        let signature = sig_provider
+            .generate_partial_signature(payload)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let public_key_share = sig_provider
+            .dkg_state
+            .read()
+            .map_err(|err| NodeError::Other(err.to_string()))?
+            .public_key_set
+            .as_ref()
+            .map(|public_key_set| public_key_set.public_key_share(signer_idx as usize))
+            .ok_or_else(|| NodeError::Other("harvester public key set is missing".into()))?;
+
+        self.signature_attendance
+            .observe_signature(&block.hash, signer_idx, signature.clone());
+
+        let peer_addrs = self
+            .quorum_memberships
+            .get(&QuorumKind::Harvester)
+            .map(|membership| {
+                membership
+                    .peers
+                    .iter()
+                    .filter(|peer| peer.node_id != self.node_id)
+                    .map(|peer| peer.udp_gossip_addr)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let evt = Event::SendPeerConvergenceBlockSign(
+            signer_idx,
+            block.hash.clone(),
+            public_key_share.to_bytes().to_vec(),
+            signature,
+            peer_addrs,
+        );
+
+        self.events_tx
+            .send(EventMessage::new(Some("network-events".into()), evt))
+            .await
+            .map_err(NodeError::from)?;
+
+        self.generate_and_broadcast_certificate(&block.hash, &sig_provider)
+            .await
     }
 
     //
-    //     Event::PeerConvergenceBlockSign(
-    //         node_idx,
-    //         block_hash,
-    //         public_key_share_bytes,
-    //         partial_signature,
-    //     ) => {
-    //         let mut pb_key_share = None;
-    //         let preliminary_check = TryInto::<[u8;
-    // 48]>::try_into(public_key_share_bytes)
-    // .and_then(|public_key_share_bytes| {
-    // PublicKeyShare::from_bytes(public_key_share_bytes).map_err(|e| {
-    //                     format!("Invalid Public Key, Expected 48byte array:
-    // {e}").into_bytes()                 })
-    //             })
-    //             .and_then(|public_key_share| {
-    //                 pb_key_share = Some(public_key_share);
-    //                 TryInto::<[u8; 96]>::try_into(partial_signature.clone())
-    //                     .and_then(|signature_share_bytes| {
-    //
-    // SignatureShare::from_bytes(signature_share_bytes).map_err(|e| {
-    //                             format!("Invalid Signature, Expected 96byte
-    // array: {e}")                                 .into_bytes()
-    //                         })
-    //                     })
-    //                     .and_then(|signature_share| {
-    //                         hex::decode(block_hash.clone())
-    //                             .map_err(|e| {
-    //                                 format!(
-    //                                     "Invalid Hex Representation of Signature
-    // Share: {e}",                                 )
-    //                                 .into_bytes()
-    //                             })
-    //                             .and_then(|block_hash_bytes| {
-    //                                 if public_key_share
-    //                                     .verify(&signature_share,
-    // block_hash_bytes)                                 {
-    //                                     Ok(())
-    //                                 } else {
-    //                                     Err("signature verification failed"
-    //                                         .to_string()
-    //                                         .into_bytes())
-    //                                 }
-    //                             })
-    //                     })
-    //             });
-    //
-    //         if preliminary_check.is_ok() {
-    //             if let Some(certificates_share) =
-    //                 self.convergence_block_certificates.get(&block_hash)
-    //             {
-    //                 let mut new_certificate_share = certificates_share.clone();
-    //                 if let Some(pb_key_share) = pb_key_share {
-    //                     new_certificate_share.insert((
-    //                         node_idx,
-    //                         pb_key_share,
-    //                         partial_signature,
-    //                     ));
-    //                     self.convergence_block_certificates
-    //                         .push(block_hash.clone(),
-    // new_certificate_share.clone());                     if let
-    // Some(sig_provider) = self.sig_provider.as_ref() {
-    // self.generate_and_broadcast_certificate(
-    // block_hash,                             &new_certificate_share,
-    //                             sig_provider,
-    //                         )
-    //                         .await?;
-    //                     }
-    //                 }
-    //             }
-    //         }
-    //     },
     //     Event::PrecheckConvergenceBlock(block, last_confirmed_block_header) => {
     //         let claims = block.claims.clone();
     //         let txns = block.txns.clone();
@@ -805,97 +904,1492 @@ impl ConsensusModule {
         //     });
     }
 
-    // Event  "Farm" fetches a batch of transactions from a transaction mempool and
-    // sends them to scheduler to get it validated and voted
-    pub fn farm_transactions(&mut self, transactions: Vec<(TransactionDigest, TxnRecord)>) {
-        // let keys: Vec<GroupPublicKey> = self
-        //     .neighbouring_farmer_quorum_peers
-        //     .keys()
-        //     .cloned()
-        //     .collect();
+    /// Lets the transactions pending in the mempool pile up for a while, so
+    /// they are farmed in batches rather than on every insert.
+    pub fn handle_farm(&mut self) {
+        if self.farm_scheduled {
+            return;
+        }
 
-        // let maglev_hash_ring = Maglev::new(keys);
-        //
-        //     let mut new_txns = vec![];
-        //
-        //     for txn in txns.into_iter() {
-        //         if let Some(group_public_key) =
-        // maglev_hash_ring.get(&txn.0.clone()).cloned() {
-        // if group_public_key == self.group_public_key {
-        // new_txns.push(txn);             } else if let
-        // Some(broadcast_addresses) =
-        // self.neighbouring_farmer_quorum_peers.get(&group_public_key)
-        //             {
-        //                 let addresses: Vec<SocketAddr> =
-        // broadcast_addresses.iter().cloned().collect();
-        //
-        //                 self.broadcast_events_tx
-        //                     .send(EventMessage::new(
-        //                         None,
-        //                         Event::ForwardTxn((txn.1.clone(),
-        // addresses.clone())),                     ))
-        //                     .await
-        //                     .map_err(|err| {
-        //                         theater::TheaterError::Other(format!(
-        //                             "failed to forward txn {:?} to peers
-        // {addresses:?}:     {err}",
-        //                             txn.1
-        //                         ))
-        //                     })?
-        //             }
-        //         } else {
-        //             new_txns.push(txn);
-        //         }
-        //     }
-        //
-        //     if let Some(sig_provider) = self.sig_provider.clone() {
-        //         if let Err(err) = self.sync_jobs_sender.send(Job::Farm((
-        //             new_txns,
-        //             self.farmer_id.clone(),
-        //             self.farmer_node_idx,
-        //             self.group_public_key.clone(),
-        //             sig_provider,
-        //             self.quorum_threshold,
-        //         ))) {
-        //             telemetry::error!("error sending job to scheduler: {}",
-        // err);         }
-        //     }
+        self.farm_scheduled = true;
+
+        let events_tx = self.events_tx.clone();
+
+        tokio::spawn(async move {
+            tokio::time::sleep(FARM_INTERVAL).await;
+
+            let em = EventMessage::new(Some("consensus-events".into()), Event::FarmIntervalElapsed);
+            if let Err(err) = events_tx.send(em).await {
+                telemetry::error!("failed to notify farm interval: {}", err);
+            }
+        });
     }
 
-    pub fn handle_dkg_protocol_initiated(&self) {
-        //     let threshold_config = self.dkg_engine.threshold_config.clone();
-        //     if self.quorum_type.clone().is_some() {
-        //         match self
-        //             .dkg_engine
-        //             .generate_sync_keygen_instance(threshold_config.threshold
-        // as usize)         {
-        //             Ok(part_commitment) => {
-        //                 if let DkgResult::PartMessageGenerated(node_idx,
-        // part) = part_commitment                 {
-        //                     if let Ok(part_committment_bytes) =
-        // bincode::serialize(&part) {                         let _ =
-        // self                             .broadcast_events_tx
-        //                             .send(
-        //                                 Event::PartMessage(node_idx,
-        // part_committment_bytes)
-        // .into(),                             )
-        //                             .await.map_err(|e| {
-        //                                 error!("Error occured while sending
-        // part message to broadcast event channel {:?}", e);
-        // TheaterError::Other(format!("{e:?}"))                             });
-        //                     }
-        //                 }
-        //             },
-        //             Err(_e) => {
-        //                 error!("Error occured while generating synchronized
-        // keygen instance for node {:?}", self.dkg_engine.node_idx);
-        // },         }
-        //     } else {
-        //         error!(
-        //             "Cannot participate into DKG ,since current node {:?}
-        // dint win any Quorum Election",
-        // self.dkg_engine.node_idx         );
-        //     }
-        //     return Ok(ActorState::Running);
+    /// Votes on the next batch of mempool transactions the local farmer did
+    /// not vote on yet.
+    pub async fn handle_farm_interval_elapsed(&mut self) -> crate::Result<()> {
+        self.farm_scheduled = false;
+
+        // NOTE: transactions that left the mempool will not be farmed again
+        let mempool_read_handle_factory = &self.mempool_read_handle_factory;
+        self.farmed_txns
+            .retain(|txn_id| mempool_read_handle_factory.contains(txn_id));
+
+        if !self.sig_providers.contains_key(&QuorumKind::Farmer) {
+            return self.forward_pending_txns().await;
+        }
+
+        let transactions = self
+            .mempool_read_handle_factory
+            .batch(PULL_TXN_BATCH_SIZE, |txn_id| {
+                self.farmed_txns.contains(txn_id)
+            });
+
+        // NOTE: a full batch likely left more transactions behind
+        if transactions.len() == PULL_TXN_BATCH_SIZE {
+            self.handle_farm();
+        }
+
+        self.farmed_txns
+            .extend(transactions.iter().map(|(txn_id, _)| txn_id.clone()));
+
+        self.farm_transactions(transactions).await
+    }
+
+    /// Hands the pending mempool transactions over to the farmer quorums
+    /// owning them, on nodes that don't farm themselves.
+    async fn forward_pending_txns(&mut self) -> crate::Result<()> {
+        let keys: Vec<QuorumPubkey> = self.farmer_quorums.keys().cloned().collect();
+
+        // NOTE: transactions wait in the mempool until a farmer quorum is known
+        if keys.is_empty() {
+            return Ok(());
+        }
+
+        let maglev_hash_ring = Maglev::new(keys);
+        let pending_txns = self
+            .mempool_read_handle_factory
+            .batch(PULL_TXN_BATCH_SIZE, |txn_id| {
+                self.farmed_txns.contains(txn_id)
+            });
+
+        if pending_txns.len() == PULL_TXN_BATCH_SIZE {
+            self.handle_farm();
+        }
+
+        for (txn_id, txn_record) in pending_txns {
+            let farmer_quorum = maglev_hash_ring
+                .get(&txn_id)
+                .and_then(|owner_pubkey| self.farmer_quorums.get(owner_pubkey));
+
+            if let Some(farmer_quorum) = farmer_quorum {
+                let evt = Event::TxnForwarded(txn_record, farmer_quorum.peers.clone());
+                let em = EventMessage::new(Some("network-events".into()), evt);
+
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+
+                self.farmed_txns.insert(txn_id);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends the given transactions to the job scheduler to be voted on,
+    /// forwarding the ones owned by another farmer quorum to it.
+    pub async fn farm_transactions(
+        &mut self,
+        transactions: Vec<(TransactionDigest, TxnRecord)>,
+    ) -> crate::Result<()> {
+        let Some(sig_provider) = self.sig_provider(&QuorumKind::Farmer) else {
+            return Ok(());
+        };
+
+        let group_public_key = quorum_pubkey(&sig_provider)?;
+        let farmer_node_idx = signer_idx(&sig_provider, &self.node_id)?;
+
+        let keys: Vec<QuorumPubkey> = self.farmer_quorums.keys().cloned().collect();
+
+        // NOTE: with no farmer quorum known yet, every transaction is farmed locally
+        let maglev_hash_ring = (!keys.is_empty()).then(|| Maglev::new(keys));
+
+        let mut new_txns = vec![];
+
+        for txn in transactions.into_iter() {
+            let owner_pubkey = maglev_hash_ring
+                .as_ref()
+                .and_then(|hash_ring| hash_ring.get(&txn.0.clone()).cloned());
+
+            if let Some(owner_pubkey) = owner_pubkey {
+                if owner_pubkey != group_public_key {
+                    if let Some(farmer_quorum) = self.farmer_quorums.get(&owner_pubkey) {
+                        let evt = Event::TxnForwarded(txn.1.clone(), farmer_quorum.peers.clone());
+                        let em = EventMessage::new(Some("network-events".into()), evt);
+
+                        self.events_tx.send(em).await.map_err(NodeError::from)?;
+
+                        continue;
+                    }
+                }
+            }
+
+            new_txns.push(txn);
+        }
+
+        if new_txns.is_empty() {
+            return Ok(());
+        }
+
+        let quorum_public_key =
+            hex::decode(&group_public_key).map_err(|err| NodeError::Other(err.to_string()))?;
+        let farmer_quorum_threshold = sig_provider.quorum_config.threshold as usize + 1;
+
+        self.sync_jobs_sender
+            .send(Job::Farm((
+                new_txns,
+                self.node_id.clone().into_bytes(),
+                farmer_node_idx,
+                Address::new(self.keypair.miner_kp.1),
+                quorum_public_key,
+                sig_provider,
+                farmer_quorum_threshold,
+            )))
+            .map_err(|err| NodeError::Other(format!("failed to send farm job: {err}")))
+    }
+
+    /// Broadcasts the votes the job scheduler cast on a batch of farmed
+    /// transactions, and aggregates them locally.
+    pub async fn handle_processed_votes(&mut self, votes: Vec<Option<Vote>>) -> crate::Result<()> {
+        for vote in votes.into_iter().flatten() {
+            let evt = Event::VoteCreated(vote.clone());
+            let em = EventMessage::new(Some("network-events".into()), evt);
+
+            self.events_tx.send(em).await.map_err(NodeError::from)?;
+
+            self.handle_vote_received(vote)?;
+        }
+
+        Ok(())
+    }
+
+    /// Aggregates a farmer's vote, keeping the transaction around for the
+    /// next proposal block once its farmer quorum certified it.
+    pub fn handle_vote_received(&mut self, vote: Vote) -> crate::Result<()> {
+        // NOTE: only harvesters certify transactions
+        if !self.sig_providers.contains_key(&QuorumKind::Harvester) {
+            return Ok(());
+        }
+
+        let farmer_quorum_pubkey = hex::encode(&vote.quorum_public_key);
+        let Some(farmer_quorum) = self.farmer_quorums.get(&farmer_quorum_pubkey) else {
+            return Err(NodeError::Other(format!(
+                "vote was cast by unknown farmer quorum {farmer_quorum_pubkey}"
+            )));
+        };
+
+        if let Some(certified_txn) =
+            self.vote_pool
+                .add_vote(vote, &farmer_quorum.public_key_set, Instant::now())?
+        {
+            info!("Transaction {} was certified", certified_txn.txn().id());
+
+            self.quorum_certified_txns.push(certified_txn);
+        }
+
+        Ok(())
+    }
+
+    /// Remembers the keys of a farmer quorum, so transactions can be routed
+    /// to it and the votes of its members aggregated, once more than its
+    /// threshold of members with a committed claim vouched for them.
+    pub fn handle_farmer_quorum_key_set_received(
+        &mut self,
+        key_set: FarmerQuorumKeySet,
+    ) -> crate::Result<()> {
+        let farmer_quorum_pubkey = hex::encode(key_set.public_key_set.public_key().to_bytes());
+
+        if self.farmer_quorums.contains_key(&farmer_quorum_pubkey) {
+            return Ok(());
+        }
+
+        // NOTE: quorums can't lower their threshold below the configured one
+        // by advertising fewer members than they have. The addresses are the
+        // ones of the members other than the one vouching for the keys
+        let threshold = key_set.public_key_set.threshold();
+        let minimum_threshold = (self.threshold_config.threshold as usize).min(key_set.peers.len());
+
+        if threshold < minimum_threshold {
+            return Err(NodeError::Other(format!(
+                "farmer quorum {farmer_quorum_pubkey} has a threshold of {threshold} rather than {minimum_threshold}"
+            )));
+        }
+
+        let claim = self
+            .vrrbdb_read_handle
+            .claim_store_handle()
+            .get(&key_set.member_id)
+            .map_err(|_| {
+                NodeError::Other(format!("{} has no committed claim", key_set.member_id))
+            })?;
+
+        if !key_set.verify(&claim.public_key) {
+            return Err(NodeError::Other(format!(
+                "keys of farmer quorum {farmer_quorum_pubkey} were not vouched for by {}",
+                key_set.member_id
+            )));
+        }
+
+        let attestations = self
+            .farmer_quorum_attestations
+            .entry(farmer_quorum_pubkey.clone())
+            .or_default();
+
+        // NOTE: a member only vouches for its own share of the keys
+        if attestations
+            .values()
+            .any(|member_id| member_id == &key_set.member_id)
+        {
+            return Ok(());
+        }
+
+        attestations
+            .entry(key_set.member_idx)
+            .or_insert(key_set.member_id.clone());
+
+        if attestations.len() > threshold {
+            info!(
+                "Keys of farmer quorum {} were accepted",
+                farmer_quorum_pubkey
+            );
+
+            self.farmer_quorum_attestations
+                .remove(&farmer_quorum_pubkey);
+            self.farmer_quorums.insert(farmer_quorum_pubkey, key_set);
+        }
+
+        Ok(())
+    }
+
+    /// Vouches for the keys of the local node's farmer quorum with its share
+    /// of them and the key of its claim.
+    fn vouch_for_farmer_quorum_keys(
+        &self,
+        public_key_set: PublicKeySet,
+        peers: Vec<SocketAddr>,
+    ) -> crate::Result<FarmerQuorumKeySet> {
+        let sig_provider = self
+            .sig_provider(&QuorumKind::Farmer)
+            .ok_or_else(|| NodeError::Other("farmer quorum keys are missing".into()))?;
+
+        let member_idx = signer_idx(&sig_provider, &self.node_id)?;
+        let payload =
+            FarmerQuorumKeySet::payload(&public_key_set, &peers, &self.node_id, member_idx);
+
+        let share_signature = sig_provider
+            .generate_partial_signature(payload.clone())
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let claim_signature =
+            Keypair::ecdsa_sign(&payload, self.keypair.miner_kp.0.secret_bytes().to_vec())
+                .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        Ok(FarmerQuorumKeySet {
+            public_key_set,
+            peers,
+            member_id: self.node_id.clone(),
+            member_idx,
+            share_signature,
+            claim_signature,
+        })
+    }
+
+    pub fn handle_quorum_membership_assigment_created(
+        &mut self,
+        assigned_membership: AssignedQuorumMembership,
+    ) {
+        let quorum_kind = &assigned_membership.quorum_kind;
+
+        let membership_changed = self.dkg_sessions.get(quorum_kind).map(|session| {
+            session.members().len() != assigned_membership.peers.len()
+                || assigned_membership
+                    .peers
+                    .iter()
+                    .any(|peer| !session.members().contains(&peer.node_id))
+        });
+
+        // NOTE: the keys of the outgoing quorum are kept around until it
+        // certified the hand-off to the keys of the incoming one
+        if membership_changed == Some(true) {
+            info!(
+                "Membership of the {:?} quorum changed, its keys will be handed off",
+                quorum_kind
+            );
+
+            self.dkg_sessions.remove(quorum_kind);
+
+            if let Some(sig_provider) = self.sig_providers.remove(quorum_kind) {
+                self.retiring_sig_providers
+                    .insert(quorum_kind.clone(), sig_provider);
+            }
+
+            if let Some(membership) = self.quorum_memberships.get(quorum_kind) {
+                self.retiring_memberships
+                    .insert(quorum_kind.clone(), membership.clone());
+            }
+        }
+
+        self.quorum_memberships
+            .insert(assigned_membership.quorum_kind.clone(), assigned_membership);
+    }
+
+    /// Drops a peer that left the network from the quorums the local node is
+    /// a member of. The quorums keep their keys until they're handed off to
+    /// the memberships re-elected without the peer.
+    pub fn handle_node_removed_from_peer_list(&mut self, node_id: &NodeId) {
+        for membership in self.quorum_memberships.values_mut() {
+            let peer_count = membership.peers.len();
+            membership.peers.retain(|peer| &peer.node_id != node_id);
+
+            if membership.peers.len() != peer_count {
+                info!(
+                    "Node {} left the {:?} quorum, awaiting re-election",
+                    node_id, membership.quorum_kind
+                );
+            }
+        }
+
+        self.pending_dkg_messages
+            .retain(|message| &message.sender_id != node_id);
+    }
+
+    /// Starts a DKG run for every quorum the local node was assigned to and
+    /// is not running one for yet.
+    pub async fn handle_dkg_protocol_initiated(&mut self) -> crate::Result<()> {
+        let epoch = self.epoch()?;
+
+        // NOTE: sessions of an earlier epoch or membership, and failed ones, are
+        // replaced by a new run
+        let memberships: Vec<AssignedQuorumMembership> = self
+            .quorum_memberships
+            .values()
+            .filter(|membership| {
+                let Some(session) = self.dkg_sessions.get(&membership.quorum_kind) else {
+                    return true;
+                };
+
+                let member_ids: BTreeSet<NodeId> = membership
+                    .peers
+                    .iter()
+                    .map(|peer| peer.node_id.clone())
+                    .chain(std::iter::once(self.node_id.clone()))
+                    .collect();
+
+                session.epoch() != epoch
+                    || session.members() != &member_ids
+                    || session.phase() == &DkgPhase::Failed
+            })
+            .cloned()
+            .collect();
+
+        if self.quorum_memberships.is_empty() {
+            telemetry::error!(
+                "Cannot participate in DKG since node {} was not assigned to any quorum",
+                self.node_id
+            );
+
+            return Ok(());
+        }
+
+        if memberships.is_empty() {
+            telemetry::debug!("DKG is already running for every quorum of the current epoch");
+
+            return Ok(());
+        }
+
+        for membership in memberships {
+            let quorum_kind = membership.quorum_kind.clone();
+
+            let mut session = DkgSession::new(DkgSessionConfig {
+                node_id: self.node_id.clone(),
+                node_type: self.node_type,
+                secret_key: self.keypair.get_validator_secret_key().clone(),
+                threshold_config: self.threshold_config.clone(),
+                quorum_kind: quorum_kind.clone(),
+                members: membership.peers,
+                epoch,
+                data_dir: Some(self.data_dir.clone()),
+            })?;
+
+            if session.phase() == &DkgPhase::Completed {
+                info!("Restored DKG shares of the {:?} quorum", quorum_kind);
+
+                self.dkg_sessions.insert(quorum_kind.clone(), session);
+                self.handle_dkg_phase_changed(&quorum_kind).await?;
+
+                continue;
+            }
+
+            let messages = session.start()?;
+            self.dkg_sessions.insert(quorum_kind.clone(), session);
+
+            info!("Started DKG for the {:?} quorum", quorum_kind);
+
+            self.send_dkg_messages(&quorum_kind, messages).await?;
+            self.schedule_dkg_phase_timeout(&quorum_kind);
+
+            let (pending, others) = std::mem::take(&mut self.pending_dkg_messages)
+                .into_iter()
+                .partition(|message| message.message.quorum_kind() == &quorum_kind);
+
+            self.pending_dkg_messages = others;
+
+            for message in pending {
+                self.handle_dkg_message_received(message).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Feeds a DKG message sent by another quorum member to the matching run.
+    pub async fn handle_dkg_message_received(
+        &mut self,
+        message: SignedDkgMessage,
+    ) -> crate::Result<()> {
+        let quorum_kind = message.message.quorum_kind().clone();
+
+        let Some(session) = self.dkg_sessions.get_mut(&quorum_kind) else {
+            self.pending_dkg_messages.push(message);
+            return Ok(());
+        };
+
+        let phase_token = session.phase_token();
+        let messages = session.handle_message(message)?;
+        let phase_changed = session.phase_token() != phase_token;
+
+        self.send_dkg_messages(&quorum_kind, messages).await?;
+
+        if phase_changed {
+            self.schedule_dkg_phase_timeout(&quorum_kind);
+            self.handle_dkg_phase_changed(&quorum_kind).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends the messages of a DKG phase again, or gives up on the run once
+    /// it ran out of retries.
+    pub async fn handle_dkg_phase_timed_out(
+        &mut self,
+        quorum_kind: QuorumKind,
+        phase_token: DkgPhaseToken,
+    ) -> crate::Result<()> {
+        let Some(session) = self.dkg_sessions.get_mut(&quorum_kind) else {
+            return Ok(());
+        };
+
+        match session.handle_timeout(phase_token) {
+            DkgTimeoutOutcome::Ignored => {},
+            DkgTimeoutOutcome::Retry(messages) => {
+                telemetry::warn!(
+                    "DKG phase {:?} of the {:?} quorum timed out. Retrying",
+                    session.phase(),
+                    quorum_kind
+                );
+
+                self.send_dkg_messages(&quorum_kind, messages).await?;
+                self.schedule_dkg_phase_timeout(&quorum_kind);
+            },
+            DkgTimeoutOutcome::Abandoned => {
+                telemetry::error!(
+                    "DKG of the {:?} quorum was abandoned after too many retries",
+                    quorum_kind
+                );
+
+                self.dkg_sessions.remove(&quorum_kind);
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Hands the keys generated by a completed DKG run over to the modules
+    /// that need them.
+    async fn handle_dkg_phase_changed(&mut self, quorum_kind: &QuorumKind) -> crate::Result<()> {
+        let Some(session) = self.dkg_sessions.get(quorum_kind) else {
+            return Ok(());
+        };
+
+        if session.phase() != &DkgPhase::Completed {
+            return Ok(());
+        }
+
+        let (Some(dkg_state), Some(public_key_set)) =
+            (session.dkg_state(), session.public_key_set())
+        else {
+            return Err(NodeError::Other(format!(
+                "DKG of the {quorum_kind:?} quorum completed without generating keys"
+            )));
+        };
+
+        info!("DKG of the {:?} quorum completed", quorum_kind);
+
+        // NOTE: the quorums elected at genesis hold the duties of the genesis
+        // epoch without a hand-off
+        let incoming_pubkey = hex::encode(public_key_set.public_key().to_bytes());
+        if self.epoch()? == GENESIS_EPOCH
+            && !self.certified_quorum_pubkeys.contains_key(quorum_kind)
+        {
+            self.certified_quorum_pubkeys
+                .insert(quorum_kind.clone(), incoming_pubkey.clone());
+        }
+
+        self.sig_providers.insert(
+            quorum_kind.clone(),
+            SignatureProvider {
+                dkg_state: Arc::new(RwLock::new(dkg_state)),
+                quorum_config: session.threshold_config(),
+            },
+        );
+
+        if quorum_kind == &QuorumKind::Harvester {
+            let public_key_set = serde_json::to_vec(&public_key_set)
+                .map_err(|err| NodeError::Other(err.to_string()))?;
+
+            self.events_tx
+                .send(Event::HarvesterPublicKey(public_key_set).into())
+                .await
+                .map_err(NodeError::from)?;
+        }
+
+        if quorum_kind == &QuorumKind::Farmer {
+            let peers = self
+                .quorum_memberships
+                .get(quorum_kind)
+                .map(|membership| {
+                    membership
+                        .peers
+                        .iter()
+                        .map(|peer| peer.udp_gossip_addr)
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            let key_set = self.vouch_for_farmer_quorum_keys(public_key_set.clone(), peers)?;
+
+            // NOTE: the keys of the local node's own quorum need no vouching
+            self.farmer_quorums
+                .insert(incoming_pubkey.clone(), key_set.clone());
+
+            let evt = Event::FarmerQuorumKeySetCreated(key_set);
+            let em = EventMessage::new(Some("network-events".into()), evt);
+
+            self.events_tx.send(em).await.map_err(NodeError::from)?;
+        }
+
+        // NOTE: the outgoing quorum, if any, is asked to certify the new keys
+        // for the next epoch
+        let message = QuorumHandOffMessage::Requested {
+            quorum_kind: quorum_kind.clone(),
+            epoch: self.epoch()? + 1,
+            incoming_pubkey,
+        };
+
+        self.broadcast_quorum_hand_off_message(message.clone())
+            .await?;
+        self.handle_quorum_hand_off_message(message).await
+    }
+
+    /// Takes part in the hand-off of a quorum's duties from the outgoing
+    /// members to the incoming ones.
+    pub async fn handle_quorum_hand_off_message(
+        &mut self,
+        message: QuorumHandOffMessage,
+    ) -> crate::Result<()> {
+        match message {
+            QuorumHandOffMessage::Requested {
+                quorum_kind,
+                epoch,
+                incoming_pubkey,
+            } => {
+                self.handle_quorum_hand_off_requested(quorum_kind, epoch, incoming_pubkey)
+                    .await
+            },
+            QuorumHandOffMessage::Signed {
+                quorum_kind,
+                epoch,
+                outgoing_pubkey,
+                incoming_pubkey,
+                signer_idx,
+                signature,
+            } => {
+                self.handle_quorum_hand_off_signed(
+                    quorum_kind,
+                    epoch,
+                    outgoing_pubkey,
+                    incoming_pubkey,
+                    signer_idx,
+                    signature,
+                )
+                .await
+            },
+            QuorumHandOffMessage::Certified {
+                quorum_kind,
+                hand_off,
+            } => self.handle_quorum_hand_off_certified(quorum_kind, hand_off),
+        }
+    }
+
+    /// Signs the hand-off to the incoming quorum's keys with the local
+    /// node's share of the outgoing quorum's keys.
+    async fn handle_quorum_hand_off_requested(
+        &mut self,
+        quorum_kind: QuorumKind,
+        epoch: Epoch,
+        incoming_pubkey: QuorumPubkey,
+    ) -> crate::Result<()> {
+        let node_id = self.node_id.clone();
+
+        let Some(collector) = self.hand_off_collector(&quorum_kind, epoch, &incoming_pubkey)? else {
+            return Ok(());
+        };
+
+        let outgoing_pubkey = collector.outgoing_pubkey().clone();
+        let signer_idx = collector.signer_idx(&node_id)?;
+        let signature = collector.sign()?;
+
+        info!("Signed the hand-off of the {:?} quorum", quorum_kind);
+
+        self.broadcast_quorum_hand_off_message(QuorumHandOffMessage::Signed {
+            quorum_kind: quorum_kind.clone(),
+            epoch,
+            outgoing_pubkey: outgoing_pubkey.clone(),
+            incoming_pubkey: incoming_pubkey.clone(),
+            signer_idx,
+            signature: signature.clone(),
+        })
+        .await?;
+
+        self.handle_quorum_hand_off_signed(
+            quorum_kind,
+            epoch,
+            outgoing_pubkey,
+            incoming_pubkey,
+            signer_idx,
+            signature,
+        )
+        .await
+    }
+
+    /// Gathers the hand-off signature shares of the outgoing quorum's
+    /// members, certifying the hand-off once there are enough of them.
+    async fn handle_quorum_hand_off_signed(
+        &mut self,
+        quorum_kind: QuorumKind,
+        epoch: Epoch,
+        outgoing_pubkey: QuorumPubkey,
+        incoming_pubkey: QuorumPubkey,
+        signer_idx: NodeIdx,
+        signature: RawSignature,
+    ) -> crate::Result<()> {
+        let Some(collector) = self.hand_off_collector(&quorum_kind, epoch, &incoming_pubkey)? else {
+            return Ok(());
+        };
+
+        if collector.outgoing_pubkey() != &outgoing_pubkey {
+            return Ok(());
+        }
+
+        let Some(hand_off) = collector.add_signature_share(signer_idx, signature)? else {
+            return Ok(());
+        };
+
+        self.broadcast_quorum_hand_off_message(QuorumHandOffMessage::Certified {
+            quorum_kind: quorum_kind.clone(),
+            hand_off: hand_off.clone(),
+        })
+        .await?;
+
+        self.handle_quorum_hand_off_certified(quorum_kind, hand_off)
+    }
+
+    /// Keeps a certified hand-off around until it is recorded in the
+    /// certificate of the epoch's final block, and forgets the outgoing
+    /// quorum's keys.
+    fn handle_quorum_hand_off_certified(
+        &mut self,
+        quorum_kind: QuorumKind,
+        hand_off: QuorumHandOff,
+    ) -> crate::Result<()> {
+        if hand_off.quorum_id != dkg_quorum_id(&quorum_kind) || !hand_off.verify() {
+            return Err(NodeError::Other(format!(
+                "invalid hand-off certificate for the {quorum_kind:?} quorum"
+            )));
+        }
+
+        let next_epoch = self.epoch()? + 1;
+        if hand_off.epoch != next_epoch {
+            return Err(NodeError::Other(format!(
+                "hand-off of the {quorum_kind:?} quorum is for epoch {} rather than {next_epoch}",
+                hand_off.epoch
+            )));
+        }
+
+        // NOTE: hand-offs are only accepted from the keys the quorum holds the
+        // duties of the current epoch with
+        match self.certified_quorum_pubkeys.get(&quorum_kind) {
+            Some(certified_pubkey) if certified_pubkey == &hand_off.outgoing_pubkey => {},
+            Some(_) => {
+                return Err(NodeError::Other(format!(
+                    "hand-off of the {quorum_kind:?} quorum was certified by uncertified keys"
+                )))
+            },
+            None => {
+                return Err(NodeError::Other(format!(
+                    "keys of the {quorum_kind:?} quorum are not known"
+                )))
+            },
+        }
+
+        if self.certified_hand_offs.contains_key(&hand_off.quorum_id) {
+            return Ok(());
+        }
+
+        info!(
+            "Hand-off of the {:?} quorum was certified for epoch {}",
+            quorum_kind, hand_off.epoch
+        );
+
+        self.hand_off_collectors.remove(&quorum_kind);
+        self.retiring_sig_providers.remove(&quorum_kind);
+        self.retiring_memberships.remove(&quorum_kind);
+        self.certified_hand_offs
+            .insert(hand_off.quorum_id.clone(), hand_off);
+
+        Ok(())
+    }
+
+    /// Returns the keys of the outgoing quorum of the given kind, if the
+    /// local node is a member of it.
+    fn outgoing_sig_provider(
+        &self,
+        quorum_kind: &QuorumKind,
+        incoming_pubkey: &str,
+    ) -> crate::Result<Option<SignatureProvider>> {
+        if let Some(sig_provider) = self.retiring_sig_providers.get(quorum_kind) {
+            return Ok(Some(sig_provider.clone()));
+        }
+
+        // NOTE: members dropped from a quorum are not assigned to its next
+        // membership, so their keys are still the current ones
+        match self.sig_providers.get(quorum_kind) {
+            Some(sig_provider) if quorum_pubkey(sig_provider)? != incoming_pubkey => {
+                Ok(Some(sig_provider.clone()))
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the collector of the hand-off to the given keys, if the local
+    /// node is a member of the outgoing quorum and the hand-off is for the
+    /// next epoch.
+    fn hand_off_collector(
+        &mut self,
+        quorum_kind: &QuorumKind,
+        epoch: Epoch,
+        incoming_pubkey: &str,
+    ) -> crate::Result<Option<&mut QuorumHandOffCollector>> {
+        if epoch != self.epoch()? + 1 {
+            return Ok(None);
+        }
+
+        // NOTE: collectors of earlier epochs are replaced, while only the first
+        // keys asking for a hand-off within an epoch are certified
+        let collector_epoch = self.hand_off_collectors.get(quorum_kind).map(|collector| {
+            (
+                collector.epoch(),
+                collector.incoming_pubkey() == incoming_pubkey,
+            )
+        });
+
+        match collector_epoch {
+            Some((collector_epoch, true)) if collector_epoch == epoch => {},
+            Some((collector_epoch, false)) if collector_epoch == epoch => return Ok(None),
+            _ => {
+                let sig_provider = self.outgoing_sig_provider(quorum_kind, incoming_pubkey)?;
+                let Some(sig_provider) = sig_provider else {
+                    return Ok(None);
+                };
+
+                let outgoing_pubkey = quorum_pubkey(&sig_provider)?;
+                if self.certified_quorum_pubkeys.get(quorum_kind) != Some(&outgoing_pubkey) {
+                    return Ok(None);
+                }
+
+                let collector = QuorumHandOffCollector::new(
+                    dkg_quorum_id(quorum_kind),
+                    epoch,
+                    incoming_pubkey.to_string(),
+                    sig_provider,
+                )?;
+
+                self.hand_off_collectors
+                    .insert(quorum_kind.clone(), collector);
+            },
+        }
+
+        Ok(self.hand_off_collectors.get_mut(quorum_kind))
+    }
+
+    /// Sends a hand-off message to the members of the outgoing and incoming
+    /// quorums known to the local node, along with the harvesters, which
+    /// record certified hand-offs in block certificates.
+    async fn broadcast_quorum_hand_off_message(
+        &self,
+        message: QuorumHandOffMessage,
+    ) -> crate::Result<()> {
+        let quorum_kind = message.quorum_kind();
+
+        let peer_addrs: BTreeSet<SocketAddr> = self
+            .quorum_memberships
+            .get(quorum_kind)
+            .into_iter()
+            .chain(self.retiring_memberships.get(quorum_kind))
+            .chain(self.quorum_memberships.get(&QuorumKind::Harvester))
+            .flat_map(|membership| membership.peers.iter())
+            .filter(|peer| peer.node_id != self.node_id)
+            .map(|peer| peer.udp_gossip_addr)
+            .collect();
+
+        let evt = Event::QuorumHandOffMessageCreated(message, peer_addrs.into_iter().collect());
+        let em = EventMessage::new(Some("network-events".into()), evt);
+
+        self.events_tx.send(em).await.map_err(NodeError::from)
+    }
+
+    /// Checks a proposal block for equivocations of the claim that proposed
+    /// it, and remembers and signs convergence blocks so the signatures of
+    /// harvesters over them can be checked and certified.
+    pub async fn handle_block_received(&mut self, block: Block) -> crate::Result<()> {
+        let misbehaviour = match block {
+            Block::Proposal { block } => self.misbehaviour_detector.observe_proposal_block(&block),
+            Block::Convergence { block } => {
+                self.misbehaviour_detector.observe_convergence_block(&block);
+                self.sign_convergence_block(&block).await?;
+                None
+            },
+            Block::Genesis { .. } => None,
+        };
+
+        match misbehaviour {
+            Some(misbehaviour) => self.report_misbehaviour(misbehaviour).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the partial signature of a harvester over a convergence block
+    /// against the ones it already produced for the same round, and certifies
+    /// the block once enough of them were collected.
+    pub async fn handle_peer_convergence_block_sign(
+        &mut self,
+        signer_idx: NodeIdx,
+        block_hash: block::BlockHash,
+        signature: RawSignature,
+    ) -> crate::Result<()> {
+        let Some(sig_provider) = self.sig_provider(&QuorumKind::Harvester) else {
+            return Ok(());
+        };
+
+        let root_hash = self.block_root_hash(&block_hash)?;
+        let payload = Certificate::payload(&block_hash, &root_hash);
+
+        // NOTE: forged signature shares must not shadow the harvester's own
+        let is_valid = sig_provider
+            .verify_signature(
+                signer_idx,
+                payload,
+                signature.clone(),
+                SignatureType::PartialSignature,
+            )
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        if !is_valid {
+            return Err(NodeError::Other(format!(
+                "invalid block signature share from harvester #{signer_idx}"
+            )));
+        }
+
+        let signer_id = signer_id(&sig_provider, signer_idx)?;
+
+        if let Some(behaviour) =
+            self.signature_attendance
+                .observe_signature(&block_hash, signer_idx, signature.clone())
+        {
+            self.report_peer_behaviour(signer_id.clone(), behaviour)
+                .await?;
+        }
+
+        if let Some(misbehaviour) = self.misbehaviour_detector.observe_block_signature(
+            signer_id,
+            signer_idx,
+            &block_hash,
+            &root_hash,
+            signature,
+        ) {
+            self.report_misbehaviour(misbehaviour).await?;
+        }
+
+        self.generate_and_broadcast_certificate(&block_hash, &sig_provider)
+            .await
+    }
+
+    /// Returns the hex encoded root of the state committed by the last block
+    /// this node committed, if any.
+    fn committed_root_hash(&self) -> crate::Result<String> {
+        let block_hash = self
+            .vrrbdb_read_handle
+            .last_committed_block_hash()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let root_hash = match block_hash {
+            Some(block_hash) => self
+                .vrrbdb_read_handle
+                .state_root_at(&block_hash)
+                .map_err(|err| NodeError::Other(err.to_string()))?,
+            None => None,
+        };
+
+        Ok(root_hash
+            .map(|root_hash| hex::encode(root_hash.0))
+            .unwrap_or_default())
+    }
+
+    /// Returns the state root the given convergence block is signed on,
+    /// which is the committed one for blocks the local harvester didn't sign
+    /// yet.
+    fn block_root_hash(&self, block_hash: &BlockHash) -> crate::Result<String> {
+        match self.block_root_hashes.get(block_hash) {
+            Some(root_hash) => Ok(root_hash.clone()),
+            None => self.committed_root_hash(),
+        }
+    }
+
+    /// Records the keys certified by the hand-offs a block certificate
+    /// inaugurates, and reports the harvesters that did not deliver their
+    /// partial signature of the block before it got certified as having
+    /// missed the round.
+    pub async fn handle_block_certificate(
+        &mut self,
+        certificate: Certificate,
+    ) -> crate::Result<()> {
+        for hand_off in certificate
+            .hand_offs
+            .iter()
+            .flat_map(|hand_offs| hand_offs.values())
+        {
+            let Some(quorum_kind) = [QuorumKind::Harvester, QuorumKind::Farmer]
+                .into_iter()
+                .find(|quorum_kind| dkg_quorum_id(quorum_kind) == hand_off.quorum_id)
+            else {
+                continue;
+            };
+
+            // NOTE: only hand-offs certified by the keys the quorum currently
+            // holds its duties with are followed
+            let certified_pubkey = self.certified_quorum_pubkeys.get(&quorum_kind);
+            if certified_pubkey == Some(&hand_off.outgoing_pubkey) && hand_off.verify() {
+                self.certified_quorum_pubkeys
+                    .insert(quorum_kind, hand_off.incoming_pubkey.clone());
+            }
+        }
+
+        let Some(sig_provider) = self.sig_provider(&QuorumKind::Harvester) else {
+            return Ok(());
+        };
+
+        let members = sig_provider
+            .dkg_state
+            .read()
+            .map_err(|err| NodeError::Other(err.to_string()))?
+            .peer_public_keys
+            .len();
+
+        let absentees = self
+            .signature_attendance
+            .observe_certificate(&certificate.block_hash, 0..members as NodeIdx);
+
+        for absentee_idx in absentees {
+            let absentee_id = signer_id(&sig_provider, absentee_idx)?;
+            // NOTE: the local node's own share is never gossiped back to it
+            if absentee_id == self.node_id {
+                continue;
+            }
+
+            self.report_peer_behaviour(absentee_id, PeerBehaviour::RoundMissed)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn report_peer_behaviour(
+        &self,
+        node_id: NodeId,
+        behaviour: PeerBehaviour,
+    ) -> crate::Result<()> {
+        self.events_tx
+            .send(Event::PeerBehaviourObserved(node_id, behaviour).into())
+            .await
+            .map_err(NodeError::from)
+    }
+
+    /// Returns the address of the claim to slash for the given misbehaviour.
+    fn offender(&self, misbehaviour: &Misbehaviour) -> crate::Result<Address> {
+        match misbehaviour {
+            Misbehaviour::ProposalEquivocation { first, .. } => Ok(first.from.address.clone()),
+            Misbehaviour::BlockSignatureEquivocation { signer_id, .. } => self
+                .vrrbdb_read_handle
+                .claim_store_handle()
+                .get(signer_id)
+                .map(|claim| claim.address)
+                .map_err(|_| NodeError::Other(format!("{signer_id} has no known claim"))),
+        }
+    }
+
+    /// Asks the harvester quorum to certify the given misbehaviour.
+    async fn report_misbehaviour(&mut self, misbehaviour: Misbehaviour) -> crate::Result<()> {
+        let offender = self.offender(&misbehaviour)?;
+
+        telemetry::warn!(
+            "Detected misbehaviour of {} in round {}",
+            offender,
+            misbehaviour.round()
+        );
+
+        let message = EvidenceMessage::Detected {
+            misbehaviour,
+            offender,
+        };
+
+        self.broadcast_evidence_message(message.clone()).await?;
+        self.handle_evidence_message(message).await
+    }
+
+    /// Takes part in the certification of evidence of misbehaviour by the
+    /// harvester quorum.
+    pub async fn handle_evidence_message(&mut self, message: EvidenceMessage) -> crate::Result<()> {
+        match message {
+            EvidenceMessage::Detected {
+                misbehaviour,
+                offender,
+            } => self.handle_evidence_detected(misbehaviour, offender).await,
+            EvidenceMessage::Signed {
+                misbehaviour,
+                offender,
+                signer_idx,
+                signature,
+            } => {
+                self.handle_evidence_signed(misbehaviour, offender, signer_idx, signature)
+                    .await
+            },
+            EvidenceMessage::Certified(evidence) => self.handle_evidence_certified(evidence).await,
+        }
+    }
+
+    /// Signs the evidence with the local node's share of the harvester
+    /// quorum's keys, once it checked the evidence proves the misbehaviour.
+    async fn handle_evidence_detected(
+        &mut self,
+        misbehaviour: Misbehaviour,
+        offender: Address,
+    ) -> crate::Result<()> {
+        let Some(sig_provider) = self.sig_provider(&QuorumKind::Harvester) else {
+            return Ok(());
+        };
+
+        if self
+            .certified_evidence
+            .contains(&Evidence::hash_of(&misbehaviour, &offender))
+        {
+            return Ok(());
+        }
+
+        let public_key_set = sig_provider
+            .dkg_state
+            .read()
+            .map_err(|err| NodeError::Other(err.to_string()))?
+            .public_key_set
+            .clone()
+            .ok_or_else(|| NodeError::Other("group public key is missing".to_string()))?;
+
+        if let Misbehaviour::BlockSignatureEquivocation {
+            signer_id: offender_id,
+            signer_idx,
+            ..
+        } = &misbehaviour
+        {
+            if &signer_id(&sig_provider, *signer_idx)? != offender_id {
+                return Err(NodeError::Other(format!(
+                    "{offender_id} does not sign as harvester #{signer_idx}"
+                )));
+            }
+        }
+
+        if !misbehaviour.verify(&public_key_set) || self.offender(&misbehaviour)? != offender {
+            return Err(NodeError::Other(format!(
+                "evidence against {offender} does not prove any misbehaviour"
+            )));
+        }
+
+        let collector = self.evidence_collector(misbehaviour.clone(), offender.clone())?;
+        let signature = collector.sign()?;
+        let signer_idx = signer_idx(&sig_provider, &self.node_id)?;
+
+        info!("Signed evidence of misbehaviour of {}", offender);
+
+        self.broadcast_evidence_message(EvidenceMessage::Signed {
+            misbehaviour: misbehaviour.clone(),
+            offender: offender.clone(),
+            signer_idx,
+            signature: signature.clone(),
+        })
+        .await?;
+
+        self.handle_evidence_signed(misbehaviour, offender, signer_idx, signature)
+            .await
+    }
+
+    /// Gathers the evidence signature shares of the harvesters, certifying
+    /// the evidence once there are enough of them.
+    async fn handle_evidence_signed(
+        &mut self,
+        misbehaviour: Misbehaviour,
+        offender: Address,
+        signer_idx: NodeIdx,
+        signature: RawSignature,
+    ) -> crate::Result<()> {
+        if !self.sig_providers.contains_key(&QuorumKind::Harvester)
+            || self
+                .certified_evidence
+                .contains(&Evidence::hash_of(&misbehaviour, &offender))
+        {
+            return Ok(());
+        }
+
+        let keypair = self.keypair.clone();
+        let collector = self.evidence_collector(misbehaviour, offender)?;
+
+        let Some(evidence) = collector.add_signature_share(signer_idx, signature, &keypair)? else {
+            return Ok(());
+        };
+
+        self.broadcast_evidence_message(EvidenceMessage::Certified(evidence.clone()))
+            .await?;
+
+        self.handle_evidence_certified(evidence).await
+    }
+
+    /// Hands certified evidence over to the modules that store it and
+    /// include it in the next convergence block.
+    async fn handle_evidence_certified(&mut self, evidence: Evidence) -> crate::Result<()> {
+        let evidence_hash = evidence.hash();
+
+        if self.certified_evidence.contains(&evidence_hash) {
+            return Ok(());
+        }
+
+        // NOTE: harvesters only accept evidence certified by their own quorum,
+        // other nodes leave it to the state manager to verify it against the
+        // keys of the epoch of the block including it
+        if let Some(sig_provider) = self.sig_providers.get(&QuorumKind::Harvester) {
+            let public_key_set = sig_provider
+                .dkg_state
+                .read()
+                .map_err(|err| NodeError::Other(err.to_string()))?
+                .public_key_set
+                .clone()
+                .ok_or_else(|| NodeError::Other("missing harvester key set".to_string()))?;
+
+            if !evidence.verify(&public_key_set) {
+                return Err(NodeError::Other(format!(
+                    "invalid certificate for evidence {evidence_hash}"
+                )));
+            }
+        }
+
+        info!(
+            "Evidence of misbehaviour of {} was certified",
+            evidence.offender
+        );
+
+        self.evidence_collectors.remove(&evidence_hash);
+        self.certified_evidence.insert(evidence_hash);
+
+        self.events_tx
+            .send(Event::EvidenceCertified(evidence).into())
+            .await
+            .map_err(NodeError::from)
+    }
+
+    /// Returns the collector of signatures over the given evidence, creating
+    /// it if needed.
+    fn evidence_collector(
+        &mut self,
+        misbehaviour: Misbehaviour,
+        offender: Address,
+    ) -> crate::Result<&mut EvidenceCollector> {
+        let sig_provider = self
+            .sig_provider(&QuorumKind::Harvester)
+            .ok_or_else(|| NodeError::Other("node is not a harvester".to_string()))?;
+
+        let evidence_hash = Evidence::hash_of(&misbehaviour, &offender);
+
+        if !self.evidence_collectors.contains_key(&evidence_hash) {
+            let collector = EvidenceCollector::new(misbehaviour, offender, sig_provider)?;
+
+            self.evidence_collectors
+                .insert(evidence_hash.clone(), collector);
+        }
+
+        self.evidence_collectors
+            .get_mut(&evidence_hash)
+            .ok_or_else(|| NodeError::Other(format!("missing collector for {evidence_hash}")))
+    }
+
+    async fn broadcast_evidence_message(&self, message: EvidenceMessage) -> crate::Result<()> {
+        let evt = Event::EvidenceMessageCreated(message);
+        let em = EventMessage::new(Some("network-events".into()), evt);
+
+        self.events_tx.send(em).await.map_err(NodeError::from)
+    }
+
+    /// Asks the harvester quorum to certify a stake submitted to this node.
+    pub async fn submit_stake(&mut self, stake: Stake) -> crate::Result<()> {
+        let message = StakeMessage::Submitted(stake);
+
+        self.broadcast_stake_message(message.clone()).await?;
+        self.handle_stake_message(message).await
+    }
+
+    /// Takes part in the certification of stakes by the harvester quorum.
+    pub async fn handle_stake_message(&mut self, message: StakeMessage) -> crate::Result<()> {
+        match message {
+            StakeMessage::Submitted(stake) => self.handle_stake_submitted(stake).await,
+            StakeMessage::Signed {
+                stake,
+                signer_idx,
+                signature,
+            } => self.handle_stake_signed(stake, signer_idx, signature).await,
+            StakeMessage::Certified(stake) => self.handle_stake_certified(stake).await,
+        }
+    }
+
+    /// Checks that the given stake can apply to the claim it targets. Slashes
+    /// are only certified as part of evidence of misbehaviour.
+    fn validate_stake(&self, stake: &Stake) -> crate::Result<()> {
+        stake
+            .verify()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let staker = stake.get_sender();
+        if staker != Address::new(stake.get_pubkey()) {
+            return Err(NodeError::Other(format!(
+                "stake was not signed by its sender {staker}"
+            )));
+        }
+
+        let receiver = stake.get_receiver();
+        let claim = self
+            .vrrbdb_read_handle
+            .claim_store_handle()
+            .get_by_address(&receiver)
+            .map_err(|err| NodeError::Other(err.to_string()))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| NodeError::Other(format!("no claim found for {receiver}")))?;
+
+        match stake.get_amount() {
+            StakeUpdate::Add(amount) if amount > 0 => Ok(()),
+            StakeUpdate::Withdrawal(amount) => {
+                let staked = if staker == claim.address {
+                    claim.get_stake()
+                } else {
+                    claim
+                        .get_delegations()
+                        .get(&staker)
+                        .copied()
+                        .unwrap_or_default()
+                };
+
+                if amount == 0 || amount > staked {
+                    return Err(NodeError::Other(format!(
+                        "{staker} cannot withdraw {amount} from the claim of {receiver}"
+                    )));
+                }
+
+                Ok(())
+            },
+            _ => Err(NodeError::Other(format!(
+                "stake of {staker} is neither a deposit nor a withdrawal"
+            ))),
+        }
+    }
+
+    /// Signs the stake with the local node's share of the harvester quorum's
+    /// keys, once it checked the stake can apply.
+    async fn handle_stake_submitted(&mut self, stake: Stake) -> crate::Result<()> {
+        let Some(sig_provider) = self.sig_provider(&QuorumKind::Harvester) else {
+            return Ok(());
+        };
+
+        if self.certified_stakes.contains(&stake_hash(&stake)) {
+            return Ok(());
+        }
+
+        self.validate_stake(&stake)?;
+
+        let signature = self.stake_collector(stake.clone())?.sign()?;
+        let signer_idx = signer_idx(&sig_provider, &self.node_id)?;
+
+        info!("Signed stake of {}", stake.get_sender());
+
+        self.broadcast_stake_message(StakeMessage::Signed {
+            stake: stake.clone(),
+            signer_idx,
+            signature: signature.clone(),
+        })
+        .await?;
+
+        self.handle_stake_signed(stake, signer_idx, signature).await
+    }
+
+    /// Gathers the stake signature shares of the harvesters, certifying the
+    /// stake once there are enough of them.
+    async fn handle_stake_signed(
+        &mut self,
+        stake: Stake,
+        signer_idx: NodeIdx,
+        signature: RawSignature,
+    ) -> crate::Result<()> {
+        if !self.sig_providers.contains_key(&QuorumKind::Harvester)
+            || self.certified_stakes.contains(&stake_hash(&stake))
+        {
+            return Ok(());
+        }
+
+        let collector = self.stake_collector(stake)?;
+        let Some(stake) = collector.add_signature_share(signer_idx, signature)? else {
+            return Ok(());
+        };
+
+        self.broadcast_stake_message(StakeMessage::Certified(stake.clone()))
+            .await?;
+
+        self.handle_stake_certified(stake).await
+    }
+
+    /// Hands a certified stake over to the miner, which includes it in the
+    /// next convergence block.
+    async fn handle_stake_certified(&mut self, stake: Stake) -> crate::Result<()> {
+        let stake_hash = stake_hash(&stake);
+
+        if self.certified_stakes.contains(&stake_hash) {
+            return Ok(());
+        }
+
+        // NOTE: harvesters only accept stakes certified by their own quorum,
+        // other nodes leave it to the state manager to verify them against
+        // the keys of the epoch of the block including them
+        if let Some(sig_provider) = self.sig_providers.get(&QuorumKind::Harvester) {
+            let public_key_set = sig_provider
+                .dkg_state
+                .read()
+                .map_err(|err| NodeError::Other(err.to_string()))?
+                .public_key_set
+                .clone()
+                .ok_or_else(|| NodeError::Other("missing harvester key set".to_string()))?;
+
+            stake
+                .verify_certificate(&public_key_set.public_key())
+                .map_err(|err| NodeError::Other(format!("stake {stake_hash}: {err}")))?;
+        }
+
+        info!("Stake of {} was certified", stake.get_sender());
+
+        self.stake_collectors.remove(&stake_hash);
+        self.certified_stakes.insert(stake_hash);
+
+        self.events_tx
+            .send(Event::StakeCertified(stake).into())
+            .await
+            .map_err(NodeError::from)
+    }
+
+    /// Returns the collector of signatures over the given stake, creating it
+    /// if needed.
+    fn stake_collector(&mut self, stake: Stake) -> crate::Result<&mut StakeCollector> {
+        let sig_provider = self
+            .sig_provider(&QuorumKind::Harvester)
+            .ok_or_else(|| NodeError::Other("node is not a harvester".to_string()))?;
+
+        let stake_hash = stake_hash(&stake);
+
+        Ok(self
+            .stake_collectors
+            .entry(stake_hash)
+            .or_insert_with(|| StakeCollector::new(stake, sig_provider)))
+    }
+
+    async fn broadcast_stake_message(&self, message: StakeMessage) -> crate::Result<()> {
+        let evt = Event::StakeMessageCreated(message);
+        let em = EventMessage::new(Some("network-events".into()), evt);
+
+        self.events_tx.send(em).await.map_err(NodeError::from)
+    }
+
+    async fn send_dkg_messages(
+        &self,
+        quorum_kind: &QuorumKind,
+        messages: Vec<DkgMessage>,
+    ) -> crate::Result<()> {
+        let Some(session) = self.dkg_sessions.get(quorum_kind) else {
+            return Ok(());
+        };
+
+        let secret_key = self.keypair.get_validator_secret_key();
+
+        for message in messages {
+            let message = SignedDkgMessage::new(self.node_id.clone(), message, secret_key);
+            let evt = Event::DkgMessageCreated(message, session.peer_addrs());
+            let em = EventMessage::new(Some("network-events".into()), evt);
+
+            self.events_tx.send(em).await.map_err(NodeError::from)?;
+        }
+
+        Ok(())
+    }
+
+    /// Notifies the module once the current phase of the given quorum's DKG
+    /// run should have been over.
+    fn schedule_dkg_phase_timeout(&self, quorum_kind: &QuorumKind) {
+        let Some(session) = self.dkg_sessions.get(quorum_kind) else {
+            return;
+        };
+
+        if matches!(session.phase(), DkgPhase::Completed | DkgPhase::Failed) {
+            return;
+        }
+
+        let evt = Event::DkgPhaseTimedOut(quorum_kind.clone(), session.phase_token());
+        let events_tx = self.events_tx.clone();
+
+        tokio::spawn(async move {
+            tokio::time::sleep(DKG_PHASE_TIMEOUT).await;
+
+            let em = EventMessage::new(Some("consensus-events".into()), evt);
+            if let Err(err) = events_tx.send(em).await {
+                telemetry::error!("failed to notify DKG phase timeout: {}", err);
+            }
+        });
     }
 }
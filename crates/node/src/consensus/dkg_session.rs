@@ -0,0 +1,723 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    net::SocketAddr,
+    path::PathBuf,
+    time::Duration,
+};
+
+use dkg_engine::{
+    dkg::DkgGenerator,
+    engine::{DkgEngine, DkgEngineConfig, DkgPersistenceConfig, DkgState},
+    share_store::{DkgShareTag, QuorumId},
+};
+use events::{DkgMessage, DkgPhaseToken, PeerData, SignedDkgMessage};
+use hbbft::{
+    crypto::{PublicKey, PublicKeySet, SecretKey},
+    sync_key_gen::{Ack, Part},
+};
+use primitives::{Epoch, NodeId, NodeType, QuorumKind};
+use vrrb_config::ThresholdConfig;
+
+use crate::{NodeError, Result};
+
+/// Identifies a quorum's shares within the node's DKG share store.
+pub fn dkg_quorum_id(quorum_kind: &QuorumKind) -> QuorumId {
+    format!("{quorum_kind:?}").to_lowercase()
+}
+
+/// How long a DKG phase may go on before its messages are sent again.
+pub const DKG_PHASE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How many times the messages of a DKG phase are sent again before the run
+/// is abandoned.
+pub const DKG_MAX_PHASE_RETRIES: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DkgPhase {
+    /// Waiting for the validator public key of every quorum member.
+    SharingPublicKeys,
+    /// Waiting for the `Part` commitment of every quorum member.
+    SharingParts,
+    /// Waiting for every quorum member to acknowledge every `Part`.
+    SharingAcks,
+    /// The `PublicKeySet` and this node's `SecretKeyShare` were generated.
+    Completed,
+    /// The run was abandoned after too many retries.
+    Failed,
+}
+
+/// What should happen after a DKG phase timed out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DkgTimeoutOutcome {
+    /// The timeout belongs to a phase that is already over.
+    Ignored,
+    /// The given messages should be sent again.
+    Retry(Vec<DkgMessage>),
+    /// The run was given up on.
+    Abandoned,
+}
+
+/// Drives a single DKG run among the members of a quorum, going from
+/// sharing public keys to sharing `Part` commitments and then `Ack`s until
+/// the quorum's `PublicKeySet` can be generated. Sessions do not perform any
+/// IO, they return the messages that should be sent to the other members.
+#[derive(Debug)]
+pub struct DkgSession {
+    quorum_kind: QuorumKind,
+    epoch: Epoch,
+    engine: DkgEngine,
+    members: BTreeSet<NodeId>,
+    /// Validator keys the members were assigned to the quorum with, which
+    /// their messages must be signed with
+    member_public_keys: BTreeMap<NodeId, PublicKey>,
+    peer_addrs: Vec<SocketAddr>,
+    phase: DkgPhase,
+    phase_token: DkgPhaseToken,
+    retries: usize,
+    sent_messages: Vec<DkgMessage>,
+    /// Whether the session completed by restoring persisted shares rather
+    /// than by running DKG
+    restored: bool,
+}
+
+/// Everything needed to take part in a quorum's DKG run.
+#[derive(Debug, Clone)]
+pub struct DkgSessionConfig {
+    pub node_id: NodeId,
+    pub node_type: NodeType,
+    pub secret_key: SecretKey,
+    pub threshold_config: ThresholdConfig,
+    pub quorum_kind: QuorumKind,
+    /// Members of the quorum, which should include the local node
+    pub members: Vec<PeerData>,
+    pub epoch: Epoch,
+    /// Directory the output of the run is persisted to, if any
+    pub data_dir: Option<PathBuf>,
+}
+
+impl DkgSession {
+    /// Creates a session for the quorum described by the given config. If
+    /// the output of an earlier run was persisted for the same epoch and
+    /// membership, the session starts out completed.
+    pub fn new(config: DkgSessionConfig) -> Result<Self> {
+        let DkgSessionConfig {
+            node_id,
+            node_type,
+            secret_key,
+            threshold_config,
+            quorum_kind,
+            members,
+            epoch,
+            data_dir,
+        } = config;
+
+        let member_ids: BTreeSet<NodeId> = members
+            .iter()
+            .map(|member| member.node_id.clone())
+            .chain(std::iter::once(node_id.clone()))
+            .collect();
+
+        let node_idx = member_ids
+            .iter()
+            .position(|member_id| member_id == &node_id)
+            .unwrap_or_default() as u16;
+
+        // NOTE: the threshold can not exceed the number of members minus one
+        let threshold_config = ThresholdConfig {
+            upper_bound: member_ids.len() as u16,
+            threshold: threshold_config
+                .threshold
+                .min(member_ids.len().saturating_sub(1) as u16),
+        };
+
+        if threshold_config.upper_bound < 2 {
+            return Err(NodeError::Other(format!(
+                "a {quorum_kind:?} quorum needs at least 2 members to run DKG"
+            )));
+        }
+
+        let public_key = secret_key.public_key();
+
+        let member_public_keys = members
+            .iter()
+            .map(|member| (member.node_id.clone(), member.validator_public_key))
+            .chain(std::iter::once((node_id.clone(), public_key)))
+            .collect();
+
+        let peer_addrs = members
+            .iter()
+            .filter(|member| member.node_id != node_id)
+            .map(|member| member.udp_gossip_addr)
+            .collect();
+
+        let persistence = data_dir.map(|data_dir| DkgPersistenceConfig {
+            data_dir,
+            tag: DkgShareTag {
+                quorum_id: dkg_quorum_id(&quorum_kind),
+                epoch,
+                members: member_ids.clone(),
+            },
+        });
+
+        let mut engine = DkgEngine::new(DkgEngineConfig {
+            node_id: node_id.clone(),
+            node_idx,
+            node_type,
+            secret_key,
+            threshold_config,
+            persistence,
+        })
+        .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        engine.add_peer_public_key(node_id, public_key);
+
+        let restored = engine.dkg_state.secret_key_share.is_some();
+        let phase = if restored {
+            DkgPhase::Completed
+        } else {
+            DkgPhase::SharingPublicKeys
+        };
+
+        Ok(Self {
+            quorum_kind,
+            epoch,
+            engine,
+            members: member_ids,
+            member_public_keys,
+            peer_addrs,
+            phase,
+            phase_token: 0,
+            retries: 0,
+            sent_messages: vec![],
+            restored,
+        })
+    }
+
+    pub fn quorum_kind(&self) -> &QuorumKind {
+        &self.quorum_kind
+    }
+
+    /// Epoch the session runs DKG for.
+    pub fn epoch(&self) -> Epoch {
+        self.epoch
+    }
+
+    pub fn phase(&self) -> &DkgPhase {
+        &self.phase
+    }
+
+    /// Identifies the current attempt at the current phase, so timeouts
+    /// scheduled for earlier attempts can be told apart.
+    pub fn phase_token(&self) -> DkgPhaseToken {
+        self.phase_token
+    }
+
+    /// Node ids of every quorum member, the local node included.
+    pub fn members(&self) -> &BTreeSet<NodeId> {
+        &self.members
+    }
+
+    /// Addresses of the other quorum members.
+    pub fn peer_addrs(&self) -> Vec<SocketAddr> {
+        self.peer_addrs.clone()
+    }
+
+    /// Starts the run by sharing the local node's public key.
+    pub fn start(&mut self) -> Result<Vec<DkgMessage>> {
+        if self.phase == DkgPhase::Completed {
+            return Ok(vec![]);
+        }
+
+        let public_key = bincode::serialize(&self.engine.get_public_key())
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let mut messages = vec![DkgMessage::PublicKeyShared {
+            quorum_kind: self.quorum_kind.clone(),
+            public_key,
+        }];
+
+        messages.extend(self.advance()?);
+        self.sent_messages.extend(messages.clone());
+
+        Ok(messages)
+    }
+
+    /// Handles a message sent by another member and returns the messages
+    /// that should be sent in response. Messages must be signed with the
+    /// validator key the sender was assigned to the quorum with.
+    pub fn handle_message(&mut self, message: SignedDkgMessage) -> Result<Vec<DkgMessage>> {
+        let sender_id = message.sender_id.clone();
+
+        let assigned_public_key = match self.member_public_keys.get(&sender_id) {
+            Some(public_key) if sender_id != self.engine.node_id() => *public_key,
+            _ => {
+                return Err(NodeError::Other(format!(
+                    "{sender_id} is not a member of the {:?} quorum",
+                    self.quorum_kind
+                )))
+            },
+        };
+
+        if !message.verify(&assigned_public_key) {
+            return Err(NodeError::Other(format!(
+                "DKG message from {sender_id} is not signed with its validator key"
+            )));
+        }
+
+        // NOTE: a member sharing its public key again is running DKG anew, most
+        // likely because it lost its shares, so restored shares are stale
+        let mut messages = vec![];
+        if self.restored && matches!(message.message, DkgMessage::PublicKeyShared { .. }) {
+            messages.extend(self.restart()?);
+        }
+
+        if matches!(self.phase, DkgPhase::Completed | DkgPhase::Failed) {
+            return Ok(messages);
+        }
+
+        match message.message {
+            DkgMessage::PublicKeyShared { public_key, .. } => {
+                let public_key: PublicKey = bincode::deserialize(&public_key)
+                    .map_err(|err| NodeError::Other(err.to_string()))?;
+
+                if public_key != assigned_public_key {
+                    return Err(NodeError::Other(format!(
+                        "{sender_id} shared a key it was not assigned to the quorum with"
+                    )));
+                }
+
+                self.engine.add_peer_public_key(sender_id, public_key);
+            },
+            DkgMessage::PartCommitmentCreated { part, .. } => {
+                let part: Part =
+                    bincode::deserialize(&part).map_err(|err| NodeError::Other(err.to_string()))?;
+
+                self.engine
+                    .dkg_state
+                    .part_message_store
+                    .entry(sender_id)
+                    .or_insert(part);
+            },
+            DkgMessage::PartCommitmentAcknowledged {
+                part_sender_id,
+                ack,
+                ..
+            } => {
+                let ack: Ack =
+                    bincode::deserialize(&ack).map_err(|err| NodeError::Other(err.to_string()))?;
+
+                self.engine
+                    .dkg_state
+                    .ack_message_store
+                    .insert((sender_id, part_sender_id), ack);
+            },
+        }
+
+        let advanced = self.advance()?;
+        self.sent_messages.extend(advanced.clone());
+        messages.extend(advanced);
+
+        Ok(messages)
+    }
+
+    /// Discards the shares the session was restored with and starts a new
+    /// run among the same members.
+    fn restart(&mut self) -> Result<Vec<DkgMessage>> {
+        self.engine
+            .discard_key_sets()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let public_key = self.engine.get_public_key();
+        self.engine
+            .add_peer_public_key(self.engine.node_id(), public_key);
+
+        self.restored = false;
+        self.sent_messages.clear();
+        self.enter_phase(DkgPhase::SharingPublicKeys);
+
+        self.start()
+    }
+
+    /// Handles the timeout of the phase identified by the given token.
+    pub fn handle_timeout(&mut self, phase_token: DkgPhaseToken) -> DkgTimeoutOutcome {
+        if phase_token != self.phase_token
+            || matches!(self.phase, DkgPhase::Completed | DkgPhase::Failed)
+        {
+            return DkgTimeoutOutcome::Ignored;
+        }
+
+        if self.retries >= DKG_MAX_PHASE_RETRIES {
+            self.phase = DkgPhase::Failed;
+            self.engine.clear_state();
+
+            return DkgTimeoutOutcome::Abandoned;
+        }
+
+        self.retries += 1;
+        self.phase_token += 1;
+
+        // NOTE: members ignore the messages they already handled
+        DkgTimeoutOutcome::Retry(self.sent_messages.clone())
+    }
+
+    /// Returns a copy of the DKG state once the run completed, to be used by
+    /// a `SignatureProvider`.
+    pub fn dkg_state(&self) -> Option<DkgState> {
+        if self.phase != DkgPhase::Completed {
+            return None;
+        }
+
+        let dkg_state = &self.engine.dkg_state;
+
+        Some(DkgState {
+            part_message_store: HashMap::new(),
+            ack_message_store: HashMap::new(),
+            peer_public_keys: dkg_state.peer_public_keys.clone(),
+            public_key_set: dkg_state.public_key_set.clone(),
+            secret_key_share: dkg_state.secret_key_share.clone(),
+            sync_key_gen: None,
+            random_number_gen: None,
+        })
+    }
+
+    pub fn public_key_set(&self) -> Option<PublicKeySet> {
+        self.engine.dkg_state.public_key_set.clone()
+    }
+
+    pub fn threshold_config(&self) -> ThresholdConfig {
+        self.engine.threshold_config()
+    }
+
+    /// Moves the run forward as far as the messages received so far allow,
+    /// returning the messages produced along the way.
+    fn advance(&mut self) -> Result<Vec<DkgMessage>> {
+        let mut messages = vec![];
+        let member_count = self.members.len();
+
+        if self.phase == DkgPhase::SharingPublicKeys
+            && self.engine.dkg_state.peer_public_keys.len() == member_count
+        {
+            let threshold = self.engine.threshold_config.threshold as usize;
+            let (part, _) = self
+                .engine
+                .generate_partial_commitment(threshold)
+                .map_err(|err| NodeError::Other(err.to_string()))?;
+
+            let part =
+                bincode::serialize(&part).map_err(|err| NodeError::Other(err.to_string()))?;
+
+            messages.push(DkgMessage::PartCommitmentCreated {
+                quorum_kind: self.quorum_kind.clone(),
+                part,
+            });
+
+            self.enter_phase(DkgPhase::SharingParts);
+        }
+
+        if self.phase == DkgPhase::SharingParts {
+            messages.extend(self.acknowledge_parts()?);
+
+            let node_id = self.engine.node_id();
+            let acked_parts = self
+                .engine
+                .dkg_state
+                .ack_message_store
+                .keys()
+                .filter(|(acker_id, _)| acker_id == &node_id)
+                .count();
+
+            if acked_parts == member_count {
+                self.enter_phase(DkgPhase::SharingAcks);
+            }
+        }
+
+        if self.phase == DkgPhase::SharingAcks
+            && self.engine.dkg_state.ack_message_store.len() == member_count * member_count
+        {
+            self.engine
+                .handle_ack_messages()
+                .map_err(|err| NodeError::Other(err.to_string()))?;
+
+            self.engine
+                .generate_key_sets()
+                .map_err(|err| NodeError::Other(err.to_string()))?;
+
+            if let Err(err) = self.engine.persist_key_sets() {
+                telemetry::warn!("failed to persist DKG shares: {}", err);
+            }
+
+            self.enter_phase(DkgPhase::Completed);
+        }
+
+        Ok(messages)
+    }
+
+    /// Acknowledges every `Part` received that was not acknowledged yet.
+    fn acknowledge_parts(&mut self) -> Result<Vec<DkgMessage>> {
+        let node_id = self.engine.node_id();
+
+        let unacknowledged_part_senders: Vec<NodeId> = self
+            .engine
+            .dkg_state
+            .part_message_store
+            .keys()
+            .filter(|part_sender_id| {
+                !self
+                    .engine
+                    .dkg_state
+                    .ack_message_store
+                    .contains_key(&(node_id.clone(), (*part_sender_id).clone()))
+            })
+            .cloned()
+            .collect();
+
+        let mut messages = vec![];
+
+        for part_sender_id in unacknowledged_part_senders {
+            self.engine
+                .ack_partial_commitment(part_sender_id.clone())
+                .map_err(|err| NodeError::Other(err.to_string()))?;
+
+            if let Some(ack) = self
+                .engine
+                .dkg_state
+                .ack_message_store
+                .get(&(node_id.clone(), part_sender_id.clone()))
+            {
+                let ack =
+                    bincode::serialize(ack).map_err(|err| NodeError::Other(err.to_string()))?;
+
+                messages.push(DkgMessage::PartCommitmentAcknowledged {
+                    quorum_kind: self.quorum_kind.clone(),
+                    part_sender_id,
+                    ack,
+                });
+            }
+        }
+
+        Ok(messages)
+    }
+
+    fn enter_phase(&mut self, phase: DkgPhase) {
+        self.phase = phase;
+        self.phase_token += 1;
+        self.retries = 0;
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::VecDeque;
+
+    use primitives::KademliaPeerId;
+    use utils::payload::digest_data_to_bytes;
+
+    use super::*;
+
+    fn quorum_members(secret_keys: &[SecretKey]) -> Vec<PeerData> {
+        (0..secret_keys.len() as u16)
+            .map(|i| PeerData {
+                node_id: format!("node-{i}"),
+                node_type: NodeType::Validator,
+                kademlia_peer_id: KademliaPeerId::try_from(digest_data_to_bytes(&i)).unwrap(),
+                udp_gossip_addr: format!("127.0.0.1:{}", 9000 + i).parse().unwrap(),
+                raptorq_gossip_addr: format!("127.0.0.1:{}", 9100 + i).parse().unwrap(),
+                kademlia_liveness_addr: format!("127.0.0.1:{}", 9200 + i).parse().unwrap(),
+                validator_public_key: secret_keys[i as usize].public_key(),
+            })
+            .collect()
+    }
+
+    fn quorum_sessions(secret_keys: &[SecretKey]) -> Vec<DkgSession> {
+        let members = quorum_members(secret_keys);
+
+        (0..secret_keys.len())
+            .map(|idx| quorum_session(&members, &secret_keys[idx], None))
+            .collect()
+    }
+
+    fn quorum_session(
+        members: &[PeerData],
+        secret_key: &SecretKey,
+        data_dir: Option<PathBuf>,
+    ) -> DkgSession {
+        let member = members
+            .iter()
+            .find(|member| member.validator_public_key == secret_key.public_key())
+            .unwrap();
+
+        DkgSession::new(DkgSessionConfig {
+            node_id: member.node_id.clone(),
+            node_type: NodeType::Validator,
+            secret_key: secret_key.clone(),
+            threshold_config: ThresholdConfig {
+                upper_bound: members.len() as u16,
+                threshold: 1,
+            },
+            quorum_kind: QuorumKind::Harvester,
+            members: members.to_vec(),
+            epoch: 0,
+            data_dir,
+        })
+        .unwrap()
+    }
+
+    /// Starts DKG on every session and delivers every message to every other
+    /// member until no more messages are produced.
+    fn run_quorum_sessions(sessions: &mut [DkgSession], secret_keys: &[SecretKey]) {
+        let mut outbox: VecDeque<(usize, DkgMessage)> = VecDeque::new();
+        for (idx, session) in sessions.iter_mut().enumerate() {
+            for message in session.start().unwrap() {
+                outbox.push_back((idx, message));
+            }
+        }
+
+        while let Some((sender_idx, message)) = outbox.pop_front() {
+            let message = SignedDkgMessage::new(
+                format!("node-{sender_idx}"),
+                message,
+                &secret_keys[sender_idx],
+            );
+
+            for (idx, session) in sessions.iter_mut().enumerate() {
+                if idx == sender_idx {
+                    continue;
+                }
+
+                for response in session.handle_message(message.clone()).unwrap() {
+                    outbox.push_back((idx, response));
+                }
+            }
+        }
+    }
+
+    /// Runs DKG to completion among a quorum of `count` members, delivering
+    /// every message to every other member.
+    pub(crate) fn completed_quorum_sessions(count: u16) -> Vec<DkgSession> {
+        let secret_keys: Vec<SecretKey> = (0..count).map(|_| SecretKey::random()).collect();
+        let mut sessions = quorum_sessions(&secret_keys);
+
+        run_quorum_sessions(&mut sessions, &secret_keys);
+
+        sessions
+    }
+
+    #[test]
+    fn quorum_members_generate_the_same_public_key_set() {
+        let sessions = completed_quorum_sessions(4);
+
+        assert!(sessions
+            .iter()
+            .all(|session| session.phase() == &DkgPhase::Completed));
+
+        let public_key_set = sessions[0].public_key_set().unwrap();
+        assert!(sessions
+            .iter()
+            .all(|session| session.public_key_set() == Some(public_key_set.clone())));
+
+        assert!(sessions[0].dkg_state().unwrap().secret_key_share.is_some());
+    }
+
+    #[test]
+    fn restored_sessions_rejoin_new_runs() {
+        let secret_keys: Vec<SecretKey> = (0..3).map(|_| SecretKey::random()).collect();
+        let members = quorum_members(&secret_keys);
+        let data_dirs: Vec<PathBuf> = secret_keys
+            .iter()
+            .map(|_| std::env::temp_dir().join(vrrb_core::helpers::generate_random_string()))
+            .collect();
+
+        let persisted_sessions = |data_dirs: &[PathBuf]| -> Vec<DkgSession> {
+            secret_keys
+                .iter()
+                .zip(data_dirs)
+                .map(|(secret_key, data_dir)| {
+                    quorum_session(&members, secret_key, Some(data_dir.clone()))
+                })
+                .collect()
+        };
+
+        let mut sessions = persisted_sessions(&data_dirs);
+        run_quorum_sessions(&mut sessions, &secret_keys);
+        let public_key_set = sessions[0].public_key_set().unwrap();
+
+        let sessions = persisted_sessions(&data_dirs);
+        assert!(sessions
+            .iter()
+            .all(|session| session.phase() == &DkgPhase::Completed));
+
+        // Member 2 lost its shares and starts a new run
+        let mut data_dirs = data_dirs;
+        data_dirs[2] = std::env::temp_dir().join(vrrb_core::helpers::generate_random_string());
+
+        let mut sessions = persisted_sessions(&data_dirs);
+        assert_eq!(sessions[2].phase(), &DkgPhase::SharingPublicKeys);
+
+        run_quorum_sessions(&mut sessions, &secret_keys);
+
+        let new_public_key_set = sessions[2].public_key_set().unwrap();
+        assert_ne!(new_public_key_set, public_key_set);
+        assert!(sessions.iter().all(|session| {
+            session.phase() == &DkgPhase::Completed
+                && session.public_key_set() == Some(new_public_key_set.clone())
+        }));
+    }
+
+    #[test]
+    fn phases_are_retried_before_being_abandoned() {
+        let secret_keys: Vec<SecretKey> = (0..2).map(|_| SecretKey::random()).collect();
+        let mut sessions = quorum_sessions(&secret_keys);
+        let session = sessions.first_mut().unwrap();
+
+        let messages = session.start().unwrap();
+        let mut phase_token = session.phase_token();
+
+        assert_eq!(session.phase(), &DkgPhase::SharingPublicKeys);
+        assert_eq!(
+            session.handle_timeout(phase_token + 1),
+            DkgTimeoutOutcome::Ignored
+        );
+
+        for _ in 0..DKG_MAX_PHASE_RETRIES {
+            assert_eq!(
+                session.handle_timeout(phase_token),
+                DkgTimeoutOutcome::Retry(messages.clone())
+            );
+            phase_token = session.phase_token();
+        }
+
+        assert_eq!(
+            session.handle_timeout(phase_token),
+            DkgTimeoutOutcome::Abandoned
+        );
+        assert_eq!(session.phase(), &DkgPhase::Failed);
+    }
+
+    #[test]
+    fn messages_not_signed_by_the_sender_are_rejected() {
+        let secret_keys: Vec<SecretKey> = (0..3).map(|_| SecretKey::random()).collect();
+        let mut sessions = quorum_sessions(&secret_keys);
+
+        let message = sessions[1].start().unwrap().remove(0);
+
+        // Member 2 impersonates member 1
+        let forged = SignedDkgMessage::new("node-1".to_string(), message.clone(), &secret_keys[2]);
+        assert!(sessions[0].handle_message(forged).is_err());
+
+        let mut tampered = SignedDkgMessage::new("node-1".to_string(), message, &secret_keys[1]);
+        tampered.sender_id = "node-2".to_string();
+        assert!(sessions[0].handle_message(tampered).is_err());
+
+        // Member 1 shares a key it was not assigned to the quorum with
+        let public_key = bincode::serialize(&SecretKey::random().public_key()).unwrap();
+        let rogue_key = SignedDkgMessage::new(
+            "node-1".to_string(),
+            DkgMessage::PublicKeyShared {
+                quorum_kind: QuorumKind::Harvester,
+                public_key,
+            },
+            &secret_keys[1],
+        );
+        assert!(sessions[0].handle_message(rogue_key).is_err());
+    }
+}
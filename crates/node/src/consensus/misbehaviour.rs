@@ -0,0 +1,379 @@
+use std::collections::{BTreeMap, HashMap};
+
+use block::{
+    header::BlockHeader,
+    BlockHash,
+    ConvergenceBlock,
+    Evidence,
+    EvidenceHash,
+    Misbehaviour,
+    ProposalBlock,
+    QuorumPubkey,
+    SignedBlockHeader,
+};
+use primitives::{Address, NodeId, NodeIdx, RawSignature, Round, SignatureType};
+use signer::signer::{SignatureProvider, Signer};
+use vrrb_core::keypair::Keypair;
+
+use crate::{consensus::quorum_pubkey, NodeError, Result};
+
+/// Number of rounds blocks and signatures are remembered for, so equivocations
+/// can be detected.
+pub const MISBEHAVIOUR_DETECTION_ROUNDS: Round = 16;
+
+/// Remembers the proposal blocks and convergence block signatures of recent
+/// rounds, and reports the ones that conflict with what was already seen.
+#[derive(Debug, Default)]
+pub struct MisbehaviourDetector {
+    latest_round: Round,
+    /// Proposal blocks by round and public key of the claim that signed them
+    proposal_blocks: HashMap<(Round, String), ProposalBlock>,
+    convergence_block_headers: HashMap<BlockHash, BlockHeader>,
+    block_signatures: HashMap<(Round, NodeIdx), SignedBlockHeader>,
+}
+
+impl MisbehaviourDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn is_stale(&self, round: Round) -> bool {
+        round.saturating_add(MISBEHAVIOUR_DETECTION_ROUNDS) < self.latest_round
+    }
+
+    fn advance_to(&mut self, round: Round) {
+        if round <= self.latest_round {
+            return;
+        }
+
+        self.latest_round = round;
+
+        let min_round = round.saturating_sub(MISBEHAVIOUR_DETECTION_ROUNDS);
+
+        self.proposal_blocks
+            .retain(|(round, _), _| *round >= min_round);
+        self.convergence_block_headers
+            .retain(|_, header| header.round >= min_round);
+        self.block_signatures
+            .retain(|(round, _), _| *round >= min_round);
+    }
+
+    /// Records a proposal block, returning the equivocation it proves if its
+    /// claim already signed a different block for the same round.
+    pub fn observe_proposal_block(&mut self, block: &ProposalBlock) -> Option<Misbehaviour> {
+        // NOTE: blocks that were not signed by their claim cannot be held
+        // against it
+        if self.is_stale(block.round) || !block.verify_signature() {
+            return None;
+        }
+
+        self.advance_to(block.round);
+
+        let key = (block.round, block.from.public_key.to_string());
+
+        match self.proposal_blocks.get(&key) {
+            Some(first) if first.payload() != block.payload() => {
+                Some(Misbehaviour::ProposalEquivocation {
+                    first: Box::new(first.clone()),
+                    second: Box::new(block.clone()),
+                })
+            },
+            Some(_) => None,
+            None => {
+                self.proposal_blocks.insert(key, block.clone());
+                None
+            },
+        }
+    }
+
+    /// Records the header of a convergence block, so the signatures of
+    /// harvesters over its hash can be checked against it.
+    pub fn observe_convergence_block(&mut self, block: &ConvergenceBlock) {
+        if self.is_stale(block.header.round) {
+            return;
+        }
+
+        self.advance_to(block.header.round);
+
+        self.convergence_block_headers
+            .insert(block.header.hash(), block.header.clone());
+    }
+
+    /// Returns the header of a recent convergence block with the given hash.
+    pub fn convergence_block_header(&self, block_hash: &BlockHash) -> Option<&BlockHeader> {
+        self.convergence_block_headers.get(block_hash)
+    }
+
+    /// Records the partial signature of a harvester over a convergence block
+    /// and the state root it was signed on, returning the equivocation it
+    /// proves if the harvester already signed a different block for the same
+    /// round.
+    pub fn observe_block_signature(
+        &mut self,
+        signer_id: NodeId,
+        signer_idx: NodeIdx,
+        block_hash: &BlockHash,
+        root_hash: &str,
+        signature: RawSignature,
+    ) -> Option<Misbehaviour> {
+        let header = self.convergence_block_headers.get(block_hash)?.clone();
+        let key = (header.round, signer_idx);
+
+        let signed_header = SignedBlockHeader {
+            header,
+            root_hash: root_hash.to_string(),
+            signature,
+        };
+
+        match self.block_signatures.get(&key) {
+            Some(first) if first.header != signed_header.header => {
+                Some(Misbehaviour::BlockSignatureEquivocation {
+                    signer_id,
+                    signer_idx,
+                    first: Box::new(first.clone()),
+                    second: Box::new(signed_header),
+                })
+            },
+            Some(_) => None,
+            None => {
+                self.block_signatures.insert(key, signed_header);
+                None
+            },
+        }
+    }
+}
+
+/// Gathers the partial signatures of harvesters over evidence of
+/// misbehaviour, until there are enough of them to certify it.
+#[derive(Debug)]
+pub struct EvidenceCollector {
+    misbehaviour: Misbehaviour,
+    offender: Address,
+    quorum_pubkey: QuorumPubkey,
+    sig_provider: SignatureProvider,
+    signature_shares: BTreeMap<NodeIdx, RawSignature>,
+}
+
+impl EvidenceCollector {
+    /// Creates a collector for the given misbehaviour of the given offender,
+    /// using the harvester quorum's signature provider.
+    pub fn new(
+        misbehaviour: Misbehaviour,
+        offender: Address,
+        sig_provider: SignatureProvider,
+    ) -> Result<Self> {
+        let quorum_pubkey = quorum_pubkey(&sig_provider)?;
+
+        Ok(Self {
+            misbehaviour,
+            offender,
+            quorum_pubkey,
+            sig_provider,
+            signature_shares: BTreeMap::new(),
+        })
+    }
+
+    pub fn hash(&self) -> EvidenceHash {
+        Evidence::hash_of(&self.misbehaviour, &self.offender)
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        Evidence::payload(&self.misbehaviour, &self.offender)
+    }
+
+    /// Signs the evidence with the local node's secret key share.
+    pub fn sign(&self) -> Result<RawSignature> {
+        self.sig_provider
+            .generate_partial_signature(self.payload())
+            .map_err(|err| NodeError::Other(err.to_string()))
+    }
+
+    /// Adds the partial signature of a harvester, and returns the certified
+    /// evidence, along with its slash signed by the given keypair, once
+    /// enough signatures were gathered.
+    pub fn add_signature_share(
+        &mut self,
+        signer_idx: NodeIdx,
+        signature: RawSignature,
+        keypair: &Keypair,
+    ) -> Result<Option<Evidence>> {
+        let is_valid = self
+            .sig_provider
+            .verify_signature(
+                signer_idx,
+                self.payload(),
+                signature.clone(),
+                SignatureType::PartialSignature,
+            )
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        if !is_valid {
+            return Err(NodeError::Other(format!(
+                "invalid evidence signature share from harvester #{signer_idx}"
+            )));
+        }
+
+        self.signature_shares.insert(signer_idx, signature);
+
+        // NOTE: t + 1 signature shares are needed to produce a threshold signature
+        let required_shares = self.sig_provider.quorum_config.threshold + 1;
+        if (self.signature_shares.len() as u16) < required_shares {
+            return Ok(None);
+        }
+
+        let signature = self
+            .sig_provider
+            .generate_quorum_signature(required_shares, self.signature_shares.clone())
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let evidence = Evidence::new(
+            self.misbehaviour.clone(),
+            self.offender.clone(),
+            self.quorum_pubkey.clone(),
+            signature,
+            keypair.miner_kp.0,
+            keypair.miner_kp.1,
+        )
+        .ok_or_else(|| NodeError::Other("failed to create the slash of the offender".into()))?;
+
+        Ok(Some(evidence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::SocketAddr,
+        sync::{Arc, RwLock},
+    };
+
+    use vrrb_core::claim::Claim;
+
+    use super::*;
+    use crate::consensus::{dkg_session::tests::completed_quorum_sessions, signer_idx};
+
+    fn proposal_blocks(round: Round) -> (Keypair, ProposalBlock, ProposalBlock) {
+        let keypair = Keypair::random();
+        let public_key = keypair.miner_kp.1;
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            keypair.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+
+        let claim =
+            Claim::new(public_key, Address::new(public_key), ip_address, signature).unwrap();
+
+        let first = ProposalBlock::build(
+            "ref-block".to_string(),
+            round,
+            0,
+            Default::default(),
+            Default::default(),
+            claim.clone(),
+            keypair.get_miner_secret_key(),
+        );
+
+        let mut claims = block::ClaimList::new();
+        claims.insert(claim.hash, claim.clone());
+
+        let second = ProposalBlock::build(
+            "ref-block".to_string(),
+            round,
+            0,
+            Default::default(),
+            claims,
+            claim,
+            keypair.get_miner_secret_key(),
+        );
+
+        (keypair, first, second)
+    }
+
+    #[test]
+    fn detects_claims_proposing_two_blocks_for_the_same_round() {
+        let mut detector = MisbehaviourDetector::new();
+        let (_, first, second) = proposal_blocks(1);
+
+        assert!(detector.observe_proposal_block(&first).is_none());
+        assert!(detector.observe_proposal_block(&first).is_none());
+
+        let misbehaviour = detector.observe_proposal_block(&second).unwrap();
+        assert_eq!(misbehaviour.round(), 1);
+
+        let (_, other_round_block, _) = proposal_blocks(2);
+        assert!(detector
+            .observe_proposal_block(&other_round_block)
+            .is_none());
+
+        let mut forged_block = second;
+        forged_block.round = 2;
+        assert!(detector.observe_proposal_block(&forged_block).is_none());
+    }
+
+    #[test]
+    fn harvester_quorum_certifies_evidence() {
+        let sessions = completed_quorum_sessions(4);
+        let sig_providers: Vec<SignatureProvider> = sessions
+            .iter()
+            .map(|session| SignatureProvider {
+                dkg_state: Arc::new(RwLock::new(session.dkg_state().unwrap())),
+                quorum_config: session.threshold_config(),
+            })
+            .collect();
+
+        let (keypair, first, second) = proposal_blocks(1);
+        let offender = first.from.address.clone();
+        let misbehaviour = Misbehaviour::ProposalEquivocation {
+            first: Box::new(first),
+            second: Box::new(second),
+        };
+
+        let mut collector = EvidenceCollector::new(
+            misbehaviour.clone(),
+            offender.clone(),
+            sig_providers[0].clone(),
+        )
+        .unwrap();
+
+        assert!(collector
+            .add_signature_share(1, vec![0; 96], &keypair)
+            .is_err());
+
+        let mut evidence = None;
+        for (idx, sig_provider) in sig_providers.iter().enumerate() {
+            let signer = EvidenceCollector::new(
+                misbehaviour.clone(),
+                offender.clone(),
+                sig_provider.clone(),
+            )
+            .unwrap();
+
+            let signer_idx = signer_idx(sig_provider, &format!("node-{idx}")).unwrap();
+
+            evidence = collector
+                .add_signature_share(signer_idx, signer.sign().unwrap(), &keypair)
+                .unwrap();
+
+            if evidence.is_some() {
+                break;
+            }
+        }
+
+        let evidence = evidence.unwrap();
+        assert_eq!(evidence.hash(), collector.hash());
+        assert_eq!(evidence.offender, offender);
+        let public_key_set = sessions[0].public_key_set().unwrap();
+        assert!(evidence.verify(&public_key_set));
+
+        let other_public_key_set = completed_quorum_sessions(4)[0].public_key_set().unwrap();
+        assert!(!evidence.verify(&other_public_key_set));
+
+        let mut forged_evidence = evidence;
+        forged_evidence.offender = Address::new(Keypair::random().miner_kp.1);
+        assert!(!forged_evidence.verify(&public_key_set));
+    }
+}
@@ -1,15 +1,29 @@
+mod attendance;
 mod consensus_component;
 mod consensus_handler;
 mod consensus_module;
+mod dkg_session;
+mod misbehaviour;
+mod quorum_hand_off;
+mod stake_certification;
 
 mod quorum_component;
 mod quorum_handler;
+mod transaction_validator;
+mod vote_pool;
 
+pub use attendance::*;
 pub use consensus_component::*;
 pub use consensus_handler::*;
 pub use consensus_module::*;
+pub use dkg_session::*;
+pub use misbehaviour::*;
+pub use quorum_hand_off::*;
 pub use quorum_component::*;
 pub use quorum_handler::*;
+pub use stake_certification::*;
+pub use transaction_validator::*;
+pub use vote_pool::*;
 
 #[cfg(test)]
 mod tests {
@@ -12,12 +12,19 @@ use quorum::{
     quorum::{Quorum, QuorumError},
 };
 use storage::vrrbdb::VrrbDbReadHandle;
+use telemetry::info;
 use theater::{Actor, ActorId, ActorImpl, ActorState};
 use vrrb_config::{BootstrapQuorumConfig, NodeConfig, QuorumMembershipConfig};
 use vrrb_core::claim::{Claim, Eligibility};
 
 use crate::{NodeError, RuntimeComponent, RuntimeComponentHandle};
 
+/// Returns how many of the given validators a bootstrap node assigns to the
+/// harvester quorum, i.e. 30% of them rounded up. The rest are farmers.
+pub fn harvester_quorum_size(validator_count: usize) -> usize {
+    (validator_count * 3 + 9) / 10
+}
+
 #[derive(Debug)]
 pub struct QuorumModule {
     pub(crate) id: ActorId,
@@ -28,8 +35,8 @@ pub struct QuorumModule {
     pub(crate) membership_config: Option<QuorumMembershipConfig>,
     pub(crate) bootstrap_quorum_config: Option<BootstrapQuorumConfig>,
 
-    /// A map of all nodes known to are available in the bootstrap quorum
-    pub(crate) bootstrap_quorum_available_nodes: HashMap<NodeId, (PeerData, bool)>,
+    /// Peers a bootstrap node assigned to the quorums
+    pub(crate) quorum_peers: Vec<PeerData>,
 }
 
 #[derive(Debug, Clone)]
@@ -42,28 +49,6 @@ pub struct QuorumModuleConfig {
 
 impl QuorumModule {
     pub fn new(cfg: QuorumModuleConfig) -> Self {
-        let mut bootstrap_quorum_available_nodes = HashMap::new();
-
-        if let Some(quorum_config) = cfg.node_config.bootstrap_quorum_config.clone() {
-            bootstrap_quorum_available_nodes = quorum_config
-                .membership_config
-                .quorum_members
-                .into_iter()
-                .map(|member| {
-                    let peer = PeerData {
-                        node_id: member.node_id,
-                        node_type: member.node_type,
-                        kademlia_peer_id: member.kademlia_peer_id,
-                        udp_gossip_addr: member.udp_gossip_address,
-                        raptorq_gossip_addr: member.raptorq_gossip_address,
-                        kademlia_liveness_addr: member.kademlia_liveness_address,
-                    };
-
-                    (peer.node_id.clone(), (peer, false))
-                })
-                .collect::<HashMap<NodeId, (PeerData, bool)>>();
-        }
-
         Self {
             id: uuid::Uuid::new_v4().to_string(),
             status: ActorState::Stopped,
@@ -72,7 +57,7 @@ impl QuorumModule {
             membership_config: None,
             node_config: cfg.node_config.clone(),
             bootstrap_quorum_config: cfg.node_config.bootstrap_quorum_config.clone(),
-            bootstrap_quorum_available_nodes,
+            quorum_peers: vec![],
         }
     }
 
@@ -81,6 +66,44 @@ impl QuorumModule {
         self.membership_config = Some(membership_config);
     }
 
+    /// Keeps track of a peer that joined the network on bootstrap nodes, so
+    /// it's assigned to a quorum the next time the quorums are re-elected.
+    pub(super) fn add_quorum_peer(&mut self, peer_data: PeerData) {
+        if !matches!(self.node_config.node_type, NodeType::Bootstrap) {
+            return;
+        }
+
+        self.quorum_peers
+            .retain(|peer| peer.node_id != peer_data.node_id);
+        self.quorum_peers.push(peer_data);
+    }
+
+    /// Removes a peer that left the network from the quorum membership. A
+    /// bootstrap node re-assigns the remaining peers to the quorums, which
+    /// makes the quorums the peer was part of hand their keys off to the new
+    /// memberships.
+    pub(super) async fn remove_peer_from_quorums(&mut self, node_id: &NodeId) -> crate::Result<()> {
+        if let Some(membership_config) = self.membership_config.as_mut() {
+            membership_config
+                .quorum_members
+                .retain(|member| &member.node_id != node_id);
+        }
+
+        let peer_count = self.quorum_peers.len();
+        self.quorum_peers.retain(|peer| &peer.node_id != node_id);
+
+        if self.quorum_peers.len() == peer_count
+            || !matches!(self.node_config.node_type, NodeType::Bootstrap)
+        {
+            return Ok(());
+        }
+
+        info!("Node {node_id} left the network. Re-electing quorums");
+
+        self.assign_peer_list_to_quorums(self.quorum_peers.clone())
+            .await
+    }
+
     async fn assign_membership_to_quorum(
         &self,
         quorum_kind: QuorumKind,
@@ -108,17 +131,14 @@ impl QuorumModule {
 
     pub(super) async fn assign_peer_list_to_quorums(
         &self,
-        peer_list: HashMap<NodeId, (PeerData, bool)>,
+        peer_list: Vec<PeerData>,
     ) -> crate::Result<()> {
         let unassigned_peers = peer_list
             .into_iter()
-            .filter(|(_, (peer_data, _))| peer_data.node_type == NodeType::Validator)
-            .map(|(_, (peer_data, _))| peer_data)
+            .filter(|peer_data| peer_data.node_type == NodeType::Validator)
             .collect::<Vec<PeerData>>();
 
-        // NOTE: select 30% of nodes to be harvester nodes and make the rest farmers
-        let unassigned_peers_count = unassigned_peers.len();
-        let harvester_count = (unassigned_peers_count as f64 * 0.3).ceil() as usize;
+        let harvester_count = harvester_quorum_size(unassigned_peers.len());
 
         // TODO: pick nodes at random
         let mut harvester_peers = unassigned_peers
@@ -156,8 +176,12 @@ impl QuorumModule {
         let last_block_height = header.block_height;
         let seed = header.next_block_seed;
 
-        if let Ok(mut quorum) = Quorum::new(seed, last_block_height) {
-            let claim_vec: Vec<Claim> = claims.values().cloned().collect();
+        if let Ok(mut quorum) =
+            Quorum::new(seed, last_block_height, &self.node_config.threshold_config)
+        {
+            // NOTE: peer reputations are only observed locally, so elections
+            // are left to the claims every node agrees on
+            let claim_vec: Vec<Claim> = claims.into_values().collect();
             if let Ok(elected_quorum) = quorum.run_election(claim_vec) {
                 return Ok(elected_quorum.clone());
             }
@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+
+use block::{QuorumHandOff, QuorumId, QuorumPubkey};
+use primitives::{Epoch, NodeId, NodeIdx, RawSignature, SignatureType};
+use signer::signer::{SignatureProvider, Signer};
+
+use crate::{NodeError, Result};
+
+/// Returns the hex encoded group public key of the quorum whose keys back the
+/// given signature provider.
+pub fn quorum_pubkey(sig_provider: &SignatureProvider) -> Result<QuorumPubkey> {
+    let dkg_state = sig_provider
+        .dkg_state
+        .read()
+        .map_err(|err| NodeError::Other(err.to_string()))?;
+
+    let public_key_set = dkg_state
+        .public_key_set
+        .as_ref()
+        .ok_or_else(|| NodeError::Other("group public key is missing".to_string()))?;
+
+    Ok(hex::encode(public_key_set.public_key().to_bytes()))
+}
+
+/// Returns the index the given node signs with within the quorum whose keys
+/// back the given signature provider.
+pub fn signer_idx(sig_provider: &SignatureProvider, node_id: &NodeId) -> Result<NodeIdx> {
+    let dkg_state = sig_provider
+        .dkg_state
+        .read()
+        .map_err(|err| NodeError::Other(err.to_string()))?;
+
+    dkg_state
+        .peer_public_keys
+        .keys()
+        .position(|member_id| member_id == node_id)
+        .map(|idx| idx as NodeIdx)
+        .ok_or_else(|| NodeError::Other(format!("{node_id} is not a member of the quorum")))
+}
+
+/// Returns the id of the node signing with the given index within the quorum
+/// whose keys back the given signature provider.
+pub fn signer_id(sig_provider: &SignatureProvider, signer_idx: NodeIdx) -> Result<NodeId> {
+    let dkg_state = sig_provider
+        .dkg_state
+        .read()
+        .map_err(|err| NodeError::Other(err.to_string()))?;
+
+    dkg_state
+        .peer_public_keys
+        .keys()
+        .nth(signer_idx as usize)
+        .cloned()
+        .ok_or_else(|| NodeError::Other(format!("#{signer_idx} is not a member of the quorum")))
+}
+
+/// Gathers the partial signatures of the members of an outgoing quorum over
+/// the group public key of the quorum replacing it, until there are enough of
+/// them to certify the hand-off.
+#[derive(Debug)]
+pub struct QuorumHandOffCollector {
+    quorum_id: QuorumId,
+    epoch: Epoch,
+    outgoing_pubkey: QuorumPubkey,
+    incoming_pubkey: QuorumPubkey,
+    sig_provider: SignatureProvider,
+    signature_shares: BTreeMap<NodeIdx, RawSignature>,
+}
+
+impl QuorumHandOffCollector {
+    /// Creates a collector for a hand-off to the quorum with the given group
+    /// public key, using the outgoing quorum's signature provider.
+    pub fn new(
+        quorum_id: QuorumId,
+        epoch: Epoch,
+        incoming_pubkey: QuorumPubkey,
+        sig_provider: SignatureProvider,
+    ) -> Result<Self> {
+        let outgoing_pubkey = quorum_pubkey(&sig_provider)?;
+
+        Ok(Self {
+            quorum_id,
+            epoch,
+            outgoing_pubkey,
+            incoming_pubkey,
+            sig_provider,
+            signature_shares: BTreeMap::new(),
+        })
+    }
+
+    pub fn epoch(&self) -> Epoch {
+        self.epoch
+    }
+
+    pub fn outgoing_pubkey(&self) -> &QuorumPubkey {
+        &self.outgoing_pubkey
+    }
+
+    pub fn incoming_pubkey(&self) -> &QuorumPubkey {
+        &self.incoming_pubkey
+    }
+
+    /// Returns the index the given node signs with within the outgoing
+    /// quorum.
+    pub fn signer_idx(&self, node_id: &NodeId) -> Result<NodeIdx> {
+        signer_idx(&self.sig_provider, node_id)
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        QuorumHandOff::payload(
+            &self.quorum_id,
+            self.epoch,
+            &self.outgoing_pubkey,
+            &self.incoming_pubkey,
+        )
+    }
+
+    /// Signs the hand-off with the local node's secret key share.
+    pub fn sign(&self) -> Result<RawSignature> {
+        self.sig_provider
+            .generate_partial_signature(self.payload())
+            .map_err(|err| NodeError::Other(err.to_string()))
+    }
+
+    /// Adds the partial signature of a member of the outgoing quorum, and
+    /// returns the certified hand-off once enough signatures were gathered.
+    pub fn add_signature_share(
+        &mut self,
+        signer_idx: NodeIdx,
+        signature: RawSignature,
+    ) -> Result<Option<QuorumHandOff>> {
+        let is_valid = self
+            .sig_provider
+            .verify_signature(
+                signer_idx,
+                self.payload(),
+                signature.clone(),
+                SignatureType::PartialSignature,
+            )
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        if !is_valid {
+            return Err(NodeError::Other(format!(
+                "invalid hand-off signature share from member #{signer_idx}"
+            )));
+        }
+
+        self.signature_shares.insert(signer_idx, signature);
+
+        // NOTE: t + 1 signature shares are needed to produce a threshold signature
+        let required_shares = self.sig_provider.quorum_config.threshold + 1;
+        if (self.signature_shares.len() as u16) < required_shares {
+            return Ok(None);
+        }
+
+        let signature = self
+            .sig_provider
+            .generate_quorum_signature(required_shares, self.signature_shares.clone())
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        Ok(Some(QuorumHandOff {
+            quorum_id: self.quorum_id.clone(),
+            epoch: self.epoch,
+            outgoing_pubkey: self.outgoing_pubkey.clone(),
+            incoming_pubkey: self.incoming_pubkey.clone(),
+            signature: hex::encode(signature),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use super::*;
+    use crate::consensus::dkg_session::tests::completed_quorum_sessions;
+
+    #[test]
+    fn outgoing_quorum_certifies_incoming_quorum_key() {
+        let outgoing_sessions = completed_quorum_sessions(4);
+        let incoming_sessions = completed_quorum_sessions(4);
+
+        let sig_providers: Vec<SignatureProvider> = outgoing_sessions
+            .iter()
+            .map(|session| SignatureProvider {
+                dkg_state: Arc::new(RwLock::new(session.dkg_state().unwrap())),
+                quorum_config: session.threshold_config(),
+            })
+            .collect();
+
+        let incoming_pubkey = hex::encode(
+            incoming_sessions[0]
+                .public_key_set()
+                .unwrap()
+                .public_key()
+                .to_bytes(),
+        );
+
+        let mut collector = QuorumHandOffCollector::new(
+            String::from("harvester"),
+            1,
+            incoming_pubkey.clone(),
+            sig_providers[0].clone(),
+        )
+        .unwrap();
+
+        assert!(collector.add_signature_share(1, vec![0; 96]).is_err());
+
+        let mut hand_off = None;
+        for (idx, sig_provider) in sig_providers.iter().enumerate() {
+            let signer = QuorumHandOffCollector::new(
+                String::from("harvester"),
+                1,
+                incoming_pubkey.clone(),
+                sig_provider.clone(),
+            )
+            .unwrap();
+
+            let signer_idx = signer_idx(sig_provider, &format!("node-{idx}")).unwrap();
+            assert_eq!(signer_idx, idx as NodeIdx);
+
+            hand_off = collector
+                .add_signature_share(signer_idx, signer.sign().unwrap())
+                .unwrap();
+
+            if hand_off.is_some() {
+                break;
+            }
+        }
+
+        let hand_off = hand_off.unwrap();
+        assert_eq!(hand_off.incoming_pubkey, incoming_pubkey);
+        assert_eq!(
+            &hand_off.outgoing_pubkey,
+            &quorum_pubkey(&sig_providers[0]).unwrap()
+        );
+        assert!(hand_off.verify());
+
+        let mut forged_hand_off = hand_off;
+        forged_hand_off.epoch = 2;
+        assert!(!forged_hand_off.verify());
+    }
+}
@@ -3,8 +3,8 @@ use std::collections::{BTreeMap, HashMap};
 use async_trait::async_trait;
 use block::header::BlockHeader;
 use ethereum_types::U256;
-use events::{Event, EventMessage, EventPublisher, EventSubscriber, PeerData};
-use primitives::{NodeId, NodeType};
+use events::{Event, EventMessage, EventPublisher, EventSubscriber};
+use primitives::NodeType;
 use quorum::{
     election::Election,
     quorum::{Quorum, QuorumError},
@@ -45,38 +45,24 @@ impl Handler<EventMessage> for QuorumModule {
 
     async fn handle(&mut self, event: EventMessage) -> theater::Result<ActorState> {
         match event.into() {
-            Event::NodeAddedToPeerList(peer_data) => {
-                if let Some(quorum_config) = self.bootstrap_quorum_config.clone() {
-                    let node_id = peer_data.node_id.clone();
-
-                    let quorum_member_ids = quorum_config
-                        .membership_config
-                        .quorum_members
-                        .iter()
-                        .cloned()
-                        .map(|member| member.node_id)
-                        .collect::<Vec<NodeId>>();
-
-                    if quorum_member_ids.contains(&node_id) {
-                        self.bootstrap_quorum_available_nodes
-                            .insert(node_id, (peer_data, true));
-                    }
-
-                    let available_nodes = self.bootstrap_quorum_available_nodes.clone();
-                    let all_nodes_available =
-                        available_nodes.iter().all(|(_, (_, is_online))| *is_online);
-
-                    if all_nodes_available {
-                        info!("All quorum members are online. Triggering genesis quorum elections");
-
-                        if matches!(self.node_config.node_type, primitives::NodeType::Bootstrap) {
-                            self.assign_peer_list_to_quorums(available_nodes)
-                                .await
-                                .map_err(|err| TheaterError::Other(err.to_string()))?;
-                        }
-                    }
+            Event::NetworkReady(peers) => {
+                info!("Network is ready. Triggering genesis quorum elections");
+
+                if matches!(self.node_config.node_type, primitives::NodeType::Bootstrap) {
+                    self.quorum_peers = peers.clone();
+                    self.assign_peer_list_to_quorums(peers)
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
                 }
             },
+            Event::NodeAddedToPeerList(peer_data) => {
+                self.add_quorum_peer(peer_data);
+            },
+            Event::NodeRemovedFromPeerList(peer_data) => {
+                self.remove_peer_from_quorums(&peer_data.node_id)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
             Event::QuorumMembershipAssigmentCreated(assigned_membership) => {
                 let quorum_kind = assigned_membership.quorum_kind.clone();
                 let quorum_membership_config = QuorumMembershipConfig {
@@ -99,8 +85,14 @@ impl Handler<EventMessage> for QuorumModule {
                 };
 
                 self.membership_config = Some(quorum_membership_config.clone());
+
+                // NOTE: memberships are only assigned once the network is ready,
+                // so the DKG can be started right away
+                let em =
+                    EventMessage::new(Some("consensus-events".into()), Event::DkgProtocolInitiated);
+
                 self.events_tx
-                    .send(Event::DkgProtocolInitiated.into())
+                    .send(em)
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             },
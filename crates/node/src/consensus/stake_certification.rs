@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+
+use primitives::{NodeIdx, RawSignature, SignatureType};
+use signer::signer::{SignatureProvider, Signer};
+use vrrb_core::staking::Stake;
+
+use crate::{NodeError, Result};
+
+/// Hex encoded payload of a stake, which identifies it while it gets
+/// certified.
+pub type StakeHash = String;
+
+pub fn stake_hash(stake: &Stake) -> StakeHash {
+    hex::encode(stake.get_payload())
+}
+
+/// Gathers the partial signatures of harvesters over a stake, until there are
+/// enough of them to certify it.
+#[derive(Debug)]
+pub struct StakeCollector {
+    stake: Stake,
+    sig_provider: SignatureProvider,
+    signature_shares: BTreeMap<NodeIdx, RawSignature>,
+}
+
+impl StakeCollector {
+    /// Creates a collector for the given stake, using the harvester quorum's
+    /// signature provider.
+    pub fn new(stake: Stake, sig_provider: SignatureProvider) -> Self {
+        Self {
+            stake,
+            sig_provider,
+            signature_shares: BTreeMap::new(),
+        }
+    }
+
+    pub fn hash(&self) -> StakeHash {
+        stake_hash(&self.stake)
+    }
+
+    /// Signs the stake with the local node's secret key share.
+    pub fn sign(&self) -> Result<RawSignature> {
+        self.sig_provider
+            .generate_partial_signature(self.stake.get_payload())
+            .map_err(|err| NodeError::Other(err.to_string()))
+    }
+
+    /// Adds the partial signature of a harvester, and returns the certified
+    /// stake once enough signatures were gathered.
+    pub fn add_signature_share(
+        &mut self,
+        signer_idx: NodeIdx,
+        signature: RawSignature,
+    ) -> Result<Option<Stake>> {
+        let is_valid = self
+            .sig_provider
+            .verify_signature(
+                signer_idx,
+                self.stake.get_payload(),
+                signature.clone(),
+                SignatureType::PartialSignature,
+            )
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        if !is_valid {
+            return Err(NodeError::Other(format!(
+                "invalid stake signature share from harvester #{signer_idx}"
+            )));
+        }
+
+        self.signature_shares.insert(signer_idx, signature);
+
+        // NOTE: t + 1 signature shares are needed to produce a threshold signature
+        let required_shares = self.sig_provider.quorum_config.threshold + 1;
+        if (self.signature_shares.len() as u16) < required_shares {
+            return Ok(None);
+        }
+
+        let signature = self
+            .sig_provider
+            .generate_quorum_signature(required_shares, self.signature_shares.clone())
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let mut stake = self.stake.clone();
+        stake
+            .certify((signature, stake.get_payload()))
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        Ok(Some(stake))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use primitives::Address;
+    use vrrb_core::{keypair::Keypair, staking::StakeUpdate};
+
+    use super::*;
+    use crate::consensus::{dkg_session::tests::completed_quorum_sessions, signer_idx};
+
+    #[test]
+    fn harvester_quorum_certifies_stakes() {
+        let sessions = completed_quorum_sessions(4);
+        let sig_providers: Vec<SignatureProvider> = sessions
+            .iter()
+            .map(|session| SignatureProvider {
+                dkg_state: Arc::new(RwLock::new(session.dkg_state().unwrap())),
+                quorum_config: session.threshold_config(),
+            })
+            .collect();
+
+        let keypair = Keypair::random();
+        let (secret_key, public_key) = keypair.miner_kp;
+        let stake = Stake::new(
+            StakeUpdate::Add(10_000),
+            secret_key,
+            public_key,
+            Address::new(public_key),
+            None,
+        )
+        .unwrap();
+
+        let mut collector = StakeCollector::new(stake.clone(), sig_providers[0].clone());
+        assert_eq!(collector.hash(), stake_hash(&stake));
+
+        assert!(collector.add_signature_share(1, vec![0; 96]).is_err());
+
+        let mut certified_stake = None;
+        for (idx, sig_provider) in sig_providers.iter().enumerate() {
+            let signer = StakeCollector::new(stake.clone(), sig_provider.clone());
+            let signer_idx = signer_idx(sig_provider, &format!("node-{idx}")).unwrap();
+
+            certified_stake = collector
+                .add_signature_share(signer_idx, signer.sign().unwrap())
+                .unwrap();
+
+            if certified_stake.is_some() {
+                break;
+            }
+        }
+
+        let certified_stake = certified_stake.unwrap();
+        let public_key_set = sessions[0].public_key_set().unwrap();
+        assert!(certified_stake
+            .verify_certificate(&public_key_set.public_key())
+            .is_ok());
+
+        let other_public_key_set = completed_quorum_sessions(4)[0].public_key_set().unwrap();
+        assert!(certified_stake
+            .verify_certificate(&other_public_key_set.public_key())
+            .is_err());
+    }
+}
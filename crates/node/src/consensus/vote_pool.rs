@@ -0,0 +1,282 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, Instant},
+};
+
+use events::Vote;
+use hbbft::crypto::{PublicKeySet, SignatureShare, SIG_SIZE};
+use primitives::{Address, ByteVec, NodeIdx};
+use vrrb_core::txn::{QuorumCertifiedTxn, TransactionDigest, Txn, VoteReceipt};
+
+use crate::{NodeError, Result};
+
+/// Returns the payload farmers sign when voting on the validity of a
+/// transaction. The address the farmer gets its share of the validator fees
+/// at is signed along, so it can't be swapped once the vote is cast.
+pub fn vote_payload(txn: &Txn, is_txn_valid: bool, farmer_address: &Address) -> Result<ByteVec> {
+    bincode::serialize(&(txn, is_txn_valid, farmer_address))
+        .map_err(|err| NodeError::Other(err.to_string()))
+}
+
+/// How long the votes on a transaction are kept around, and how long a
+/// certified transaction is remembered so late votes don't certify it again.
+pub const VOTE_RETENTION: Duration = Duration::from_secs(300);
+
+/// Gathers the votes farmer quorums cast on transactions, certifying a
+/// transaction once enough members of the quorum that voted on it agree on
+/// its validity.
+#[derive(Debug, Default)]
+pub struct VotePool {
+    votes: HashMap<(TransactionDigest, ByteVec), (Instant, BTreeMap<NodeIdx, Vote>)>,
+    certified_txns: HashMap<(TransactionDigest, ByteVec), Instant>,
+    last_pruned_at: Option<Instant>,
+}
+
+impl VotePool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a vote cast by a member of the farmer quorum with the given
+    /// public key set, and returns the certified transaction once t + 1
+    /// members agreed on its validity.
+    pub fn add_vote(
+        &mut self,
+        vote: Vote,
+        public_key_set: &PublicKeySet,
+        now: Instant,
+    ) -> Result<Option<QuorumCertifiedTxn>> {
+        self.prune(now);
+
+        if vote.quorum_public_key != public_key_set.public_key().to_bytes() {
+            return Err(NodeError::Other(
+                "vote was cast by a member of another quorum".to_string(),
+            ));
+        }
+
+        let signature_share = TryInto::<[u8; SIG_SIZE]>::try_into(vote.signature.as_slice())
+            .ok()
+            .and_then(|bytes| SignatureShare::from_bytes(bytes).ok())
+            .ok_or_else(|| NodeError::Other("vote signature is corrupt".to_string()))?;
+
+        let payload = vote_payload(&vote.txn, vote.is_txn_valid, &vote.farmer_address)?;
+        if !public_key_set
+            .public_key_share(vote.farmer_node_id as usize)
+            .verify(&signature_share, payload)
+        {
+            return Err(NodeError::Other(format!(
+                "invalid vote from farmer #{}",
+                vote.farmer_node_id
+            )));
+        }
+
+        let key = (vote.txn.id(), vote.quorum_public_key.clone());
+        if self.certified_txns.contains_key(&key) {
+            return Ok(None);
+        }
+
+        let is_txn_valid = vote.is_txn_valid;
+
+        // NOTE: farmers get a single vote per transaction
+        let (_, votes) = self
+            .votes
+            .entry(key.clone())
+            .or_insert_with(|| (now, BTreeMap::new()));
+        votes.entry(vote.farmer_node_id).or_insert(vote);
+
+        let agreeing_votes: Vec<&Vote> = votes
+            .values()
+            .filter(|vote| vote.is_txn_valid == is_txn_valid)
+            .collect();
+
+        // NOTE: t + 1 signature shares are needed to produce a threshold signature
+        if agreeing_votes.len() <= public_key_set.threshold() {
+            return Ok(None);
+        }
+
+        let signature_shares = agreeing_votes
+            .iter()
+            .filter_map(|vote| {
+                TryInto::<[u8; SIG_SIZE]>::try_into(vote.signature.as_slice())
+                    .ok()
+                    .and_then(|bytes| SignatureShare::from_bytes(bytes).ok())
+                    .map(|signature_share| (vote.farmer_node_id as usize, signature_share))
+            })
+            .collect::<BTreeMap<usize, SignatureShare>>();
+
+        let signature = public_key_set
+            .combine_signatures(&signature_shares)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let vote_receipts = agreeing_votes
+            .iter()
+            .map(|vote| VoteReceipt {
+                farmer_id: vote.farmer_id.clone(),
+                farmer_node_id: vote.farmer_node_id,
+                farmer_address: vote.farmer_address.clone(),
+                signature: vote.signature.clone(),
+            })
+            .collect();
+
+        let sender_farmer_id = agreeing_votes[0].farmer_id.clone();
+        let txn = agreeing_votes[0].txn.clone();
+
+        self.votes.remove(&key);
+        self.certified_txns.insert(key, now);
+
+        Ok(Some(QuorumCertifiedTxn::new(
+            sender_farmer_id,
+            vote_receipts,
+            txn,
+            signature.to_bytes().to_vec(),
+            is_txn_valid,
+        )))
+    }
+
+    /// Forgets the votes and certified transactions older than
+    /// `VOTE_RETENTION`, at most once per retention period.
+    fn prune(&mut self, now: Instant) {
+        let is_due = self.last_pruned_at.map_or(true, |last_pruned_at| {
+            now.duration_since(last_pruned_at) >= VOTE_RETENTION
+        });
+
+        if !is_due {
+            return;
+        }
+
+        self.votes
+            .retain(|_, (first_seen_at, _)| now.duration_since(*first_seen_at) < VOTE_RETENTION);
+        self.certified_txns
+            .retain(|_, certified_at| now.duration_since(*certified_at) < VOTE_RETENTION);
+        self.last_pruned_at = Some(now);
+    }
+
+    pub fn len(&self) -> usize {
+        self.votes.len() + self.certified_txns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use primitives::Address;
+    use signer::signer::{SignatureProvider, Signer};
+    use vrrb_core::keypair::Keypair;
+
+    use super::*;
+    use crate::consensus::dkg_session::tests::completed_quorum_sessions;
+
+    fn farmer_votes(txn: &Txn) -> (PublicKeySet, Vec<Vote>) {
+        let sessions = completed_quorum_sessions(4);
+        let public_key_set = sessions[0].public_key_set().unwrap();
+
+        let votes = sessions
+            .iter()
+            .enumerate()
+            .map(|(idx, session)| {
+                let sig_provider = SignatureProvider {
+                    dkg_state: Arc::new(RwLock::new(session.dkg_state().unwrap())),
+                    quorum_config: session.threshold_config(),
+                };
+
+                let farmer_address = Address::new(Keypair::random().miner_kp.1);
+                let payload = vote_payload(txn, true, &farmer_address).unwrap();
+
+                Vote {
+                    farmer_id: format!("node-{idx}").into_bytes(),
+                    farmer_node_id: idx as NodeIdx,
+                    farmer_address,
+                    signature: sig_provider.generate_partial_signature(payload).unwrap(),
+                    txn: txn.clone(),
+                    quorum_public_key: public_key_set.public_key().to_bytes().to_vec(),
+                    quorum_threshold: public_key_set.threshold() + 1,
+                    is_txn_valid: true,
+                    execution_result: None,
+                }
+            })
+            .collect();
+
+        (public_key_set, votes)
+    }
+
+    #[test]
+    fn farmer_votes_are_certified_once_the_threshold_is_reached() {
+        let txn = Txn::null_txn();
+        let (public_key_set, votes) = farmer_votes(&txn);
+
+        let mut vote_pool = VotePool::new();
+        let now = Instant::now();
+
+        let mut forged_vote = votes[1].clone();
+        forged_vote.is_txn_valid = false;
+        assert!(vote_pool
+            .add_vote(forged_vote, &public_key_set, now)
+            .is_err());
+
+        // NOTE: fees can't be redirected to another address
+        let mut forged_vote = votes[1].clone();
+        forged_vote.farmer_address = Address::new(Keypair::random().miner_kp.1);
+        assert!(vote_pool
+            .add_vote(forged_vote, &public_key_set, now)
+            .is_err());
+
+        assert!(vote_pool
+            .add_vote(votes[0].clone(), &public_key_set, now)
+            .unwrap()
+            .is_none());
+
+        // NOTE: repeated votes do not count towards the threshold
+        assert!(vote_pool
+            .add_vote(votes[0].clone(), &public_key_set, now)
+            .unwrap()
+            .is_none());
+
+        let certified_txn = vote_pool
+            .add_vote(votes[1].clone(), &public_key_set, now)
+            .unwrap()
+            .unwrap();
+
+        assert!(certified_txn.is_txn_valid);
+        assert_eq!(certified_txn.txn().id(), txn.id());
+        assert_eq!(certified_txn.votes().len(), 2);
+
+        assert!(vote_pool
+            .add_vote(votes[2].clone(), &public_key_set, now)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn votes_are_forgotten_after_the_retention_period() {
+        let txn = Txn::null_txn();
+        let (public_key_set, votes) = farmer_votes(&txn);
+
+        let mut vote_pool = VotePool::new();
+        let now = Instant::now();
+
+        assert!(vote_pool
+            .add_vote(votes[0].clone(), &public_key_set, now)
+            .unwrap()
+            .is_none());
+        assert!(!vote_pool.is_empty());
+
+        let later = now + VOTE_RETENTION;
+
+        // NOTE: the vote cast before the retention period is not counted
+        assert!(vote_pool
+            .add_vote(votes[1].clone(), &public_key_set, later)
+            .unwrap()
+            .is_none());
+        assert_eq!(vote_pool.len(), 1);
+
+        assert!(vote_pool
+            .add_vote(votes[2].clone(), &public_key_set, later)
+            .unwrap()
+            .is_some());
+    }
+}
@@ -315,42 +315,55 @@ impl Handler<EventMessage> for DagModule {
             Event::Stop => {
                 return Ok(ActorState::Stopped);
             },
-            Event::BlockReceived(block) => match block {
-                Block::Genesis { block } => {
-                    if let Err(e) = self.append_genesis(&block) {
-                        let err_note = format!("Encountered GraphError: {e:?}");
-                        return Err(TheaterError::Other(err_note));
-                    };
-                },
-                Block::Proposal { block } => {
-                    if let Err(e) = self.append_proposal(&block) {
-                        let err_note = format!("Encountered GraphError: {e:?}");
-                        return Err(TheaterError::Other(err_note));
-                    }
-                },
-                Block::Convergence { block } => {
-                    if let Err(e) = self.append_convergence(&block) {
-                        let err_note = format!("Encountered GraphError: {e:?}");
-                        return Err(TheaterError::Other(err_note));
-                    }
-                    if block.certificate.is_none() {
-                        if let Some(header) = self.last_confirmed_block_header.clone() {
-                            if let Err(err) = self
-                                .events_tx
-                                .send(EventMessage::new(
-                                    None,
-                                    Event::PrecheckConvergenceBlock(block, header),
-                                ))
-                                .await
-                            {
-                                let err_note = format!(
-                                    "Failed to send EventMessage for PrecheckConvergenceBlock: {err}"
-                                );
-                                return Err(TheaterError::Other(err_note));
+            Event::BlockReceived(block) => {
+                // NOTE: blocks are checked for misbehaviour of their producers
+                let evt = Event::BlockReceived(block.clone());
+                if let Err(err) = self
+                    .events_tx
+                    .send(EventMessage::new(Some("consensus-events".into()), evt))
+                    .await
+                {
+                    let err_note = format!("Failed to forward block to consensus: {err}");
+                    return Err(TheaterError::Other(err_note));
+                }
+
+                match block {
+                    Block::Genesis { block } => {
+                        if let Err(e) = self.append_genesis(&block) {
+                            let err_note = format!("Encountered GraphError: {e:?}");
+                            return Err(TheaterError::Other(err_note));
+                        };
+                    },
+                    Block::Proposal { block } => {
+                        if let Err(e) = self.append_proposal(&block) {
+                            let err_note = format!("Encountered GraphError: {e:?}");
+                            return Err(TheaterError::Other(err_note));
+                        }
+                    },
+                    Block::Convergence { block } => {
+                        if let Err(e) = self.append_convergence(&block) {
+                            let err_note = format!("Encountered GraphError: {e:?}");
+                            return Err(TheaterError::Other(err_note));
+                        }
+                        if block.certificate.is_none() {
+                            if let Some(header) = self.last_confirmed_block_header.clone() {
+                                if let Err(err) = self
+                                    .events_tx
+                                    .send(EventMessage::new(
+                                        None,
+                                        Event::PrecheckConvergenceBlock(block, header),
+                                    ))
+                                    .await
+                                {
+                                    let err_note = format!(
+                                        "Failed to send EventMessage for PrecheckConvergenceBlock: {err}"
+                                    );
+                                    return Err(TheaterError::Other(err_note));
+                                }
                             }
                         }
-                    }
-                },
+                    },
+                }
             },
             Event::BlockCertificate(certificate) => {
                 let mut mine_block: Option<ConvergenceBlock> = None;
@@ -0,0 +1,226 @@
+use async_trait::async_trait;
+use events::{Event, EventMessage, EventSubscriber, DEFAULT_BUFFER};
+use storage::vrrbdb::EventStore;
+use telemetry::{error, info};
+use theater::{Actor, ActorId, ActorImpl, ActorLabel, ActorState, Handler};
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    task::JoinHandle,
+};
+
+use crate::{NodeError, Result};
+
+pub struct EventStoreModuleConfig {
+    pub event_store: EventStore,
+}
+
+/// Persists every event published through the router into the node's
+/// [EventStore].
+#[derive(Debug)]
+pub struct EventStoreModule {
+    status: ActorState,
+    label: ActorLabel,
+    id: ActorId,
+    event_store: EventStore,
+}
+
+impl EventStoreModule {
+    pub fn new(config: EventStoreModuleConfig) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            status: ActorState::Stopped,
+            label: String::from("EventStore"),
+            event_store: config.event_store,
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<EventMessage> for EventStoreModule {
+    fn id(&self) -> ActorId {
+        self.id.clone()
+    }
+
+    fn label(&self) -> ActorLabel {
+        self.label.clone()
+    }
+
+    fn status(&self) -> ActorState {
+        self.status.clone()
+    }
+
+    fn set_status(&mut self, actor_status: ActorState) {
+        self.status = actor_status;
+    }
+
+    fn on_stop(&self) {
+        info!(
+            "{}-{} received stop signal. Stopping",
+            self.label(),
+            self.id(),
+        );
+    }
+
+    async fn handle(&mut self, event: EventMessage) -> theater::Result<ActorState> {
+        let event: Event = event.into();
+
+        if let Err(err) = self.event_store.append(event.clone()) {
+            error!("failed to persist event: {err}");
+        }
+
+        if let Event::Stop = event {
+            return Ok(ActorState::Stopped);
+        }
+
+        Ok(ActorState::Running)
+    }
+}
+
+pub fn setup_event_store_module(
+    event_store: EventStore,
+    event_store_events_rx: Vec<EventSubscriber>,
+) -> Result<Option<JoinHandle<Result<()>>>> {
+    let module = EventStoreModule::new(EventStoreModuleConfig { event_store });
+
+    let mut event_store_events_rx = merge_subscribers(event_store_events_rx);
+
+    let mut event_store_module_actor = ActorImpl::new(module);
+
+    let event_store_handle = tokio::spawn(async move {
+        event_store_module_actor
+            .start(&mut event_store_events_rx)
+            .await
+            .map_err(|err| NodeError::Other(err.to_string()))
+    });
+
+    Ok(Some(event_store_handle))
+}
+
+/// Forwards the events received by every given subscriber into a single
+/// subscriber, so the events sent to each of the router's topics are
+/// persisted. Forwarding stops once the merged subscriber is dropped.
+fn merge_subscribers(subscribers: Vec<EventSubscriber>) -> EventSubscriber {
+    let (merged_tx, merged_rx) = broadcast::channel(DEFAULT_BUFFER);
+
+    for mut events_rx in subscribers {
+        let merged_tx = merged_tx.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match events_rx.recv().await {
+                    Ok(evt) => {
+                        if merged_tx.send(evt).is_err() {
+                            break;
+                        }
+                    },
+                    Err(RecvError::Lagged(missed)) => {
+                        error!("event store missed {missed} events");
+                    },
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    merged_rx
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use events::{EventRouter, Topic};
+    use serial_test::serial;
+
+    use super::*;
+
+    #[tokio::test]
+    #[serial]
+    async fn event_store_module_persists_received_events() {
+        let temp_dir_path = std::env::temp_dir();
+        let db_path = temp_dir_path.join(vrrb_core::helpers::generate_random_string());
+        let event_store = EventStore::new(&db_path).unwrap();
+
+        let module = EventStoreModule::new(EventStoreModuleConfig {
+            event_store: event_store.clone(),
+        });
+
+        let (ctrl_tx, mut event_store_events_rx) =
+            tokio::sync::broadcast::channel(DEFAULT_BUFFER);
+
+        let mut event_store_module_actor = ActorImpl::new(module);
+
+        let handle = tokio::spawn(async move {
+            event_store_module_actor
+                .start(&mut event_store_events_rx)
+                .await
+                .unwrap()
+        });
+
+        ctrl_tx.send(Event::NoOp.into()).unwrap();
+        ctrl_tx.send(Event::Ping("node-1".to_string()).into()).unwrap();
+        ctrl_tx.send(Event::Stop.into()).unwrap();
+        handle.await.unwrap();
+
+        let stored_events = event_store.range(0, 2).unwrap();
+
+        assert_eq!(stored_events.len(), 2);
+        assert_eq!(stored_events[0].sequence, 0);
+        assert_eq!(stored_events[0].event, Event::NoOp);
+        assert_eq!(stored_events[1].variant, "Ping");
+
+        let pings = event_store.by_variant("Ping", 0, 10).unwrap();
+        assert_eq!(pings.len(), 1);
+        assert_eq!(pings[0].sequence, 1);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn event_store_module_persists_events_sent_to_every_topic() {
+        let temp_dir_path = std::env::temp_dir();
+        let db_path = temp_dir_path.join(vrrb_core::helpers::generate_random_string());
+        let event_store = EventStore::new(&db_path).unwrap();
+
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+        let mut router = EventRouter::new();
+        router.add_topic(Topic::from("network-events"), Some(DEFAULT_BUFFER));
+
+        let event_store_events_rx = vec![
+            router.subscribe(None).unwrap(),
+            router.subscribe(Some("network-events".into())).unwrap(),
+        ];
+
+        tokio::spawn(async move { router.start(&mut events_rx).await });
+
+        let handle = setup_event_store_module(event_store.clone(), event_store_events_rx)
+            .unwrap()
+            .unwrap();
+
+        events_tx
+            .send(EventMessage::new(
+                Some("network-events".into()),
+                Event::PingIntervalElapsed,
+            ))
+            .await
+            .unwrap();
+
+        // NOTE: topics are forwarded independently, so the event has to be
+        // stored before the stop signal is sent to the default topic
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while event_store.is_empty() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        events_tx.send(Event::Stop.into()).await.unwrap();
+        handle.await.unwrap().unwrap();
+
+        let stored_events = event_store.range(0, 2).unwrap();
+
+        assert_eq!(stored_events.len(), 2);
+        assert_eq!(stored_events[0].event, Event::PingIntervalElapsed);
+        assert_eq!(stored_events[1].event, Event::Stop);
+    }
+}
@@ -7,6 +7,7 @@ mod runtime_module;
 pub(crate) mod api;
 pub(crate) mod consensus;
 pub(crate) mod dag_module;
+pub(crate) mod event_store_module;
 pub(crate) mod data_store;
 pub(crate) mod indexer_module;
 pub(crate) mod mining_module;
@@ -14,6 +15,7 @@ pub(crate) mod network;
 pub(crate) mod runtime;
 pub(crate) mod state_manager;
 pub(crate) mod state_reader;
+pub(crate) mod sync_module;
 pub(crate) mod ui;
 
 pub mod test_utils;
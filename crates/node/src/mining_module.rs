@@ -39,12 +39,19 @@ pub struct MiningModuleConfig {
 
 impl MiningModule {
     pub fn new(cfg: MiningModuleConfig) -> Self {
+        let mut miner = cfg.miner;
+
+        // NOTE: evidence certified before a restart still has to be included
+        for evidence in cfg.vrrbdb_read_handle.pending_evidence() {
+            miner.add_evidence(evidence);
+        }
+
         Self {
             id: uuid::Uuid::new_v4().to_string(),
             label: String::from("Miner"),
             status: ActorState::Stopped,
             events_tx: cfg.events_tx,
-            miner: cfg.miner,
+            miner,
             _vrrbdb_read_handle: cfg.vrrbdb_read_handle,
             _mempool_read_handle_factory: cfg.mempool_read_handle_factory,
         }
@@ -144,6 +151,12 @@ impl Handler<EventMessage> for MiningModule {
             //         };
             //     }
             // },
+            Event::EvidenceCertified(evidence) => {
+                self.miner.add_evidence(evidence);
+            },
+            Event::StakeCertified(stake) => {
+                self.miner.add_stake(stake);
+            },
             Event::NoOp => {},
             _ => {},
         }
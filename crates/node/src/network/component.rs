@@ -5,24 +5,49 @@ use std::{
 };
 
 use async_trait::async_trait;
+use block::{BlockHash, Certificate};
 use dyswarm::{
     client::{BroadcastArgs, BroadcastConfig},
     server::ServerConfig,
 };
-use events::{AssignedQuorumMembership, Event, EventMessage, EventPublisher, EventSubscriber};
+use events::{
+    AssignedQuorumMembership,
+    Event,
+    EventMessage,
+    EventPublisher,
+    EventSubscriber,
+    EvidenceMessage,
+    FarmerQuorumKeySet,
+    PeerData,
+    QuorumHandOffMessage,
+    SignedDkgMessage,
+    SignedPeerLeave,
+    SignedTxnForward,
+    StakeMessage,
+    SyncMessage,
+    Vote,
+};
+use hbbft::crypto::{PublicKey, SecretKey};
 use kademlia_dht::{Key, Node as KademliaNode, NodeData};
-use primitives::{KademliaPeerId, NodeId, NodeType};
+use mempool::TxnRecord;
+use primitives::{KademliaPeerId, NodeId, NodeIdx, NodeType, PublicKeyShareVec, RawSignature};
 use storage::vrrbdb::VrrbDbReadHandle;
 use telemetry::info;
 use theater::{Actor, ActorId, ActorImpl, ActorLabel, ActorState, Handler, TheaterError};
 use tracing::Subscriber;
 use utils::payload::digest_data_to_bytes;
-use vrrb_config::{BootstrapQuorumConfig, NodeConfig, QuorumMembershipConfig};
+use vrrb_config::{BootstrapQuorumConfig, NodeConfig, QuorumMembershipConfig, ThresholdConfig};
 use vrrb_core::claim::Claim;
 
 use super::NetworkEvent;
 use crate::{
-    network::DyswarmHandler,
+    network::{
+        DyswarmHandler,
+        NetworkReadinessTracker,
+        PeerLivenessTracker,
+        NETWORK_READINESS_TIMEOUT,
+        PING_INTERVAL,
+    },
     result::Result,
     NodeError,
     RuntimeComponent,
@@ -42,9 +67,15 @@ pub struct NetworkModule {
     pub(crate) udp_gossip_addr: SocketAddr,
     pub(crate) raptorq_gossip_addr: SocketAddr,
     pub(crate) kademlia_liveness_addr: SocketAddr,
+    pub(crate) validator_public_key: PublicKey,
+    pub(crate) validator_secret_key: SecretKey,
     pub(crate) dyswarm_server_handle: dyswarm::server::ServerHandle,
     pub(crate) dyswarm_client: dyswarm::client::Client,
     pub(crate) membership_config: Option<QuorumMembershipConfig>,
+
+    /// Tracks bootstrap peer discovery, only set on bootstrap nodes
+    pub(crate) readiness_tracker: Option<NetworkReadinessTracker>,
+    pub(crate) liveness_tracker: PeerLivenessTracker,
 }
 
 #[derive(Debug, Clone)]
@@ -64,11 +95,22 @@ pub struct NetworkModuleConfig {
 
     pub kademlia_peer_id: Option<KademliaPeerId>,
 
+    /// Key the node signs its DKG messages and its leave notice with. Its
+    /// public key is shared with peers as the node joins the network
+    pub validator_secret_key: SecretKey,
+
     /// Configuration used to connect to a bootstrap node
     pub bootstrap_node_config: Option<vrrb_config::BootstrapConfig>,
 
     pub membership_config: Option<QuorumMembershipConfig>,
 
+    /// Members of the bootstrap quorum waited for during peer discovery
+    pub bootstrap_quorum_config: Option<BootstrapQuorumConfig>,
+
+    /// Configuration the minimum amount of validators waited for during peer
+    /// discovery is taken from
+    pub threshold_config: ThresholdConfig,
+
     pub events_tx: EventPublisher,
 }
 
@@ -100,6 +142,16 @@ impl NetworkModule {
 
         let dyswarm_server_handle = dyswarm_server.run(handler).await?;
 
+        // NOTE: if there's bootstrap config, this node is a bootstrap node
+        let is_bootstrap = config.bootstrap_node_config.is_none();
+
+        let readiness_tracker = is_bootstrap.then(|| {
+            NetworkReadinessTracker::new(
+                config.bootstrap_quorum_config.as_ref(),
+                &config.threshold_config,
+            )
+        });
+
         let network_component = Self {
             id: uuid::Uuid::new_v4().to_string(),
             events_tx,
@@ -107,15 +159,18 @@ impl NetworkModule {
             node_type: config.node_type,
             status: ActorState::Stopped,
 
-            // NOTE: if there's bootstrap config, this node is a bootstrap node
-            is_bootstrap: config.bootstrap_node_config.is_none(),
+            is_bootstrap,
             kademlia_node,
             kademlia_liveness_addr: config.kademlia_liveness_addr,
+            validator_public_key: config.validator_secret_key.public_key(),
+            validator_secret_key: config.validator_secret_key,
             udp_gossip_addr: config.udp_gossip_addr,
             raptorq_gossip_addr: config.raptorq_gossip_addr,
             dyswarm_server_handle,
             dyswarm_client,
             membership_config: config.membership_config.clone(),
+            readiness_tracker,
+            liveness_tracker: PeerLivenessTracker::default(),
         };
 
         Ok(network_component)
@@ -218,6 +273,7 @@ impl NetworkModule {
             udp_gossip_addr: self.udp_gossip_addr(),
             raptorq_gossip_addr: self.raptorq_gossip_addr(),
             kademlia_liveness_addr: self.kademlia_liveness_addr(),
+            validator_public_key: self.validator_public_key,
         });
 
         let nid = self.kademlia_node.node_data().id;
@@ -247,6 +303,218 @@ impl NetworkModule {
         Ok(())
     }
 
+    /// Records a peer that joined the network and announces the network is
+    /// ready once bootstrap peer discovery completed.
+    pub(crate) async fn track_peer_readiness(&mut self, peer_data: PeerData) -> Result<()> {
+        let Some(tracker) = self.readiness_tracker.as_mut() else {
+            return Ok(());
+        };
+
+        if tracker.peer_joined(peer_data) {
+            info!("Bootstrap peer discovery completed");
+            self.notify_network_ready().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Reports the bootstrap quorum members that haven't joined the network
+    /// once peer discovery timed out and announces the network is ready if
+    /// enough validators joined regardless.
+    pub(crate) async fn handle_readiness_timeout(&mut self) -> Result<()> {
+        let Some(tracker) = self.readiness_tracker.as_mut() else {
+            return Ok(());
+        };
+
+        if tracker.is_ready() {
+            return Ok(());
+        }
+
+        telemetry::warn!(
+            "Bootstrap peer discovery timed out. Missing quorum members: {:?}",
+            tracker.missing_members()
+        );
+
+        if tracker.timed_out() {
+            self.notify_network_ready().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn notify_network_ready(&mut self) -> Result<()> {
+        let peers = self
+            .readiness_tracker
+            .as_ref()
+            .map(|tracker| tracker.joined_peers())
+            .unwrap_or_default();
+
+        let em = EventMessage::new(Some("consensus-events".into()), Event::NetworkReady(peers));
+
+        self.events_tx.send(em).await.map_err(NodeError::from)
+    }
+
+    /// Notifies the module once bootstrap peer discovery should have been
+    /// over.
+    fn schedule_readiness_timeout(&self) {
+        if self.readiness_tracker.is_none() {
+            return;
+        }
+
+        let events_tx = self.events_tx.clone();
+
+        tokio::spawn(async move {
+            tokio::time::sleep(NETWORK_READINESS_TIMEOUT).await;
+
+            let em = EventMessage::new(
+                Some("network-events".into()),
+                Event::NetworkReadinessTimedOut,
+            );
+
+            if let Err(err) = events_tx.send(em).await {
+                telemetry::error!("failed to notify network readiness timeout: {}", err);
+            }
+        });
+    }
+
+    /// Lets the closest peers know this node is leaving the network.
+    pub(crate) async fn broadcast_leave_intent(&mut self) -> Result<()> {
+        let signed_leave = SignedPeerLeave::new(self.node_id.clone(), &self.validator_secret_key);
+        let network_event = NetworkEvent::PeerUnregistered(signed_leave);
+
+        self.broadcast_to_closest_peers(network_event).await
+    }
+
+    /// Unregisters a peer that signed its intent to leave the network with
+    /// the validator key it joined with. Notices that can't be authenticated
+    /// only get the peer pinged, and it's unregistered if it doesn't answer.
+    pub(crate) async fn handle_peer_leave(&mut self, signed_leave: SignedPeerLeave) -> Result<()> {
+        let Some(peer_data) = self.liveness_tracker.peer(&signed_leave.node_id) else {
+            return Ok(());
+        };
+
+        if signed_leave.verify(&peer_data.validator_public_key) {
+            info!("Removing peer {} from DHT", peer_data.node_id);
+            return self.unregister_peer(peer_data).await;
+        }
+
+        telemetry::warn!(
+            "Leave notice of node {} isn't signed by it. Pinging it",
+            peer_data.node_id
+        );
+
+        for (peer_data, is_alive) in self.ping_peers(vec![peer_data]).await? {
+            if is_alive {
+                self.liveness_tracker.record_response(&peer_data.node_id);
+            } else {
+                self.unregister_peer(peer_data).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pings every known peer. Peers that don't answer miss the ping and are
+    /// unregistered once they missed too many of them in a row.
+    pub(crate) async fn check_peer_liveness(&mut self) -> Result<()> {
+        let peers = self.liveness_tracker.peers();
+
+        for (peer_data, is_alive) in self.ping_peers(peers).await? {
+            if is_alive {
+                self.liveness_tracker.record_response(&peer_data.node_id);
+                continue;
+            }
+
+            if let Some(peer_data) = self.liveness_tracker.record_missed_ping(&peer_data.node_id) {
+                telemetry::warn!("Node {} is unresponsive", peer_data.node_id);
+                self.unregister_peer(peer_data).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pings the given peers on their Kademlia liveness address all at once.
+    /// Returns every peer along with whether it answered.
+    async fn ping_peers(&self, peers: Vec<PeerData>) -> Result<Vec<(PeerData, bool)>> {
+        let pings: Vec<_> = peers
+            .into_iter()
+            .map(|peer_data| {
+                let mut kademlia_node = self.kademlia_node.clone();
+                let node_data = NodeData::new(
+                    peer_data.kademlia_peer_id,
+                    peer_data.kademlia_liveness_addr,
+                    peer_data.udp_gossip_addr,
+                );
+
+                // NOTE: Kademlia RPCs block until the peer answers or the
+                // request times out
+                tokio::task::spawn_blocking(move || {
+                    let is_alive = kademlia_node.rpc_ping(&node_data).is_some();
+                    (peer_data, is_alive)
+                })
+            })
+            .collect();
+
+        let mut responses = Vec::with_capacity(pings.len());
+        for ping in pings {
+            let response = ping
+                .await
+                .map_err(|err| NodeError::Other(err.to_string()))?;
+            responses.push(response);
+        }
+
+        Ok(responses)
+    }
+
+    /// Evicts a peer that left the network or turned unresponsive from the
+    /// routing table and the quorum membership, then lets the consensus
+    /// modules know so the quorums it was part of can be re-elected.
+    pub(crate) async fn unregister_peer(&mut self, peer_data: PeerData) -> Result<()> {
+        self.liveness_tracker.untrack(&peer_data.node_id);
+
+        let node_data = NodeData::new(
+            peer_data.kademlia_peer_id,
+            peer_data.kademlia_liveness_addr,
+            peer_data.udp_gossip_addr,
+        );
+
+        let mut routing_table = self.kademlia_node.get_routing_table();
+        routing_table.remove_node(&node_data);
+
+        if let Some(membership_config) = self.membership_config.as_mut() {
+            membership_config
+                .quorum_members
+                .retain(|member| member.node_id != peer_data.node_id);
+        }
+
+        let evt = Event::NodeRemovedFromPeerList(peer_data);
+        let em = EventMessage::new(Some("consensus-events".into()), evt);
+
+        self.events_tx.send(em).await.map_err(NodeError::from)
+    }
+
+    /// Notifies the module every time the liveness of its peers should be
+    /// checked, until the module stops listening.
+    fn schedule_liveness_checks(&self) {
+        let events_tx = self.events_tx.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(PING_INTERVAL);
+
+            loop {
+                interval.tick().await;
+
+                let em =
+                    EventMessage::new(Some("network-events".into()), Event::PingIntervalElapsed);
+
+                if events_tx.send(em).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
     pub(crate) async fn notify_quorum_membership_assignment(
         &mut self,
         assigned_membership: AssignedQuorumMembership,
@@ -276,6 +544,223 @@ impl NetworkModule {
         Ok(())
     }
 
+    pub(crate) async fn send_dkg_message(
+        &mut self,
+        dkg_message: SignedDkgMessage,
+        peer_addrs: Vec<SocketAddr>,
+    ) -> Result<()> {
+        self.send_to_peers(NetworkEvent::Dkg(dkg_message), peer_addrs)
+            .await
+    }
+
+    /// Sends a hand-off message to the members of the quorums taking part in
+    /// the hand-off, rather than gossiping it to the closest peers.
+    pub(crate) async fn send_quorum_hand_off_message(
+        &mut self,
+        hand_off_message: QuorumHandOffMessage,
+        peer_addrs: Vec<SocketAddr>,
+    ) -> Result<()> {
+        self.send_to_peers(NetworkEvent::QuorumHandOff(hand_off_message), peer_addrs)
+            .await
+    }
+
+    /// Shares the local harvester's partial signature of a convergence block
+    /// with the other harvesters.
+    pub(crate) async fn send_convergence_block_signature(
+        &mut self,
+        signer_idx: NodeIdx,
+        block_hash: BlockHash,
+        public_key_share: PublicKeyShareVec,
+        signature: RawSignature,
+        peer_addrs: Vec<SocketAddr>,
+    ) -> Result<()> {
+        let network_event = NetworkEvent::ConvergenceBlockSigned {
+            signer_idx,
+            block_hash,
+            public_key_share,
+            signature,
+        };
+
+        self.send_to_peers(network_event, peer_addrs).await
+    }
+
+    pub(crate) async fn broadcast_block_certificate(
+        &mut self,
+        certificate: Certificate,
+    ) -> Result<()> {
+        self.broadcast_to_closest_peers(NetworkEvent::BlockCertified(certificate))
+            .await
+    }
+
+    async fn send_to_peers(
+        &mut self,
+        network_event: NetworkEvent,
+        peer_addrs: Vec<SocketAddr>,
+    ) -> Result<()> {
+        for addr in peer_addrs {
+            let message = dyswarm::types::Message::new(network_event.clone());
+
+            self.dyswarm_client
+                .send_data_via_quic(message, addr)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn forward_txn(
+        &mut self,
+        txn_record: TxnRecord,
+        peer_addrs: Vec<SocketAddr>,
+    ) -> Result<()> {
+        let signed_forward =
+            SignedTxnForward::new(self.node_id.clone(), txn_record, &self.validator_secret_key);
+
+        for addr in peer_addrs {
+            let network_event = NetworkEvent::ForwardedTxn(signed_forward.clone());
+            let message = dyswarm::types::Message::new(network_event);
+
+            self.dyswarm_client
+                .send_data_via_quic(message, addr)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Hands a forwarded transaction over to the state manager, along with
+    /// the id of the peer that forwarded it if it was signed with the key the
+    /// peer joined with. Forwards of unknown peers are passed on anonymously,
+    /// so the gossip filter limits them all together.
+    pub(crate) async fn handle_txn_forward(
+        &mut self,
+        signed_forward: SignedTxnForward,
+    ) -> Result<()> {
+        let sender_id = self
+            .liveness_tracker
+            .peer(&signed_forward.sender_id)
+            .filter(|peer_data| signed_forward.verify(&peer_data.validator_public_key))
+            .map(|peer_data| peer_data.node_id);
+
+        // NOTE: forwarded transactions go through the local mempool before
+        // being farmed
+        let evt = Event::ForwardedTxnReceived(sender_id, signed_forward.txn_record.txn);
+
+        self.events_tx
+            .send(evt.into())
+            .await
+            .map_err(NodeError::from)
+    }
+
+    pub(crate) async fn broadcast_vote(&mut self, vote: Vote) -> Result<()> {
+        self.broadcast_to_closest_peers(NetworkEvent::VoteCast(vote))
+            .await
+    }
+
+    pub(crate) async fn broadcast_farmer_quorum_key_set(
+        &mut self,
+        key_set: FarmerQuorumKeySet,
+    ) -> Result<()> {
+        self.broadcast_to_closest_peers(NetworkEvent::FarmerQuorumKeySetShared(key_set))
+            .await
+    }
+
+    pub(crate) async fn broadcast_evidence_message(
+        &mut self,
+        evidence_message: EvidenceMessage,
+    ) -> Result<()> {
+        self.broadcast_to_closest_peers(NetworkEvent::Evidence(evidence_message))
+            .await
+    }
+
+    pub(crate) async fn broadcast_stake_message(
+        &mut self,
+        stake_message: StakeMessage,
+    ) -> Result<()> {
+        self.broadcast_to_closest_peers(NetworkEvent::Stake(stake_message))
+            .await
+    }
+
+    /// Sends a sync message to the peer at the given address, or to the
+    /// closest peers if none is given.
+    pub(crate) async fn send_sync_message(
+        &mut self,
+        sync_message: SyncMessage,
+        peer_addr: Option<SocketAddr>,
+    ) -> Result<()> {
+        let sender_addr = self.udp_gossip_addr();
+
+        let network_event = match sync_message {
+            SyncMessage::BlocksRequested(request) => NetworkEvent::BlocksRequested {
+                sender_addr,
+                request,
+            },
+            SyncMessage::BlocksProvided(request, blocks) => NetworkEvent::BlocksProvided {
+                sender_addr,
+                request,
+                blocks,
+            },
+            SyncMessage::StateSnapshotRequested(block_hash, after) => {
+                NetworkEvent::StateSnapshotRequested {
+                    sender_addr,
+                    block_hash,
+                    after,
+                }
+            },
+            SyncMessage::StateSnapshotProvided(snapshot) => NetworkEvent::StateSnapshotProvided {
+                sender_addr,
+                snapshot,
+            },
+            SyncMessage::HarvesterKeysRequested => {
+                NetworkEvent::HarvesterKeysRequested { sender_addr }
+            },
+            SyncMessage::HarvesterKeysProvided(genesis_pubkey, hand_offs) => {
+                NetworkEvent::HarvesterKeysProvided {
+                    sender_addr,
+                    genesis_pubkey,
+                    hand_offs,
+                }
+            },
+        };
+
+        match peer_addr {
+            Some(addr) => {
+                let message = dyswarm::types::Message::new(network_event);
+
+                self.dyswarm_client
+                    .send_data_via_quic(message, addr)
+                    .await?;
+            },
+            None => self.broadcast_to_closest_peers(network_event).await?,
+        }
+
+        Ok(())
+    }
+
+    async fn broadcast_to_closest_peers(&mut self, network_event: NetworkEvent) -> Result<()> {
+        let closest_nodes = self
+            .node_ref()
+            .get_routing_table()
+            .get_closest_nodes(&self.node_ref().node_data().id, 8);
+
+        let socket_address = closest_nodes
+            .iter()
+            .map(|node| node.udp_gossip_addr)
+            .collect();
+
+        self.dyswarm_client.add_peers(socket_address).await?;
+
+        self.dyswarm_client
+            .broadcast(BroadcastArgs {
+                config: Default::default(),
+                message: dyswarm::types::Message::new(network_event),
+                erasure_count: 0,
+            })
+            .await?;
+
+        Ok(())
+    }
+
     pub(crate) async fn broadcast_claim(&mut self, claim: Claim) -> Result<()> {
         let closest_nodes = self
             .node_ref()
@@ -342,9 +827,12 @@ impl RuntimeComponent<NetworkModuleComponentConfig, NetworkModuleComponentResolv
             raptorq_gossip_addr: args.config.raptorq_gossip_address,
             kademlia_peer_id: args.config.kademlia_peer_id,
             kademlia_liveness_addr: args.config.kademlia_liveness_address,
+            validator_secret_key: args.config.keypair.get_validator_secret_key().clone(),
             bootstrap_node_config: args.config.bootstrap_config,
             events_tx: args.events_tx,
             membership_config: args.membership_config,
+            bootstrap_quorum_config: args.bootstrap_quorum_config,
+            threshold_config: args.config.threshold_config.clone(),
         };
 
         let mut network_module = NetworkModule::new(network_module_config).await?;
@@ -359,8 +847,19 @@ impl RuntimeComponent<NetworkModuleComponentConfig, NetworkModuleComponentResolv
 
         if is_not_bootstrap {
             network_module.broadcast_join_intent().await?;
+
+            // NOTE: nodes joining the network catch up with the chain from
+            // the peers they just joined
+            network_module
+                .events_tx
+                .send(Event::SyncRequested.into())
+                .await
+                .map_err(NodeError::from)?;
         }
 
+        network_module.schedule_readiness_timeout();
+        network_module.schedule_liveness_checks();
+
         let mut network_module_actor = ActorImpl::new(network_module);
 
         let network_handle = tokio::spawn(async move {
@@ -66,6 +66,34 @@ impl Handler<EventMessage> for NetworkModule {
                     .send(em)
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                self.liveness_tracker.track(peer_data.clone());
+
+                self.track_peer_readiness(peer_data)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            Event::PeerUnregistered(signed_leave) => {
+                self.handle_peer_leave(signed_leave)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            Event::Ping(node_id) => {
+                self.liveness_tracker.record_response(&node_id);
+            },
+
+            Event::PingIntervalElapsed => {
+                self.check_peer_liveness()
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            Event::NetworkReadinessTimedOut => {
+                self.handle_readiness_timeout()
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
             },
 
             Event::QuorumMembershipAssigmentCreated(assigned_membership) => {
@@ -78,7 +106,89 @@ impl Handler<EventMessage> for NetworkModule {
                 self.broadcast_claim(claim).await?;
             },
 
+            Event::DkgMessageCreated(dkg_message, peer_addrs) => {
+                self.send_dkg_message(dkg_message, peer_addrs)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            Event::QuorumHandOffMessageCreated(hand_off_message, peer_addrs) => {
+                self.send_quorum_hand_off_message(hand_off_message, peer_addrs)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            Event::SendPeerConvergenceBlockSign(
+                signer_idx,
+                block_hash,
+                public_key_share,
+                signature,
+                peer_addrs,
+            ) => {
+                self.send_convergence_block_signature(
+                    signer_idx,
+                    block_hash,
+                    public_key_share,
+                    signature,
+                    peer_addrs,
+                )
+                .await
+                .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            Event::SendBlockCertificate(certificate) => {
+                self.broadcast_block_certificate(certificate)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            Event::TxnForwardReceived(signed_forward) => {
+                self.handle_txn_forward(signed_forward)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            Event::TxnForwarded(txn_record, peer_addrs) => {
+                self.forward_txn(txn_record, peer_addrs)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            Event::VoteCreated(vote) => {
+                self.broadcast_vote(vote)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            Event::FarmerQuorumKeySetCreated(key_set) => {
+                self.broadcast_farmer_quorum_key_set(key_set)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            Event::EvidenceMessageCreated(evidence_message) => {
+                self.broadcast_evidence_message(evidence_message)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            Event::StakeMessageCreated(stake_message) => {
+                self.broadcast_stake_message(stake_message)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
+            Event::SyncMessageCreated(sync_message, peer_addr) => {
+                self.send_sync_message(sync_message, peer_addr)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
             Event::Stop => {
+                if let Err(err) = self.broadcast_leave_intent().await {
+                    telemetry::warn!("Failed to broadcast leave intent: {err}");
+                }
+
                 // NOTE: stop the kademlia node instance
                 self.node_ref().kill();
                 return Ok(ActorState::Stopped);
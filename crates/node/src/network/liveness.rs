@@ -0,0 +1,99 @@
+use std::{collections::HashMap, time::Duration};
+
+use events::PeerData;
+use primitives::NodeId;
+
+/// Time between two liveness pings sent to every known peer.
+pub const PING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Number of pings in a row a peer can miss before it's deemed unresponsive.
+pub const MAX_MISSED_PINGS: u32 = 3;
+
+/// Keeps track of the pings every known peer missed in a row.
+#[derive(Debug, Clone)]
+pub struct PeerLivenessTracker {
+    max_missed_pings: u32,
+    peers: HashMap<NodeId, (PeerData, u32)>,
+}
+
+impl Default for PeerLivenessTracker {
+    fn default() -> Self {
+        Self::new(MAX_MISSED_PINGS)
+    }
+}
+
+impl PeerLivenessTracker {
+    pub fn new(max_missed_pings: u32) -> Self {
+        Self {
+            max_missed_pings,
+            peers: HashMap::new(),
+        }
+    }
+
+    pub fn track(&mut self, peer_data: PeerData) {
+        self.peers.insert(peer_data.node_id.clone(), (peer_data, 0));
+    }
+
+    pub fn untrack(&mut self, node_id: &NodeId) -> Option<PeerData> {
+        self.peers.remove(node_id).map(|(peer_data, _)| peer_data)
+    }
+
+    pub fn peer(&self, node_id: &NodeId) -> Option<PeerData> {
+        self.peers
+            .get(node_id)
+            .map(|(peer_data, _)| peer_data.clone())
+    }
+
+    pub fn peers(&self) -> Vec<PeerData> {
+        self.peers
+            .values()
+            .map(|(peer_data, _)| peer_data.clone())
+            .collect()
+    }
+
+    /// Records a sign of life from the given peer.
+    pub fn record_response(&mut self, node_id: &NodeId) {
+        if let Some((_, missed_pings)) = self.peers.get_mut(node_id) {
+            *missed_pings = 0;
+        }
+    }
+
+    /// Records a ping the given peer missed. Returns the peer, which is no
+    /// longer tracked, if it missed too many pings in a row.
+    pub fn record_missed_ping(&mut self, node_id: &NodeId) -> Option<PeerData> {
+        let (_, missed_pings) = self.peers.get_mut(node_id)?;
+        *missed_pings += 1;
+
+        if *missed_pings < self.max_missed_pings {
+            return None;
+        }
+
+        self.untrack(node_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use primitives::NodeType;
+
+    use super::*;
+    use crate::test_utils::create_mock_peer_data;
+
+    #[test]
+    fn peers_missing_too_many_pings_in_a_row_are_unresponsive() {
+        let mut tracker = PeerLivenessTracker::new(2);
+        let node_id = "node-1".to_string();
+
+        tracker.track(create_mock_peer_data(&node_id, NodeType::Validator));
+
+        assert!(tracker.record_missed_ping(&node_id).is_none());
+        tracker.record_response(&node_id);
+        assert!(tracker.record_missed_ping(&node_id).is_none());
+
+        let unresponsive_peer = tracker.record_missed_ping(&node_id).unwrap();
+
+        assert_eq!(unresponsive_peer.node_id, node_id);
+        assert!(tracker.peers().is_empty());
+        assert!(tracker.record_missed_ping(&node_id).is_none());
+    }
+}
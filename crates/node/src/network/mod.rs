@@ -1,9 +1,13 @@
 mod component;
 mod handler;
+mod liveness;
 mod network_event;
 mod network_event_handler;
+mod readiness;
 
 pub use component::*;
 pub use handler::*;
+pub use liveness::*;
 pub use network_event::*;
 pub use network_event_handler::*;
+pub use readiness::*;
@@ -1,8 +1,29 @@
 use std::net::SocketAddr;
 
-use events::AssignedQuorumMembership;
-use mempool::TxnRecord;
-use primitives::{KademliaPeerId, NodeId, NodeType, PeerId};
+use block::{Block, BlockHash, Certificate, QuorumHandOff, QuorumPubkey};
+use events::{
+    AssignedQuorumMembership,
+    BlockRequest,
+    EvidenceMessage,
+    FarmerQuorumKeySet,
+    QuorumHandOffMessage,
+    SignedDkgMessage,
+    SignedPeerLeave,
+    SignedTxnForward,
+    StakeMessage,
+    StateSnapshot,
+    Vote,
+};
+use hbbft::crypto::PublicKey;
+use primitives::{
+    Address,
+    KademliaPeerId,
+    NodeId,
+    NodeIdx,
+    NodeType,
+    PublicKeyShareVec,
+    RawSignature,
+};
 use serde::{Deserialize, Serialize};
 use vrrb_config::QuorumMembershipConfig;
 use vrrb_core::claim::Claim;
@@ -28,6 +49,7 @@ pub enum NetworkEvent {
         udp_gossip_addr: SocketAddr,
         raptorq_gossip_addr: SocketAddr,
         kademlia_liveness_addr: SocketAddr,
+        validator_public_key: PublicKey,
     },
 
     /// Peer was assigned to a specific quorum by a bootstrap node
@@ -39,13 +61,81 @@ pub enum NetworkEvent {
         assigned_membership: AssignedQuorumMembership,
     },
 
-    /// Peer is unresponsive or signaled its intent to leave the network
-    PeerUnregistered {
-        peer_id: PeerId,
-        socket_addr: SocketAddr,
+    /// Peer signed its intent to leave the network
+    PeerUnregistered(SignedPeerLeave),
+
+    /// Peer forwarded a transaction to the farmer quorum owning it
+    ForwardedTxn(SignedTxnForward),
+
+    /// Farmer voted on the validity of a transaction
+    VoteCast(Vote),
+
+    /// Farmer quorum completed its DKG run and shared its group keys
+    FarmerQuorumKeySetShared(FarmerQuorumKeySet),
+
+    /// Message of an outgoing quorum handing its duties over to the quorum
+    /// replacing it
+    QuorumHandOff(QuorumHandOffMessage),
+
+    /// Message of a harvester quorum certifying evidence of misbehaviour
+    Evidence(EvidenceMessage),
+
+    /// Message of a harvester quorum certifying a stake
+    Stake(StakeMessage),
+
+    /// Harvester shared its partial signature of a convergence block
+    ConvergenceBlockSigned {
+        signer_idx: NodeIdx,
+        block_hash: BlockHash,
+        public_key_share: PublicKeyShareVec,
+        signature: RawSignature,
+    },
+
+    /// Harvester quorum certified a convergence block
+    BlockCertified(Certificate),
+
+    /// Syncing peer asked for blocks it is missing
+    BlocksRequested {
+        sender_addr: SocketAddr,
+        request: BlockRequest,
+    },
+
+    /// Peer provided the blocks a syncing node asked for
+    BlocksProvided {
+        sender_addr: SocketAddr,
+        request: BlockRequest,
+        blocks: Vec<Block>,
+    },
+
+    /// Syncing peer asked for a page of the state the given block was
+    /// certified on, starting right after the given address
+    StateSnapshotRequested {
+        sender_addr: SocketAddr,
+        block_hash: BlockHash,
+        after: Option<Address>,
+    },
+
+    /// Peer provided the state snapshot a syncing node asked for
+    StateSnapshotProvided {
+        sender_addr: SocketAddr,
+        snapshot: StateSnapshot,
+    },
+
+    /// Syncing peer asked for the keys of the harvester quorums since genesis
+    HarvesterKeysRequested {
+        sender_addr: SocketAddr,
+    },
+
+    /// Peer provided the group public key of the genesis harvester quorum,
+    /// along with the hand-offs to the quorums that replaced it
+    HarvesterKeysProvided {
+        sender_addr: SocketAddr,
+        genesis_pubkey: QuorumPubkey,
+        hand_offs: Vec<QuorumHandOff>,
     },
 
-    ForwardedTxn(TxnRecord),
+    /// Message of a quorum member taking part in a DKG run
+    Dkg(SignedDkgMessage),
 
     Ping(NodeId),
 
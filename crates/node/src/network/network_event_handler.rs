@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use dyswarm::types::Message as DyswarmMessage;
-use events::{Event, EventMessage, EventPublisher, PeerData};
+use events::{Event, EventMessage, EventPublisher, PeerData, SyncMessage};
 use primitives::{NodeId, NodeType};
 use vrrb_config::{QuorumMember, QuorumMembershipConfig};
 
@@ -29,6 +29,7 @@ impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
                 udp_gossip_addr,
                 raptorq_gossip_addr,
                 kademlia_liveness_addr,
+                validator_public_key,
             } => {
                 telemetry::info!("Node {} joined network", node_id);
 
@@ -39,11 +40,23 @@ impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
                     udp_gossip_addr,
                     raptorq_gossip_addr,
                     kademlia_liveness_addr,
+                    validator_public_key,
                 });
 
-                // TODO: once all known peers have been joined, send a `NetworkReady` event so a
-                // dkg can be started and the first quorums can be formed
+                let em = EventMessage::new(Some("network-events".into()), evt);
+
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+            NetworkEvent::PeerUnregistered(signed_leave) => {
+                telemetry::info!("Node {} is leaving the network", signed_leave.node_id);
 
+                let evt = Event::PeerUnregistered(signed_leave);
+                let em = EventMessage::new(Some("network-events".into()), evt);
+
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+            NetworkEvent::Ping(node_id) => {
+                let evt = Event::Ping(node_id);
                 let em = EventMessage::new(Some("network-events".into()), evt);
 
                 self.events_tx.send(em).await.map_err(NodeError::from)?;
@@ -77,6 +90,172 @@ impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
                 self.events_tx.send(em).await.map_err(NodeError::from)?;
             },
 
+            NetworkEvent::Dkg(dkg_message) => {
+                let evt = Event::DkgMessageReceived(dkg_message);
+                let em = EventMessage::new(Some("consensus-events".into()), evt);
+
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::QuorumHandOff(hand_off_message) => {
+                let evt = Event::QuorumHandOffMessageReceived(hand_off_message);
+                let em = EventMessage::new(Some("consensus-events".into()), evt);
+
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::ForwardedTxn(signed_forward) => {
+                let evt = Event::TxnForwardReceived(signed_forward);
+                let em = EventMessage::new(Some("network-events".into()), evt);
+
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::VoteCast(vote) => {
+                let evt = Event::VoteReceived(vote);
+                let em = EventMessage::new(Some("consensus-events".into()), evt);
+
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::FarmerQuorumKeySetShared(key_set) => {
+                let evt = Event::FarmerQuorumKeySetReceived(key_set);
+                let em = EventMessage::new(Some("consensus-events".into()), evt);
+
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::Evidence(evidence_message) => {
+                let evt = Event::EvidenceMessageReceived(evidence_message);
+                let em = EventMessage::new(Some("consensus-events".into()), evt);
+
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::Stake(stake_message) => {
+                let evt = Event::StakeMessageReceived(stake_message);
+                let em = EventMessage::new(Some("consensus-events".into()), evt);
+
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::ConvergenceBlockSigned {
+                signer_idx,
+                block_hash,
+                public_key_share,
+                signature,
+            } => {
+                let evt = Event::PeerConvergenceBlockSign(
+                    signer_idx,
+                    block_hash,
+                    public_key_share,
+                    signature,
+                );
+                let em = EventMessage::new(Some("consensus-events".into()), evt);
+
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::BlockCertified(certificate) => {
+                // NOTE: the DAG appends the certificate to its block while the
+                // consensus module checks which harvesters signed it
+                let evt = Event::BlockCertificate(certificate.clone());
+                let em = EventMessage::new(Some("consensus-events".into()), evt);
+
+                self.events_tx.send(em).await.map_err(NodeError::from)?;
+
+                self.events_tx
+                    .send(Event::BlockCertificate(certificate).into())
+                    .await
+                    .map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::BlocksRequested {
+                sender_addr,
+                request,
+            } => {
+                let evt =
+                    Event::SyncMessageReceived(SyncMessage::BlocksRequested(request), sender_addr);
+
+                self.events_tx
+                    .send(evt.into())
+                    .await
+                    .map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::BlocksProvided {
+                sender_addr,
+                request,
+                blocks,
+            } => {
+                let evt = Event::SyncMessageReceived(
+                    SyncMessage::BlocksProvided(request, blocks),
+                    sender_addr,
+                );
+
+                self.events_tx
+                    .send(evt.into())
+                    .await
+                    .map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::StateSnapshotRequested {
+                sender_addr,
+                block_hash,
+                after,
+            } => {
+                let evt = Event::SyncMessageReceived(
+                    SyncMessage::StateSnapshotRequested(block_hash, after),
+                    sender_addr,
+                );
+
+                self.events_tx
+                    .send(evt.into())
+                    .await
+                    .map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::StateSnapshotProvided {
+                sender_addr,
+                snapshot,
+            } => {
+                let evt = Event::SyncMessageReceived(
+                    SyncMessage::StateSnapshotProvided(snapshot),
+                    sender_addr,
+                );
+
+                self.events_tx
+                    .send(evt.into())
+                    .await
+                    .map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::HarvesterKeysRequested { sender_addr } => {
+                let evt =
+                    Event::SyncMessageReceived(SyncMessage::HarvesterKeysRequested, sender_addr);
+
+                self.events_tx
+                    .send(evt.into())
+                    .await
+                    .map_err(NodeError::from)?;
+            },
+
+            NetworkEvent::HarvesterKeysProvided {
+                sender_addr,
+                genesis_pubkey,
+                hand_offs,
+            } => {
+                let evt = Event::SyncMessageReceived(
+                    SyncMessage::HarvesterKeysProvided(genesis_pubkey, hand_offs),
+                    sender_addr,
+                );
+
+                self.events_tx
+                    .send(evt.into())
+                    .await
+                    .map_err(NodeError::from)?;
+            },
+
             _ => {},
         }
 
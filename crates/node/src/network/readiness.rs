@@ -0,0 +1,255 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use events::PeerData;
+use primitives::{NodeId, NodeType};
+use vrrb_config::{BootstrapQuorumConfig, ThresholdConfig};
+
+use crate::consensus::harvester_quorum_size;
+
+/// Time bootstrap peer discovery is given before the members that are still
+/// missing get reported.
+pub const NETWORK_READINESS_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Keeps track of the peers that joined the network during bootstrap peer
+/// discovery. The network is ready once every member of the bootstrap quorum
+/// joined, along with enough validators for every quorum to get more members
+/// than the DKG threshold, so each of them can produce group signatures.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkReadinessTracker {
+    expected_members: HashSet<NodeId>,
+    minimum_validators: usize,
+    joined_peers: HashMap<NodeId, PeerData>,
+    has_timed_out: bool,
+    is_ready: bool,
+}
+
+impl NetworkReadinessTracker {
+    pub fn new(
+        bootstrap_quorum_config: Option<&BootstrapQuorumConfig>,
+        threshold_config: &ThresholdConfig,
+    ) -> Self {
+        let expected_members = bootstrap_quorum_config
+            .map(|quorum_config| {
+                quorum_config
+                    .membership_config_ref()
+                    .quorum_members
+                    .iter()
+                    .map(|member| member.node_id.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            expected_members,
+            minimum_validators: minimum_validators(threshold_config),
+            joined_peers: HashMap::new(),
+            has_timed_out: false,
+            is_ready: false,
+        }
+    }
+
+    /// Records a peer that joined the network. Returns true if the network
+    /// just became ready.
+    pub fn peer_joined(&mut self, peer_data: PeerData) -> bool {
+        self.joined_peers
+            .insert(peer_data.node_id.clone(), peer_data);
+
+        let has_members = self.has_timed_out || self.missing_members().is_empty();
+        if self.is_ready || !has_members || !self.has_minimum_validators() {
+            return false;
+        }
+
+        self.is_ready = true;
+
+        true
+    }
+
+    /// Gives up on the bootstrap quorum members that are still missing once
+    /// peer discovery timed out, so only the minimum amount of validators is
+    /// waited for from then on. Returns true if the network just became
+    /// ready.
+    pub fn timed_out(&mut self) -> bool {
+        self.has_timed_out = true;
+
+        if self.is_ready || !self.has_minimum_validators() {
+            return false;
+        }
+
+        self.is_ready = true;
+
+        true
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    /// Members of the bootstrap quorum that haven't joined the network yet,
+    /// sorted by node id.
+    pub fn missing_members(&self) -> Vec<NodeId> {
+        let mut missing_members: Vec<NodeId> = self
+            .expected_members
+            .iter()
+            .filter(|node_id| !self.joined_peers.contains_key(*node_id))
+            .cloned()
+            .collect();
+
+        missing_members.sort();
+
+        missing_members
+    }
+
+    pub fn joined_peers(&self) -> Vec<PeerData> {
+        self.joined_peers.values().cloned().collect()
+    }
+
+    fn has_minimum_validators(&self) -> bool {
+        let validator_count = self
+            .joined_peers
+            .values()
+            .filter(|peer| peer.node_type == NodeType::Validator)
+            .count();
+
+        validator_count >= self.minimum_validators
+    }
+}
+
+/// Returns the least amount of validators for which the harvester quorum, the
+/// smallest of the quorums they're assigned to, gets at least threshold + 1
+/// members.
+fn minimum_validators(threshold_config: &ThresholdConfig) -> usize {
+    let quorum_size = threshold_config.threshold as usize + 1;
+
+    (quorum_size..)
+        .find(|validator_count| harvester_quorum_size(*validator_count) >= quorum_size)
+        .unwrap_or(quorum_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use primitives::{NodeType, QuorumKind};
+    use vrrb_config::{
+        BootstrapQuorumConfig,
+        QuorumMember,
+        QuorumMembershipConfig,
+        ThresholdConfig,
+    };
+
+    use super::*;
+    use crate::test_utils::create_mock_peer_data;
+
+    fn bootstrap_quorum_config(node_ids: &[&str]) -> BootstrapQuorumConfig {
+        let quorum_members = node_ids
+            .iter()
+            .map(|node_id| {
+                let peer = create_mock_peer_data(node_id, NodeType::Validator);
+
+                QuorumMember {
+                    node_id: peer.node_id,
+                    kademlia_peer_id: peer.kademlia_peer_id,
+                    node_type: peer.node_type,
+                    udp_gossip_address: peer.udp_gossip_addr,
+                    raptorq_gossip_address: peer.raptorq_gossip_addr,
+                    kademlia_liveness_address: peer.kademlia_liveness_addr,
+                }
+            })
+            .collect();
+
+        BootstrapQuorumConfig {
+            membership_config: QuorumMembershipConfig {
+                quorum_kind: QuorumKind::Harvester,
+                quorum_members,
+            },
+            genesis_transaction_threshold: 0,
+        }
+    }
+
+    #[test]
+    fn every_quorum_gets_more_members_than_the_threshold() {
+        for threshold in 0..20 {
+            let threshold_config = ThresholdConfig {
+                upper_bound: threshold + 1,
+                threshold,
+            };
+
+            let minimum_validators = minimum_validators(&threshold_config);
+            let quorum_size = threshold as usize + 1;
+
+            assert!(harvester_quorum_size(minimum_validators) >= quorum_size);
+            assert!(harvester_quorum_size(minimum_validators - 1) < quorum_size);
+        }
+    }
+
+    #[test]
+    fn network_is_ready_once_every_bootstrap_quorum_member_joined() {
+        let quorum_config = bootstrap_quorum_config(&["node-1", "node-2"]);
+        let threshold_config = ThresholdConfig {
+            upper_bound: 2,
+            threshold: 1,
+        };
+
+        let mut tracker = NetworkReadinessTracker::new(Some(&quorum_config), &threshold_config);
+
+        assert!(!tracker.peer_joined(create_mock_peer_data("node-1", NodeType::Validator)));
+        assert!(!tracker.peer_joined(create_mock_peer_data("node-3", NodeType::Full)));
+        assert!(!tracker.peer_joined(create_mock_peer_data("node-4", NodeType::Validator)));
+        assert!(!tracker.peer_joined(create_mock_peer_data("node-5", NodeType::Validator)));
+        assert_eq!(tracker.missing_members(), vec!["node-2".to_string()]);
+
+        assert!(tracker.peer_joined(create_mock_peer_data("node-2", NodeType::Validator)));
+        assert!(tracker.is_ready());
+        assert!(tracker.missing_members().is_empty());
+        assert_eq!(tracker.joined_peers().len(), 5);
+
+        // NOTE: readiness is only reported once
+        assert!(!tracker.peer_joined(create_mock_peer_data("node-6", NodeType::Validator)));
+        assert!(!tracker.timed_out());
+    }
+
+    #[test]
+    fn network_is_ready_on_timeout_if_enough_validators_joined() {
+        let quorum_config = bootstrap_quorum_config(&["node-1", "node-2", "node-3"]);
+        let threshold_config = ThresholdConfig {
+            upper_bound: 3,
+            threshold: 1,
+        };
+
+        let mut tracker = NetworkReadinessTracker::new(Some(&quorum_config), &threshold_config);
+
+        assert!(!tracker.peer_joined(create_mock_peer_data("node-1", NodeType::Validator)));
+        assert!(!tracker.peer_joined(create_mock_peer_data("node-2", NodeType::Validator)));
+        assert!(!tracker.peer_joined(create_mock_peer_data("node-4", NodeType::Validator)));
+        assert!(!tracker.peer_joined(create_mock_peer_data("node-5", NodeType::Validator)));
+        assert!(!tracker.is_ready());
+        assert_eq!(tracker.missing_members(), vec!["node-3".to_string()]);
+
+        assert!(tracker.timed_out());
+        assert!(tracker.is_ready());
+    }
+
+    #[test]
+    fn network_is_ready_after_timeout_once_enough_validators_joined() {
+        let quorum_config = bootstrap_quorum_config(&["node-1", "node-2", "node-3"]);
+        let threshold_config = ThresholdConfig {
+            upper_bound: 3,
+            threshold: 1,
+        };
+
+        let mut tracker = NetworkReadinessTracker::new(Some(&quorum_config), &threshold_config);
+
+        assert!(!tracker.peer_joined(create_mock_peer_data("node-1", NodeType::Validator)));
+        assert!(!tracker.peer_joined(create_mock_peer_data("node-4", NodeType::Validator)));
+        assert!(!tracker.peer_joined(create_mock_peer_data("node-5", NodeType::Validator)));
+        assert!(!tracker.timed_out());
+        assert!(!tracker.is_ready());
+
+        assert!(tracker.peer_joined(create_mock_peer_data("node-6", NodeType::Validator)));
+        assert_eq!(
+            tracker.missing_members(),
+            vec!["node-2".to_string(), "node-3".to_string()]
+        );
+    }
+}
@@ -82,6 +82,7 @@ where
 
         let mut router = EventRouter::new();
         router.add_topic(Topic::from("json-rpc-api-control"), Some(1));
+        router.add_topic(Topic::from("grpc-api-control"), Some(1));
         router.add_topic(Topic::from("network-events"), Some(1000));
         router.add_topic(Topic::from("consensus-events"), Some(1000));
 
@@ -40,6 +40,9 @@ pub enum NodeError {
     #[error("{0}")]
     JsonRpc(#[from] vrrb_rpc::ApiError),
 
+    #[error("{0}")]
+    Grpc(#[from] vrrb_grpc::GrpcError),
+
     #[error("{0}")]
     Messr(#[from] messr::Error),
 
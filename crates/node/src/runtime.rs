@@ -7,11 +7,12 @@ use primitives::{Address, NodeType};
 use telemetry::info;
 use theater::{Actor, ActorImpl};
 use tokio::task::JoinHandle;
+use validator::validator_core_manager::ValidatorCoreManager;
 use vrrb_config::{NodeConfig, QuorumMembershipConfig};
 use vrrb_core::claim::Claim;
 
 use crate::{
-    api::setup_rpc_api_server,
+    api::{setup_grpc_api_server, setup_rpc_api_server},
     consensus::{
         self,
         ConsensusModule,
@@ -19,11 +20,13 @@ use crate::{
         QuorumModuleComponentConfig,
     },
     dag_module::setup_dag_module,
+    event_store_module::setup_event_store_module,
     indexer_module::{self, setup_indexer_module, IndexerModuleConfig},
     mining_module::{MiningModule, MiningModuleComponentConfig},
     network::{NetworkModule, NetworkModuleComponentConfig},
     result::{NodeError, Result},
     state_manager::{StateManager, StateManagerComponentConfig},
+    sync_module::setup_sync_module,
     ui::setup_node_gui,
     RuntimeComponent,
     RuntimeComponentManager,
@@ -31,6 +34,9 @@ use crate::{
 
 pub const PULL_TXN_BATCH_SIZE: usize = 100;
 
+/// Threads the job scheduler validates farmed transactions with
+pub const VALIDATOR_CORES: usize = 8;
+
 pub async fn setup_runtime_components(
     original_config: &NodeConfig,
     router: &EventRouter,
@@ -42,10 +48,20 @@ pub async fn setup_runtime_components(
     let network_events_rx = router.subscribe(Some("network-events".into()))?;
     let miner_events_rx = router.subscribe(None)?;
     let jsonrpc_events_rx = router.subscribe(Some("json-rpc-api-control".into()))?;
+    let grpc_events_rx = router.subscribe(Some("grpc-api-control".into()))?;
+    let subscription_events_rx = router.subscribe(None)?;
     let quorum_events_rx = router.subscribe(Some("consensus-events".into()))?;
     let consensus_events_rx = router.subscribe(Some("consensus-events".into()))?;
     let indexer_events_rx = router.subscribe(None)?;
     let dag_events_rx = router.subscribe(None)?;
+    let sync_events_rx = router.subscribe(None)?;
+    let event_store_events_rx = vec![
+        router.subscribe(None)?,
+        router.subscribe(Some("network-events".into()))?,
+        router.subscribe(Some("consensus-events".into()))?,
+        router.subscribe(Some("json-rpc-api-control".into()))?,
+        router.subscribe(Some("grpc-api-control".into()))?,
+    ];
 
     let mut runtime_manager = RuntimeComponentManager::new();
 
@@ -100,6 +116,7 @@ pub async fn setup_runtime_components(
         state_read_handle.clone(),
         mempool_read_handle_factory.clone(),
         jsonrpc_events_rx,
+        subscription_events_rx,
     )
     .await?;
 
@@ -109,7 +126,20 @@ pub async fn setup_runtime_components(
 
     runtime_manager.register_component("API".to_string(), jsonrpc_server_handle);
 
-    let dag: Arc<RwLock<BullDag<Block, String>>> = Arc::new(RwLock::new(BullDag::new()));
+    let (grpc_server_handle, resolved_grpc_server_addr) = setup_grpc_api_server(
+        &config,
+        events_tx.clone(),
+        state_read_handle.clone(),
+        mempool_read_handle_factory.clone(),
+        grpc_events_rx,
+    )
+    .await?;
+
+    config.grpc_server_address = resolved_grpc_server_addr;
+
+    info!("gRPC server address: {}", config.grpc_server_address);
+
+    runtime_manager.register_component("gRPC API".to_string(), grpc_server_handle);
 
     let miner_component = MiningModule::setup(MiningModuleComponentConfig {
         config: config.clone(),
@@ -164,11 +194,30 @@ pub async fn setup_runtime_components(
 
     runtime_manager.register_component(quorum_component.label(), quorum_component.handle());
 
+    let (sync_jobs_sender, sync_jobs_receiver) = crossbeam_channel::unbounded();
+    let (_async_jobs_sender, async_jobs_receiver) = crossbeam_channel::unbounded();
+
+    let validator_core_manager = ValidatorCoreManager::new(VALIDATOR_CORES)
+        .map_err(|err| NodeError::Other(err.to_string()))?;
+
+    let scheduler_handle = consensus::setup_scheduler_module(
+        &config,
+        sync_jobs_receiver,
+        async_jobs_receiver,
+        validator_core_manager,
+        events_tx.clone(),
+        state_read_handle.clone(),
+    );
+
+    runtime_manager.register_component("JobScheduler".to_string(), scheduler_handle);
+
     let consensus_component = ConsensusModule::setup(ConsensusModuleComponentConfig {
         events_tx: events_tx.clone(),
         node_config: config.clone(),
         vrrbdb_read_handle: state_read_handle.clone(),
+        mempool_read_handle_factory: mempool_read_handle_factory.clone(),
         consensus_events_rx,
+        sync_jobs_sender,
     })
     .await?;
 
@@ -183,8 +232,24 @@ pub async fn setup_runtime_components(
         // TODO: register indexer module handle
     }
 
+    if let Some(handle) = setup_sync_module(
+        dag.clone(),
+        events_tx.clone(),
+        sync_events_rx,
+        state_read_handle.clone(),
+        config.sync_mode,
+    )? {
+        runtime_manager.register_component("Sync".to_string(), handle);
+    }
+
     let dag_handle = setup_dag_module(dag, events_tx, dag_events_rx, claim)?;
 
+    if let Some(handle) =
+        setup_event_store_module(state_read_handle.event_store(), event_store_events_rx)?
+    {
+        runtime_manager.register_component("EventStore".to_string(), handle);
+    }
+
     let mut node_gui_handle = None;
     if config.gui {
         node_gui_handle = setup_node_gui(&config).await?;
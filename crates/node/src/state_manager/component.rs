@@ -1,7 +1,7 @@
 use std::sync::{Arc, RwLock};
 
 use async_trait::async_trait;
-use block::Block;
+use block::{vesting::GenesisAllocations, Block};
 use bulldag::graph::BullDag;
 use events::{EventPublisher, EventSubscriber};
 use mempool::{LeftRightMempool, MempoolReadHandleFactory};
@@ -41,19 +41,27 @@ impl RuntimeComponent<StateManagerComponentConfig, (VrrbDbReadHandle, MempoolRea
             vrrbdb_config.with_path(node_config.db_path().to_path_buf());
         }
 
-        let database = storage::vrrbdb::VrrbDb::new(vrrbdb_config);
+        let database = storage::vrrbdb::VrrbDb::new(vrrbdb_config)?;
         let mempool = LeftRightMempool::new();
 
         let vrrbdb_read_handle = database.read_handle();
         let mempool_read_handle = mempool.factory();
 
-        let state_module = StateManager::new(StateManagerConfig {
+        let mut state_module = StateManager::new(StateManagerConfig {
             database,
             mempool,
             events_tx,
             dag,
+            unbonding_epochs: node_config.unbonding_epochs,
         });
 
+        if let Some(genesis_file) = node_config.genesis_file.as_ref() {
+            let genesis = GenesisAllocations::from_file(genesis_file)
+                .map_err(|err| NodeError::Other(err.to_string()))?;
+
+            state_module.apply_genesis_allocations(&genesis)?;
+        }
+
         let label = state_module.label();
 
         let mut state_module_actor = ActorImpl::new(state_module);
@@ -1,28 +1,40 @@
 mod component;
 mod state_handler;
 mod state_manager;
+mod txn_gossip;
 
 pub use component::*;
 pub use state_handler::*;
 pub use state_manager::*;
+pub use txn_gossip::*;
 
 #[cfg(test)]
 mod tests {
     use std::{
+        collections::{BTreeMap, HashSet},
         env,
         sync::{Arc, RwLock},
     };
 
-    use block::{Block, BlockHash};
+    use block::{
+        vesting::{GenesisAllocation, GenesisAllocations, VestingPlan},
+        Block, BlockHash,
+    };
     use bulldag::{graph::BullDag, vertex::Vertex};
     use events::{Event, DEFAULT_BUFFER};
     use mempool::LeftRightMempool;
-    use primitives::Address;
+    use primitives::{generate_account_keypair, Address};
     use serial_test::serial;
     use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
     use theater::{Actor, ActorImpl, ActorState};
     use tokio::sync::mpsc::channel;
-    use vrrb_core::{account::Account, txn::Txn};
+    use vrrb_core::{
+        account::Account,
+        claim::Claim,
+        keypair::KeyPair,
+        staking::{Stake, StakeUpdate, DEFAULT_UNBONDING_EPOCHS},
+        txn::Txn,
+    };
 
     use super::*;
     use crate::test_utils::{
@@ -43,7 +55,7 @@ mod tests {
 
         let dag: Arc<RwLock<BullDag<Block, String>>> = Arc::new(RwLock::new(BullDag::new()));
 
-        let db = VrrbDb::new(db_config);
+        let db = VrrbDb::new(db_config).unwrap();
         let mempool = LeftRightMempool::new();
 
         let state_module = StateManager::new(StateManagerConfig {
@@ -51,6 +63,7 @@ mod tests {
             mempool,
             database: db,
             dag: dag.clone(),
+            unbonding_epochs: DEFAULT_UNBONDING_EPOCHS,
         });
 
         let mut state_module = ActorImpl::new(state_module);
@@ -77,7 +90,7 @@ mod tests {
         let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
         let db_config = VrrbDbConfig::default();
 
-        let db = VrrbDb::new(db_config);
+        let db = VrrbDb::new(db_config).unwrap();
         let mempool = LeftRightMempool::default();
 
         let dag: Arc<RwLock<BullDag<Block, String>>> = Arc::new(RwLock::new(BullDag::new()));
@@ -87,6 +100,7 @@ mod tests {
             mempool,
             database: db,
             dag: dag.clone(),
+            unbonding_epochs: DEFAULT_UNBONDING_EPOCHS,
         });
 
         let mut state_module = ActorImpl::new(state_module);
@@ -117,7 +131,7 @@ mod tests {
 
         let db_config = VrrbDbConfig::default();
 
-        let db = VrrbDb::new(db_config);
+        let db = VrrbDb::new(db_config).unwrap();
         let mempool = LeftRightMempool::default();
 
         let dag: StateDag = Arc::new(RwLock::new(BullDag::new()));
@@ -127,6 +141,7 @@ mod tests {
             events_tx,
             database: db,
             dag: dag.clone(),
+            unbonding_epochs: DEFAULT_UNBONDING_EPOCHS,
         });
 
         let mut state_module = ActorImpl::new(state_module);
@@ -157,12 +172,160 @@ mod tests {
 
     pub type StateDag = Arc<RwLock<BullDag<Block, BlockHash>>>;
 
+    #[test]
+    fn deploy_txn_updates_only_write_code_to_receiver() {
+        let code = vec![0x00, 0x61, 0x73, 0x6d];
+        let mut txn = Txn::null_txn();
+        txn.kind = vrrb_core::txn::TxnKind::Deploy { code: code.clone() };
+
+        let updates = IntoUpdates::from_txn(txn);
+        let sender_args: vrrb_core::account::UpdateArgs = updates.sender_update.into();
+        let receiver_args: vrrb_core::account::UpdateArgs = updates.receiver_update.into();
+
+        assert_eq!(sender_args.code, None);
+        assert_eq!(sender_args.storage, None);
+        assert_eq!(receiver_args.code, Some(Some(hex::encode(code))));
+        assert_eq!(receiver_args.storage, None);
+    }
+
+    #[test]
+    #[serial]
+    fn genesis_allocations_are_only_applied_once() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+        let dag: StateDag = Arc::new(RwLock::new(BullDag::new()));
+
+        let mut state_manager = StateManager::new(StateManagerConfig {
+            events_tx,
+            mempool: LeftRightMempool::new(),
+            database: VrrbDb::new(VrrbDbConfig::default()).unwrap(),
+            dag,
+            unbonding_epochs: DEFAULT_UNBONDING_EPOCHS,
+        });
+
+        let (_, public_key) = generate_account_keypair();
+        let address = Address::new(public_key);
+
+        let mut genesis = GenesisAllocations {
+            genesis_timestamp: 0,
+            allocations: vec![GenesisAllocation {
+                address: address.clone(),
+                amount: 1_200,
+                vesting: Some(VestingPlan::Employee),
+            }],
+        };
+
+        state_manager.apply_genesis_allocations(&genesis).unwrap();
+
+        genesis.allocations[0].amount = 5_000;
+        state_manager.apply_genesis_allocations(&genesis).unwrap();
+
+        let account = state_manager
+            .read_handle()
+            .state_store_values()
+            .remove(&address)
+            .unwrap();
+
+        assert_eq!(account.credits(), 1_200);
+        assert_eq!(account.locked_balance(0), 1_200);
+        assert_eq!(account.spendable_balance(0), 0);
+    }
+
+    fn certified_stake(keypair: &KeyPair, amount: StakeUpdate) -> Stake {
+        let (secret_key, public_key) = keypair.miner_kp;
+        let mut stake = Stake::new(
+            amount,
+            secret_key,
+            public_key,
+            Address::new(public_key),
+            None,
+        )
+        .unwrap();
+
+        stake.certify((vec![0; 96], stake.get_payload())).unwrap();
+        stake
+    }
+
+    #[test]
+    #[serial]
+    fn withdrawn_stake_is_released_at_its_release_epoch() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+        let dag: StateDag = Arc::new(RwLock::new(BullDag::new()));
+
+        let mut state_manager = StateManager::new(StateManagerConfig {
+            events_tx,
+            mempool: LeftRightMempool::new(),
+            database: VrrbDb::new(VrrbDbConfig::default()).unwrap(),
+            dag,
+            unbonding_epochs: 2,
+        });
+
+        let keypair = KeyPair::random();
+        let (secret_key, public_key) = keypair.miner_kp;
+        let address = Address::new(public_key);
+        let ip_address = "127.0.0.1:8080".parse().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            secret_key.secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let claim = Claim::new(public_key, address.clone(), ip_address, signature).unwrap();
+        let claim_hash = claim.hash;
+
+        let mut account = Account::new(public_key);
+        account.set_credits(50_000);
+        state_manager.database.insert_claim(claim).unwrap();
+        state_manager
+            .insert_account(address.clone(), account)
+            .unwrap();
+
+        state_manager
+            .apply_stake(certified_stake(&keypair, StakeUpdate::Add(10_000)))
+            .unwrap();
+        state_manager
+            .apply_stake(certified_stake(&keypair, StakeUpdate::Withdrawal(4_000)))
+            .unwrap();
+
+        assert_eq!(
+            state_manager.unbonding_claims,
+            BTreeMap::from([(2, HashSet::from([claim_hash]))])
+        );
+
+        let restarted_state_manager = StateManager::new(StateManagerConfig {
+            events_tx: tokio::sync::mpsc::channel(DEFAULT_BUFFER).0,
+            mempool: LeftRightMempool::new(),
+            database: state_manager.database.clone(),
+            dag: Arc::new(RwLock::new(BullDag::new())),
+            unbonding_epochs: 2,
+        });
+        assert_eq!(
+            restarted_state_manager.unbonding_claims,
+            state_manager.unbonding_claims
+        );
+
+        let credits = |state_manager: &StateManager| {
+            state_manager
+                .read_handle()
+                .state_store_values()
+                .remove(&address)
+                .unwrap()
+                .credits()
+        };
+
+        state_manager.release_unbonded_stakes(1);
+        assert_eq!(credits(&state_manager), 50_000);
+
+        state_manager.release_unbonded_stakes(2);
+        assert_eq!(credits(&state_manager), 54_000);
+        assert!(state_manager.unbonding_claims.is_empty());
+    }
+
     #[ignore = "state write is not yet persistent in the state module"]
     #[tokio::test]
     async fn vrrbdb_should_update_with_new_block() {
         let path = std::env::temp_dir().join("db");
         let db_config = VrrbDbConfig::default().with_path(path);
-        let db = VrrbDb::new(db_config);
+        let db = VrrbDb::new(db_config).unwrap();
         let mempool = LeftRightMempool::default();
         let accounts: Vec<(Address, Account)> = produce_accounts(5);
         let dag: StateDag = Arc::new(RwLock::new(BullDag::new()));
@@ -172,6 +335,7 @@ mod tests {
             database: db,
             events_tx,
             dag: dag.clone(),
+            unbonding_epochs: DEFAULT_UNBONDING_EPOCHS,
         };
 
         let mut state_module = StateManager::new(config);
@@ -1,8 +1,9 @@
 use async_trait::async_trait;
 use events::{Event, EventMessage};
+use hbbft::crypto::PublicKeySet;
 use telemetry::info;
 use theater::{ActorId, ActorLabel, ActorState, Handler, TheaterError};
-use vrrb_core::serde_helpers::decode_from_binary_byte_slice;
+use vrrb_core::{reputation::PeerBehaviour, serde_helpers::decode_from_binary_byte_slice};
 
 use crate::state_manager::StateManager;
 
@@ -41,20 +42,10 @@ impl Handler<EventMessage> for StateManager {
             Event::NewTxnCreated(txn) => {
                 info!("Storing transaction in mempool for validation");
 
-                let txn_hash = txn.id();
-
-                let _mempool_size = self
-                    .mempool
-                    .insert(txn)
-                    .map_err(|err| TheaterError::Other(err.to_string()))?;
-
-                self.events_tx
-                    .send(Event::TxnAddedToMempool(txn_hash.clone()).into())
+                self.add_txn_to_mempool(txn)
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
 
-                info!("Transaction {} sent to mempool", txn_hash);
-
                 // if self.mempool.size_in_kilobytes() >= MEMPOOL_THRESHOLD_SIZE
                 //     && self.cutoff_transaction.is_none()
                 // {
@@ -72,6 +63,12 @@ impl Handler<EventMessage> for StateManager {
                 // TheaterError::Other(err.to_string()))?; }
             },
 
+            Event::ForwardedTxnReceived(sender_id, txn) => {
+                self.add_forwarded_txn_to_mempool(sender_id, txn)
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+
             Event::TxnValidated(txn) => {
                 self.mempool
                     .remove(&txn.id())
@@ -103,11 +100,60 @@ impl Handler<EventMessage> for StateManager {
                 //               }
                 todo!()
             },
-            Event::UpdateState(block_hash) => {
-                if let Err(err) = self.update_state(block_hash) {
+            Event::UpdateState(block_hash) => match self.update_state(block_hash) {
+                Ok(updated_addresses) if !updated_addresses.is_empty() => {
+                    self.events_tx
+                        .send(Event::AccountsUpdated(updated_addresses).into())
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                },
+                Ok(_) => {},
+                Err(err) => {
                     telemetry::error!("error updating state: {}", err);
+                },
+            },
+            Event::HarvesterPublicKey(pubkey_bytes) => {
+                match serde_json::from_slice::<PublicKeySet>(&pubkey_bytes) {
+                    Ok(public_key_set) => self.add_harvester_key_set(public_key_set),
+                    Err(err) => telemetry::error!("invalid harvester key set: {}", err),
                 }
             },
+            Event::ClaimAbandoned(node_id, _) => {
+                if let Err(err) =
+                    self.record_peer_behaviour(&node_id, PeerBehaviour::ClaimAbandoned)
+                {
+                    telemetry::error!("error recording abandoned claim: {}", err);
+                }
+            },
+            Event::PeerBehaviourObserved(node_id, behaviour) => {
+                if let Err(err) = self.record_peer_behaviour(&node_id, behaviour) {
+                    telemetry::error!("error recording peer behaviour: {}", err);
+                }
+            },
+            Event::EvidenceCertified(evidence) => {
+                if let Err(err) = self.insert_evidence(evidence) {
+                    telemetry::error!("error storing evidence: {}", err);
+                }
+            },
+            Event::StateSnapshotCertified(snapshot, root_hash, epoch) => {
+                let block_hash = snapshot.block_hash.clone();
+
+                let event = match self.load_state_snapshot(snapshot, &root_hash, epoch) {
+                    Ok(()) => {
+                        info!("Loaded state snapshot of block {block_hash}");
+                        Event::StateSnapshotLoaded(block_hash)
+                    },
+                    Err(err) => {
+                        telemetry::error!("error loading state snapshot: {}", err);
+                        Event::StateSnapshotRejected(block_hash)
+                    },
+                };
+
+                self.events_tx
+                    .send(event.into())
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
             Event::ClaimCreated(claim) => {},
             Event::ClaimReceived(claim) => {
                 info!("Storing claim from: {}", claim.address);
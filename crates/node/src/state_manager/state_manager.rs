@@ -1,28 +1,44 @@
 use std::{
-    collections::{HashMap, HashSet},
-    str::FromStr,
+    collections::{BTreeMap, HashMap, HashSet},
+    env,
     sync::{Arc, RwLock},
+    time::Instant,
 };
 
-use block::{Block, BlockHash, ClaimHash, ConvergenceBlock, ProposalBlock};
+use block::{
+    vesting::{generate_genesis_accounts, GenesisAllocations},
+    Block, BlockHash, ClaimHash, ConvergenceBlock, Evidence, ProposalBlock, QuorumPubkey,
+};
 use bulldag::{graph::BullDag, vertex::Vertex};
 use ethereum_types::U256;
-use events::{Event, EventPublisher};
+use events::{Event, EventMessage, EventPublisher, StateSnapshot};
+use hbbft::crypto::PublicKeySet;
 use mempool::LeftRightMempool;
-use primitives::{Address, NodeId, Round};
+use primitives::{Address, Epoch, NodeId, NodeIdx, QuorumKind, Round, GENESIS_EPOCH};
 use storage::{
     storage_utils::StorageError,
-    vrrbdb::{Claims, StateStoreReadHandle, VrrbDb, VrrbDbReadHandle},
+    vrrbdb::{Claims, StateStore, StateStoreReadHandle, VrrbDb, VrrbDbReadHandle},
 };
 use telemetry::info;
 use theater::{ActorId, ActorState};
 use vrrb_core::{
     account::{Account, AccountDigests, UpdateArgs},
     claim::Claim,
+    receipt::{TxnReceipt, TxnReceiptStatus},
+    reputation::PeerBehaviour,
+    reward_credit::{RewardCredit, RewardKind},
+    staking::{Stake, StakeUpdate},
     txn::{Token, TransactionDigest, Txn},
 };
 
-use crate::{data_store::DataStore, state_reader::StateReader, NodeError, Result};
+use crate::{
+    consensus::dkg_quorum_id,
+    data_store::DataStore,
+    state_manager::TxnGossipFilter,
+    state_reader::StateReader,
+    NodeError,
+    Result,
+};
 
 /// Provides a wrapper around the current rounds `ConvergenceBlock` and
 /// the `ProposalBlock`s that it is made up of. Provides a convenient
@@ -100,8 +116,8 @@ impl From<StateUpdate> for UpdateArgs {
                     nonce: item.nonce,
                     credits: None,
                     debits: Some(item.amount),
-                    storage: Some(item.storage.clone()),
-                    code: Some(item.code.clone()),
+                    storage: item.storage.clone().map(Some),
+                    code: item.code.clone().map(Some),
                     digests: Some(digest.clone()),
                 }
             },
@@ -112,8 +128,8 @@ impl From<StateUpdate> for UpdateArgs {
                     nonce: item.nonce,
                     credits: Some(item.amount),
                     debits: None,
-                    storage: Some(item.storage.clone()),
-                    code: Some(item.code.clone()),
+                    storage: item.storage.clone().map(Some),
+                    code: item.code.clone().map(Some),
                     digests: Some(digest.clone()),
                 }
             },
@@ -155,36 +171,18 @@ impl From<StateUpdate> for UpdateArgs {
 /// Converts a `ProposalBlock` into a `HashSet` of
 /// `StateUpdate`s which can then be easily converted into
 /// a `HashSet` of `UpdateArgs` to update Accounts, Claims, etc.
+/// Fee shares are credited separately, see
+/// `StateManager::get_reward_credits` and `get_reward_update_args`.
 impl FromBlock for HashSet<StateUpdate> {
     fn from_block(block: ProposalBlock) -> Self {
         let mut set = HashSet::new();
-        let mut proposer_fees = 0u128;
 
         block.txns.into_iter().for_each(|(_digest, txn)| {
-            let fee = txn.proposer_fee_share();
-            proposer_fees += fee;
-
             let updates = IntoUpdates::from_txn(txn.txn());
             set.insert(updates.sender_update);
             set.insert(updates.receiver_update);
-
-            let validator_fees = HashSet::<StateUpdate>::from_txn(txn.txn());
-            set.extend(validator_fees);
         });
 
-        let fee_update = StateUpdate {
-            address: block.from.address,
-            token: Some(Token::default()),
-            nonce: None,
-            amount: proposer_fees,
-            storage: None,
-            code: None,
-            digest: TransactionDigest::default(),
-            update_account: UpdateAccount::Fee,
-        };
-
-        set.insert(fee_update);
-
         set
     }
 }
@@ -205,13 +203,15 @@ impl FromTxn for IntoUpdates {
             update_account: UpdateAccount::Sender,
         };
 
+        // Contract deployments write the module's code to the receiving
+        // account
         let receiver_update = StateUpdate {
             address: txn.receiver_address(),
             token: Some(txn.token()),
             amount: txn.amount(),
             nonce: None,
             storage: None,
-            code: None,
+            code: txn.deploy_code().map(hex::encode),
             digest: txn.id(),
             update_account: UpdateAccount::Receiver,
         };
@@ -231,6 +231,11 @@ impl FromTxn for HashSet<StateUpdate> {
         let fees = txn.validator_fee_share();
         let mut validator_set = txn.validators();
         validator_set.retain(|_, vote| *vote);
+
+        if validator_set.is_empty() {
+            return set;
+        }
+
         let validator_share = fees / (validator_set.len() as u128);
         validator_set.iter().for_each(|(k, _v)| {
             let address = Address::from_str(k);
@@ -260,6 +265,7 @@ pub struct StateManagerConfig {
     pub events_tx: EventPublisher,
     pub dag: Arc<RwLock<BullDag<Block, String>>>,
     pub mempool: LeftRightMempool,
+    pub unbonding_epochs: Epoch,
 }
 
 #[derive(Debug)]
@@ -270,10 +276,24 @@ pub struct StateManager {
     pub(crate) dag: Arc<RwLock<BullDag<Block, String>>>,
     pub(crate) database: VrrbDb,
     pub(crate) mempool: LeftRightMempool,
+    pub(crate) unbonding_epochs: Epoch,
+    /// Epoch of the latest convergence block applied to the state
+    pub(crate) epoch: Epoch,
+    pub(crate) txn_gossip_filter: TxnGossipFilter,
+    /// Harvester quorum key sets this node learned about, by group public key
+    pub(crate) harvester_key_sets: HashMap<QuorumPubkey, PublicKeySet>,
+    /// Group public key the harvester quorum held its duties with, by the
+    /// epoch it took over at
+    pub(crate) harvester_pubkeys: BTreeMap<Epoch, QuorumPubkey>,
+    /// Hashes of the claims with withdrawn stake, by the epoch the stake is
+    /// released at
+    pub(crate) unbonding_claims: BTreeMap<Epoch, HashSet<ClaimHash>>,
 }
 
 impl StateManager {
     pub fn new(config: StateManagerConfig) -> Self {
+        let unbonding_claims = Self::index_unbondings(&config.database);
+
         Self {
             id: uuid::Uuid::new_v4().to_string(),
             database: config.database,
@@ -281,7 +301,129 @@ impl StateManager {
             status: ActorState::Stopped,
             dag: config.dag,
             mempool: config.mempool,
+            unbonding_epochs: config.unbonding_epochs,
+            epoch: Epoch::default(),
+            txn_gossip_filter: TxnGossipFilter::default(),
+            harvester_key_sets: HashMap::new(),
+            harvester_pubkeys: BTreeMap::new(),
+            unbonding_claims,
+        }
+    }
+
+    /// Indexes the claims stored in the given database by the epochs their
+    /// withdrawn stake is released at.
+    fn index_unbondings(database: &VrrbDb) -> BTreeMap<Epoch, HashSet<ClaimHash>> {
+        let mut unbonding_claims: BTreeMap<Epoch, HashSet<ClaimHash>> = BTreeMap::new();
+
+        for claim in database.read_handle().claim_store_values().into_values() {
+            for unbonding in claim.get_unbonding() {
+                unbonding_claims
+                    .entry(unbonding.release_epoch)
+                    .or_default()
+                    .insert(claim.hash);
+            }
+        }
+
+        unbonding_claims
+    }
+
+    /// Records the key set of a harvester quorum. The quorum elected at
+    /// genesis holds its duties without a hand-off, so the first key set
+    /// learned during the genesis epoch is taken to be its key set.
+    pub(crate) fn add_harvester_key_set(&mut self, public_key_set: PublicKeySet) {
+        let quorum_pubkey = hex::encode(public_key_set.public_key().to_bytes());
+
+        if self.epoch == GENESIS_EPOCH && self.harvester_pubkeys.is_empty() {
+            self.harvester_pubkeys
+                .insert(GENESIS_EPOCH, quorum_pubkey.clone());
         }
+
+        self.harvester_key_sets
+            .insert(quorum_pubkey, public_key_set);
+    }
+
+    /// Returns the key set of the harvester quorum holding its duties at the
+    /// given epoch, if this node knows it.
+    fn harvester_key_set(&self, epoch: Epoch) -> Option<&PublicKeySet> {
+        let (_, quorum_pubkey) = self.harvester_pubkeys.range(..=epoch).next_back()?;
+
+        self.harvester_key_sets.get(quorum_pubkey)
+    }
+
+    /// Follows the harvester hand-offs the certificate of the given
+    /// `ConvergenceBlock` inaugurates, as long as they were certified by the
+    /// keys the harvester quorum holds its duties with.
+    fn follow_harvester_hand_offs(&mut self, convergence: &ConvergenceBlock) {
+        let harvester_quorum_id = dkg_quorum_id(&QuorumKind::Harvester);
+
+        for hand_off in convergence
+            .certificate
+            .iter()
+            .filter_map(|certificate| certificate.hand_offs.as_ref())
+            .flat_map(|hand_offs| hand_offs.values())
+            .filter(|hand_off| hand_off.quorum_id == harvester_quorum_id)
+        {
+            let current_pubkey = self
+                .harvester_pubkeys
+                .range(..hand_off.epoch)
+                .next_back()
+                .map(|(_, quorum_pubkey)| quorum_pubkey);
+
+            if current_pubkey == Some(&hand_off.outgoing_pubkey) && hand_off.verify() {
+                self.harvester_pubkeys
+                    .insert(hand_off.epoch, hand_off.incoming_pubkey.clone());
+            }
+        }
+    }
+
+    /// Inserts a transaction into the mempool and lets the farmers know
+    /// there's a new transaction to vote on.
+    pub(crate) async fn add_txn_to_mempool(&mut self, txn: Txn) -> Result<()> {
+        let txn_hash = txn.id();
+
+        self.txn_gossip_filter.mark_seen(&txn_hash);
+
+        let _mempool_size = self
+            .mempool
+            .insert(txn)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        self.events_tx
+            .send(Event::TxnAddedToMempool(txn_hash.clone()).into())
+            .await
+            .map_err(NodeError::from)?;
+
+        info!("Transaction {} sent to mempool", txn_hash);
+
+        // NOTE: farmers vote on pending transactions in batches
+        let em = EventMessage::new(Some("consensus-events".into()), Event::Farm);
+
+        self.events_tx.send(em).await.map_err(NodeError::from)
+    }
+
+    /// Adds a transaction forwarded by a peer to the mempool, unless it was
+    /// seen before or the peer forwarded too many transactions lately. The
+    /// sender id is only set for peers whose forward was authenticated.
+    pub(crate) async fn add_forwarded_txn_to_mempool(
+        &mut self,
+        sender_id: Option<NodeId>,
+        txn: Txn,
+    ) -> Result<()> {
+        let mempool = &mut self.mempool;
+        let transaction_store = self.database.read_handle().transaction_store_handle();
+
+        let is_known = |txn_id: &TransactionDigest| {
+            mempool.get(txn_id).is_some() || transaction_store.get(txn_id).is_ok()
+        };
+
+        if !self
+            .txn_gossip_filter
+            .admit(sender_id.as_ref(), &txn.id(), Instant::now(), is_known)
+        {
+            return Ok(());
+        }
+
+        self.add_txn_to_mempool(txn).await
     }
 
     /// Produces the read handle for the VrrbDb instance in this
@@ -317,12 +459,26 @@ impl StateManager {
     /// Given the hash of a `ConvergenceBlock` this method
     /// updates the StateStore, ClaimStore and TransactionStore
     /// for all new claims and transactions (excluding
-    /// ClaimStaking transactions currently).
-    pub fn update_state(&mut self, block_hash: BlockHash) -> Result<()> {
-        if let Some(mut round_blocks) = self.get_proposal_blocks(block_hash) {
+    /// ClaimStaking transactions currently), and records the
+    /// resulting state so it can be queried by block hash later on,
+    /// along with a receipt for every proposed transaction. Returns
+    /// the addresses of the accounts the block changed.
+    pub fn update_state(&mut self, block_hash: BlockHash) -> Result<Vec<Address>> {
+        if let Some(mut round_blocks) = self.get_proposal_blocks(block_hash.clone()) {
+            let receipts = self.get_receipts(&round_blocks);
             let update_list = self.get_update_list(&mut round_blocks);
-            let update_args = get_update_args(update_list);
-            let consolidated_update_args = consolidate_update_args(update_args);
+            let reward_credits = self.get_reward_credits(&round_blocks);
+            let mut contract_code = self.insert_contract_accounts(&round_blocks.proposals);
+            let mut update_args = get_update_args(update_list);
+            update_args.extend(get_reward_update_args(&reward_credits));
+            let mut consolidated_update_args = consolidate_update_args(update_args);
+
+            // NOTE: only contract accounts created this round get code
+            // written to them
+            consolidated_update_args
+                .iter_mut()
+                .for_each(|(address, args)| args.code = contract_code.remove(address));
+
             consolidated_update_args.into_iter().for_each(|(_, args)| {
                 if let Err(err) = self.database.update_account(args) {
                     telemetry::error!("error updating account: {err}");
@@ -333,8 +489,38 @@ impl StateManager {
 
             self.update_txn_trie(&proposals);
             self.update_claim_store(&proposals);
+            self.apply_slashes(&round_blocks.convergence);
+            self.apply_certified_stakes(&round_blocks.convergence);
+            self.follow_harvester_hand_offs(&round_blocks.convergence);
+
+            let epoch = round_blocks.convergence.header.epoch;
+            self.epoch = self.epoch.max(epoch);
+            self.release_unbonded_stakes(self.epoch);
+
+            let updated_addresses = match self.database.record_block_state(&block_hash, epoch) {
+                Ok(record) => record
+                    .accounts
+                    .into_iter()
+                    .map(|(address, _)| address)
+                    .collect(),
+                Err(err) => {
+                    telemetry::error!("error recording state of block {block_hash}: {err}");
+                    vec![]
+                },
+            };
 
-            return Ok(());
+            if let Err(err) = self.database.extend_receipts(&receipts) {
+                telemetry::error!("error storing receipts of block {block_hash}: {err}");
+            }
+
+            if let Err(err) = self
+                .database
+                .insert_rewards(epoch, &block_hash, &reward_credits)
+            {
+                telemetry::error!("error storing rewards of block {block_hash}: {err}");
+            }
+
+            return Ok(updated_addresses);
         }
 
         Err(NodeError::Other(
@@ -342,6 +528,58 @@ impl StateManager {
         ))
     }
 
+    /// Provided a reference to an array of `ProposalBlock`s
+    /// making up the current round's `ConvergenceBlock`, inserts a new,
+    /// empty account for every contract deployed to an address that
+    /// does not exist within the `StateStore` yet, and returns the hex
+    /// encoded code to write to each of them along with the rest of the
+    /// round's updates.
+    ///
+    /// Deployments to existing addresses are ignored so they can't overwrite
+    /// the code of other accounts. If several deployments of the round
+    /// target the same address, the one with the lowest digest wins.
+    fn insert_contract_accounts(
+        &mut self,
+        proposals: &[ProposalBlock],
+    ) -> HashMap<Address, String> {
+        let state_store_handle = self.database.state_store_factory().handle();
+
+        let mut deploy_txns: Vec<Txn> = proposals
+            .iter()
+            .flat_map(|block| block.txns.iter().map(|(_, v)| v.txn()))
+            .filter(|txn| txn.is_deploy())
+            .collect();
+
+        deploy_txns.sort_by_key(|txn| txn.id());
+
+        let mut contract_code: HashMap<Address, String> = HashMap::new();
+
+        for txn in deploy_txns {
+            let address = txn.receiver_address();
+
+            if contract_code.contains_key(&address) || state_store_handle.get(&address).is_ok() {
+                telemetry::warn!(
+                    "ignoring deployment {} to existing address {address}",
+                    txn.id()
+                );
+                continue;
+            }
+
+            if let Some(code) = txn.deploy_code() {
+                contract_code.insert(address, hex::encode(code));
+            }
+        }
+
+        contract_code.keys().for_each(|address| {
+            let account = Account::new(address.public_key());
+            if let Err(err) = self.database.insert_account(address.clone(), account) {
+                telemetry::error!("error inserting contract account: {err}");
+            }
+        });
+
+        contract_code
+    }
+
     /// Provided a reference to an array of `ProposalBlock`s
     /// making up the current round's `ConvergenceBlock`, writes all
     /// the conflict resolved transactions into the `TransactionTrie`
@@ -378,6 +616,358 @@ impl StateManager {
             .extend_claims(consolidated.into_iter().collect());
     }
 
+    /// Slashes the claims of the offenders of every certified evidence the
+    /// given `ConvergenceBlock` includes. Evidence is only applied the first
+    /// time it is included in a block.
+    fn apply_slashes(&mut self, convergence: &ConvergenceBlock) {
+        if convergence.evidence.is_empty() {
+            return;
+        }
+
+        let epoch = convergence.header.epoch;
+        let Some(harvester_key_set) = self.harvester_key_set(epoch).cloned() else {
+            telemetry::error!(
+                "unknown harvester keys at epoch {epoch}, skipping evidence in block {}",
+                convergence.hash
+            );
+            return;
+        };
+
+        for evidence in convergence.evidence.iter() {
+            if !evidence.verify(&harvester_key_set) {
+                telemetry::error!("block {} includes invalid evidence", convergence.hash);
+                continue;
+            }
+
+            match self
+                .database
+                .mark_evidence_included(evidence.clone(), &convergence.hash)
+            {
+                Ok(true) => {},
+                Ok(false) => continue,
+                Err(err) => {
+                    telemetry::error!("error recording evidence {}: {err}", evidence.hash());
+                    continue;
+                },
+            }
+
+            if let Err(err) = self.apply_stake(evidence.slash.clone()) {
+                telemetry::error!("error slashing claim of {}: {err}", evidence.offender);
+                continue;
+            }
+
+            info!("Slashed claim of {}", evidence.offender);
+        }
+    }
+
+    /// Applies the certified stakes the given `ConvergenceBlock` includes to
+    /// the claims they target. Stakes are only applied the first time they
+    /// are included in a block.
+    fn apply_certified_stakes(&mut self, convergence: &ConvergenceBlock) {
+        if convergence.stakes.is_empty() {
+            return;
+        }
+
+        let epoch = convergence.header.epoch;
+        let Some(harvester_public_key) = self
+            .harvester_key_set(epoch)
+            .map(|public_key_set| public_key_set.public_key())
+        else {
+            telemetry::error!(
+                "unknown harvester keys at epoch {epoch}, skipping stakes in block {}",
+                convergence.hash
+            );
+            return;
+        };
+
+        for stake in convergence.stakes.iter() {
+            let staker = stake.get_sender();
+
+            // NOTE: slashes only apply as part of certified evidence
+            let is_valid = !matches!(stake.get_amount(), StakeUpdate::Slash(_))
+                && stake.verify().is_ok()
+                && stake.verify_certificate(&harvester_public_key).is_ok();
+
+            if !is_valid {
+                telemetry::error!(
+                    "block {} includes invalid stake of {staker}",
+                    convergence.hash
+                );
+                continue;
+            }
+
+            let receiver = stake.get_receiver();
+            let applied_stakes = self
+                .get_claim_by_address(&receiver)
+                .map(|claim| claim.get_stake_txns())
+                .unwrap_or_default();
+
+            if applied_stakes
+                .iter()
+                .any(|applied| applied.get_payload() == stake.get_payload())
+            {
+                continue;
+            }
+
+            if let Err(err) = self.apply_stake(stake.clone()) {
+                telemetry::error!("error applying stake of {staker}: {err}");
+                continue;
+            }
+
+            info!("Applied stake of {staker} to the claim of {receiver}");
+        }
+    }
+
+    fn get_claim_by_address(&self, address: &Address) -> Option<Claim> {
+        self.database
+            .read_handle()
+            .claim_store_handle()
+            .get_by_address(address)
+            .ok()?
+            .into_iter()
+            .next()
+    }
+
+    /// Applies a certified stake to the claim it targets. Added stake is
+    /// debited from the account of its sender, while withdrawn stake is
+    /// queued for release `unbonding_epochs` after the current epoch.
+    pub(crate) fn apply_stake(&mut self, stake: Stake) -> Result<()> {
+        let receiver = stake.get_receiver();
+        let mut claim = self
+            .get_claim_by_address(&receiver)
+            .ok_or_else(|| NodeError::Other(format!("no claim found for {receiver}")))?;
+
+        let release_epoch = self.epoch.saturating_add(self.unbonding_epochs);
+        claim
+            .update_stake_with_unbonding(stake.clone(), release_epoch)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        if let StakeUpdate::Add(amount) = stake.get_amount() {
+            self.database.update_account(UpdateArgs {
+                address: stake.get_sender(),
+                nonce: None,
+                credits: None,
+                debits: Some(amount),
+                storage: None,
+                code: None,
+                digests: None,
+            })?;
+        }
+
+        if let StakeUpdate::Withdrawal(_) = stake.get_amount() {
+            self.unbonding_claims
+                .entry(release_epoch)
+                .or_default()
+                .insert(claim.hash);
+        }
+
+        self.database.insert_claim(claim)?;
+
+        Ok(())
+    }
+
+    /// Credits the stakers of every unbonding that is due by the given epoch
+    /// with the stake they withdrew. Only the claims indexed under the epochs
+    /// that are due are visited.
+    pub(crate) fn release_unbonded_stakes(&mut self, epoch: Epoch) {
+        let pending = self.unbonding_claims.split_off(&epoch.saturating_add(1));
+        let due = std::mem::replace(&mut self.unbonding_claims, pending);
+
+        let claim_hashes: HashSet<ClaimHash> = due.into_values().flatten().collect();
+        let claims: Vec<Claim> = {
+            let claim_store = self.database.read_handle().claim_store_handle();
+
+            claim_hashes
+                .iter()
+                .filter_map(|claim_hash| match claim_store.get_by_hash(claim_hash) {
+                    Ok(claim) => Some(claim),
+                    Err(err) => {
+                        telemetry::error!("error reading unbonding claim {claim_hash}: {err}");
+                        None
+                    },
+                })
+                .collect()
+        };
+
+        for mut claim in claims {
+            let released = claim.release_unbonded(epoch);
+            if released.is_empty() {
+                continue;
+            }
+
+            for unbonding in released {
+                let credited = self.database.update_account(UpdateArgs {
+                    address: unbonding.staker.clone(),
+                    nonce: None,
+                    credits: Some(unbonding.amount),
+                    debits: None,
+                    storage: None,
+                    code: None,
+                    digests: None,
+                });
+
+                if let Err(err) = credited {
+                    telemetry::error!("error releasing stake of {}: {err}", unbonding.staker);
+                }
+            }
+
+            if let Err(err) = self.database.insert_claim(claim) {
+                telemetry::error!("error storing claim with released stake: {err}");
+            }
+        }
+    }
+
+    /// Computes the rewards credited when committing the given `RoundBlocks`,
+    /// whose proposals must only hold the transactions the `ConvergenceBlock`
+    /// included. The miner of the `ConvergenceBlock` gets its block reward,
+    /// the proposer of every `ProposalBlock` gets the proposer fee share of
+    /// its transactions, and the farmers that voted on a transaction split
+    /// its validator fee share. Delegators of a rewarded claim get a cut of
+    /// its reward, pro rata to the stake they delegated to it.
+    pub(crate) fn get_reward_credits(&self, round_blocks: &RoundBlocks) -> Vec<RewardCredit> {
+        let header = &round_blocks.convergence.header;
+        let mut rewards: Vec<(Address, u128, RewardKind)> = vec![(
+            header.miner_claim.address.clone(),
+            header.block_reward.amount,
+            RewardKind::BlockReward,
+        )];
+
+        for block in round_blocks.proposals.iter() {
+            let proposer_fees = block
+                .txns
+                .values()
+                .map(|txn| txn.proposer_fee_share())
+                .sum::<u128>();
+
+            rewards.push((
+                block.from.address.clone(),
+                proposer_fees,
+                RewardKind::ProposerFee,
+            ));
+
+            for txn in block.txns.values() {
+                let voters: HashMap<NodeIdx, &Address> = txn
+                    .votes()
+                    .iter()
+                    .map(|vote| (vote.farmer_node_id, &vote.farmer_address))
+                    .collect();
+
+                // NOTE: transactions without vote receipts fall back to the
+                // validators recorded on the transaction itself
+                if voters.is_empty() {
+                    let validator_fees = HashSet::<StateUpdate>::from_txn(txn.txn());
+                    for update in validator_fees {
+                        rewards.push((update.address, update.amount, RewardKind::ValidatorFee));
+                    }
+                    continue;
+                }
+
+                let validator_share = txn.validator_fee_share() / voters.len() as u128;
+
+                for address in voters.into_values() {
+                    rewards.push((address.clone(), validator_share, RewardKind::ValidatorFee));
+                }
+            }
+        }
+
+        let claims: HashMap<Address, Claim> = self
+            .database
+            .read_handle()
+            .claim_store_values()
+            .into_values()
+            .map(|claim| (claim.address.clone(), claim))
+            .collect();
+
+        let mut credits = vec![];
+        let mut credit = |address: Address, amount: u128, kind: RewardKind| {
+            if amount > 0 {
+                credits.push(RewardCredit {
+                    epoch: header.epoch,
+                    round: header.round,
+                    block_hash: round_blocks.convergence.hash.clone(),
+                    address,
+                    amount,
+                    kind,
+                });
+            }
+        };
+
+        for (address, amount, kind) in rewards {
+            let mut remainder = amount;
+
+            if let Some(claim) = claims.get(&address) {
+                let total_stake = claim.get_total_stake();
+
+                for (delegator, delegated) in claim.get_delegations() {
+                    if total_stake == 0 {
+                        break;
+                    }
+
+                    // NOTE: widened so `amount * delegated` cannot overflow,
+                    // and capped so the cuts never exceed the reward
+                    let cut = U256::from(amount) * U256::from(delegated) / U256::from(total_stake);
+                    let cut = cut.min(U256::from(remainder)).as_u128();
+                    remainder -= cut;
+                    credit(delegator, cut, RewardKind::Delegation);
+                }
+            }
+
+            credit(address, remainder, kind);
+        }
+
+        credits
+    }
+
+    /// Produces a receipt for every transaction proposed by the
+    /// `ProposalBlock`s of the given `RoundBlocks`. Transactions that
+    /// were left out of the `ConvergenceBlock` are marked as rejected,
+    /// unless another proposal got them included.
+    fn get_receipts(&self, round_blocks: &RoundBlocks) -> Vec<TxnReceipt> {
+        let convergence = &round_blocks.convergence;
+        let mut receipts: HashMap<TransactionDigest, TxnReceipt> = HashMap::new();
+
+        round_blocks.proposals.iter().for_each(|block| {
+            let included = convergence.txns.get(&block.hash);
+
+            block.txns.iter().for_each(|(digest, txn)| {
+                let receipt = if included.map_or(false, |digests| digests.contains(digest)) {
+                    TxnReceipt::included(
+                        txn,
+                        block.hash.clone(),
+                        convergence.hash.clone(),
+                        convergence.header.round,
+                        convergence.header.epoch,
+                    )
+                } else {
+                    let reason = if txn.is_txn_valid {
+                        "conflicts with another transaction in the convergence block"
+                    } else {
+                        "voted invalid by the validator quorum"
+                    };
+
+                    TxnReceipt::rejected(
+                        txn,
+                        block.hash.clone(),
+                        convergence.hash.clone(),
+                        convergence.header.round,
+                        convergence.header.epoch,
+                        reason,
+                    )
+                };
+
+                let already_included = receipts.get(digest).map_or(false, |existing| {
+                    existing.status == TxnReceiptStatus::Included
+                });
+
+                if !already_included {
+                    receipts.insert(digest.clone(), receipt);
+                }
+            });
+        });
+
+        receipts.into_values().collect()
+    }
+
     /// Provides a method to convert a `RoundBlocks` wrapper struct into
     /// a HashSet of unique `StateUpdate`s
     fn get_update_list(&self, round_blocks: &mut RoundBlocks) -> HashSet<StateUpdate> {
@@ -416,6 +1006,94 @@ impl StateManager {
         Ok(())
     }
 
+    /// Writes the accounts of the genesis allocation table into state. Accounts
+    /// that already exist are left untouched, so restarting a node doesn't
+    /// grant its allocations twice.
+    pub fn apply_genesis_allocations(&mut self, genesis: &GenesisAllocations) -> Result<()> {
+        let genesis_accounts =
+            generate_genesis_accounts(genesis).map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let existing_accounts = self.database.read_handle().state_store_values();
+
+        for (address, account) in genesis_accounts {
+            if existing_accounts.contains_key(&address) {
+                continue;
+            }
+
+            self.insert_account(address, account)?;
+        }
+
+        Ok(())
+    }
+
+    /// Loads a state snapshot fetched from a peer while fast syncing, as the
+    /// state committed by the snapshot's block of the given epoch. The
+    /// snapshot is only loaded into an empty state, once its accounts are
+    /// found to hash to the given hex encoded state root.
+    pub(crate) fn load_state_snapshot(
+        &mut self,
+        snapshot: StateSnapshot,
+        root_hash: &str,
+        epoch: Epoch,
+    ) -> Result<()> {
+        if !self.database.read_handle().state_store_values().is_empty() {
+            return Err(NodeError::Other(
+                "state snapshots can only be loaded into an empty state".to_string(),
+            ));
+        }
+
+        // NOTE: the snapshot is checked within a scratch store so a snapshot that
+        // doesn't match its certified root never makes it into state
+        let scratch_path = env::temp_dir().join(format!("snapshot-{}", uuid::Uuid::new_v4()));
+        let mut scratch_store = StateStore::new(&scratch_path)?;
+        scratch_store.extend(snapshot.accounts.clone());
+        let snapshot_root_hash = scratch_store.root_hash().map(|root| hex::encode(root.0));
+
+        drop(scratch_store);
+        if let Err(err) = std::fs::remove_dir_all(&scratch_path) {
+            telemetry::warn!("failed to remove scratch state store: {err}");
+        }
+
+        if snapshot_root_hash.as_deref() != Some(root_hash) {
+            return Err(NodeError::Other(format!(
+                "state snapshot of block {} does not match its certified state root",
+                snapshot.block_hash
+            )));
+        }
+
+        self.database.extend_accounts(snapshot.accounts);
+        self.database
+            .record_block_state(&snapshot.block_hash, epoch)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        self.epoch = self.epoch.max(epoch);
+
+        Ok(())
+    }
+
+    /// Records a behaviour observed from the given peer into its reputation.
+    pub fn record_peer_behaviour(
+        &mut self,
+        node_id: &NodeId,
+        behaviour: PeerBehaviour,
+    ) -> Result<()> {
+        self.database
+            .record_peer_behaviour(node_id, behaviour)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Stores certified evidence of misbehaviour until a `ConvergenceBlock`
+    /// includes it.
+    pub fn insert_evidence(&mut self, evidence: Evidence) -> Result<()> {
+        self.database
+            .insert_evidence(evidence)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        Ok(())
+    }
+
     /// Returns a read handle for the StateStore to be able to read
     /// values from it.
     fn _get_state_store_handle(&self) -> StateStoreReadHandle {
@@ -488,6 +1166,49 @@ fn get_update_args(updates: HashSet<StateUpdate>) -> HashSet<UpdateArgs> {
     updates.into_iter().map(|update| update.into()).collect()
 }
 
+/// Sums the given reward credits into fee and reward `StateUpdate`s, and
+/// converts them into a single `UpdateArgs` per credited address.
+fn get_reward_update_args(credits: &[RewardCredit]) -> HashSet<UpdateArgs> {
+    let mut rewards: HashMap<(Address, UpdateAccount), u128> = HashMap::new();
+
+    for credit in credits {
+        let update_account = match credit.kind {
+            RewardKind::ProposerFee | RewardKind::ValidatorFee => UpdateAccount::Fee,
+            RewardKind::BlockReward | RewardKind::Delegation => UpdateAccount::Reward,
+        };
+
+        *rewards
+            .entry((credit.address.clone(), update_account))
+            .or_default() += credit.amount;
+    }
+
+    // NOTE: fee and reward updates of the same address are merged here, as
+    // equal `UpdateArgs` would otherwise collapse into one in a `HashSet`
+    let mut update_args: HashMap<Address, UpdateArgs> = HashMap::new();
+    for ((address, update_account), amount) in rewards {
+        let update: UpdateArgs = StateUpdate {
+            address: address.clone(),
+            token: None,
+            amount,
+            nonce: None,
+            storage: None,
+            code: None,
+            digest: TransactionDigest::default(),
+            update_account,
+        }
+        .into();
+
+        update_args
+            .entry(address)
+            .and_modify(|existing| {
+                existing.credits = Some(existing.credits.unwrap_or_default() + amount)
+            })
+            .or_insert(update);
+    }
+
+    update_args.into_values().collect()
+}
+
 /// Iterates through all `UpdateArgs` structs in a HashSet and consolidates
 /// them into a single `UpdateArgs` struct for each address which has
 /// activity in a given round.
@@ -511,8 +1232,13 @@ fn consolidate_update_args(updates: HashSet<UpdateArgs>) -> HashMap<Address, Upd
                     (a, None) => a,
                     (_, b) => b,
                 };
-                existing_update.storage = update.storage.clone(); // TODO: Update this to use the most recent value
-                existing_update.code = update.code.clone(); // TODO: Update this to use the most recent value
+                // TODO: Update these to use the most recent value
+                if update.storage.is_some() {
+                    existing_update.storage = update.storage.clone();
+                }
+                if update.code.is_some() {
+                    existing_update.code = update.code.clone();
+                }
                 if let Some(digests) = update.digests.clone() {
                     if let Some(ref mut existing_digests) = existing_update.digests {
                         existing_digests.extend_all(digests);
@@ -0,0 +1,187 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use primitives::NodeId;
+use vrrb_core::{bloom::Bloom, txn::TransactionDigest};
+
+/// Number of transaction digests remembered before the filter starts over.
+pub const SEEN_TXNS_CAPACITY: usize = 100_000;
+
+/// Maximum number of transactions a single peer can forward per window.
+pub const PEER_TXN_RATE_LIMIT: u32 = 100;
+
+/// Window the forwarding rate of every peer is measured over.
+pub const PEER_TXN_RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Filters the transactions forwarded by peers before they reach the mempool.
+/// Transactions that were already seen are dropped, as is everything a peer
+/// forwards past its rate limit. Peers are told apart by the validator key
+/// they signed their forwards with, and every forward that couldn't be
+/// authenticated counts towards a single shared limit.
+#[derive(Debug)]
+pub struct TxnGossipFilter {
+    seen_txns: Bloom,
+    rate_limit: u32,
+    rate_window: Duration,
+    /// Start of the current window of every authenticated peer, and of the
+    /// unauthenticated ones as a whole, along with the number of transactions
+    /// forwarded within it
+    peer_windows: HashMap<Option<NodeId>, (Instant, u32)>,
+}
+
+impl Default for TxnGossipFilter {
+    fn default() -> Self {
+        Self::new(PEER_TXN_RATE_LIMIT, PEER_TXN_RATE_WINDOW)
+    }
+}
+
+impl TxnGossipFilter {
+    pub fn new(rate_limit: u32, rate_window: Duration) -> Self {
+        Self {
+            seen_txns: Bloom::new(SEEN_TXNS_CAPACITY),
+            rate_limit,
+            rate_window,
+            peer_windows: HashMap::new(),
+        }
+    }
+
+    /// Remembers a transaction so copies of it forwarded later on are
+    /// dropped.
+    pub fn mark_seen(&mut self, txn_id: &TransactionDigest) {
+        if self.seen_txns.contains(txn_id) {
+            return;
+        }
+
+        // NOTE: the filter starts over once it's full, which can let a few
+        // duplicates through to the mempool
+        if self.seen_txns.push(txn_id).is_err() {
+            self.seen_txns = Bloom::new(SEEN_TXNS_CAPACITY);
+            self.seen_txns.push(txn_id).ok();
+        }
+    }
+
+    /// Returns true if the transaction forwarded by the given authenticated
+    /// peer, or by an unauthenticated one if `None`, should be added to the
+    /// mempool. Since the filter of seen transactions can report false
+    /// positives, `is_known` is asked whether a transaction it reports was
+    /// really seen before.
+    pub fn admit(
+        &mut self,
+        sender_id: Option<&NodeId>,
+        txn_id: &TransactionDigest,
+        now: Instant,
+        is_known: impl FnOnce(&TransactionDigest) -> bool,
+    ) -> bool {
+        let rate_window = self.rate_window;
+
+        // NOTE: windows of peers that went idle are dropped, so they don't
+        // pile up
+        self.peer_windows
+            .retain(|_, (window_start, _)| now.duration_since(*window_start) < rate_window);
+
+        let (_, forwarded_txns) = self
+            .peer_windows
+            .entry(sender_id.cloned())
+            .or_insert((now, 0));
+
+        // NOTE: duplicates count towards the limit too, so a peer can't
+        // flood the node with them either
+        *forwarded_txns += 1;
+
+        if *forwarded_txns > self.rate_limit {
+            return false;
+        }
+
+        if self.seen_txns.contains(txn_id) && is_known(txn_id) {
+            return false;
+        }
+
+        self.mark_seen(txn_id);
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use vrrb_core::txn::Txn;
+
+    use super::*;
+
+    #[test]
+    fn forwarded_txns_are_only_admitted_once() {
+        let mut filter = TxnGossipFilter::default();
+        let sender_id = "node-1".to_string();
+        let txn_id = Txn::null_txn().id();
+        let now = Instant::now();
+
+        assert!(filter.admit(Some(&sender_id), &txn_id, now, |_| true));
+        assert!(!filter.admit(Some(&sender_id), &txn_id, now, |_| true));
+        assert!(!filter.admit(Some(&"node-2".to_string()), &txn_id, now, |_| true));
+    }
+
+    #[test]
+    fn locally_created_txns_are_not_admitted_from_peers() {
+        let mut filter = TxnGossipFilter::default();
+        let sender_id = "node-1".to_string();
+        let txn_id = Txn::null_txn().id();
+
+        filter.mark_seen(&txn_id);
+
+        assert!(!filter.admit(Some(&sender_id), &txn_id, Instant::now(), |_| true));
+    }
+
+    #[test]
+    fn unauthenticated_peers_share_a_rate_limit() {
+        let mut filter = TxnGossipFilter::new(1, Duration::from_secs(1));
+        let now = Instant::now();
+
+        let txn_ids: Vec<TransactionDigest> = (0..2u8)
+            .map(|i| TransactionDigest::from(vec![i; 32]))
+            .collect();
+
+        assert!(filter.admit(None, &txn_ids[0], now, |_| true));
+        assert!(!filter.admit(None, &txn_ids[1], now, |_| true));
+        assert!(filter.admit(Some(&"node-1".to_string()), &txn_ids[1], now, |_| true));
+    }
+
+    #[test]
+    fn txns_reported_by_the_filter_but_unknown_are_admitted() {
+        let mut filter = TxnGossipFilter::default();
+        let txn_id = Txn::null_txn().id();
+
+        filter.mark_seen(&txn_id);
+
+        // NOTE: a false positive of the filter of seen transactions
+        assert!(filter.admit(None, &txn_id, Instant::now(), |_| false));
+    }
+
+    #[test]
+    fn peers_forwarding_past_their_rate_limit_are_dropped() {
+        let mut filter = TxnGossipFilter::new(2, Duration::from_secs(1));
+        let sender_id = "node-1".to_string();
+        let now = Instant::now();
+
+        let txn_ids: Vec<TransactionDigest> = (0..4u8)
+            .map(|i| TransactionDigest::from(vec![i; 32]))
+            .collect();
+
+        assert!(filter.admit(Some(&sender_id), &txn_ids[0], now, |_| true));
+        assert!(filter.admit(Some(&sender_id), &txn_ids[1], now, |_| true));
+        assert!(!filter.admit(Some(&sender_id), &txn_ids[2], now, |_| true));
+
+        // NOTE: other peers have a limit of their own
+        assert!(filter.admit(Some(&"node-2".to_string()), &txn_ids[2], now, |_| true));
+        assert_eq!(filter.peer_windows.len(), 2);
+
+        let next_window = now + Duration::from_secs(1);
+        assert!(filter.admit(Some(&sender_id), &txn_ids[3], next_window, |_| true));
+
+        // NOTE: windows of idle peers are dropped
+        assert_eq!(filter.peer_windows.len(), 1);
+    }
+}
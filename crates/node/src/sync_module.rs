@@ -0,0 +1,1310 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use block::{
+    decode_quorum_pubkey,
+    Block,
+    BlockHash,
+    ConvergenceBlock,
+    QuorumHandOff,
+    QuorumPubkey,
+};
+use bulldag::{
+    graph::{BullDag, GraphError},
+    vertex::{Direction, Vertex},
+};
+use events::{
+    BlockRequest,
+    Event,
+    EventMessage,
+    EventPublisher,
+    EventSubscriber,
+    StateSnapshot,
+    SyncMessage,
+};
+use hbbft::crypto::{PublicKey, PublicKeySet};
+use primitives::{Address, Epoch, QuorumKind, Round, GENESIS_EPOCH};
+use storage::vrrbdb::{AccountFilter, VrrbDbReadHandle};
+use telemetry::{info, warn};
+use theater::{Actor, ActorId, ActorImpl, ActorLabel, ActorState, Handler, TheaterError};
+use tokio::task::JoinHandle;
+use vrrb_config::SyncMode;
+
+use crate::{consensus::dkg_quorum_id, dag_module::GraphResult, NodeError, Result};
+
+/// Maximum number of blocks provided in response to a single request.
+pub const MAX_SYNC_BLOCKS: usize = 500;
+
+/// Maximum number of accounts provided in a single page of a state snapshot.
+pub const MAX_SNAPSHOT_ACCOUNTS: usize = 1_000;
+
+/// Maximum number of pages a state snapshot is gathered from before the peer
+/// providing it is given up on.
+pub const MAX_SNAPSHOT_PAGES: usize = 1_000;
+
+/// Maximum number of requests served to a single address per window.
+pub const PEER_SYNC_REQUEST_LIMIT: u32 = 20;
+
+/// Window the request rate of every address is measured over.
+pub const PEER_SYNC_REQUEST_WINDOW: Duration = Duration::from_secs(1);
+
+/// The runtime module that catches a joining or restarting node up with the
+/// chain. Blocks are fetched from peers, verified, appended to the DAG once
+/// their parents are part of it and every newly appended `ConvergenceBlock`
+/// is replayed into state. The module also serves the blocks and state
+/// snapshots its peers ask for.
+pub struct SyncModule {
+    status: ActorState,
+    id: ActorId,
+    events_tx: EventPublisher,
+    dag: Arc<RwLock<BullDag<Block, String>>>,
+    vrrbdb_read_handle: VrrbDbReadHandle,
+    sync_mode: SyncMode,
+    /// Group public keys of the harvester quorums by the epoch they took
+    /// their duties over at
+    harvester_pubkeys: BTreeMap<Epoch, QuorumPubkey>,
+    /// Whether blocks are only requested once the harvester keys are known
+    awaiting_harvester_keys: bool,
+    /// Block whose effects are already part of state, i.e. the last block
+    /// committed before a restart or the block a snapshot was loaded at. It
+    /// becomes a root of the DAG without being replayed.
+    anchor_hash: Option<BlockHash>,
+    /// Certified block a state snapshot was requested at while fast syncing
+    snapshot_block: Option<ConvergenceBlock>,
+    /// Peer the state snapshot is fetched from, along with the pages of it
+    /// fetched so far
+    snapshot_peer: Option<SocketAddr>,
+    partial_snapshot: Option<StateSnapshot>,
+    /// Block the snapshot handed over to the state manager was taken at,
+    /// until it was either loaded or rejected
+    loading_snapshot: Option<BlockHash>,
+    /// Peers whose snapshot was given up on, which aren't asked for one again
+    rejected_snapshot_peers: HashSet<SocketAddr>,
+    /// Blocks that can't be verified until the harvester key set is known
+    unverified_blocks: Vec<Block>,
+    /// Verified blocks waiting for their parents to be part of the DAG
+    pending_blocks: HashMap<BlockHash, Block>,
+    requested_hashes: HashSet<BlockHash>,
+    /// Round the next range of blocks is requested from, while syncing by
+    /// range
+    next_round: Option<Round>,
+    /// Start of the current window of every address requests are served to,
+    /// along with the number of requests served within it
+    peer_windows: HashMap<IpAddr, (Instant, u32)>,
+}
+
+impl SyncModule {
+    pub fn new(
+        dag: Arc<RwLock<BullDag<Block, String>>>,
+        events_tx: EventPublisher,
+        vrrbdb_read_handle: VrrbDbReadHandle,
+        sync_mode: SyncMode,
+    ) -> Self {
+        Self {
+            status: ActorState::Stopped,
+            id: uuid::Uuid::new_v4().to_string(),
+            events_tx,
+            dag,
+            vrrbdb_read_handle,
+            sync_mode,
+            harvester_pubkeys: BTreeMap::new(),
+            awaiting_harvester_keys: false,
+            anchor_hash: None,
+            snapshot_block: None,
+            snapshot_peer: None,
+            partial_snapshot: None,
+            loading_snapshot: None,
+            rejected_snapshot_peers: HashSet::new(),
+            unverified_blocks: vec![],
+            pending_blocks: HashMap::new(),
+            requested_hashes: HashSet::new(),
+            next_round: None,
+            peer_windows: HashMap::new(),
+        }
+    }
+
+    /// Records the key set of the local harvester quorum. The quorum elected
+    /// at genesis holds its duties without a hand-off, so a key set learned
+    /// before any block past the genesis epoch was committed is taken to be
+    /// its key set.
+    pub fn add_harvester_key_set(&mut self, public_key_set: PublicKeySet) -> Result<()> {
+        let epoch = self
+            .vrrbdb_read_handle
+            .last_committed_epoch()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        if epoch == GENESIS_EPOCH && self.harvester_pubkeys.is_empty() {
+            let quorum_pubkey = hex::encode(public_key_set.public_key().to_bytes());
+            self.harvester_pubkeys.insert(GENESIS_EPOCH, quorum_pubkey);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the group public key of the harvester quorum holding its
+    /// duties at the given epoch, if this node knows it.
+    fn harvester_pubkey(&self, epoch: Epoch) -> Option<PublicKey> {
+        let (_, quorum_pubkey) = self.harvester_pubkeys.range(..=epoch).next_back()?;
+
+        decode_quorum_pubkey(quorum_pubkey)
+    }
+
+    /// Records the key a harvester hand-off inaugurates, as long as it was
+    /// certified by the key the harvester quorum held its duties with.
+    fn follow_hand_off(&mut self, hand_off: &QuorumHandOff) {
+        if hand_off.quorum_id != dkg_quorum_id(&QuorumKind::Harvester) {
+            return;
+        }
+
+        let current_pubkey = self
+            .harvester_pubkeys
+            .range(..hand_off.epoch)
+            .next_back()
+            .map(|(_, quorum_pubkey)| quorum_pubkey);
+
+        if current_pubkey == Some(&hand_off.outgoing_pubkey) && hand_off.verify() {
+            self.harvester_pubkeys
+                .insert(hand_off.epoch, hand_off.incoming_pubkey.clone());
+        }
+    }
+
+    /// Returns the harvester hand-offs certified by the blocks of the DAG,
+    /// ordered by the epoch they take effect at.
+    fn harvester_hand_offs(&self) -> Vec<QuorumHandOff> {
+        let guard = match self.dag.read() {
+            Ok(guard) => guard,
+            Err(_) => return vec![],
+        };
+
+        let harvester_quorum_id = dkg_quorum_id(&QuorumKind::Harvester);
+
+        let mut hand_offs: Vec<QuorumHandOff> = get_all_blocks(&guard)
+            .iter()
+            .filter_map(|block| block.certificate())
+            .filter_map(|certificate| certificate.hand_offs.as_ref())
+            .flat_map(|hand_offs| hand_offs.values())
+            .filter(|hand_off| hand_off.quorum_id == harvester_quorum_id)
+            .cloned()
+            .collect();
+
+        hand_offs.sort_by_key(|hand_off| hand_off.epoch);
+        hand_offs
+    }
+
+    /// Starts catching up with the chain. Nodes that don't know the keys of
+    /// the harvester quorums ask their peers for them first, since no block
+    /// certificate can be checked without them.
+    async fn start_sync(&mut self) -> Result<()> {
+        if self.harvester_pubkeys.is_empty() {
+            info!("Requesting the harvester keys before syncing");
+            self.awaiting_harvester_keys = true;
+
+            return self
+                .send_sync_message(SyncMessage::HarvesterKeysRequested, None)
+                .await;
+        }
+
+        self.request_blocks().await
+    }
+
+    /// Requests the first blocks to sync. Restarting nodes resume from the
+    /// last block they committed, while new nodes either replay the chain
+    /// from genesis or load a state snapshot, depending on their sync mode.
+    async fn request_blocks(&mut self) -> Result<()> {
+        let last_committed_block_hash = self
+            .vrrbdb_read_handle
+            .last_committed_block_hash()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        let request = match (last_committed_block_hash, self.sync_mode) {
+            (Some(block_hash), _) => {
+                info!("Resuming sync from block {block_hash}");
+                self.anchor_hash = Some(block_hash.clone());
+                self.requested_hashes.insert(block_hash.clone());
+                BlockRequest::ByHash(vec![block_hash])
+            },
+            (None, SyncMode::Fast) => {
+                info!("Fast syncing from the latest certified block");
+                BlockRequest::Latest
+            },
+            (None, SyncMode::Full) => {
+                info!("Syncing from genesis");
+                self.next_round = Some(0);
+                BlockRequest::ByRange {
+                    from_round: 0,
+                    to_round: None,
+                }
+            },
+        };
+
+        self.send_sync_message(SyncMessage::BlocksRequested(request), None)
+            .await
+    }
+
+    async fn send_sync_message(
+        &self,
+        sync_message: SyncMessage,
+        peer_addr: Option<SocketAddr>,
+    ) -> Result<()> {
+        self.events_tx
+            .send(Event::SyncMessageCreated(sync_message, peer_addr).into())
+            .await
+            .map_err(NodeError::from)
+    }
+
+    /// Answers the sync messages of peers and handles the responses to the
+    /// requests of this node.
+    async fn handle_sync_message(
+        &mut self,
+        sync_message: SyncMessage,
+        peer_addr: SocketAddr,
+    ) -> Result<()> {
+        let is_request = matches!(
+            sync_message,
+            SyncMessage::BlocksRequested(..)
+                | SyncMessage::StateSnapshotRequested(..)
+                | SyncMessage::HarvesterKeysRequested
+        );
+
+        if is_request && !self.admit_request(peer_addr, Instant::now()) {
+            warn!("Dropping sync request from {peer_addr} past its rate limit");
+            return Ok(());
+        }
+
+        match sync_message {
+            SyncMessage::BlocksRequested(request) => {
+                let blocks = self.get_requested_blocks(&request);
+                let response = SyncMessage::BlocksProvided(request, blocks);
+
+                self.send_sync_message(response, Some(peer_addr)).await
+            },
+            SyncMessage::BlocksProvided(request, blocks) => {
+                self.handle_provided_blocks(request, blocks, peer_addr)
+                    .await
+            },
+            SyncMessage::StateSnapshotRequested(certified_hash, after) => {
+                // NOTE: blocks are certified on the state committed by the
+                // block before them, which is the one the snapshot is taken at
+                let Some(block_hash) = self.parent_convergence_hash(&certified_hash) else {
+                    return Err(NodeError::Other(format!(
+                        "no parent known for block {certified_hash}"
+                    )));
+                };
+
+                let accounts = self
+                    .vrrbdb_read_handle
+                    .state_page_at(
+                        &block_hash,
+                        after.as_ref(),
+                        MAX_SNAPSHOT_ACCOUNTS,
+                        &AccountFilter::default(),
+                    )
+                    .map_err(|err| NodeError::Other(err.to_string()))?;
+
+                let is_last = accounts.len() < MAX_SNAPSHOT_ACCOUNTS;
+                let snapshot = StateSnapshot {
+                    block_hash,
+                    after,
+                    accounts,
+                    is_last,
+                };
+
+                self.send_sync_message(
+                    SyncMessage::StateSnapshotProvided(snapshot),
+                    Some(peer_addr),
+                )
+                .await
+            },
+            SyncMessage::StateSnapshotProvided(snapshot) => {
+                self.handle_provided_snapshot(snapshot, peer_addr).await
+            },
+            SyncMessage::HarvesterKeysRequested => {
+                let Some(genesis_pubkey) = self.harvester_pubkeys.get(&GENESIS_EPOCH).cloned()
+                else {
+                    return Ok(());
+                };
+
+                let response =
+                    SyncMessage::HarvesterKeysProvided(genesis_pubkey, self.harvester_hand_offs());
+
+                self.send_sync_message(response, Some(peer_addr)).await
+            },
+            SyncMessage::HarvesterKeysProvided(genesis_pubkey, hand_offs) => {
+                self.handle_provided_harvester_keys(genesis_pubkey, hand_offs)
+                    .await
+            },
+        }
+    }
+
+    /// Learns the keys of the harvester quorums from the first peer to
+    /// provide them, following the certified hand-offs from the genesis
+    /// quorum on.
+    async fn handle_provided_harvester_keys(
+        &mut self,
+        genesis_pubkey: QuorumPubkey,
+        mut hand_offs: Vec<QuorumHandOff>,
+    ) -> Result<()> {
+        if !self.harvester_pubkeys.is_empty() || decode_quorum_pubkey(&genesis_pubkey).is_none() {
+            return Ok(());
+        }
+
+        self.harvester_pubkeys.insert(GENESIS_EPOCH, genesis_pubkey);
+
+        hand_offs.sort_by_key(|hand_off| hand_off.epoch);
+        for hand_off in hand_offs.iter() {
+            self.follow_hand_off(hand_off);
+        }
+
+        self.resume_with_harvester_keys().await
+    }
+
+    /// Verifies the blocks that were waiting for the harvester keys, and
+    /// requests the first blocks to sync if that was waiting for them too.
+    async fn resume_with_harvester_keys(&mut self) -> Result<()> {
+        let blocks = std::mem::take(&mut self.unverified_blocks);
+        self.add_blocks(blocks);
+        self.append_pending_blocks().await?;
+
+        if std::mem::take(&mut self.awaiting_harvester_keys) {
+            self.request_blocks().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if a request from the given address is to be served. The
+    /// rate is limited per address responses are sent to, so neither a
+    /// requesting peer nor the address it names can be flooded. Windows that
+    /// ended are dropped along the way.
+    pub(crate) fn admit_request(&mut self, peer_addr: SocketAddr, now: Instant) -> bool {
+        self.peer_windows.retain(|_, (window_start, _)| {
+            now.duration_since(*window_start) < PEER_SYNC_REQUEST_WINDOW
+        });
+
+        let (_, requests) = self.peer_windows.entry(peer_addr.ip()).or_insert((now, 0));
+
+        *requests += 1;
+
+        *requests <= PEER_SYNC_REQUEST_LIMIT
+    }
+
+    /// Returns the blocks of the DAG matching the request, up to
+    /// `MAX_SYNC_BLOCKS`. Ranges are cut at round boundaries so the rest of
+    /// them can be requested from the round after the last one provided.
+    pub(crate) fn get_requested_blocks(&self, request: &BlockRequest) -> Vec<Block> {
+        let guard = match self.dag.read() {
+            Ok(guard) => guard,
+            Err(_) => return vec![],
+        };
+
+        match request {
+            BlockRequest::ByHash(hashes) => hashes
+                .iter()
+                .take(MAX_SYNC_BLOCKS)
+                .filter_map(|hash| guard.get_vertex(hash.clone()))
+                .map(|vertex| vertex.get_data())
+                .collect(),
+
+            BlockRequest::ByRange {
+                from_round,
+                to_round,
+            } => {
+                let mut blocks: Vec<Block> = get_all_blocks(&guard)
+                    .into_iter()
+                    .filter(|block| block.round() >= *from_round)
+                    .filter(|block| to_round.map_or(true, |to_round| block.round() <= to_round))
+                    .collect();
+
+                // NOTE: parents are provided before the blocks referencing them
+                blocks.sort_by_key(|block| (block.round(), block_order(block)));
+
+                let mut rounds: Vec<Vec<Block>> = vec![];
+                for block in blocks {
+                    match rounds.last_mut() {
+                        Some(round_blocks) if round_blocks[0].round() == block.round() => {
+                            round_blocks.push(block)
+                        },
+                        _ => rounds.push(vec![block]),
+                    }
+                }
+
+                let mut provided = Vec::new();
+                for round_blocks in rounds {
+                    if !provided.is_empty() && provided.len() + round_blocks.len() > MAX_SYNC_BLOCKS
+                    {
+                        break;
+                    }
+
+                    provided.extend(round_blocks);
+                }
+
+                provided
+            },
+
+            BlockRequest::Latest => get_all_blocks(&guard)
+                .into_iter()
+                .filter(|block| block.is_convergence() && block.certificate().is_some())
+                .max_by_key(|block| block.round())
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    async fn handle_provided_blocks(
+        &mut self,
+        request: BlockRequest,
+        blocks: Vec<Block>,
+        peer_addr: SocketAddr,
+    ) -> Result<()> {
+        let last_round = blocks.iter().map(|block| block.round()).max();
+        let anchor_round = blocks
+            .iter()
+            .find(|block| self.is_anchor(block))
+            .map(|block| block.round());
+
+        if request == BlockRequest::Latest
+            && self.snapshot_block.is_none()
+            && self.anchor_hash.is_none()
+        {
+            return self.request_snapshot(blocks, peer_addr).await;
+        }
+
+        self.add_blocks(blocks);
+        self.append_pending_blocks().await?;
+
+        // NOTE: ancestors of the provided blocks that are still missing are
+        // walked back until a block that is part of the DAG is reached
+        let missing_hashes = self.get_missing_hashes();
+        if !missing_hashes.is_empty() {
+            self.requested_hashes.extend(missing_hashes.clone());
+
+            let request = BlockRequest::ByHash(missing_hashes);
+            self.send_sync_message(SyncMessage::BlocksRequested(request), Some(peer_addr))
+                .await?;
+        }
+
+        // NOTE: once the block a snapshot was taken at is part of the DAG, the
+        // blocks after it are synced by range
+        if let Some(anchor_round) = anchor_round {
+            if self.snapshot_block.take().is_some() {
+                let from_round = anchor_round + 1;
+                self.next_round = Some(from_round);
+
+                let request = BlockRequest::ByRange {
+                    from_round,
+                    to_round: None,
+                };
+
+                self.send_sync_message(SyncMessage::BlocksRequested(request), Some(peer_addr))
+                    .await?;
+            }
+        }
+
+        if let BlockRequest::ByRange { from_round, .. } = request {
+            // NOTE: peers answer the same request, only the first answer
+            // moves the range forward
+            if self.next_round != Some(from_round) {
+                return Ok(());
+            }
+
+            match last_round {
+                Some(last_round) => {
+                    let from_round = last_round + 1;
+                    self.next_round = Some(from_round);
+
+                    let request = BlockRequest::ByRange {
+                        from_round,
+                        to_round: None,
+                    };
+
+                    self.send_sync_message(SyncMessage::BlocksRequested(request), Some(peer_addr))
+                        .await?;
+                },
+                None => {
+                    info!("Caught up with the chain");
+                    self.next_round = None;
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Requests a state snapshot at the latest certified block a peer
+    /// provided.
+    async fn request_snapshot(&mut self, blocks: Vec<Block>, peer_addr: SocketAddr) -> Result<()> {
+        if self.rejected_snapshot_peers.contains(&peer_addr) {
+            return Ok(());
+        }
+
+        let block = blocks.into_iter().find_map(|block| match block {
+            Block::Convergence { block } => Some(block),
+            _ => None,
+        });
+
+        let block = match block {
+            Some(block) if self.verify_block(&block.clone().into()) => block,
+            _ => {
+                warn!("Peer at {peer_addr} provided no certified block to fast sync from");
+                return Ok(());
+            },
+        };
+
+        let block_hash = block.hash.clone();
+        self.snapshot_block = Some(block);
+        self.snapshot_peer = Some(peer_addr);
+
+        self.send_sync_message(
+            SyncMessage::StateSnapshotRequested(block_hash, None),
+            Some(peer_addr),
+        )
+        .await
+    }
+
+    /// Gathers the pages of a state snapshot in the order they were requested
+    /// in. Once the last one was provided, the snapshot is handed over to the
+    /// state manager along with the state root the latest certified block was
+    /// certified on. Peers providing more pages than a snapshot may span are
+    /// given up on.
+    async fn handle_provided_snapshot(
+        &mut self,
+        snapshot: StateSnapshot,
+        peer_addr: SocketAddr,
+    ) -> Result<()> {
+        if self.anchor_hash.is_some()
+            || self.loading_snapshot.is_some()
+            || self.snapshot_peer != Some(peer_addr)
+        {
+            return Ok(());
+        }
+
+        let cursor = self.partial_snapshot.as_ref().and_then(snapshot_cursor);
+        if self.snapshot_block.is_none() || snapshot.after != cursor {
+            return Ok(());
+        }
+
+        let max_accounts = MAX_SNAPSHOT_PAGES * MAX_SNAPSHOT_ACCOUNTS;
+        let gathered_accounts = self
+            .partial_snapshot
+            .as_ref()
+            .map_or(0, |partial| partial.accounts.len());
+
+        if snapshot.accounts.len() > MAX_SNAPSHOT_ACCOUNTS
+            || gathered_accounts + snapshot.accounts.len() > max_accounts
+        {
+            warn!("Peer at {peer_addr} provided a state snapshot past the size limit");
+            return self.abandon_snapshot_peer().await;
+        }
+
+        let Some(block) = &self.snapshot_block else {
+            return Ok(());
+        };
+
+        let snapshot = match self.partial_snapshot.take() {
+            Some(partial) if partial.block_hash != snapshot.block_hash => {
+                self.partial_snapshot = Some(partial);
+                return Ok(());
+            },
+            Some(mut partial) => {
+                partial.accounts.extend(snapshot.accounts);
+                partial.is_last = snapshot.is_last;
+                partial
+            },
+            None => snapshot,
+        };
+
+        if !snapshot.is_last {
+            let request =
+                SyncMessage::StateSnapshotRequested(block.hash.clone(), snapshot_cursor(&snapshot));
+            self.partial_snapshot = Some(snapshot);
+
+            return self.send_sync_message(request, Some(peer_addr)).await;
+        }
+
+        let root_hash = block
+            .certificate
+            .as_ref()
+            .map(|certificate| certificate.root_hash.clone())
+            .unwrap_or_default();
+
+        let epoch = block.header.epoch;
+        self.loading_snapshot = Some(snapshot.block_hash.clone());
+
+        self.events_tx
+            .send(Event::StateSnapshotCertified(snapshot, root_hash, epoch).into())
+            .await
+            .map_err(NodeError::from)
+    }
+
+    /// Anchors the DAG at the block the loaded snapshot was taken at, and
+    /// requests that block from the peer that provided the snapshot.
+    async fn handle_loaded_snapshot(&mut self, block_hash: BlockHash) -> Result<()> {
+        if self.loading_snapshot.as_ref() != Some(&block_hash) {
+            return Ok(());
+        }
+
+        self.loading_snapshot = None;
+        self.anchor_hash = Some(block_hash.clone());
+        self.requested_hashes.insert(block_hash.clone());
+
+        let request = BlockRequest::ByHash(vec![block_hash]);
+
+        self.send_sync_message(SyncMessage::BlocksRequested(request), self.snapshot_peer)
+            .await
+    }
+
+    /// Gives up on the peer whose snapshot the state manager rejected.
+    async fn handle_rejected_snapshot(&mut self, block_hash: BlockHash) -> Result<()> {
+        if self.loading_snapshot.as_ref() != Some(&block_hash) {
+            return Ok(());
+        }
+
+        self.abandon_snapshot_peer().await
+    }
+
+    /// Drops the state snapshot fetched so far along with the peer it was
+    /// fetched from, and fast syncs from the latest certified block another
+    /// peer provides instead.
+    async fn abandon_snapshot_peer(&mut self) -> Result<()> {
+        if let Some(peer_addr) = self.snapshot_peer.take() {
+            warn!("Giving up on the state snapshot provided by {peer_addr}");
+            self.rejected_snapshot_peers.insert(peer_addr);
+        }
+
+        self.snapshot_block = None;
+        self.partial_snapshot = None;
+        self.loading_snapshot = None;
+
+        self.send_sync_message(SyncMessage::BlocksRequested(BlockRequest::Latest), None)
+            .await
+    }
+
+    /// Returns the hash of the convergence block the given one builds on,
+    /// which its proposal blocks reference.
+    fn parent_convergence_hash(&self, block_hash: &BlockHash) -> Option<BlockHash> {
+        let guard = self.dag.read().ok()?;
+        let block = guard.get_vertex(block_hash.clone())?.get_data();
+        let proposal_hash = block.parent_hashes().into_iter().next()?;
+
+        match guard.get_vertex(proposal_hash)?.get_data() {
+            Block::Proposal { block } => Some(block.ref_block),
+            _ => None,
+        }
+    }
+
+    /// Verifies the given blocks and queues them until their parents are
+    /// part of the DAG, following the harvester hand-offs their certificates
+    /// inaugurate. Blocks that can't be verified yet are kept until the
+    /// harvester keys are known.
+    fn add_blocks(&mut self, blocks: Vec<Block>) {
+        for block in blocks {
+            let block_hash = block.hash();
+            self.requested_hashes.remove(&block_hash);
+
+            if self.contains_block(&block_hash) || self.pending_blocks.contains_key(&block_hash) {
+                continue;
+            }
+
+            if self.harvester_pubkeys.is_empty() && !block.is_proposal() {
+                self.unverified_blocks.push(block);
+                continue;
+            }
+
+            if !self.verify_block(&block) {
+                warn!("Discarding block {block_hash} that failed verification");
+                continue;
+            }
+
+            let hand_offs = block
+                .certificate()
+                .and_then(|certificate| certificate.hand_offs.clone())
+                .unwrap_or_default();
+
+            for hand_off in hand_offs.values() {
+                self.follow_hand_off(hand_off);
+            }
+
+            self.pending_blocks.insert(block_hash, block);
+        }
+    }
+
+    /// Checks that the hash of a block matches its contents, that a proposal
+    /// block was signed by its proposer and that a convergence block was
+    /// certified by the harvester quorum of its epoch. Genesis blocks are
+    /// only accepted into an empty DAG.
+    pub(crate) fn verify_block(&self, block: &Block) -> bool {
+        if !block.verify_hash() {
+            return false;
+        }
+
+        let is_certified = |block_hash: &BlockHash, epoch: Epoch| {
+            let Some(certificate) = block.certificate() else {
+                return false;
+            };
+            let Some(harvester_pubkey) = self.harvester_pubkey(epoch) else {
+                return false;
+            };
+
+            &certificate.block_hash == block_hash
+                && certificate.verify_with_pubkey(&harvester_pubkey)
+        };
+
+        match block {
+            Block::Proposal { block } => block.verify_signature(),
+            Block::Convergence { block } => is_certified(&block.hash, block.header.epoch),
+            Block::Genesis { block } => {
+                self.is_dag_empty()
+                    && (block.certificate.is_none()
+                        || is_certified(&block.hash, block.header.epoch))
+            },
+        }
+    }
+
+    /// Appends every pending block whose parents are part of the DAG, until
+    /// none is left, and replays the appended convergence blocks into state
+    /// in the order they were appended.
+    async fn append_pending_blocks(&mut self) -> Result<()> {
+        let mut appended_convergence_hashes = vec![];
+
+        loop {
+            let mut ready_blocks: Vec<Block> = self
+                .pending_blocks
+                .values()
+                .filter(|block| self.is_anchor(block) || self.has_parents(block))
+                .cloned()
+                .collect();
+
+            if ready_blocks.is_empty() {
+                break;
+            }
+
+            ready_blocks.sort_by_key(|block| (block.round(), block_order(block)));
+
+            for block in ready_blocks {
+                let block_hash = block.hash();
+                self.pending_blocks.remove(&block_hash);
+
+                let is_anchor = self.is_anchor(&block);
+                let is_convergence = block.is_convergence();
+
+                let appended = if is_anchor || block.is_genesis() {
+                    self.write_root(block)
+                } else {
+                    self.write_block(block)
+                };
+
+                if let Err(err) = appended {
+                    warn!("Failed to append block {block_hash} to the DAG: {err:?}");
+                    continue;
+                }
+
+                // NOTE: the anchor's effects are already part of state
+                if is_convergence && !is_anchor {
+                    appended_convergence_hashes.push(block_hash);
+                }
+            }
+        }
+
+        for block_hash in appended_convergence_hashes {
+            self.events_tx
+                .send(Event::UpdateState(block_hash).into())
+                .await
+                .map_err(NodeError::from)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the parents of pending blocks that are neither part of the DAG
+    /// nor pending or requested already.
+    fn get_missing_hashes(&self) -> Vec<BlockHash> {
+        let missing_hashes: HashSet<BlockHash> = self
+            .pending_blocks
+            .values()
+            .filter(|block| !self.is_anchor(block))
+            .flat_map(|block| block.parent_hashes())
+            .filter(|hash| {
+                !self.contains_block(hash)
+                    && !self.pending_blocks.contains_key(hash)
+                    && !self.requested_hashes.contains(hash)
+            })
+            .collect();
+
+        missing_hashes.into_iter().collect()
+    }
+
+    fn is_anchor(&self, block: &Block) -> bool {
+        self.anchor_hash.as_ref() == Some(&block.hash())
+    }
+
+    fn has_parents(&self, block: &Block) -> bool {
+        block
+            .parent_hashes()
+            .iter()
+            .all(|hash| self.contains_block(hash))
+    }
+
+    fn contains_block(&self, block_hash: &BlockHash) -> bool {
+        self.dag
+            .read()
+            .map(|guard| guard.get_vertex(block_hash.clone()).is_some())
+            .unwrap_or(false)
+    }
+
+    fn is_dag_empty(&self) -> bool {
+        self.dag
+            .read()
+            .map(|guard| guard.len() == 0)
+            .unwrap_or(false)
+    }
+
+    /// Writes a block the DAG holds none of the parents of.
+    fn write_root(&self, block: Block) -> GraphResult<()> {
+        let vertex: Vertex<Block, String> = block.into();
+
+        let mut guard = self
+            .dag
+            .write()
+            .map_err(|_| GraphError::Other("Error getting write guard".to_string()))?;
+
+        guard.add_vertex(&vertex);
+
+        Ok(())
+    }
+
+    /// Writes a block along with an edge from each of its parents.
+    fn write_block(&self, block: Block) -> GraphResult<()> {
+        let parent_hashes = block.parent_hashes();
+        let vertex: Vertex<Block, String> = block.into();
+
+        let mut guard = self
+            .dag
+            .write()
+            .map_err(|_| GraphError::Other("Error getting write guard".to_string()))?;
+
+        for parent_hash in parent_hashes {
+            let parent = guard
+                .get_vertex(parent_hash)
+                .cloned()
+                .ok_or(GraphError::NonExistentReference)?;
+
+            guard.add_edge((&parent, &vertex));
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns every block within the DAG.
+fn get_all_blocks(dag: &BullDag<Block, String>) -> Vec<Block> {
+    let mut hashes: HashSet<String> = HashSet::new();
+
+    for root in dag.get_roots() {
+        if let Some(vertex) = dag.get_vertex(root.clone()) {
+            hashes.extend(dag.trace(vertex, Direction::Reference));
+        }
+
+        hashes.insert(root);
+    }
+
+    hashes
+        .into_iter()
+        .filter_map(|hash| dag.get_vertex(hash))
+        .map(|vertex| vertex.get_data())
+        .collect()
+}
+
+/// Returns the address the page after the given snapshot starts after.
+fn snapshot_cursor(snapshot: &StateSnapshot) -> Option<Address> {
+    snapshot.accounts.last().map(|(address, _)| address.clone())
+}
+
+/// Orders the blocks of a single round so that parents come first.
+fn block_order(block: &Block) -> u8 {
+    match block {
+        Block::Genesis { .. } => 0,
+        Block::Proposal { .. } => 1,
+        Block::Convergence { .. } => 2,
+    }
+}
+
+#[async_trait]
+impl Handler<EventMessage> for SyncModule {
+    fn id(&self) -> ActorId {
+        self.id.clone()
+    }
+
+    fn label(&self) -> ActorLabel {
+        format!("Sync::{}", self.id())
+    }
+
+    fn status(&self) -> ActorState {
+        self.status.clone()
+    }
+
+    fn set_status(&mut self, actor_status: ActorState) {
+        self.status = actor_status;
+    }
+
+    fn on_start(&self) {
+        info!("{} starting", self.label());
+    }
+
+    fn on_stop(&self) {
+        info!("{} received stop signal. Stopping", self.label());
+    }
+
+    async fn handle(&mut self, event: EventMessage) -> theater::Result<ActorState> {
+        match event.into() {
+            Event::Stop => {
+                return Ok(ActorState::Stopped);
+            },
+            Event::SyncRequested => {
+                self.start_sync()
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            },
+            Event::SyncMessageReceived(sync_message, peer_addr) => {
+                if let Err(err) = self.handle_sync_message(sync_message, peer_addr).await {
+                    telemetry::error!("error handling sync message from {peer_addr}: {err}");
+                }
+            },
+            Event::StateSnapshotLoaded(block_hash) => {
+                if let Err(err) = self.handle_loaded_snapshot(block_hash).await {
+                    telemetry::error!("error anchoring the loaded state snapshot: {err}");
+                }
+            },
+            Event::StateSnapshotRejected(block_hash) => {
+                if let Err(err) = self.handle_rejected_snapshot(block_hash).await {
+                    telemetry::error!("error retrying the rejected state snapshot: {err}");
+                }
+            },
+            Event::HarvesterPublicKey(pubkey_bytes) => {
+                if let Ok(public_key_set) =
+                    serde_json::from_slice::<PublicKeySet>(pubkey_bytes.as_slice())
+                {
+                    self.add_harvester_key_set(public_key_set)
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                    self.resume_with_harvester_keys()
+                        .await
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+                }
+            },
+            Event::NoOp => {},
+            _ => {},
+        }
+
+        Ok(ActorState::Running)
+    }
+}
+
+pub fn setup_sync_module(
+    dag: Arc<RwLock<BullDag<Block, String>>>,
+    events_tx: EventPublisher,
+    mut sync_module_events_rx: EventSubscriber,
+    vrrbdb_read_handle: VrrbDbReadHandle,
+    sync_mode: SyncMode,
+) -> Result<Option<JoinHandle<crate::Result<()>>>> {
+    let module = SyncModule::new(dag, events_tx, vrrbdb_read_handle, sync_mode);
+
+    let mut sync_module_actor = ActorImpl::new(module);
+    let sync_module_handle = tokio::spawn(async move {
+        sync_module_actor
+            .start(&mut sync_module_events_rx)
+            .await
+            .map_err(|err| NodeError::Other(err.to_string()))
+    });
+
+    Ok(Some(sync_module_handle))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use block::{Block, BlockHash};
+    use bulldag::{graph::BullDag, vertex::Vertex};
+    use events::{BlockRequest, DEFAULT_BUFFER};
+    use primitives::NodeIdx;
+    use serial_test::serial;
+    use signer::signer::SignatureProvider;
+    use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
+    use vrrb_config::SyncMode;
+
+    use super::*;
+    use crate::consensus::{
+        dkg_session::tests::completed_quorum_sessions,
+        quorum_pubkey,
+        QuorumHandOffCollector,
+    };
+    use crate::test_utils::{
+        produce_accounts,
+        produce_convergence_block,
+        produce_genesis_block,
+        produce_proposal_blocks,
+    };
+
+    #[tokio::test]
+    #[serial]
+    async fn provides_requested_blocks_from_the_dag() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+        let dag: Arc<RwLock<BullDag<Block, String>>> = Arc::new(RwLock::new(BullDag::new()));
+        let db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+
+        let sync_module = SyncModule::new(dag.clone(), events_tx, db.read_handle(), SyncMode::Full);
+
+        let genesis = produce_genesis_block();
+        let gblock: Block = genesis.clone().into();
+
+        assert!(sync_module.verify_block(&gblock));
+
+        let gvtx: Vertex<Block, BlockHash> = gblock.clone().into();
+        if let Ok(mut guard) = dag.write() {
+            guard.add_vertex(&gvtx);
+        }
+
+        // NOTE: a second genesis block is never accepted
+        assert!(!sync_module.verify_block(&gblock));
+
+        // NOTE: blocks whose contents don't match their hash are discarded
+        let mut forged_proposal =
+            produce_proposal_blocks(genesis.hash.clone(), produce_accounts(1), 1, 1).remove(0);
+        forged_proposal.hash = "forged".to_string();
+        assert!(forged_proposal.verify_signature());
+        assert!(!sync_module.verify_block(&forged_proposal.into()));
+
+        let accounts = produce_accounts(5);
+        let proposals = produce_proposal_blocks(genesis.hash.clone(), accounts, 5, 5);
+
+        if let Ok(mut guard) = dag.write() {
+            proposals.iter().for_each(|pblock| {
+                let pblock: Block = pblock.clone().into();
+                assert!(sync_module.verify_block(&pblock));
+
+                let pvtx: Vertex<Block, BlockHash> = pblock.into();
+                guard.add_edge((&gvtx, &pvtx));
+            });
+        }
+
+        let block_hash = produce_convergence_block(dag.clone()).unwrap();
+
+        let blocks = sync_module.get_requested_blocks(&BlockRequest::ByHash(vec![
+            block_hash.clone(),
+            "unknown".to_string(),
+        ]));
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].hash(), block_hash);
+
+        let blocks = sync_module.get_requested_blocks(&BlockRequest::ByRange {
+            from_round: 0,
+            to_round: None,
+        });
+        assert_eq!(blocks.len(), 7);
+
+        // NOTE: every block is provided after its parents
+        blocks.iter().enumerate().for_each(|(idx, block)| {
+            block.parent_hashes().iter().for_each(|parent_hash| {
+                assert!(blocks[..idx]
+                    .iter()
+                    .any(|parent| &parent.hash() == parent_hash));
+            });
+        });
+
+        // NOTE: the convergence block was never certified
+        assert!(sync_module
+            .get_requested_blocks(&BlockRequest::Latest)
+            .is_empty());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn requests_past_the_rate_limit_of_an_address_are_dropped() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+        let dag: Arc<RwLock<BullDag<Block, String>>> = Arc::new(RwLock::new(BullDag::new()));
+        let db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+
+        let mut sync_module = SyncModule::new(dag, events_tx, db.read_handle(), SyncMode::Full);
+
+        let peer_addr: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        let now = Instant::now();
+
+        for _ in 0..PEER_SYNC_REQUEST_LIMIT {
+            assert!(sync_module.admit_request(peer_addr, now));
+        }
+        assert!(!sync_module.admit_request(peer_addr, now));
+
+        // NOTE: every port of a host shares its limit
+        assert!(!sync_module.admit_request("127.0.0.1:9001".parse().unwrap(), now));
+        assert!(sync_module.admit_request("127.0.0.2:9000".parse().unwrap(), now));
+
+        let later = now + PEER_SYNC_REQUEST_WINDOW;
+        assert!(sync_module.admit_request(peer_addr, later));
+        assert_eq!(sync_module.peer_windows.len(), 1);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn anchors_at_a_snapshot_only_once_it_was_loaded() {
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+        let dag: Arc<RwLock<BullDag<Block, String>>> = Arc::new(RwLock::new(BullDag::new()));
+        let db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+
+        let mut sync_module = SyncModule::new(dag, events_tx, db.read_handle(), SyncMode::Fast);
+
+        let peer_addr: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        let block_hash: BlockHash = "snapshot-block".to_string();
+
+        sync_module.snapshot_peer = Some(peer_addr);
+        sync_module.loading_snapshot = Some(block_hash.clone());
+
+        sync_module
+            .handle_loaded_snapshot("other-block".to_string())
+            .await
+            .unwrap();
+        assert!(sync_module.anchor_hash.is_none());
+
+        sync_module
+            .handle_loaded_snapshot(block_hash.clone())
+            .await
+            .unwrap();
+        assert_eq!(sync_module.anchor_hash, Some(block_hash.clone()));
+
+        let event: Event = events_rx.recv().await.unwrap().into();
+        assert!(matches!(
+            event,
+            Event::SyncMessageCreated(
+                SyncMessage::BlocksRequested(BlockRequest::ByHash(hashes)),
+                Some(addr),
+            ) if hashes == vec![block_hash] && addr == peer_addr
+        ));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn rejected_snapshots_are_fetched_again_from_another_peer() {
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+        let dag: Arc<RwLock<BullDag<Block, String>>> = Arc::new(RwLock::new(BullDag::new()));
+        let db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+
+        let mut sync_module = SyncModule::new(dag, events_tx, db.read_handle(), SyncMode::Fast);
+
+        let peer_addr: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        let block_hash: BlockHash = "snapshot-block".to_string();
+
+        sync_module.snapshot_peer = Some(peer_addr);
+        sync_module.loading_snapshot = Some(block_hash.clone());
+
+        sync_module
+            .handle_rejected_snapshot(block_hash)
+            .await
+            .unwrap();
+
+        assert!(sync_module.anchor_hash.is_none());
+        assert!(sync_module.snapshot_peer.is_none());
+        assert!(sync_module.partial_snapshot.is_none());
+        assert!(sync_module.loading_snapshot.is_none());
+        assert!(sync_module.rejected_snapshot_peers.contains(&peer_addr));
+
+        let event: Event = events_rx.recv().await.unwrap().into();
+        assert!(matches!(
+            event,
+            Event::SyncMessageCreated(SyncMessage::BlocksRequested(BlockRequest::Latest), None)
+        ));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn follows_certified_harvester_hand_offs_from_the_genesis_quorum() {
+        let (events_tx, _) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
+        let dag: Arc<RwLock<BullDag<Block, String>>> = Arc::new(RwLock::new(BullDag::new()));
+        let db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+
+        let mut sync_module = SyncModule::new(dag, events_tx, db.read_handle(), SyncMode::Full);
+
+        let outgoing_sessions = completed_quorum_sessions(4);
+        let incoming_sessions = completed_quorum_sessions(4);
+
+        let sig_providers: Vec<SignatureProvider> = outgoing_sessions
+            .iter()
+            .map(|session| SignatureProvider {
+                dkg_state: Arc::new(RwLock::new(session.dkg_state().unwrap())),
+                quorum_config: session.threshold_config(),
+            })
+            .collect();
+
+        let genesis_pubkey = quorum_pubkey(&sig_providers[0]).unwrap();
+        let incoming_pubkey = hex::encode(
+            incoming_sessions[0]
+                .public_key_set()
+                .unwrap()
+                .public_key()
+                .to_bytes(),
+        );
+
+        let harvester_quorum_id = dkg_quorum_id(&QuorumKind::Harvester);
+        let mut collector = QuorumHandOffCollector::new(
+            harvester_quorum_id.clone(),
+            1,
+            incoming_pubkey.clone(),
+            sig_providers[0].clone(),
+        )
+        .unwrap();
+
+        let mut hand_off = None;
+        for (idx, sig_provider) in sig_providers.iter().enumerate() {
+            let signer = QuorumHandOffCollector::new(
+                harvester_quorum_id.clone(),
+                1,
+                incoming_pubkey.clone(),
+                sig_provider.clone(),
+            )
+            .unwrap();
+
+            hand_off = collector
+                .add_signature_share(idx as NodeIdx, signer.sign().unwrap())
+                .unwrap();
+
+            if hand_off.is_some() {
+                break;
+            }
+        }
+        let hand_off = hand_off.unwrap();
+
+        // NOTE: a hand-off that wasn't certified by the outgoing quorum is ignored
+        let mut forged_hand_off = hand_off.clone();
+        forged_hand_off.epoch = 2;
+        forged_hand_off.incoming_pubkey = genesis_pubkey.clone();
+
+        sync_module
+            .handle_provided_harvester_keys(genesis_pubkey.clone(), vec![forged_hand_off, hand_off])
+            .await
+            .unwrap();
+
+        assert_eq!(sync_module.harvester_pubkeys.len(), 2);
+        assert_eq!(
+            sync_module.harvester_pubkeys.get(&GENESIS_EPOCH),
+            Some(&genesis_pubkey)
+        );
+        assert_eq!(
+            sync_module.harvester_pubkeys.get(&1),
+            Some(&incoming_pubkey)
+        );
+        assert!(sync_module.harvester_pubkey(5).is_some());
+
+        // NOTE: only the first peer to provide the keys is trusted
+        sync_module
+            .handle_provided_harvester_keys(incoming_pubkey, vec![])
+            .await
+            .unwrap();
+        assert_eq!(
+            sync_module.harvester_pubkeys.get(&GENESIS_EPOCH),
+            Some(&genesis_pubkey)
+        );
+    }
+}
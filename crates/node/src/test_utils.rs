@@ -9,10 +9,20 @@ use std::{
 use async_trait::async_trait;
 use block::{Block, BlockHash, ClaimHash, GenesisBlock, InnerBlock, ProposalBlock};
 use bulldag::{graph::BullDag, vertex::Vertex};
+use events::PeerData;
 pub use miner::test_helpers::{create_address, create_claim, create_miner};
-use primitives::{generate_account_keypair, Address, NodeId, NodeType, RawSignature, Round};
+use primitives::{
+    generate_account_keypair,
+    Address,
+    KademliaPeerId,
+    NodeId,
+    NodeType,
+    RawSignature,
+    Round,
+};
 use secp256k1::{Message, PublicKey, SecretKey};
 use storage::vrrbdb::Claims;
+use utils::payload::digest_data_to_bytes;
 use uuid::Uuid;
 use vrrb_config::{NodeConfig, NodeConfigBuilder};
 use vrrb_core::{
@@ -250,6 +260,22 @@ pub fn produce_convergence_block(dag: Arc<RwLock<BullDag<Block, BlockHash>>>) ->
     None
 }
 
+/// Creates the data of a peer with the given id, listening on localhost and
+/// holding a random validator key.
+pub fn create_mock_peer_data(node_id: &str, node_type: NodeType) -> PeerData {
+    let addr: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+
+    PeerData {
+        node_id: node_id.to_string(),
+        node_type,
+        kademlia_peer_id: KademliaPeerId::try_from(digest_data_to_bytes(&node_id)).unwrap(),
+        udp_gossip_addr: addr,
+        raptorq_gossip_addr: addr,
+        kademlia_liveness_addr: addr,
+        validator_public_key: hbbft::crypto::SecretKey::random().public_key(),
+    }
+}
+
 pub fn create_keypair() -> (SecretKey, PublicKey) {
     let kp = Keypair::random();
     kp.miner_kp
@@ -323,6 +349,7 @@ pub(crate) fn create_blank_certificate(claim_signature: String) -> block::Certif
     block::Certificate {
         signature: claim_signature,
         inauguration: None,
+        hand_offs: None,
         root_hash: "".to_string(),
         next_root_hash: "".to_string(),
         block_hash: "".to_string(),
@@ -42,7 +42,7 @@ async fn process_full_node_event_flow() {
             .unwrap();
     }
 
-    let mempool_snapshot = client.get_full_mempool().await.unwrap();
+    let mempool_snapshot = client.get_full_mempool(None, None, None).await.unwrap();
 
     assert!(!mempool_snapshot.is_empty());
 
@@ -56,7 +56,7 @@ async fn node_can_join_network() {
     let client = create_client(addr).await.unwrap();
 
     assert!(client.is_connected());
-    let state = client.get_full_state().await;
+    let state = client.get_full_state(None, None, None, None).await;
 
     assert!(state.is_ok());
     assert_eq!(client.get_node_type().await.unwrap(), NodeType::Bootstrap);
@@ -54,7 +54,7 @@ async fn nodes_can_synchronize_state() {
             .unwrap();
     }
 
-    let mempool_snapshot = client_2.get_full_mempool().await.unwrap();
+    let mempool_snapshot = client_2.get_full_mempool(None, None, None).await.unwrap();
 
     assert!(!mempool_snapshot.is_empty());
     assert!(vrrb_node_1.stop().await.unwrap());
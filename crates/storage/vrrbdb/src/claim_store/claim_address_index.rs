@@ -0,0 +1,73 @@
+use std::{path::PathBuf, sync::Arc};
+
+use ethereum_types::U256;
+use primitives::Address;
+use rocksdb::WriteBatch;
+use storage_utils::{Result, StorageError};
+
+use crate::RocksDbAdapter;
+
+/// Size of a big endian encoded claim hash.
+const CLAIM_HASH_SIZE: usize = 32;
+
+/// Keeps the hashes of claims by an address related to them, e.g. the
+/// address owning them, so the claims of an address can be read without
+/// walking the claim trie. Entries are never removed, so readers check the
+/// claims they find still relate to the address.
+#[derive(Debug, Clone)]
+pub struct ClaimAddressIndex {
+    db: Arc<RocksDbAdapter>,
+}
+
+impl ClaimAddressIndex {
+    pub fn new(path: PathBuf, column_family: &str) -> Result<Self> {
+        let db = RocksDbAdapter::new(path, column_family)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Relates every given claim hash to the address it is paired with.
+    pub fn insert<'a>(
+        &self,
+        entries: impl IntoIterator<Item = (&'a Address, &'a U256)>,
+    ) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        for (address, claim_hash) in entries {
+            let mut key = address_prefix(address)?;
+            let mut encoded_hash = [0; CLAIM_HASH_SIZE];
+            claim_hash.to_big_endian(&mut encoded_hash);
+            key.extend_from_slice(&encoded_hash);
+
+            batch.put(key, []);
+        }
+
+        self.db.write(batch)
+    }
+
+    /// Returns the hashes of the claims related to the given address, in
+    /// hash order.
+    pub fn claim_hashes(&self, address: &Address) -> Result<Vec<U256>> {
+        let prefix = address_prefix(address)?;
+
+        Ok(self
+            .db
+            .entries_with_prefix(&prefix)?
+            .into_iter()
+            .filter_map(|(key, _)| {
+                let encoded_hash = key.get(prefix.len()..)?;
+                (encoded_hash.len() == CLAIM_HASH_SIZE).then(|| U256::from_big_endian(encoded_hash))
+            })
+            .collect())
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.db.iter_from(&[]).next().is_none())
+    }
+}
+
+/// Prefix of the keys of the claims related to the given address. Encoded
+/// addresses all have the same size, so no address's prefix is a prefix of
+/// another's.
+fn address_prefix(address: &Address) -> Result<Vec<u8>> {
+    bincode::serialize(address).map_err(|err| StorageError::Other(err.to_string()))
+}
@@ -1,21 +1,35 @@
 use std::collections::HashMap;
 
+use ethereum_types::U256;
 use lr_trie::{InnerTrieWrapper, ReadHandleFactory};
 use patriecia::inner::InnerTrie;
-use primitives::NodeId;
+use primitives::{Address, NodeId};
 use storage_utils::{Result, StorageError};
-use vrrb_core::claim::Claim;
+use vrrb_core::{claim::Claim, proof::MerkleProof};
 
-use crate::RocksDbAdapter;
+use crate::{ClaimAddressIndex, RocksDbAdapter, TrieKeyIndex};
 
 #[derive(Debug, Clone)]
 pub struct ClaimStoreReadHandle {
     inner: InnerTrieWrapper<RocksDbAdapter>,
+    keys: TrieKeyIndex,
+    owners: ClaimAddressIndex,
+    unbonding_stakers: ClaimAddressIndex,
 }
 
 impl ClaimStoreReadHandle {
-    pub fn new(inner: InnerTrieWrapper<RocksDbAdapter>) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: InnerTrieWrapper<RocksDbAdapter>,
+        keys: TrieKeyIndex,
+        owners: ClaimAddressIndex,
+        unbonding_stakers: ClaimAddressIndex,
+    ) -> Self {
+        Self {
+            inner,
+            keys,
+            owners,
+            unbonding_stakers,
+        }
     }
 
     /// Returns `Some(Claim)` if an account exist under given PublicKey.
@@ -26,6 +40,41 @@ impl ClaimStoreReadHandle {
             .map_err(|err| StorageError::Other(err.to_string()))
     }
 
+    /// Returns the claim stored under the given claim hash.
+    pub fn get_by_hash(&self, hash: &U256) -> Result<Claim> {
+        self.inner
+            .get(hash)
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Returns the claims owned by the given address, in claim hash order.
+    pub fn get_by_address(&self, address: &Address) -> Result<Vec<Claim>> {
+        Ok(self
+            .owners
+            .claim_hashes(address)?
+            .iter()
+            .filter_map(|hash| self.get_by_hash(hash).ok())
+            .filter(|claim| &claim.address == address)
+            .collect())
+    }
+
+    /// Returns the claims the given staker has stake unbonding from, in claim
+    /// hash order.
+    pub fn get_by_unbonding_staker(&self, staker: &Address) -> Result<Vec<Claim>> {
+        Ok(self
+            .unbonding_stakers
+            .claim_hashes(staker)?
+            .iter()
+            .filter_map(|hash| self.get_by_hash(hash).ok())
+            .filter(|claim| {
+                claim
+                    .get_unbonding()
+                    .iter()
+                    .any(|unbonding| &unbonding.staker == staker)
+            })
+            .collect())
+    }
+
     /// Get a batch of claims by providing Vec of PublicKeysHash
     ///
     /// Returns HashMap indexed by PublicKeys and containing either
@@ -56,6 +105,41 @@ impl ClaimStoreReadHandle {
             .collect()
     }
 
+    /// Returns up to `limit` claims, ordered by their key within the trie and
+    /// starting right after the `after` claim hash. The claims are read from
+    /// the cursor on through the trie's key index.
+    pub fn page(&self, after: Option<&U256>, limit: usize) -> Vec<(U256, Claim)> {
+        let after = after.and_then(|after| bincode::serialize(after).ok());
+
+        self.keys
+            .keys_after(after.as_deref())
+            .map_while(|key| key.ok())
+            .filter_map(|key| {
+                let key: U256 = bincode::deserialize(&key).ok()?;
+                let value: Claim = self.inner.get(&key).ok()?;
+
+                Some((key, value))
+            })
+            .take(limit)
+            .collect()
+    }
+
+    /// Returns a Merkle proof of the claim stored under the claim hash `key`, generated
+    /// against the current root hash of the trie.
+    pub fn get_proof(&self, key: &U256) -> Result<MerkleProof> {
+        let mut inner = self.inner.clone();
+
+        let root_hash = inner
+            .root_hash()
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        let nodes = inner
+            .get_proof::<U256, Claim>(key)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        MerkleProof::new(key, nodes, root_hash).map_err(|err| StorageError::Other(err.to_string()))
+    }
+
     /// Returns a number of initialized claims in the database
     pub fn len(&self) -> usize {
         self.inner.len()
@@ -70,11 +154,24 @@ impl ClaimStoreReadHandle {
 #[derive(Debug, Clone)]
 pub struct ClaimStoreReadHandleFactory {
     inner: ReadHandleFactory<InnerTrie<RocksDbAdapter>>,
+    keys: TrieKeyIndex,
+    owners: ClaimAddressIndex,
+    unbonding_stakers: ClaimAddressIndex,
 }
 
 impl ClaimStoreReadHandleFactory {
-    pub fn new(inner: ReadHandleFactory<InnerTrie<RocksDbAdapter>>) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: ReadHandleFactory<InnerTrie<RocksDbAdapter>>,
+        keys: TrieKeyIndex,
+        owners: ClaimAddressIndex,
+        unbonding_stakers: ClaimAddressIndex,
+    ) -> Self {
+        Self {
+            inner,
+            keys,
+            owners,
+            unbonding_stakers,
+        }
     }
 
     pub fn handle(&self) -> ClaimStoreReadHandle {
@@ -87,6 +184,11 @@ impl ClaimStoreReadHandleFactory {
 
         let inner = InnerTrieWrapper::new(handle);
 
-        ClaimStoreReadHandle { inner }
+        ClaimStoreReadHandle {
+            inner,
+            keys: self.keys.clone(),
+            owners: self.owners.clone(),
+            unbonding_stakers: self.unbonding_stakers.clone(),
+        }
     }
 }
@@ -3,11 +3,13 @@ use std::{path::Path, sync::Arc};
 use ethereum_types::U256;
 use lr_trie::{LeftRightTrie, H256};
 use storage_utils::{Result, StorageError};
-use vrrb_core::claim::Claim;
+use vrrb_core::{claim::Claim, proof::MerkleProof};
 
-use crate::RocksDbAdapter;
+use crate::{RocksDbAdapter, TrieKeyIndex};
 
+mod claim_address_index;
 mod claim_store_rh;
+pub use claim_address_index::*;
 pub use claim_store_rh::*;
 
 pub type Claims = Vec<Claim>;
@@ -16,38 +18,80 @@ pub type FailedClaimUpdates = Vec<(U256, Claims, Result<()>)>;
 #[derive(Debug, Clone)]
 pub struct ClaimStore {
     trie: LeftRightTrie<'static, U256, Claim, RocksDbAdapter>,
+    /// Hashes of the claims within the trie, used to page through them.
+    keys: TrieKeyIndex,
+    /// Hashes of the claims within the trie by the address owning them
+    owners: ClaimAddressIndex,
+    /// Hashes of the claims within the trie by the addresses with stake
+    /// unbonding from them
+    unbonding_stakers: ClaimAddressIndex,
 }
 
-impl Default for ClaimStore {
-    fn default() -> Self {
-        let db_path = storage_utils::get_node_data_dir()
-            .unwrap_or_default()
-            .join("db")
-            .join("claim");
+impl ClaimStore {
+    /// Opens the claim store located at `path`, creating it if it does not
+    /// exist yet.
+    pub fn new(path: &Path) -> Result<Self> {
+        let db_adapter = RocksDbAdapter::new(path.join("claims"), "claim")?;
+        let trie = LeftRightTrie::new(Arc::new(db_adapter));
+        let keys = TrieKeyIndex::new(path.join("claim_keys"), "claim_keys")?;
+        let owners = ClaimAddressIndex::new(path.join("claim_owners"), "claim_owners")?;
+        let unbonding_stakers = ClaimAddressIndex::new(
+            path.join("claim_unbonding_stakers"),
+            "claim_unbonding_stakers",
+        )?;
+
+        // NOTE: stores created before the key index existed get it built from
+        // the keys within the trie
+        if keys.is_empty()? && !trie.is_empty() {
+            let handle = trie.handle();
+            keys.insert_encoded(handle.iter().map(|(key, _)| key))?;
+        }
 
-        let db_adapter = RocksDbAdapter::new(db_path, "claim").unwrap_or_default();
+        let store = Self {
+            trie,
+            keys,
+            owners,
+            unbonding_stakers,
+        };
 
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
+        // NOTE: the same goes for the address indexes and the claims within
+        // the trie
+        if store.owners.is_empty()? && !store.trie.is_empty() {
+            let handle = store.trie.handle();
+            for (_, value) in handle.iter() {
+                let claim: Claim = bincode::deserialize(&value)
+                    .map_err(|err| StorageError::Other(err.to_string()))?;
+
+                store.index_addresses(&claim)?;
+            }
+        }
 
-        Self { trie }
+        Ok(store)
     }
-}
 
-impl ClaimStore {
-    /// Returns new, empty instance of ClaimDb
-    pub fn new(path: &Path) -> Self {
-        let path = path.join("claims");
-        let db_adapter = RocksDbAdapter::new(path, "claim").unwrap_or_default();
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
+    /// Indexes the claim by the address owning it and by the stakers with
+    /// stake unbonding from it.
+    fn index_addresses(&self, claim: &Claim) -> Result<()> {
+        self.owners.insert([(&claim.address, &claim.hash)])?;
 
-        Self { trie }
+        let unbonding = claim.get_unbonding();
+        self.unbonding_stakers.insert(
+            unbonding
+                .iter()
+                .map(|unbonding| (&unbonding.staker, &claim.hash)),
+        )
     }
 
     /// Returns new ReadHandle to the VrrDb data. As long as the returned value
     /// lives, no write to the database will be committed.
     pub fn read_handle(&self) -> ClaimStoreReadHandle {
         let inner = self.trie.handle();
-        ClaimStoreReadHandle::new(inner)
+        ClaimStoreReadHandle::new(
+            inner,
+            self.keys.clone(),
+            self.owners.clone(),
+            self.unbonding_stakers.clone(),
+        )
     }
 
     /// Commits uncommitted changes to the underlying trie by calling
@@ -70,6 +114,8 @@ impl ClaimStore {
         //            ));
         //        }
 
+        self.keys.insert([&claim.hash])?;
+        self.index_addresses(&claim)?;
         self.trie.insert(claim.hash, claim);
 
         Ok(())
@@ -273,13 +319,58 @@ impl ClaimStore {
         self.trie.root()
     }
 
+    /// Returns a Merkle proof of the claim stored under `key`.
+    pub fn get_proof(&self, key: &U256) -> Result<MerkleProof> {
+        self.read_handle().get_proof(key)
+    }
+
+    /// Verifies `proof` against the current root hash of the store and
+    /// returns the proven claim, if any.
+    pub fn verify_proof(&self, proof: &MerkleProof) -> Result<Option<Claim>> {
+        let root_hash = self
+            .root_hash()
+            .ok_or_else(|| StorageError::Other("claim trie has no root hash".to_string()))?;
+
+        let key: U256 = bincode::deserialize(&proof.key)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        let value = self
+            .trie
+            .verify_proof(root_hash, &key, proof.nodes.clone())
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        value
+            .map(|value| {
+                bincode::deserialize(&value).map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .transpose()
+    }
+
     pub fn extend(&mut self, claims: Vec<(U256, Claim)>) {
+        if let Err(err) = self.keys.insert(claims.iter().map(|(key, _)| key)) {
+            telemetry::error!("failed to index the keys of the claim trie: {err}");
+        }
+
+        for (_, claim) in claims.iter() {
+            if let Err(err) = self.index_addresses(claim) {
+                telemetry::error!(
+                    "failed to index the addresses of claim {}: {err}",
+                    claim.hash
+                );
+            }
+        }
+
         self.trie.extend(claims)
     }
 
     pub fn factory(&self) -> ClaimStoreReadHandleFactory {
         let inner = self.trie.factory();
 
-        ClaimStoreReadHandleFactory::new(inner)
+        ClaimStoreReadHandleFactory::new(
+            inner,
+            self.keys.clone(),
+            self.owners.clone(),
+            self.unbonding_stakers.clone(),
+        )
     }
 }
@@ -1 +1,263 @@
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
 
+use events::Event;
+use rocksdb::{ColumnFamily, Direction, IteratorMode, WriteBatch, DB};
+use serde::{Deserialize, Serialize};
+use storage_utils::{Result, StorageError};
+
+use crate::rocksdb_adapter::{base_db_options, new_db_instance};
+
+pub const EVENT_STORE_COLUMN_FAMILY: &str = "events";
+/// Column family indexing the sequence numbers of the stored events by
+/// variant name.
+pub const EVENT_VARIANT_INDEX_COLUMN_FAMILY: &str = "events_by_variant";
+
+pub type EventSequence = u64;
+
+/// An event as persisted by the [EventStore].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredEvent {
+    /// Position of the event within the store, starting at 0.
+    pub sequence: EventSequence,
+    /// Time the event was stored at, in milliseconds since the unix epoch.
+    pub timestamp: i64,
+    /// Name of the event's variant, e.g. `TxnAddedToMempool`.
+    pub variant: String,
+    pub event: Event,
+}
+
+/// Append-only log of every event processed by a node, kept within its own
+/// RocksDB column family so it survives restarts. A second column family
+/// indexes the events by variant.
+#[derive(Debug, Clone)]
+pub struct EventStore {
+    db: Arc<DB>,
+    /// Sequence number of the next event to be stored. Only advanced once an
+    /// event was written, so failed writes don't leave gaps.
+    next_sequence: Arc<Mutex<EventSequence>>,
+}
+
+impl EventStore {
+    /// Opens the event store located at `path`, creating it if it does not
+    /// exist yet. Appends continue after the last stored event.
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.join("events");
+
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let mut db = new_db_instance(options, path, EVENT_STORE_COLUMN_FAMILY)?;
+
+        let has_variant_index = db.cf_handle(EVENT_VARIANT_INDEX_COLUMN_FAMILY).is_some();
+        if !has_variant_index {
+            db.create_cf(EVENT_VARIANT_INDEX_COLUMN_FAMILY, &base_db_options())
+                .map_err(|err| StorageError::Other(err.to_string()))?;
+        }
+
+        let store = Self {
+            db: Arc::new(db),
+            next_sequence: Arc::new(Mutex::new(0)),
+        };
+
+        // NOTE: stores created before the index existed get it built from
+        // the events they hold
+        if !has_variant_index {
+            store.rebuild_variant_index()?;
+        }
+
+        let next_sequence = store.last_sequence()?.map_or(0, |last| last + 1);
+        *store.lock_next_sequence()? = next_sequence;
+
+        Ok(store)
+    }
+
+    fn column_family(&self) -> Result<&ColumnFamily> {
+        self.db
+            .cf_handle(EVENT_STORE_COLUMN_FAMILY)
+            .ok_or_else(|| StorageError::Other("event store column family is missing".to_string()))
+    }
+
+    fn variant_index(&self) -> Result<&ColumnFamily> {
+        self.db
+            .cf_handle(EVENT_VARIANT_INDEX_COLUMN_FAMILY)
+            .ok_or_else(|| StorageError::Other("event variant index is missing".to_string()))
+    }
+
+    fn lock_next_sequence(&self) -> Result<std::sync::MutexGuard<'_, EventSequence>> {
+        self.next_sequence
+            .lock()
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Persists an event under the next sequence number, along with its
+    /// entry in the variant index.
+    pub fn append(&self, event: Event) -> Result<StoredEvent> {
+        let mut next_sequence = self.lock_next_sequence()?;
+        let sequence = *next_sequence;
+
+        let stored_event = StoredEvent {
+            sequence,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            variant: event.variant_name().to_string(),
+            event,
+        };
+
+        let value = serde_json::to_vec(&stored_event)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        let mut batch = WriteBatch::default();
+        batch.put_cf(self.column_family()?, sequence.to_be_bytes(), value);
+        batch.put_cf(
+            self.variant_index()?,
+            variant_index_key(&stored_event.variant, sequence),
+            [],
+        );
+
+        self.db
+            .write(batch)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        *next_sequence += 1;
+
+        Ok(stored_event)
+    }
+
+    /// Indexes every stored event by variant.
+    fn rebuild_variant_index(&self) -> Result<()> {
+        let mut batch = WriteBatch::default();
+
+        for stored_event in self.iter_from(0)? {
+            batch.put_cf(
+                self.variant_index()?,
+                variant_index_key(&stored_event.variant, stored_event.sequence),
+                [],
+            );
+        }
+
+        self.db
+            .write(batch)
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Returns the event stored under the given sequence number.
+    pub fn get(&self, sequence: EventSequence) -> Result<StoredEvent> {
+        let value = self
+            .db
+            .get_cf(self.column_family()?, sequence.to_be_bytes())
+            .map_err(|err| StorageError::Other(err.to_string()))?
+            .ok_or_else(|| StorageError::NotFound(sequence.to_string()))?;
+
+        serde_json::from_slice(&value).map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Returns the sequence number of the most recently stored event.
+    pub fn last_sequence(&self) -> Result<Option<EventSequence>> {
+        let last = self
+            .db
+            .iterator_cf(self.column_family()?, IteratorMode::End)
+            .next()
+            .transpose()
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        Ok(last.and_then(|(key, _)| decode_sequence(&key)))
+    }
+
+    /// Iterates over the stored events in order, starting at sequence number
+    /// `from`.
+    pub fn iter_from(&self, from: EventSequence) -> Result<impl Iterator<Item = StoredEvent> + '_> {
+        let start = from.to_be_bytes();
+        let iter = self.db.iterator_cf(
+            self.column_family()?,
+            IteratorMode::From(&start, Direction::Forward),
+        );
+
+        Ok(iter.filter_map(|entry| {
+            let (_, value) = entry.ok()?;
+            serde_json::from_slice(&value).ok()
+        }))
+    }
+
+    /// Returns the events with sequence numbers within `from..to`.
+    pub fn range(&self, from: EventSequence, to: EventSequence) -> Result<Vec<StoredEvent>> {
+        Ok(self
+            .iter_from(from)?
+            .take_while(|stored_event| stored_event.sequence < to)
+            .collect())
+    }
+
+    /// Returns up to `limit` events of the given variant, starting at sequence
+    /// number `from`. Only the events of the variant are read, through the
+    /// variant index.
+    pub fn by_variant(
+        &self,
+        variant: &str,
+        from: EventSequence,
+        limit: usize,
+    ) -> Result<Vec<StoredEvent>> {
+        let prefix = variant_index_prefix(variant);
+        let start = variant_index_key(variant, from);
+
+        let iter = self.db.iterator_cf(
+            self.variant_index()?,
+            IteratorMode::From(&start, Direction::Forward),
+        );
+
+        let mut stored_events = vec![];
+
+        for entry in iter {
+            if stored_events.len() >= limit {
+                break;
+            }
+
+            let (key, _) = entry.map_err(|err| StorageError::Other(err.to_string()))?;
+
+            let sequence = match key.strip_prefix(prefix.as_slice()) {
+                Some(sequence) => decode_sequence(sequence),
+                None => break,
+            };
+
+            if let Some(sequence) = sequence {
+                stored_events.push(self.get(sequence)?);
+            }
+        }
+
+        Ok(stored_events)
+    }
+
+    /// Returns the number of stored events.
+    pub fn len(&self) -> usize {
+        self.lock_next_sequence()
+            .map(|next_sequence| *next_sequence as usize)
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn decode_sequence(key: &[u8]) -> Option<EventSequence> {
+    let bytes: [u8; 8] = key.try_into().ok()?;
+    Some(EventSequence::from_be_bytes(bytes))
+}
+
+/// Prefix of the variant index keys of the given variant. Variant names are
+/// terminated by a zero byte so no variant's prefix is a prefix of another's.
+fn variant_index_prefix(variant: &str) -> Vec<u8> {
+    let mut prefix = variant.as_bytes().to_vec();
+    prefix.push(0);
+    prefix
+}
+
+/// Variant index key of the event of the given variant and sequence number,
+/// ordered by sequence number within the variant.
+fn variant_index_key(variant: &str, sequence: EventSequence) -> Vec<u8> {
+    let mut key = variant_index_prefix(variant);
+    key.extend_from_slice(&sequence.to_be_bytes());
+    key
+}
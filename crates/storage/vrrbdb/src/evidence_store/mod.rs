@@ -0,0 +1,139 @@
+use std::{path::Path, sync::Arc};
+
+use block::{BlockHash, Evidence, EvidenceHash};
+use rocksdb::{ColumnFamily, IteratorMode, DB};
+use serde::{Deserialize, Serialize};
+use storage_utils::{Result, StorageError};
+
+use crate::rocksdb_adapter::{base_db_options, new_db_instance};
+
+pub const EVIDENCE_STORE_COLUMN_FAMILY: &str = "evidence";
+
+/// Certified evidence of misbehaviour, along with the convergence block that
+/// included it, if any.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvidenceRecord {
+    pub evidence: Evidence,
+    pub included_in: Option<BlockHash>,
+}
+
+/// Keeps the evidence of misbehaviour certified by harvester quorums, keyed
+/// by evidence hash, until it is included in a convergence block and the
+/// offender's claim is slashed.
+#[derive(Debug, Clone)]
+pub struct EvidenceStore {
+    db: Arc<DB>,
+}
+
+impl EvidenceStore {
+    /// Opens the evidence store located at `path`, creating it if it does
+    /// not exist yet.
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.join("evidence");
+
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = new_db_instance(options, path, EVIDENCE_STORE_COLUMN_FAMILY)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    fn column_family(&self) -> Result<&ColumnFamily> {
+        self.db
+            .cf_handle(EVIDENCE_STORE_COLUMN_FAMILY)
+            .ok_or_else(|| {
+                StorageError::Other("evidence store column family is missing".to_string())
+            })
+    }
+
+    fn put(&self, record: &EvidenceRecord) -> Result<()> {
+        let value =
+            bincode::serialize(record).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.db
+            .put_cf(
+                self.column_family()?,
+                record.evidence.hash().as_bytes(),
+                value,
+            )
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Stores the given evidence, unless it was stored already. Returns
+    /// whether it was new.
+    pub fn insert(&self, evidence: Evidence) -> Result<bool> {
+        match self.get(&evidence.hash()) {
+            Ok(_) => return Ok(false),
+            Err(StorageError::NotFound(_)) => {},
+            Err(err) => return Err(err),
+        }
+
+        self.put(&EvidenceRecord {
+            evidence,
+            included_in: None,
+        })?;
+
+        Ok(true)
+    }
+
+    /// Records that the given evidence was included in the given block,
+    /// storing the evidence if needed. Returns whether it had not been
+    /// included in a block before.
+    pub fn mark_included(&self, evidence: Evidence, block_hash: &BlockHash) -> Result<bool> {
+        let record = match self.get(&evidence.hash()) {
+            Ok(record) => record,
+            Err(StorageError::NotFound(_)) => EvidenceRecord {
+                evidence,
+                included_in: None,
+            },
+            Err(err) => return Err(err),
+        };
+
+        if record.included_in.is_some() {
+            return Ok(false);
+        }
+
+        self.put(&EvidenceRecord {
+            included_in: Some(block_hash.clone()),
+            ..record
+        })?;
+
+        Ok(true)
+    }
+
+    /// Returns the record of the evidence with the given hash.
+    pub fn get(&self, evidence_hash: &EvidenceHash) -> Result<EvidenceRecord> {
+        let value = self
+            .db
+            .get_cf(self.column_family()?, evidence_hash.as_bytes())
+            .map_err(|err| StorageError::Other(err.to_string()))?
+            .ok_or_else(|| StorageError::NotFound(evidence_hash.to_string()))?;
+
+        bincode::deserialize(&value).map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Returns every stored evidence record.
+    pub fn entries(&self) -> Result<Vec<EvidenceRecord>> {
+        self.db
+            .iterator_cf(self.column_family()?, IteratorMode::Start)
+            .map(|entry| {
+                let (_, value) = entry.map_err(|err| StorageError::Other(err.to_string()))?;
+
+                bincode::deserialize(&value).map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .collect()
+    }
+
+    /// Returns the evidence that was not included in a block yet.
+    pub fn pending(&self) -> Result<Vec<Evidence>> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .filter(|record| record.included_in.is_none())
+            .map(|record| record.evidence)
+            .collect())
+    }
+}
@@ -1,18 +1,30 @@
 mod claim_store;
 mod event_store;
+mod evidence_store;
+mod receipt_store;
+mod reputation_store;
+mod reward_store;
 pub mod result;
 mod rocksdb_adapter;
+mod state_history_store;
 mod state_store;
 mod transaction_store;
+mod trie_key_index;
 mod vrrbdb;
 mod vrrbdb_read_handle;
 mod vrrbdb_serialized_values;
 
 pub use claim_store::*;
 pub use event_store::*;
+pub use evidence_store::*;
+pub use receipt_store::*;
+pub use reputation_store::*;
+pub use reward_store::*;
 pub use rocksdb_adapter::*;
+pub use state_history_store::*;
 pub use state_store::*;
 pub use transaction_store::*;
+pub use trie_key_index::*;
 pub use vrrbdb_read_handle::*;
 pub use vrrbdb_serialized_values::*;
 
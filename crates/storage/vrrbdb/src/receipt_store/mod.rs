@@ -0,0 +1,88 @@
+use std::{path::Path, sync::Arc};
+
+use rocksdb::{ColumnFamily, WriteBatch, DB};
+use storage_utils::{Result, StorageError};
+use vrrb_core::{receipt::TxnReceipt, txn::TransactionDigest};
+
+use crate::rocksdb_adapter::{base_db_options, new_db_instance};
+
+pub const RECEIPT_STORE_COLUMN_FAMILY: &str = "receipts";
+
+/// Keeps the receipt of every transaction proposed within a committed
+/// `ConvergenceBlock`, keyed by the transaction's digest.
+#[derive(Debug, Clone)]
+pub struct ReceiptStore {
+    db: Arc<DB>,
+}
+
+impl ReceiptStore {
+    /// Opens the receipt store located at `path`, creating it if it does not
+    /// exist yet.
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.join("receipts");
+
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = new_db_instance(options, path, RECEIPT_STORE_COLUMN_FAMILY)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    fn column_family(&self) -> Result<&ColumnFamily> {
+        self.db
+            .cf_handle(RECEIPT_STORE_COLUMN_FAMILY)
+            .ok_or_else(|| {
+                StorageError::Other("receipt store column family is missing".to_string())
+            })
+    }
+
+    /// Stores a receipt, replacing any previous receipt of the same
+    /// transaction.
+    pub fn insert(&self, receipt: &TxnReceipt) -> Result<()> {
+        let value = encode_receipt(receipt)?;
+
+        self.db
+            .put_cf(self.column_family()?, receipt_key(&receipt.txn_id)?, value)
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Stores multiple receipts within a single atomic write.
+    pub fn extend(&self, receipts: &[TxnReceipt]) -> Result<()> {
+        let column_family = self.column_family()?;
+        let mut batch = WriteBatch::default();
+
+        for receipt in receipts {
+            batch.put_cf(
+                column_family,
+                receipt_key(&receipt.txn_id)?,
+                encode_receipt(receipt)?,
+            );
+        }
+
+        self.db
+            .write(batch)
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Returns the receipt of the given transaction.
+    pub fn get(&self, txn_id: &TransactionDigest) -> Result<TxnReceipt> {
+        let value = self
+            .db
+            .get_cf(self.column_family()?, receipt_key(txn_id)?)
+            .map_err(|err| StorageError::Other(err.to_string()))?
+            .ok_or_else(|| StorageError::NotFound(txn_id.to_string()))?;
+
+        bincode::deserialize(&value).map_err(|err| StorageError::Other(err.to_string()))
+    }
+}
+
+fn receipt_key(txn_id: &TransactionDigest) -> Result<Vec<u8>> {
+    bincode::serialize(txn_id).map_err(|err| StorageError::Other(err.to_string()))
+}
+
+fn encode_receipt(receipt: &TxnReceipt) -> Result<Vec<u8>> {
+    bincode::serialize(receipt).map_err(|err| StorageError::Other(err.to_string()))
+}
@@ -0,0 +1,91 @@
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use primitives::NodeId;
+use rocksdb::{ColumnFamily, IteratorMode, DB};
+use storage_utils::{Result, StorageError};
+use vrrb_core::reputation::{PeerBehaviour, PeerReputation};
+
+use crate::rocksdb_adapter::{base_db_options, new_db_instance};
+
+pub const REPUTATION_STORE_COLUMN_FAMILY: &str = "reputations";
+
+/// Keeps the reputation of every peer observed while taking part in
+/// consensus, keyed by the peer's `NodeId`.
+#[derive(Debug, Clone)]
+pub struct ReputationStore {
+    db: Arc<DB>,
+}
+
+impl ReputationStore {
+    /// Opens the reputation store located at `path`, creating it if it does
+    /// not exist yet.
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.join("reputations");
+
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = new_db_instance(options, path, REPUTATION_STORE_COLUMN_FAMILY)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    fn column_family(&self) -> Result<&ColumnFamily> {
+        self.db
+            .cf_handle(REPUTATION_STORE_COLUMN_FAMILY)
+            .ok_or_else(|| {
+                StorageError::Other("reputation store column family is missing".to_string())
+            })
+    }
+
+    /// Records a behaviour observed from the given peer and returns its
+    /// updated reputation.
+    pub fn record(&self, node_id: &NodeId, behaviour: PeerBehaviour) -> Result<PeerReputation> {
+        let mut reputation = match self.get(node_id) {
+            Ok(reputation) => reputation,
+            Err(StorageError::NotFound(_)) => PeerReputation::default(),
+            Err(err) => return Err(err),
+        };
+
+        reputation.record(behaviour);
+
+        let value =
+            bincode::serialize(&reputation).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.db
+            .put_cf(self.column_family()?, node_id.as_bytes(), value)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        Ok(reputation)
+    }
+
+    /// Returns the reputation of the given peer.
+    pub fn get(&self, node_id: &NodeId) -> Result<PeerReputation> {
+        let value = self
+            .db
+            .get_cf(self.column_family()?, node_id.as_bytes())
+            .map_err(|err| StorageError::Other(err.to_string()))?
+            .ok_or_else(|| StorageError::NotFound(node_id.to_string()))?;
+
+        bincode::deserialize(&value).map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Returns the reputation of every peer observed so far.
+    pub fn entries(&self) -> Result<HashMap<NodeId, PeerReputation>> {
+        self.db
+            .iterator_cf(self.column_family()?, IteratorMode::Start)
+            .map(|entry| {
+                let (key, value) = entry.map_err(|err| StorageError::Other(err.to_string()))?;
+
+                let node_id = String::from_utf8(key.to_vec())
+                    .map_err(|err| StorageError::Other(err.to_string()))?;
+                let reputation = bincode::deserialize(&value)
+                    .map_err(|err| StorageError::Other(err.to_string()))?;
+
+                Ok((node_id, reputation))
+            })
+            .collect()
+    }
+}
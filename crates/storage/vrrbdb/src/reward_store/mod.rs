@@ -0,0 +1,83 @@
+use std::{path::Path, sync::Arc};
+
+use primitives::Epoch;
+use rocksdb::{ColumnFamily, Direction, IteratorMode, DB};
+use storage_utils::{Result, StorageError};
+use vrrb_core::reward_credit::RewardCredit;
+
+use crate::rocksdb_adapter::{base_db_options, new_db_instance};
+
+pub const REWARD_STORE_COLUMN_FAMILY: &str = "rewards";
+
+/// Keeps the rewards credited when committing every `ConvergenceBlock`,
+/// keyed by epoch and block hash so they can be audited per epoch.
+#[derive(Debug, Clone)]
+pub struct RewardStore {
+    db: Arc<DB>,
+}
+
+impl RewardStore {
+    /// Opens the reward store located at `path`, creating it if it does not
+    /// exist yet.
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.join("rewards");
+
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = new_db_instance(options, path, REWARD_STORE_COLUMN_FAMILY)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    fn column_family(&self) -> Result<&ColumnFamily> {
+        self.db
+            .cf_handle(REWARD_STORE_COLUMN_FAMILY)
+            .ok_or_else(|| StorageError::Other("reward store column family is missing".to_string()))
+    }
+
+    /// Stores the rewards credited when committing the given block, replacing
+    /// any rewards previously stored for it.
+    pub fn insert(&self, epoch: Epoch, block_hash: &str, credits: &[RewardCredit]) -> Result<()> {
+        let value =
+            bincode::serialize(credits).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.db
+            .put_cf(self.column_family()?, reward_key(epoch, block_hash), value)
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Returns every reward credited within the given epoch.
+    pub fn epoch_credits(&self, epoch: Epoch) -> Result<Vec<RewardCredit>> {
+        let prefix = epoch.to_be_bytes();
+        let mut credits = Vec::new();
+
+        let entries = self.db.iterator_cf(
+            self.column_family()?,
+            IteratorMode::From(&prefix, Direction::Forward),
+        );
+
+        for entry in entries {
+            let (key, value) = entry.map_err(|err| StorageError::Other(err.to_string()))?;
+
+            if !key.starts_with(&prefix) {
+                break;
+            }
+
+            let block_credits: Vec<RewardCredit> =
+                bincode::deserialize(&value).map_err(|err| StorageError::Other(err.to_string()))?;
+
+            credits.extend(block_credits);
+        }
+
+        Ok(credits)
+    }
+}
+
+fn reward_key(epoch: Epoch, block_hash: &str) -> Vec<u8> {
+    let mut key = epoch.to_be_bytes().to_vec();
+    key.extend_from_slice(block_hash.as_bytes());
+    key
+}
@@ -1,6 +1,6 @@
 use patriecia::db::Database;
 use primitives::{get_vrrb_environment, Environment, DEFAULT_VRRB_DB_PATH};
-use rocksdb::{DB, DEFAULT_COLUMN_FAMILY_NAME};
+use rocksdb::{Direction, IteratorMode, WriteBatch, DB, DEFAULT_COLUMN_FAMILY_NAME};
 use storage_utils::{get_node_data_dir, StorageError};
 use telemetry::error;
 
@@ -10,7 +10,7 @@ pub struct RocksDbAdapter {
     column: String,
 }
 
-fn base_db_options() -> rocksdb::Options {
+pub(crate) fn base_db_options() -> rocksdb::Options {
     let mut options = rocksdb::Options::default();
 
     let environ = get_vrrb_environment();
@@ -35,7 +35,7 @@ fn base_db_options() -> rocksdb::Options {
     options
 }
 
-fn new_db_instance(
+pub(crate) fn new_db_instance(
     options: rocksdb::Options,
     path: std::path::PathBuf,
     column_family: &str,
@@ -79,6 +79,39 @@ impl RocksDbAdapter {
             column: column_family.to_string(),
         })
     }
+
+    /// Iterates over the entries in key order, starting at the given key.
+    pub(crate) fn iter_from(&self, from: &[u8]) -> rocksdb::DBIterator<'_> {
+        self.db
+            .iterator(IteratorMode::From(from, Direction::Forward))
+    }
+
+    /// Returns every entry whose key starts with the given prefix, in key
+    /// order.
+    pub(crate) fn entries_with_prefix(
+        &self,
+        prefix: &[u8],
+    ) -> storage_utils::Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let mut entries = vec![];
+
+        for entry in self.iter_from(prefix) {
+            let (key, value) = entry.map_err(|err| StorageError::Other(err.to_string()))?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+
+            entries.push((key.into(), value.into()));
+        }
+
+        Ok(entries)
+    }
+
+    /// Applies every write within the batch atomically.
+    pub(crate) fn write(&self, batch: WriteBatch) -> storage_utils::Result<()> {
+        self.db
+            .write(batch)
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
 }
 
 // TODO: handle these unwrap
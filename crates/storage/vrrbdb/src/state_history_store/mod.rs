@@ -0,0 +1,477 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    sync::Arc,
+};
+
+use lr_trie::H256;
+use patriecia::db::Database;
+use primitives::{Address, Epoch};
+use rocksdb::WriteBatch;
+use serde::{Deserialize, Serialize};
+use storage_utils::{Result, StorageError};
+use vrrb_core::account::Account;
+
+use crate::{AccountFilter, RocksDbAdapter};
+
+/// Number of records between two full snapshots of the state, which bounds
+/// how many records have to be replayed to rebuild the state at any block.
+pub const STATE_CHECKPOINT_INTERVAL: u64 = 64;
+
+const NEXT_INDEX_KEY: &[u8] = b"next_index";
+const BLOCK_KEY_PREFIX: &[u8] = b"block/";
+const RECORD_KEY_PREFIX: &[u8] = b"record/";
+const CHECKPOINT_KEY_PREFIX: &[u8] = b"checkpoint/";
+const CHECKPOINT_ACCOUNT_KEY_PREFIX: &[u8] = b"checkpoint_account/";
+const PENDING_KEY_PREFIX: &[u8] = b"pending/";
+
+/// The accounts written while committing a single `ConvergenceBlock`, along
+/// with the state root the block was committed with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateHistoryRecord {
+    /// Position of the block within the history, starting at 0.
+    pub index: u64,
+    /// Hash of the `ConvergenceBlock` the record was committed for.
+    pub block_hash: String,
+    /// Epoch of the `ConvergenceBlock` the record was committed for.
+    pub epoch: Epoch,
+    pub root_hash: Option<[u8; 32]>,
+    /// Every account written since the previous record, as it was once the
+    /// block was committed.
+    pub accounts: Vec<(Address, Account)>,
+}
+
+impl StateHistoryRecord {
+    pub fn root_hash(&self) -> Option<H256> {
+        self.root_hash.map(H256::from)
+    }
+}
+
+/// Keeps a per block diff of the state trie so that the state can be read as
+/// it was right after any committed block. Every `STATE_CHECKPOINT_INTERVAL`
+/// records the full state is stored as well, one entry per account, so reads
+/// only replay the records since the closest checkpoint.
+///
+/// The accounts written since the last record are tracked within the store
+/// too, so they survive restarts and are shared by every handle to it.
+#[derive(Debug, Clone)]
+pub struct StateHistoryStore {
+    db: Arc<RocksDbAdapter>,
+}
+
+impl StateHistoryStore {
+    /// Opens the state history located at `path`, creating it if it does not
+    /// exist yet.
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.join("state_history");
+        let db_adapter = RocksDbAdapter::new(path, "state_history")?;
+
+        Ok(Self {
+            db: Arc::new(db_adapter),
+        })
+    }
+
+    /// Returns the number of blocks recorded so far.
+    pub fn len(&self) -> Result<u64> {
+        let next_index = self
+            .db
+            .get(NEXT_INDEX_KEY)?
+            .map(|value| decode_index(&value))
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(next_index)
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Marks the given accounts as written since the last record, so they get
+    /// included in the next one.
+    pub fn mark_written<'a>(&self, addresses: impl IntoIterator<Item = &'a Address>) -> Result<()> {
+        let mut batch = WriteBatch::default();
+
+        for address in addresses {
+            let value =
+                bincode::serialize(address).map_err(|err| StorageError::Other(err.to_string()))?;
+
+            batch.put(pending_key(address), value);
+        }
+
+        self.db.write(batch)
+    }
+
+    /// Returns the accounts written since the last record.
+    pub fn pending_accounts(&self) -> Result<Vec<Address>> {
+        self.db
+            .entries_with_prefix(PENDING_KEY_PREFIX)?
+            .into_iter()
+            .map(|(_, value)| {
+                bincode::deserialize(&value).map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .collect()
+    }
+
+    /// Records the accounts written while committing the block with the given
+    /// hash and clears the accounts marked as written. Every block can only
+    /// be recorded once.
+    pub fn record(
+        &self,
+        block_hash: &str,
+        epoch: Epoch,
+        root_hash: Option<H256>,
+        accounts: Vec<(Address, Account)>,
+    ) -> Result<StateHistoryRecord> {
+        if self.index_of(block_hash)?.is_some() {
+            return Err(StorageError::RecordExists);
+        }
+
+        let index = self.len()?;
+
+        let record = StateHistoryRecord {
+            index,
+            block_hash: block_hash.to_string(),
+            epoch,
+            root_hash: root_hash.map(|root_hash| root_hash.0),
+            accounts,
+        };
+
+        let value =
+            bincode::serialize(&record).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        let mut batch = WriteBatch::default();
+        batch.put(record_key(index), value);
+        batch.put(block_key(block_hash), index.to_be_bytes());
+        batch.put(NEXT_INDEX_KEY, (index + 1).to_be_bytes());
+
+        if is_checkpoint(index) {
+            let mut state = match index.checked_sub(1) {
+                Some(previous) => self.state_at_index(previous)?,
+                None => HashMap::new(),
+            };
+            state.extend(record.accounts.iter().cloned());
+
+            for (address, account) in state {
+                let value = bincode::serialize(&account)
+                    .map_err(|err| StorageError::Other(err.to_string()))?;
+
+                batch.put(
+                    checkpoint_account_key(index, &encode_address(&address)?),
+                    value,
+                );
+            }
+
+            batch.put(checkpoint_key(index), []);
+        }
+
+        for (key, _) in self.db.entries_with_prefix(PENDING_KEY_PREFIX)? {
+            batch.delete(key);
+        }
+
+        self.db.write(batch)?;
+
+        Ok(record)
+    }
+
+    /// Returns the position of the given block within the history.
+    pub fn index_of(&self, block_hash: &str) -> Result<Option<u64>> {
+        self.db
+            .get(&block_key(block_hash))?
+            .map(|value| decode_index(&value))
+            .transpose()
+    }
+
+    /// Returns the record at the given position within the history.
+    pub fn get(&self, index: u64) -> Result<StateHistoryRecord> {
+        let value = self
+            .db
+            .get(&record_key(index))?
+            .ok_or_else(|| StorageError::NotFound(format!("state history record {index}")))?;
+
+        bincode::deserialize(&value).map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Returns the record of the most recently committed block, if any.
+    pub fn last(&self) -> Result<Option<StateHistoryRecord>> {
+        match self.len()? {
+            0 => Ok(None),
+            len => self.get(len - 1).map(Some),
+        }
+    }
+
+    /// Returns the record committed for the given block.
+    pub fn get_by_block_hash(&self, block_hash: &str) -> Result<StateHistoryRecord> {
+        let index = self
+            .index_of(block_hash)?
+            .ok_or_else(|| StorageError::NotFound(format!("block {block_hash}")))?;
+
+        self.get(index)
+    }
+
+    /// Returns the state root the given block was committed with.
+    pub fn root_at(&self, block_hash: &str) -> Result<Option<H256>> {
+        Ok(self.get_by_block_hash(block_hash)?.root_hash())
+    }
+
+    /// Rebuilds all accounts as they were right after the given block was
+    /// committed, from the closest checkpoint and the records since.
+    pub fn state_at(&self, block_hash: &str) -> Result<HashMap<Address, Account>> {
+        let index = self
+            .index_of(block_hash)?
+            .ok_or_else(|| StorageError::NotFound(format!("block {block_hash}")))?;
+
+        self.state_at_index(index)
+    }
+
+    fn state_at_index(&self, index: u64) -> Result<HashMap<Address, Account>> {
+        let (mut state, from) = match self.checkpoint_before(index)? {
+            Some(checkpoint_index) => (
+                self.checkpoint_accounts(checkpoint_index, None)
+                    .map(|entry| entry.map(|(_, address, account)| (address, account)))
+                    .collect::<Result<_>>()?,
+                checkpoint_index + 1,
+            ),
+            None => (HashMap::new(), 0),
+        };
+
+        for i in from..=index {
+            state.extend(self.get(i)?.accounts);
+        }
+
+        Ok(state)
+    }
+
+    /// Returns up to `limit` accounts matching `filter` as they were right
+    /// after the given block was committed, ordered by their key within the
+    /// state trie and starting right after the `after` address. The closest
+    /// checkpoint is read from the cursor on and merged with the accounts
+    /// written since.
+    pub fn page_at(
+        &self,
+        block_hash: &str,
+        after: Option<&Address>,
+        limit: usize,
+        filter: &AccountFilter,
+    ) -> Result<Vec<(Address, Account)>> {
+        let index = self
+            .index_of(block_hash)?
+            .ok_or_else(|| StorageError::NotFound(format!("block {block_hash}")))?;
+
+        let after = after.map(encode_address).transpose()?;
+
+        let checkpoint_index = self.checkpoint_before(index)?;
+        let from = checkpoint_index.map_or(0, |checkpoint_index| checkpoint_index + 1);
+
+        let mut written = BTreeMap::new();
+        for i in from..=index {
+            for (address, account) in self.get(i)?.accounts {
+                let key = encode_address(&address)?;
+                if after.as_ref().map_or(true, |after| &key > after) {
+                    written.insert(key, (address, account));
+                }
+            }
+        }
+
+        let mut written = written.into_iter().peekable();
+        let mut checkpointed = checkpoint_index
+            .map(|checkpoint_index| self.checkpoint_accounts(checkpoint_index, after.as_deref()))
+            .into_iter()
+            .flatten()
+            .peekable();
+
+        let mut page = vec![];
+        while page.len() < limit {
+            let take_written = match (checkpointed.peek(), written.peek()) {
+                (None, None) => break,
+                (Some(Ok((checkpointed_key, _, _))), Some((written_key, _))) => {
+                    written_key <= checkpointed_key
+                },
+                (Some(_), _) => false,
+                (None, Some(_)) => true,
+            };
+
+            let (address, account) = if take_written {
+                let (key, entry) = match written.next() {
+                    Some(written) => written,
+                    None => break,
+                };
+
+                // The accounts written since the checkpoint replace their
+                // checkpointed versions.
+                let replaces_checkpointed = matches!(
+                    checkpointed.peek(),
+                    Some(Ok((checkpointed_key, _, _))) if *checkpointed_key == key
+                );
+                if replaces_checkpointed {
+                    checkpointed.next();
+                }
+
+                entry
+            } else {
+                match checkpointed.next() {
+                    Some(entry) => {
+                        let (_, address, account) = entry?;
+                        (address, account)
+                    },
+                    None => break,
+                }
+            };
+
+            if filter.matches(&account) {
+                page.push((address, account));
+            }
+        }
+
+        Ok(page)
+    }
+
+    /// Returns an account as it was right after the given block was committed.
+    pub fn account_at(&self, block_hash: &str, address: &Address) -> Result<Account> {
+        let index = self
+            .index_of(block_hash)?
+            .ok_or_else(|| StorageError::NotFound(format!("block {block_hash}")))?;
+
+        let checkpoint_index = self.checkpoint_before(index)?;
+        let from = checkpoint_index.map_or(0, |checkpoint_index| checkpoint_index + 1);
+
+        // Walk the history backwards since the most recent write wins.
+        for i in (from..=index).rev() {
+            let record = self.get(i)?;
+            if let Some((_, account)) = record.accounts.into_iter().find(|(key, _)| key == address)
+            {
+                return Ok(account);
+            }
+        }
+
+        let checkpointed = match checkpoint_index {
+            Some(checkpoint_index) => self.db.get(&checkpoint_account_key(
+                checkpoint_index,
+                &encode_address(address)?,
+            ))?,
+            None => None,
+        };
+
+        checkpointed
+            .map(|value| {
+                bincode::deserialize(&value).map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .transpose()?
+            .ok_or_else(|| StorageError::NotFound(address.to_string()))
+    }
+
+    /// Returns the position of the most recent checkpoint taken at or before
+    /// the given position within the history.
+    fn checkpoint_before(&self, index: u64) -> Result<Option<u64>> {
+        let mut checkpoint_index = match last_checkpoint(index) {
+            Some(checkpoint_index) => checkpoint_index,
+            None => return Ok(None),
+        };
+
+        loop {
+            if self.db.get(&checkpoint_key(checkpoint_index))?.is_some() {
+                return Ok(Some(checkpoint_index));
+            }
+
+            // NOTE: histories recorded before checkpoints were introduced
+            // don't have them, so older ones are looked up instead
+            match checkpoint_index.checked_sub(STATE_CHECKPOINT_INTERVAL) {
+                Some(previous) => checkpoint_index = previous,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Iterates over the accounts of the given checkpoint in key order,
+    /// starting right after the `after` encoded address, along with their
+    /// encoded addresses.
+    fn checkpoint_accounts<'a>(
+        &'a self,
+        checkpoint_index: u64,
+        after: Option<&[u8]>,
+    ) -> impl Iterator<Item = Result<(Vec<u8>, Address, Account)>> + 'a {
+        let prefix = checkpoint_account_key(checkpoint_index, &[]);
+        let start = checkpoint_account_key(checkpoint_index, after.unwrap_or_default());
+        let skip_start = after.is_some();
+
+        self.db
+            .iter_from(&start)
+            .skip_while(move |entry| {
+                skip_start && matches!(entry, Ok((key, _)) if key.as_ref() == start.as_slice())
+            })
+            .map_while(move |entry| {
+                let (key, value) = match entry {
+                    Ok(entry) => entry,
+                    Err(err) => return Some(Err(StorageError::Other(err.to_string()))),
+                };
+
+                let encoded_address = key.strip_prefix(prefix.as_slice())?.to_vec();
+
+                Some(decode_checkpoint_account(encoded_address, &value))
+            })
+    }
+}
+
+/// Returns whether a full snapshot of the state is stored along with the
+/// record at the given position.
+fn is_checkpoint(index: u64) -> bool {
+    (index + 1) % STATE_CHECKPOINT_INTERVAL == 0
+}
+
+/// Returns the position of the last checkpoint at or before `index`.
+fn last_checkpoint(index: u64) -> Option<u64> {
+    ((index + 1) / STATE_CHECKPOINT_INTERVAL)
+        .checked_mul(STATE_CHECKPOINT_INTERVAL)?
+        .checked_sub(1)
+}
+
+fn block_key(block_hash: &str) -> Vec<u8> {
+    [BLOCK_KEY_PREFIX, block_hash.as_bytes()].concat()
+}
+
+fn record_key(index: u64) -> Vec<u8> {
+    [RECORD_KEY_PREFIX, &index.to_be_bytes()].concat()
+}
+
+fn checkpoint_key(index: u64) -> Vec<u8> {
+    [CHECKPOINT_KEY_PREFIX, &index.to_be_bytes()].concat()
+}
+
+fn checkpoint_account_key(index: u64, encoded_address: &[u8]) -> Vec<u8> {
+    [
+        CHECKPOINT_ACCOUNT_KEY_PREFIX,
+        &index.to_be_bytes(),
+        encoded_address,
+    ]
+    .concat()
+}
+
+/// Encodes an address the way the state trie encodes its keys, so accounts
+/// are ordered the same way.
+fn encode_address(address: &Address) -> Result<Vec<u8>> {
+    bincode::serialize(address).map_err(|err| StorageError::Other(err.to_string()))
+}
+
+fn decode_checkpoint_account(
+    encoded_address: Vec<u8>,
+    value: &[u8],
+) -> Result<(Vec<u8>, Address, Account)> {
+    let address = bincode::deserialize(&encoded_address)
+        .map_err(|err| StorageError::Other(err.to_string()))?;
+    let account =
+        bincode::deserialize(value).map_err(|err| StorageError::Other(err.to_string()))?;
+
+    Ok((encoded_address, address, account))
+}
+
+fn pending_key(address: &Address) -> Vec<u8> {
+    [PENDING_KEY_PREFIX, address.to_string().as_bytes()].concat()
+}
+
+fn decode_index(value: &[u8]) -> Result<u64> {
+    let bytes: [u8; 8] = value
+        .try_into()
+        .map_err(|_| StorageError::Other("invalid state history index".to_string()))?;
+
+    Ok(u64::from_be_bytes(bytes))
+}
@@ -3,9 +3,12 @@ use std::{collections::HashMap, path::Path, sync::Arc};
 use lr_trie::{LeftRightTrie, H256};
 use primitives::Address;
 use storage_utils::{Result, StorageError};
-use vrrb_core::account::{Account, UpdateArgs};
+use vrrb_core::{
+    account::{Account, UpdateArgs},
+    proof::MerkleProof,
+};
 
-use crate::RocksDbAdapter;
+use crate::{RocksDbAdapter, TrieKeyIndex};
 
 mod state_store_rh;
 pub use state_store_rh::*;
@@ -16,39 +19,33 @@ pub type FailedAccountUpdates = Vec<(Address, Vec<UpdateArgs>, Result<()>)>;
 #[derive(Debug, Clone)]
 pub struct StateStore {
     trie: LeftRightTrie<'static, Address, Account, RocksDbAdapter>,
-}
-
-impl Default for StateStore {
-    fn default() -> Self {
-        let db_path = storage_utils::get_node_data_dir()
-            .unwrap_or_default()
-            .join("db")
-            .join("state");
-
-        let db_adapter = RocksDbAdapter::new(db_path, "state").unwrap_or_default();
-
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
-
-        Self { trie }
-    }
+    /// Keys of the accounts within the trie, used to page through them.
+    keys: TrieKeyIndex,
 }
 
 impl StateStore {
-    /// Returns new, empty instance of StateDb
-
-    pub fn new(path: &Path) -> Self {
-        let path = path.join("state");
-        let db_adapter = RocksDbAdapter::new(path, "state").unwrap_or_default();
+    /// Opens the state store located at `path`, creating it if it does not
+    /// exist yet.
+    pub fn new(path: &Path) -> Result<Self> {
+        let db_adapter = RocksDbAdapter::new(path.join("state"), "state")?;
         let trie = LeftRightTrie::new(Arc::new(db_adapter));
+        let keys = TrieKeyIndex::new(path.join("state_keys"), "state_keys")?;
 
-        Self { trie }
+        // NOTE: stores created before the key index existed get it built from
+        // the keys within the trie
+        if keys.is_empty()? && !trie.is_empty() {
+            let handle = trie.handle();
+            keys.insert_encoded(handle.iter().map(|(key, _)| key))?;
+        }
+
+        Ok(Self { trie, keys })
     }
 
     /// Returns new ReadHandle to the VrrDb data. As long as the returned value
     /// lives, no write to the database will be committed.
     pub fn read_handle(&self) -> StateStoreReadHandle {
         let inner = self.trie.handle();
-        StateStoreReadHandle::new(inner)
+        StateStoreReadHandle::new(inner, self.keys.clone())
     }
 
     pub fn commit(&mut self) {
@@ -80,6 +77,7 @@ impl StateStore {
             ));
         }
 
+        self.keys.insert([&key])?;
         self.trie.insert(key, account);
 
         Ok(())
@@ -280,13 +278,44 @@ impl StateStore {
         self.trie.root()
     }
 
+    /// Returns a Merkle proof of the account stored under `key`.
+    pub fn get_proof(&self, key: &Address) -> Result<MerkleProof> {
+        self.read_handle().get_proof(key)
+    }
+
+    /// Verifies `proof` against the current root hash of the store and
+    /// returns the proven account, if any.
+    pub fn verify_proof(&self, proof: &MerkleProof) -> Result<Option<Account>> {
+        let root_hash = self
+            .root_hash()
+            .ok_or_else(|| StorageError::Other("state trie has no root hash".to_string()))?;
+
+        let key: Address = bincode::deserialize(&proof.key)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        let value = self
+            .trie
+            .verify_proof(root_hash, &key, proof.nodes.clone())
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        value
+            .map(|value| {
+                bincode::deserialize(&value).map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .transpose()
+    }
+
     pub fn extend(&mut self, accounts: Vec<(Address, Account)>) {
+        if let Err(err) = self.keys.insert(accounts.iter().map(|(key, _)| key)) {
+            telemetry::error!("failed to index the keys of the state trie: {err}");
+        }
+
         self.trie.extend(accounts)
     }
 
     pub fn factory(&self) -> StateStoreReadHandleFactory {
         let inner = self.trie.factory();
 
-        StateStoreReadHandleFactory::new(inner)
+        StateStoreReadHandleFactory::new(inner, self.keys.clone())
     }
 }
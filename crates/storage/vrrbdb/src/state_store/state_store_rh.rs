@@ -3,19 +3,43 @@ use std::collections::HashMap;
 use lr_trie::{InnerTrieWrapper, ReadHandleFactory};
 use patriecia::inner::InnerTrie;
 use primitives::Address;
+use serde::{Deserialize, Serialize};
 use storage_utils::{Result, StorageError};
-use vrrb_core::account::Account;
+use vrrb_core::{account::Account, proof::MerkleProof};
+
+use crate::{RocksDbAdapter, TrieKeyIndex};
+
+/// Criteria an account must match to be returned by
+/// `StateStoreReadHandle::page`. Unset fields match every account.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountFilter {
+    /// Minimum balance, i.e. credits minus debits, of the account
+    pub min_balance: Option<u128>,
+    /// Whether the account must or must not have code deployed to it
+    pub has_code: Option<bool>,
+}
+
+impl AccountFilter {
+    pub fn matches(&self, account: &Account) -> bool {
+        let balance = account.credits().saturating_sub(account.debits());
 
-use crate::RocksDbAdapter;
+        self.min_balance
+            .map_or(true, |min_balance| balance >= min_balance)
+            && self
+                .has_code
+                .map_or(true, |has_code| account.code().is_some() == has_code)
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct StateStoreReadHandle {
     inner: InnerTrieWrapper<RocksDbAdapter>,
+    keys: TrieKeyIndex,
 }
 
 impl StateStoreReadHandle {
-    pub fn new(inner: InnerTrieWrapper<RocksDbAdapter>) -> Self {
-        Self { inner }
+    pub fn new(inner: InnerTrieWrapper<RocksDbAdapter>, keys: TrieKeyIndex) -> Self {
+        Self { inner, keys }
     }
 
     /// Returns `Some(Account)` if an account exist under given PublicKey.
@@ -56,6 +80,46 @@ impl StateStoreReadHandle {
             .collect()
     }
 
+    /// Returns up to `limit` accounts matching `filter`, ordered by their key
+    /// within the trie and starting right after the `after` address. The
+    /// accounts are read from the cursor on through the trie's key index.
+    pub fn page(
+        &self,
+        after: Option<&Address>,
+        limit: usize,
+        filter: &AccountFilter,
+    ) -> Vec<(Address, Account)> {
+        let after = after.and_then(|after| bincode::serialize(after).ok());
+
+        self.keys
+            .keys_after(after.as_deref())
+            .map_while(|key| key.ok())
+            .filter_map(|key| {
+                let key: Address = bincode::deserialize(&key).ok()?;
+                let value = self.get(&key).ok()?;
+
+                filter.matches(&value).then_some((key, value))
+            })
+            .take(limit)
+            .collect()
+    }
+
+    /// Returns a Merkle proof of the account stored under `key`, generated
+    /// against the current root hash of the trie.
+    pub fn get_proof(&self, key: &Address) -> Result<MerkleProof> {
+        let mut inner = self.inner.clone();
+
+        let root_hash = inner
+            .root_hash()
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        let nodes = inner
+            .get_proof::<Address, Account>(key)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        MerkleProof::new(key, nodes, root_hash).map_err(|err| StorageError::Other(err.to_string()))
+    }
+
     /// Returns a number of initialized accounts in the database
     pub fn len(&self) -> usize {
         self.inner.len()
@@ -70,11 +134,12 @@ impl StateStoreReadHandle {
 #[derive(Debug, Clone)]
 pub struct StateStoreReadHandleFactory {
     inner: ReadHandleFactory<InnerTrie<RocksDbAdapter>>,
+    keys: TrieKeyIndex,
 }
 
 impl StateStoreReadHandleFactory {
-    pub fn new(inner: ReadHandleFactory<InnerTrie<RocksDbAdapter>>) -> Self {
-        Self { inner }
+    pub fn new(inner: ReadHandleFactory<InnerTrie<RocksDbAdapter>>, keys: TrieKeyIndex) -> Self {
+        Self { inner, keys }
     }
 
     pub fn handle(&self) -> StateStoreReadHandle {
@@ -87,6 +152,9 @@ impl StateStoreReadHandleFactory {
 
         let inner = InnerTrieWrapper::new(handle);
 
-        StateStoreReadHandle { inner }
+        StateStoreReadHandle {
+            inner,
+            keys: self.keys.clone(),
+        }
     }
 }
@@ -1,8 +1,11 @@
 use std::{path::Path, sync::Arc};
 
-use lr_trie::{LeftRightTrie, Proof, H256};
-use storage_utils::Result;
-use vrrb_core::txn::{TransactionDigest, Txn};
+use lr_trie::{LeftRightTrie, H256};
+use storage_utils::{Result, StorageError};
+use vrrb_core::{
+    proof::MerkleProof,
+    txn::{TransactionDigest, Txn},
+};
 
 use crate::RocksDbAdapter;
 
@@ -14,29 +17,15 @@ pub struct TransactionStore {
     trie: LeftRightTrie<'static, TransactionDigest, Txn, RocksDbAdapter>,
 }
 
-impl Default for TransactionStore {
-    fn default() -> Self {
-        let db_path = storage_utils::get_node_data_dir()
-            .unwrap_or_default()
-            .join("db")
-            .join("transactions");
-
-        let db_adapter = RocksDbAdapter::new(db_path, "transactions").unwrap_or_default();
-
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
-
-        Self { trie }
-    }
-}
-
 impl TransactionStore {
-    /// Returns new, empty instance of TransactionStore
-    pub fn new(path: &Path) -> Self {
+    /// Opens the transaction store located at `path`, creating it if it does
+    /// not exist yet.
+    pub fn new(path: &Path) -> Result<Self> {
         let path = path.join("transactions");
-        let db_adapter = RocksDbAdapter::new(path, "transactions").unwrap_or_default();
+        let db_adapter = RocksDbAdapter::new(path, "transactions")?;
         let trie = LeftRightTrie::new(Arc::new(db_adapter));
 
-        Self { trie }
+        Ok(Self { trie })
     }
 
     pub fn factory(&self) -> TransactionStoreReadHandleFactory {
@@ -72,11 +61,30 @@ impl TransactionStore {
         self.trie.root()
     }
 
-    pub fn get_proof(&self) -> Result<Vec<Proof>> {
-        todo!()
+    /// Returns a Merkle proof of the transaction stored under `key`.
+    pub fn get_proof(&self, key: &TransactionDigest) -> Result<MerkleProof> {
+        self.read_handle().get_proof(key)
     }
 
-    pub fn verify_proof(&self) -> Option<H256> {
-        todo!()
+    /// Verifies `proof` against the current root hash of the store and
+    /// returns the proven transaction, if any.
+    pub fn verify_proof(&self, proof: &MerkleProof) -> Result<Option<Txn>> {
+        let root_hash = self
+            .root_hash()
+            .ok_or_else(|| StorageError::Other("transaction trie has no root hash".to_string()))?;
+
+        let key: TransactionDigest = bincode::deserialize(&proof.key)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        let value = self
+            .trie
+            .verify_proof(root_hash, &key, proof.nodes.clone())
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        value
+            .map(|value| {
+                bincode::deserialize(&value).map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .transpose()
     }
 }
@@ -3,7 +3,10 @@ use std::collections::HashMap;
 use lr_trie::{InnerTrieWrapper, ReadHandleFactory};
 use patriecia::inner::InnerTrie;
 use storage_utils::{Result, StorageError};
-use vrrb_core::txn::{TransactionDigest, Txn};
+use vrrb_core::{
+    proof::MerkleProof,
+    txn::{TransactionDigest, Txn},
+};
 
 use crate::RocksDbAdapter;
 
@@ -50,6 +53,22 @@ impl TransactionStoreReadHandle {
             .collect()
     }
 
+    /// Returns a Merkle proof of the transaction stored under `key`, generated
+    /// against the current root hash of the trie.
+    pub fn get_proof(&self, key: &TransactionDigest) -> Result<MerkleProof> {
+        let mut inner = self.inner.clone();
+
+        let root_hash = inner
+            .root_hash()
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        let nodes = inner
+            .get_proof::<TransactionDigest, Txn>(key)
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        MerkleProof::new(key, nodes, root_hash).map_err(|err| StorageError::Other(err.to_string()))
+    }
+
     /// Returns a number of transactions in the ledger
     pub fn len(&self) -> usize {
         self.inner.len()
@@ -0,0 +1,70 @@
+use std::{path::PathBuf, sync::Arc};
+
+use rocksdb::WriteBatch;
+use serde::Serialize;
+use storage_utils::{Result, StorageError};
+
+use crate::RocksDbAdapter;
+
+/// Keeps the encoded keys of a trie in byte order, which is also the order
+/// the trie is walked in, so that pages of the trie can be read starting at a
+/// cursor instead of walking the trie from its first key.
+#[derive(Debug, Clone)]
+pub struct TrieKeyIndex {
+    db: Arc<RocksDbAdapter>,
+}
+
+impl TrieKeyIndex {
+    pub fn new(path: PathBuf, column_family: &str) -> Result<Self> {
+        let db = RocksDbAdapter::new(path, column_family)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Adds the given keys to the index.
+    pub fn insert<'a, K>(&self, keys: impl IntoIterator<Item = &'a K>) -> Result<()>
+    where
+        K: Serialize + 'a,
+    {
+        let encoded_keys = keys
+            .into_iter()
+            .map(|key| bincode::serialize(key).map_err(|err| StorageError::Other(err.to_string())))
+            .collect::<Result<Vec<_>>>()?;
+
+        self.insert_encoded(encoded_keys)
+    }
+
+    /// Adds the given keys, already encoded the way the trie stores them, to
+    /// the index.
+    pub fn insert_encoded(&self, encoded_keys: impl IntoIterator<Item = Vec<u8>>) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        for encoded_key in encoded_keys {
+            batch.put(encoded_key, []);
+        }
+
+        self.db.write(batch)
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.db.iter_from(&[]).next().is_none())
+    }
+
+    /// Iterates over the encoded keys in order, starting right after `after`
+    /// or at the first key if unset.
+    pub fn keys_after<'a>(
+        &'a self,
+        after: Option<&'a [u8]>,
+    ) -> impl Iterator<Item = Result<Vec<u8>>> + 'a {
+        self.db
+            .iter_from(after.unwrap_or_default())
+            .map(|entry| {
+                entry
+                    .map(|(key, _)| key.into_vec())
+                    .map_err(|err| StorageError::Other(err.to_string()))
+            })
+            .filter(move |key| match (key, after) {
+                (Ok(key), Some(after)) => key.as_slice() != after,
+                _ => true,
+            })
+    }
+}
@@ -1,19 +1,24 @@
 use std::{collections::HashMap, fmt::Display, path::PathBuf};
 
+use block::{BlockHash, Evidence};
 use ethereum_types::U256;
 use lr_trie::H256;
-use primitives::Address;
+use primitives::{Address, Epoch, NodeId};
 use serde_json::json;
 use storage_utils::{Result, StorageError};
 use vrrb_core::{
     account::{Account, UpdateArgs},
     claim::Claim,
+    receipt::TxnReceipt,
+    reputation::{PeerBehaviour, PeerReputation},
+    reward_credit::RewardCredit,
     txn::Txn,
 };
 
 use crate::{
-    ClaimStore, ClaimStoreReadHandleFactory, StateStore, StateStoreReadHandleFactory,
-    TransactionStore, TransactionStoreReadHandleFactory, VrrbDbReadHandle,
+    ClaimStore, ClaimStoreReadHandleFactory, EventStore, EvidenceStore, ReceiptStore, ReputationStore, RewardStore, StateHistoryRecord,
+    StateHistoryStore, StateStore, StateStoreReadHandleFactory, TransactionStore,
+    TransactionStoreReadHandleFactory, VrrbDbReadHandle,
 };
 
 #[derive(Debug, Clone)]
@@ -49,24 +54,44 @@ impl Default for VrrbDbConfig {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct VrrbDb {
     state_store: StateStore,
     transaction_store: TransactionStore,
     claim_store: ClaimStore,
+    state_history_store: StateHistoryStore,
+    receipt_store: ReceiptStore,
+    reputation_store: ReputationStore,
+    evidence_store: EvidenceStore,
+    reward_store: RewardStore,
+    event_store: EventStore,
 }
 
 impl VrrbDb {
-    pub fn new(config: VrrbDbConfig) -> Self {
-        let state_store = StateStore::new(&config.path);
-        let transaction_store = TransactionStore::new(&config.path);
-        let claim_store = ClaimStore::new(&config.path);
-
-        Self {
+    /// Opens every store of the database located at the configured path,
+    /// creating them if they do not exist yet.
+    pub fn new(config: VrrbDbConfig) -> Result<Self> {
+        let state_store = StateStore::new(&config.path)?;
+        let transaction_store = TransactionStore::new(&config.path)?;
+        let claim_store = ClaimStore::new(&config.path)?;
+        let state_history_store = StateHistoryStore::new(&config.path)?;
+        let receipt_store = ReceiptStore::new(&config.path)?;
+        let reputation_store = ReputationStore::new(&config.path)?;
+        let evidence_store = EvidenceStore::new(&config.path)?;
+        let reward_store = RewardStore::new(&config.path)?;
+        let event_store = EventStore::new(&config.path)?;
+
+        Ok(Self {
             state_store,
             transaction_store,
             claim_store,
-        }
+            state_history_store,
+            receipt_store,
+            reputation_store,
+            evidence_store,
+            reward_store,
+            event_store,
+        })
     }
 
     pub fn commit_transactions(&mut self) {
@@ -86,6 +111,12 @@ impl VrrbDb {
             self.state_store.factory(),
             self.transaction_store_factory(),
             self.claim_store_factory(),
+            self.state_history_store.clone(),
+            self.receipt_store.clone(),
+            self.reputation_store.clone(),
+            self.evidence_store.clone(),
+            self.reward_store.clone(),
+            self.event_store.clone(),
         )
     }
 
@@ -93,11 +124,23 @@ impl VrrbDb {
         state_store: StateStore,
         transaction_store: TransactionStore,
         claim_store: ClaimStore,
+        state_history_store: StateHistoryStore,
+        receipt_store: ReceiptStore,
+        reputation_store: ReputationStore,
+        evidence_store: EvidenceStore,
+        reward_store: RewardStore,
+        event_store: EventStore,
     ) -> Self {
         Self {
             state_store,
             transaction_store,
             claim_store,
+            state_history_store,
+            receipt_store,
+            reputation_store,
+            evidence_store,
+            reward_store,
+            event_store,
         }
     }
 
@@ -136,19 +179,104 @@ impl VrrbDb {
 
     /// Inserts an account to current state tree.
     pub fn insert_account(&mut self, key: Address, account: Account) -> Result<()> {
-        self.state_store.insert(key, account)
+        self.state_store.insert(key.clone(), account)?;
+        self.state_history_store.mark_written([&key])?;
+
+        Ok(())
     }
 
     /// Adds multiplpe accounts to current state tree.
     pub fn extend_accounts(&mut self, accounts: Vec<(Address, Account)>) {
+        if let Err(err) = self
+            .state_history_store
+            .mark_written(accounts.iter().map(|(key, _)| key))
+        {
+            telemetry::error!("failed to track written accounts in the state history: {err}");
+        }
+
         self.state_store.extend(accounts);
     }
 
     /// Updates an account on the current state tree.
     pub fn update_account(&mut self, args: UpdateArgs) -> Result<()> {
+        let key = args.address.clone();
+
         self.state_store
             .update(args)
-            .map_err(|err| StorageError::Other(err.to_string()))
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.state_history_store.mark_written([&key])?;
+
+        Ok(())
+    }
+
+    /// Records the current state root along with every account written since
+    /// the previous call as the state committed by the given block of the
+    /// given epoch, so it can later be read through
+    /// `VrrbDbReadHandle::state_at`.
+    pub fn record_block_state(
+        &mut self,
+        block_hash: &str,
+        epoch: Epoch,
+    ) -> Result<StateHistoryRecord> {
+        let state_store_handle = self.state_store.read_handle();
+
+        let accounts = self
+            .state_history_store
+            .pending_accounts()?
+            .into_iter()
+            .filter_map(|key| {
+                state_store_handle
+                    .get(&key)
+                    .ok()
+                    .map(|account| (key, account))
+            })
+            .collect();
+
+        self.state_history_store
+            .record(block_hash, epoch, self.state_root_hash(), accounts)
+    }
+
+    /// Stores the receipts of the transactions proposed for a committed
+    /// `ConvergenceBlock`.
+    pub fn extend_receipts(&mut self, receipts: &[TxnReceipt]) -> Result<()> {
+        self.receipt_store.extend(receipts)
+    }
+
+    /// Records a behaviour observed from the given peer into its reputation.
+    pub fn record_peer_behaviour(
+        &mut self,
+        node_id: &NodeId,
+        behaviour: PeerBehaviour,
+    ) -> Result<PeerReputation> {
+        self.reputation_store.record(node_id, behaviour)
+    }
+
+    /// Stores certified evidence of misbehaviour until it is included in a
+    /// block. Returns whether it was new.
+    pub fn insert_evidence(&mut self, evidence: Evidence) -> Result<bool> {
+        self.evidence_store.insert(evidence)
+    }
+
+    /// Records that the given evidence was included in the given block.
+    /// Returns whether it had not been included in a block before, in which
+    /// case its slash is still due.
+    pub fn mark_evidence_included(
+        &mut self,
+        evidence: Evidence,
+        block_hash: &BlockHash,
+    ) -> Result<bool> {
+        self.evidence_store.mark_included(evidence, block_hash)
+    }
+
+    /// Stores the rewards credited when committing the given block.
+    pub fn insert_rewards(
+        &mut self,
+        epoch: Epoch,
+        block_hash: &BlockHash,
+        credits: &[RewardCredit],
+    ) -> Result<()> {
+        self.reward_store.insert(epoch, block_hash, credits)
     }
 
     /// Inserts a confirmed transaction to the ledger. Does not check if
@@ -207,6 +335,12 @@ impl Clone for VrrbDb {
             state_store: self.state_store.clone(),
             transaction_store: self.transaction_store.clone(),
             claim_store: self.claim_store.clone(),
+            state_history_store: self.state_history_store.clone(),
+            receipt_store: self.receipt_store.clone(),
+            reputation_store: self.reputation_store.clone(),
+            evidence_store: self.evidence_store.clone(),
+            reward_store: self.reward_store.clone(),
+            event_store: self.event_store.clone(),
         }
     }
 }
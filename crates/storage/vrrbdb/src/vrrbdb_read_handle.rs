@@ -1,15 +1,31 @@
 use std::collections::HashMap;
 
-use primitives::{Address, NodeId};
+use block::Evidence;
+use lr_trie::H256;
+use primitives::{Address, Epoch, NodeId, GENESIS_EPOCH};
+use storage_utils::Result;
 use vrrb_core::{
     account::Account,
     claim::Claim,
+    receipt::TxnReceipt,
+    reputation::PeerReputation,
+    reward_credit::RewardCredit,
     txn::{TransactionDigest, Txn},
 };
 
 use crate::{
+    AccountFilter,
+    ClaimStoreReadHandle,
     ClaimStoreReadHandleFactory,
+    EventStore,
+    EvidenceStore,
+    ReceiptStore,
+    ReputationStore,
+    RewardStore,
+    StateHistoryStore,
+    StateStoreReadHandle,
     StateStoreReadHandleFactory,
+    TransactionStoreReadHandle,
     TransactionStoreReadHandleFactory,
 };
 
@@ -18,6 +34,12 @@ pub struct VrrbDbReadHandle {
     state_store_handle_factory: StateStoreReadHandleFactory,
     transaction_store_handle_factory: TransactionStoreReadHandleFactory,
     claim_store_handle_factory: ClaimStoreReadHandleFactory,
+    state_history_store: StateHistoryStore,
+    receipt_store: ReceiptStore,
+    reputation_store: ReputationStore,
+    evidence_store: EvidenceStore,
+    reward_store: RewardStore,
+    event_store: EventStore,
 }
 
 impl VrrbDbReadHandle {
@@ -25,14 +47,41 @@ impl VrrbDbReadHandle {
         state_store_handle_factory: StateStoreReadHandleFactory,
         transaction_store_handle_factory: TransactionStoreReadHandleFactory,
         claim_store_handle_factory: ClaimStoreReadHandleFactory,
+        state_history_store: StateHistoryStore,
+        receipt_store: ReceiptStore,
+        reputation_store: ReputationStore,
+        evidence_store: EvidenceStore,
+        reward_store: RewardStore,
+        event_store: EventStore,
     ) -> Self {
         Self {
             state_store_handle_factory,
             transaction_store_handle_factory,
             claim_store_handle_factory,
+            state_history_store,
+            receipt_store,
+            reputation_store,
+            evidence_store,
+            reward_store,
+            event_store,
         }
     }
 
+    /// Returns a read handle into the state trie
+    pub fn state_store_handle(&self) -> StateStoreReadHandle {
+        self.state_store_handle_factory.handle()
+    }
+
+    /// Returns a read handle into the transaction trie
+    pub fn transaction_store_handle(&self) -> TransactionStoreReadHandle {
+        self.transaction_store_handle_factory.handle()
+    }
+
+    /// Returns a read handle into the claim trie
+    pub fn claim_store_handle(&self) -> ClaimStoreReadHandle {
+        self.claim_store_handle_factory.handle()
+    }
+
     /// Returns a copy of all values stored within the state trie
     pub fn state_store_values(&self) -> HashMap<Address, Account> {
         self.state_store_handle_factory.handle().entries()
@@ -46,4 +95,88 @@ impl VrrbDbReadHandle {
     pub fn claim_store_values(&self) -> HashMap<NodeId, Claim> {
         self.claim_store_handle_factory.handle().entries()
     }
+
+    /// Returns a copy of all accounts as they were right after the
+    /// `ConvergenceBlock` with the given hash was committed
+    pub fn state_at(&self, block_hash: &str) -> Result<HashMap<Address, Account>> {
+        self.state_history_store.state_at(block_hash)
+    }
+
+    /// Returns up to `limit` accounts matching `filter` as they were right
+    /// after the `ConvergenceBlock` with the given hash was committed, in the
+    /// same order as `StateStoreReadHandle::page`
+    pub fn state_page_at(
+        &self,
+        block_hash: &str,
+        after: Option<&Address>,
+        limit: usize,
+        filter: &AccountFilter,
+    ) -> Result<Vec<(Address, Account)>> {
+        self.state_history_store
+            .page_at(block_hash, after, limit, filter)
+    }
+
+    /// Returns an account as it was right after the `ConvergenceBlock` with
+    /// the given hash was committed
+    pub fn account_at(&self, block_hash: &str, address: &Address) -> Result<Account> {
+        self.state_history_store.account_at(block_hash, address)
+    }
+
+    /// Returns the state root hash the `ConvergenceBlock` with the given hash
+    /// was committed with
+    pub fn state_root_at(&self, block_hash: &str) -> Result<Option<H256>> {
+        self.state_history_store.root_at(block_hash)
+    }
+
+    /// Returns the hash of the most recently committed `ConvergenceBlock`, if
+    /// any
+    pub fn last_committed_block_hash(&self) -> Result<Option<String>> {
+        Ok(self
+            .state_history_store
+            .last()?
+            .map(|record| record.block_hash))
+    }
+
+    /// Returns the epoch of the most recently committed `ConvergenceBlock`,
+    /// which is the genesis epoch until a block was committed
+    pub fn last_committed_epoch(&self) -> Result<Epoch> {
+        Ok(self
+            .state_history_store
+            .last()?
+            .map(|record| record.epoch)
+            .unwrap_or(GENESIS_EPOCH))
+    }
+
+    /// Returns the receipt of a transaction proposed within a committed
+    /// `ConvergenceBlock`
+    pub fn transaction_receipt(&self, txn_id: &TransactionDigest) -> Result<TxnReceipt> {
+        self.receipt_store.get(txn_id)
+    }
+
+    /// Returns the log of the events processed by the node, which the
+    /// node's event store module appends to
+    pub fn event_store(&self) -> EventStore {
+        self.event_store.clone()
+    }
+
+    /// Returns the reputation of the given peer, which is neutral for peers
+    /// that have not been observed yet
+    pub fn peer_reputation(&self, node_id: &NodeId) -> PeerReputation {
+        self.reputation_store.get(node_id).unwrap_or_default()
+    }
+
+    /// Returns the reputation of every peer observed so far
+    pub fn peer_reputations(&self) -> HashMap<NodeId, PeerReputation> {
+        self.reputation_store.entries().unwrap_or_default()
+    }
+
+    /// Returns the certified evidence that was not included in a block yet.
+    pub fn pending_evidence(&self) -> Vec<Evidence> {
+        self.evidence_store.pending().unwrap_or_default()
+    }
+
+    /// Returns every reward credited within the given epoch
+    pub fn epoch_rewards(&self, epoch: Epoch) -> Result<Vec<RewardCredit>> {
+        self.reward_store.epoch_credits(epoch)
+    }
 }
@@ -7,7 +7,7 @@ use serial_test::serial;
 #[test]
 #[serial]
 fn claims_can_be_added() {
-    let mut db = VrrbDb::new(VrrbDbConfig::default());
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
 
     let claim1 = _generate_random_claim();
     let claim2 = _generate_random_claim();
@@ -33,3 +33,32 @@ fn claims_can_be_added() {
 
     assert_eq!(entries.len(), 5);
 }
+
+#[test]
+#[serial]
+fn claims_can_be_looked_up_by_address() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+
+    let claim1 = _generate_random_claim();
+    let claim2 = _generate_random_claim();
+    let claim3 = _generate_random_claim();
+
+    db.insert_claim(claim1.clone()).unwrap();
+    db.extend_claims(vec![(claim2.hash, claim2.clone())]);
+
+    let handle = db.claim_store_factory().handle();
+
+    assert_eq!(
+        handle.get_by_address(&claim1.address).unwrap(),
+        vec![claim1]
+    );
+    assert_eq!(
+        handle.get_by_address(&claim2.address).unwrap(),
+        vec![claim2]
+    );
+    assert!(handle.get_by_address(&claim3.address).unwrap().is_empty());
+    assert!(handle
+        .get_by_unbonding_staker(&claim3.address)
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,38 @@
+use std::env;
+
+use events::Event;
+use vrrbdb::EventStore;
+mod common;
+
+use common::_generate_random_string;
+
+#[test]
+fn events_can_be_listed_by_variant() {
+    let path = env::temp_dir().join(_generate_random_string());
+
+    let store = EventStore::new(&path).unwrap();
+
+    store.append(Event::Ping("node-1".to_string())).unwrap();
+    store.append(Event::Stop).unwrap();
+    store.append(Event::Ping("node-2".to_string())).unwrap();
+    store.append(Event::PingIntervalElapsed).unwrap();
+    store.append(Event::Ping("node-3".to_string())).unwrap();
+
+    let pings = store.by_variant("Ping", 0, 10).unwrap();
+    let sequences: Vec<_> = pings.iter().map(|event| event.sequence).collect();
+    assert_eq!(sequences, vec![0, 2, 4]);
+
+    let pings = store.by_variant("Ping", 1, 1).unwrap();
+    assert_eq!(pings.len(), 1);
+    assert_eq!(pings[0].event, Event::Ping("node-2".to_string()));
+
+    assert!(store.by_variant("Pin", 0, 10).unwrap().is_empty());
+    assert_eq!(store.len(), 5);
+
+    drop(store);
+
+    let store = EventStore::new(&path).unwrap();
+    assert_eq!(store.len(), 5);
+    assert_eq!(store.append(Event::Stop).unwrap().sequence, 5);
+    assert_eq!(store.by_variant("Stop", 0, 10).unwrap().len(), 2);
+}
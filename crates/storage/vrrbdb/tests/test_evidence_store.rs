@@ -0,0 +1,79 @@
+use std::env;
+
+use block::{Evidence, Misbehaviour, ProposalBlock};
+use serial_test::serial;
+use vrrb_core::keypair::Keypair;
+use vrrbdb::{VrrbDb, VrrbDbConfig};
+mod common;
+
+use common::{_generate_random_claim, _generate_random_string};
+
+#[test]
+#[serial]
+fn evidence_is_pending_until_included_in_a_block() {
+    let temp_dir_path = env::temp_dir();
+    let state_backup_path = temp_dir_path.join(format!("{}", _generate_random_string()));
+
+    let mut db = VrrbDb::new(VrrbDbConfig {
+        path: state_backup_path,
+        state_store_path: None,
+        transaction_store_path: None,
+        event_store_path: None,
+        claim_store_path: None,
+    })
+    .unwrap();
+
+    let keypair = Keypair::random();
+    let (secret_key, public_key) = keypair.miner_kp;
+    let claim = _generate_random_claim();
+
+    let first = ProposalBlock::build(
+        "ref-block".to_string(),
+        1,
+        0,
+        Default::default(),
+        Default::default(),
+        claim.clone(),
+        &secret_key,
+    );
+
+    let mut claims = block::ClaimList::new();
+    claims.insert(claim.hash, claim.clone());
+
+    let second = ProposalBlock::build(
+        "ref-block".to_string(),
+        1,
+        0,
+        Default::default(),
+        claims,
+        claim.clone(),
+        &secret_key,
+    );
+
+    let evidence = Evidence::new(
+        Misbehaviour::ProposalEquivocation {
+            first: Box::new(first),
+            second: Box::new(second),
+        },
+        claim.address.clone(),
+        "quorum-pubkey".to_string(),
+        vec![0; 96],
+        secret_key,
+        public_key,
+    )
+    .unwrap();
+
+    assert!(db.insert_evidence(evidence.clone()).unwrap());
+    assert!(!db.insert_evidence(evidence.clone()).unwrap());
+
+    let handle = db.read_handle();
+    assert_eq!(handle.pending_evidence(), vec![evidence.clone()]);
+
+    let block_hash = "convergence-block".to_string();
+    assert!(db
+        .mark_evidence_included(evidence.clone(), &block_hash)
+        .unwrap());
+    assert!(!db.mark_evidence_included(evidence, &block_hash).unwrap());
+
+    assert!(db.read_handle().pending_evidence().is_empty());
+}
@@ -0,0 +1,53 @@
+use std::env;
+
+use serial_test::serial;
+use vrrb_core::reputation::PeerBehaviour;
+use vrrbdb::{VrrbDb, VrrbDbConfig};
+mod common;
+
+use common::_generate_random_string;
+
+#[test]
+#[serial]
+fn peer_behaviour_is_recorded_into_reputations() {
+    let temp_dir_path = env::temp_dir();
+    let state_backup_path = temp_dir_path.join(format!("{}", _generate_random_string()));
+
+    let mut db = VrrbDb::new(VrrbDbConfig {
+        path: state_backup_path,
+        state_store_path: None,
+        transaction_store_path: None,
+        event_store_path: None,
+        claim_store_path: None,
+    })
+    .unwrap();
+
+    let honest_peer = "node-1".to_string();
+    let faulty_peer = "node-2".to_string();
+
+    db.record_peer_behaviour(&honest_peer, PeerBehaviour::VoteCast {
+        with_majority: true,
+    })
+    .unwrap();
+    db.record_peer_behaviour(&honest_peer, PeerBehaviour::PartialSignatureDelivered {
+        on_time: true,
+    })
+    .unwrap();
+    db.record_peer_behaviour(&faulty_peer, PeerBehaviour::ClaimAbandoned)
+        .unwrap();
+
+    let handle = db.read_handle();
+
+    let honest_reputation = handle.peer_reputation(&honest_peer);
+    assert_eq!(honest_reputation.votes_cast, 1);
+    assert_eq!(honest_reputation.partial_signatures_on_time, 1);
+
+    let faulty_reputation = handle.peer_reputation(&faulty_peer);
+    assert_eq!(faulty_reputation.claims_abandoned, 1);
+    assert!(faulty_reputation.score() < honest_reputation.score());
+
+    let unknown_reputation = handle.peer_reputation(&"node-3".to_string());
+    assert_eq!(unknown_reputation.score(), 0.5);
+
+    assert_eq!(handle.peer_reputations().len(), 2);
+}
@@ -0,0 +1,71 @@
+use std::env;
+
+use serial_test::serial;
+use vrrb_core::reward_credit::{RewardCredit, RewardKind};
+use vrrbdb::{VrrbDb, VrrbDbConfig};
+mod common;
+
+use common::{_generate_random_address, _generate_random_string};
+
+#[test]
+#[serial]
+fn rewards_can_be_audited_per_epoch() {
+    let temp_dir_path = env::temp_dir();
+    let state_backup_path = temp_dir_path.join(format!("{}", _generate_random_string()));
+
+    let mut db = VrrbDb::new(VrrbDbConfig {
+        path: state_backup_path,
+        state_store_path: None,
+        transaction_store_path: None,
+        event_store_path: None,
+        claim_store_path: None,
+    })
+    .unwrap();
+
+    let (_, miner) = _generate_random_address();
+    let (_, delegator) = _generate_random_address();
+
+    let credit = |epoch, block_hash: &str, address, amount, kind| RewardCredit {
+        epoch,
+        round: 1,
+        block_hash: block_hash.to_string(),
+        address,
+        amount,
+        kind,
+    };
+
+    let first_block = vec![
+        credit(1, "block-a", miner.clone(), 18, RewardKind::BlockReward),
+        credit(1, "block-a", delegator.clone(), 2, RewardKind::Delegation),
+    ];
+    let second_block = vec![credit(
+        1,
+        "block-b",
+        miner.clone(),
+        20,
+        RewardKind::BlockReward,
+    )];
+    let next_epoch_block = vec![credit(2, "block-c", miner, 20, RewardKind::BlockReward)];
+
+    db.insert_rewards(1, &"block-a".to_string(), &first_block)
+        .unwrap();
+    db.insert_rewards(1, &"block-b".to_string(), &second_block)
+        .unwrap();
+    db.insert_rewards(2, &"block-c".to_string(), &next_epoch_block)
+        .unwrap();
+
+    let read_handle = db.read_handle();
+
+    let epoch_rewards = read_handle.epoch_rewards(1).unwrap();
+    assert_eq!(epoch_rewards.len(), 3);
+    assert_eq!(
+        epoch_rewards
+            .iter()
+            .map(|credit| credit.amount)
+            .sum::<u128>(),
+        40
+    );
+
+    assert_eq!(read_handle.epoch_rewards(2).unwrap(), next_epoch_block);
+    assert!(read_handle.epoch_rewards(3).unwrap().is_empty());
+}
@@ -1,15 +1,17 @@
-use vrrb_core::account::{Account, AccountDigests};
-use vrrbdb::{VrrbDb, VrrbDbConfig};
+use std::collections::HashMap;
+
+use vrrb_core::account::{Account, AccountDigests, UpdateArgs};
+use vrrbdb::{AccountFilter, VrrbDb, VrrbDbConfig};
 
 mod common;
-use common::_generate_random_address;
+use common::{_generate_random_address, _generate_random_string};
 use serial_test::serial;
 use primitives::Address;
 
 #[test]
 #[serial]
 fn accounts_can_be_added() {
-    let mut db = VrrbDb::new(VrrbDbConfig::default());
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
 
     let (_, addr1) = _generate_random_address();
     let (_, addr2) = _generate_random_address();
@@ -52,3 +54,174 @@ fn accounts_can_be_added() {
 
     assert_eq!(entries.len(), 5);
 }
+
+#[test]
+#[serial]
+fn account_proofs_can_be_verified_offline() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+
+    let (_, addr1) = _generate_random_address();
+    let (_, addr2) = _generate_random_address();
+    let (_, addr3) = _generate_random_address();
+
+    let account1 = Account::new(addr1.public_key());
+
+    db.extend_accounts(vec![
+        (addr1.clone(), account1.clone()),
+        (addr2.clone(), Account::new(addr2.public_key())),
+    ]);
+
+    let root_hash = db.state_root_hash().unwrap();
+    let handle = db.state_store_factory().handle();
+
+    let proof = handle.get_proof(&addr1).unwrap();
+
+    assert_eq!(proof.root_hash(), root_hash);
+    assert!(proof.proves(root_hash, &addr1, &account1));
+    assert_eq!(proof.verify_value::<Account>(root_hash).unwrap(), Some(account1.clone()));
+
+    let absent = handle.get_proof(&addr3).unwrap();
+    assert_eq!(absent.verify_value::<Account>(root_hash).unwrap(), None);
+
+    db.insert_account(addr3.clone(), Account::new(addr3.public_key()))
+        .unwrap();
+
+    let new_root_hash = db.state_root_hash().unwrap();
+    assert!(!proof.proves(new_root_hash, &addr1, &account1));
+}
+
+#[test]
+#[serial]
+fn state_can_be_read_at_recorded_blocks() {
+    let path = std::env::temp_dir().join(_generate_random_string());
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+
+    let (_, addr1) = _generate_random_address();
+    let (_, addr2) = _generate_random_address();
+
+    db.insert_account(addr1.clone(), Account::new(addr1.public_key()))
+        .unwrap();
+
+    let first_block = _generate_random_string();
+    let first_record = db.record_block_state(&first_block, 0).unwrap();
+
+    db.update_account(UpdateArgs {
+        address: addr1.clone(),
+        nonce: Some(1),
+        credits: Some(100),
+        debits: None,
+        storage: None,
+        code: None,
+        digests: None,
+    })
+    .unwrap();
+
+    db.insert_account(addr2.clone(), Account::new(addr2.public_key()))
+        .unwrap();
+
+    let second_block = _generate_random_string();
+    db.record_block_state(&second_block, 1).unwrap();
+
+    let read_handle = db.read_handle();
+
+    let first_state = read_handle.state_at(&first_block).unwrap();
+    assert_eq!(first_state.len(), 1);
+    assert_eq!(first_state.get(&addr1).unwrap().credits(), 0);
+    assert_eq!(
+        read_handle.state_root_at(&first_block).unwrap(),
+        first_record.root_hash()
+    );
+
+    let second_state = read_handle.state_at(&second_block).unwrap();
+    assert_eq!(second_state.len(), 2);
+    assert_eq!(second_state.get(&addr1).unwrap().credits(), 100);
+    assert_eq!(
+        read_handle.state_root_at(&second_block).unwrap(),
+        db.state_root_hash()
+    );
+
+    assert_eq!(
+        read_handle.account_at(&first_block, &addr1).unwrap().credits(),
+        0
+    );
+    assert!(read_handle.account_at(&first_block, &addr2).is_err());
+    assert_eq!(
+        read_handle.last_committed_block_hash().unwrap(),
+        Some(second_block.clone())
+    );
+    assert_eq!(read_handle.last_committed_epoch().unwrap(), 1);
+    assert!(db.record_block_state(&second_block, 1).is_err());
+}
+
+#[test]
+#[serial]
+fn state_history_is_rebuilt_from_checkpoints() {
+    let path = std::env::temp_dir().join(_generate_random_string());
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+
+    let (_, addr1) = _generate_random_address();
+    let (_, addr2) = _generate_random_address();
+
+    db.insert_account(addr1.clone(), Account::new(addr1.public_key()))
+        .unwrap();
+
+    // Accounts written through a clone are recorded too.
+    let mut cloned_db = db.clone();
+    cloned_db
+        .insert_account(addr2.clone(), Account::new(addr2.public_key()))
+        .unwrap();
+
+    let mut block_hashes = vec![];
+    for credits in 0..(vrrbdb::STATE_CHECKPOINT_INTERVAL + 2) as u128 {
+        if credits > 0 {
+            db.update_account(UpdateArgs {
+                address: addr1.clone(),
+                nonce: None,
+                credits: Some(1),
+                debits: None,
+                storage: None,
+                code: None,
+                digests: None,
+            })
+            .unwrap();
+        }
+
+        let block_hash = _generate_random_string();
+        db.record_block_state(&block_hash, 0).unwrap();
+        block_hashes.push((block_hash, credits));
+    }
+
+    let read_handle = db.read_handle();
+
+    // Pages of the last block are read from its checkpoint merged with the
+    // record written since.
+    let (last_block, _) = block_hashes.last().unwrap();
+    let filter = AccountFilter::default();
+    let first_page = read_handle
+        .state_page_at(last_block, None, 1, &filter)
+        .unwrap();
+    let second_page = read_handle
+        .state_page_at(last_block, Some(&first_page[0].0), 1, &filter)
+        .unwrap();
+    let third_page = read_handle
+        .state_page_at(last_block, Some(&second_page[0].0), 1, &filter)
+        .unwrap();
+
+    let paged: HashMap<_, _> = first_page.into_iter().chain(second_page).collect();
+    assert_eq!(paged, read_handle.state_at(last_block).unwrap());
+    assert!(third_page.is_empty());
+
+    for (block_hash, credits) in block_hashes {
+        let state = read_handle.state_at(&block_hash).unwrap();
+        assert_eq!(state.len(), 2);
+        assert_eq!(state.get(&addr1).unwrap().credits(), credits);
+        assert_eq!(
+            read_handle
+                .account_at(&block_hash, &addr1)
+                .unwrap()
+                .credits(),
+            credits
+        );
+        assert!(read_handle.account_at(&block_hash, &addr2).is_ok());
+    }
+}
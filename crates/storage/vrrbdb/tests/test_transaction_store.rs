@@ -1,6 +1,10 @@
 use std::env;
 
 use serial_test::serial;
+use vrrb_core::{
+    receipt::{TxnReceipt, TxnReceiptStatus},
+    txn::QuorumCertifiedTxn,
+};
 use vrrbdb::{VrrbDb, VrrbDbConfig};
 mod common;
 
@@ -18,7 +22,8 @@ fn transactions_can_be_added() {
         transaction_store_path: None,
         event_store_path: None,
         claim_store_path: None,
-    });
+    })
+    .unwrap();
 
     let txn1 = _generate_random_valid_transaction();
     let txn2 = _generate_random_valid_transaction();
@@ -40,3 +45,57 @@ fn transactions_can_be_added() {
 
     assert_eq!(entries.len(), 5);
 }
+
+#[test]
+#[serial]
+fn transaction_receipts_can_be_stored_and_read() {
+    let temp_dir_path = env::temp_dir();
+    let state_backup_path = temp_dir_path.join(format!("{}", _generate_random_string()));
+
+    let mut db = VrrbDb::new(VrrbDbConfig {
+        path: state_backup_path,
+        state_store_path: None,
+        transaction_store_path: None,
+        event_store_path: None,
+        claim_store_path: None,
+    })
+    .unwrap();
+
+    let included_txn = _generate_random_valid_transaction();
+    let rejected_txn = _generate_random_valid_transaction();
+    let unknown_txn = _generate_random_valid_transaction();
+
+    let included = QuorumCertifiedTxn::new(vec![], vec![], included_txn.clone(), vec![], true);
+    let rejected = QuorumCertifiedTxn::new(vec![], vec![], rejected_txn.clone(), vec![], false);
+
+    db.extend_receipts(&[
+        TxnReceipt::included(&included, "proposal".into(), "convergence".into(), 3, 1),
+        TxnReceipt::rejected(
+            &rejected,
+            "proposal".into(),
+            "convergence".into(),
+            3,
+            1,
+            "voted invalid",
+        ),
+    ])
+    .unwrap();
+
+    let handle = db.read_handle();
+
+    let receipt = handle.transaction_receipt(&included_txn.id()).unwrap();
+    assert_eq!(receipt.status, TxnReceiptStatus::Included);
+    assert_eq!(
+        receipt.convergence_block_hash,
+        Some("convergence".to_string())
+    );
+    assert_eq!(receipt.round, Some(3));
+    assert_eq!(receipt.fee, included_txn.get_fee());
+
+    let receipt = handle.transaction_receipt(&rejected_txn.id()).unwrap();
+    assert_eq!(receipt.status, TxnReceiptStatus::Rejected);
+    assert_eq!(receipt.fee, 0);
+    assert_eq!(receipt.rejection_reason, Some("voted invalid".to_string()));
+
+    assert!(handle.transaction_receipt(&unknown_txn.id()).is_err());
+}
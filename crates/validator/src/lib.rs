@@ -13,7 +13,12 @@ mod tests {
     use primitives::{Address, Signature};
     use rand::{rngs::StdRng, Rng};
     use secp256k1::ecdsa;
-    use vrrb_core::{account::Account, keypair::KeyPair, txn::*};
+    use vrrb_core::{
+        account::{Account, AccountField},
+        keypair::KeyPair,
+        txn::*,
+        vesting::VestingSchedule,
+    };
 
     use crate::validator_core_manager::ValidatorCoreManager;
 
@@ -54,6 +59,92 @@ mod tests {
         })
     }
 
+    #[test]
+    fn should_only_validate_programs_of_deploy_txns() {
+        let validator = crate::txn_validator::TxnValidator::new();
+
+        let txn = random_txn();
+        assert!(validator.validate_program(&txn).is_ok());
+
+        let mut deploy_txn = random_txn();
+        deploy_txn.kind = TxnKind::Deploy {
+            code: vec![0x00, 0x61, 0x73, 0x73],
+        };
+
+        assert!(matches!(
+            validator.validate_program(&deploy_txn),
+            Err(crate::txn_validator::TxnValidatorError::InvalidProgram(_))
+        ));
+    }
+
+    #[test]
+    fn should_reject_deploys_to_existing_accounts() {
+        let validator = crate::txn_validator::TxnValidator::new();
+
+        let mut deploy_txn = random_txn();
+        deploy_txn.kind = TxnKind::Deploy { code: vec![] };
+
+        let mut account_state = HashMap::new();
+        assert!(validator
+            .validate_contract_address(&account_state, &deploy_txn)
+            .is_ok());
+
+        account_state.insert(
+            deploy_txn.receiver_address.clone(),
+            Account::new(deploy_txn.sender_public_key),
+        );
+
+        assert!(matches!(
+            validator.validate_contract_address(&account_state, &deploy_txn),
+            Err(crate::txn_validator::TxnValidatorError::ContractAddressInUse(_))
+        ));
+
+        // NOTE: regular transfers to existing accounts are fine
+        let txn = random_txn();
+        account_state.insert(
+            txn.receiver_address.clone(),
+            Account::new(txn.sender_public_key),
+        );
+
+        assert!(validator
+            .validate_contract_address(&account_state, &txn)
+            .is_ok());
+    }
+
+    #[test]
+    fn should_reject_spends_of_locked_funds() {
+        let validator = crate::txn_validator::TxnValidator::new();
+
+        let mut txn = random_txn();
+        txn.amount = 300;
+        txn.timestamp = 100;
+
+        let mut account = Account::new(txn.sender_public_key);
+        account.update_field(AccountField::Credits(1_000)).unwrap();
+        account.set_vesting(Some(VestingSchedule {
+            amount: 800,
+            cliff_timestamp: 200,
+            cliff_amount: 800,
+            unlocks: 0,
+            unlock_interval: 0,
+        }));
+
+        let mut account_state = HashMap::new();
+        account_state.insert(txn.sender_address.clone(), account);
+
+        assert_eq!(
+            validator.validate_amount(&account_state, &txn),
+            Err(crate::txn_validator::TxnValidatorError::TxnAmountLocked)
+        );
+
+        txn.amount = 200;
+        assert!(validator.validate_amount(&account_state, &txn).is_ok());
+
+        txn.amount = 300;
+        txn.timestamp = 200;
+        assert!(validator.validate_amount(&account_state, &txn).is_ok());
+    }
+
     #[test]
     #[ignore = "Needs to be rewritten to account for change in txn"]
     fn should_validate_a_list_of_invalid_transactions() {
@@ -2,6 +2,7 @@ use std::{collections::HashMap, result::Result as StdResult, str::FromStr};
 
 use primitives::Address;
 use vrrb_core::{account::Account, keypair::KeyPair, txn::Txn};
+use wasm_loader::wasm_loader::WasmLoaderBuilder;
 
 pub type Result<T> = StdResult<T, TxnValidatorError>;
 
@@ -42,6 +43,9 @@ pub enum TxnValidatorError {
     #[error("invalid amount")]
     TxnAmountIncorrect,
 
+    #[error("amount exceeds the funds unlocked by the sender's vesting schedule")]
+    TxnAmountLocked,
+
     #[error("invalid signature")]
     TxnSignatureIncorrect,
 
@@ -53,6 +57,12 @@ pub enum TxnValidatorError {
 
     #[error("account not found within state state_snapshot: {0}")]
     AccountNotFound(String),
+
+    #[error("invalid program: {0}")]
+    InvalidProgram(String),
+
+    #[error("contract address already in use: {0}")]
+    ContractAddressInUse(String),
 }
 
 #[derive(Debug, Clone, Default)]
@@ -83,6 +93,8 @@ impl TxnValidator {
             .and_then(|_| self.validate_receiver_address(txn))
             .and_then(|_| self.validate_signature(txn))
             .and_then(|_| self.validate_timestamp(txn))
+            .and_then(|_| self.validate_program(txn))
+            .and_then(|_| self.validate_contract_address(account_state, txn))
     }
 
     /// Txn signature validator.
@@ -152,6 +164,53 @@ impl TxnValidator {
         }
     }
 
+    /// Txn program validator. Only applies to contract deployments, which must
+    /// carry a parseable WASM module exporting a `_start` entry point and
+    /// the VRRB magic symbols.
+    pub fn validate_program(&self, txn: &Txn) -> Result<()> {
+        let code = match txn.deploy_code() {
+            Some(code) => code,
+            None => return Ok(()),
+        };
+
+        let wasm_loader = WasmLoaderBuilder::default()
+            .wasm_bytes(code.clone())
+            .parse()
+            .and_then(|builder| Ok(builder.build()?))
+            .map_err(|err| TxnValidatorError::InvalidProgram(err.to_string()))?;
+
+        if !wasm_loader.has_start {
+            return Err(TxnValidatorError::InvalidProgram(
+                "missing _start entry point".to_string(),
+            ));
+        }
+
+        if !wasm_loader.has_vrrb {
+            return Err(TxnValidatorError::InvalidProgram(
+                "missing VRRB magic exports".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Txn contract address validator. Contracts can only be deployed to
+    /// addresses that don't exist yet, so a deployment can't overwrite the
+    /// code of an existing account.
+    pub fn validate_contract_address(
+        &self,
+        account_state: &HashMap<Address, Account>,
+        txn: &Txn,
+    ) -> Result<()> {
+        if txn.is_deploy() && account_state.contains_key(&txn.receiver_address) {
+            return Err(TxnValidatorError::ContractAddressInUse(
+                txn.receiver_address.to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Txn receiver validator
     // TODO, to be synchronized with transaction fees.
     pub fn validate_amount(
@@ -168,6 +227,12 @@ impl TxnValidator {
             {
                 return Err(TxnValidatorError::TxnAmountIncorrect);
             };
+
+            // NOTE: funds still locked by the account's vesting schedule at
+            // the time the transaction was created cannot be spent
+            if account.spendable_balance(txn.timestamp) < txn.amount() {
+                return Err(TxnValidatorError::TxnAmountLocked);
+            }
         } else {
             return Err(TxnValidatorError::SenderAddressIncorrect);
         }
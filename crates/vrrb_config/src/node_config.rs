@@ -5,10 +5,10 @@ use std::{
 };
 
 use derive_builder::Builder;
-use primitives::{KademliaPeerId, NodeId, NodeIdx, NodeType, DEFAULT_VRRB_DATA_DIR_PATH};
+use primitives::{Epoch, KademliaPeerId, NodeId, NodeIdx, NodeType, DEFAULT_VRRB_DATA_DIR_PATH};
 use serde::Deserialize;
 use uuid::Uuid;
-use vrrb_core::keypair::Keypair;
+use vrrb_core::{keypair::Keypair, staking::DEFAULT_UNBONDING_EPOCHS};
 
 use crate::{
     bootstrap::BootstrapConfig, BootstrapQuorumConfig, QuorumMembershipConfig, ThresholdConfig,
@@ -112,6 +112,35 @@ pub struct NodeConfig {
     pub enable_block_indexing: bool,
 
     pub threshold_config: ThresholdConfig,
+
+    #[builder(default = "DEFAULT_UNBONDING_EPOCHS")]
+    /// Number of epochs withdrawn stake stays bonded before it is released
+    /// back to its staker
+    pub unbonding_epochs: Epoch,
+
+    #[builder(default = "None")]
+    /// JSON file holding the genesis allocation table, whose accounts are
+    /// written into state when the node starts
+    pub genesis_file: Option<PathBuf>,
+
+    #[builder(default = "SyncMode::Full")]
+    /// How the node catches up with the chain after joining the network
+    pub sync_mode: SyncMode,
+}
+
+/// How a node catches up with the chain when it joins the network or restarts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncMode {
+    /// Replays every block since genesis, or since the last block the node
+    /// committed before restarting
+    #[default]
+    Full,
+
+    /// Loads a snapshot of the state at the latest certified block, then
+    /// replays the blocks after it. Falls back to a full sync for nodes that
+    /// already hold state.
+    Fast,
 }
 
 impl NodeConfig {
@@ -188,6 +217,9 @@ impl Default for NodeConfig {
             disable_networking: false,
             threshold_config: ThresholdConfig::default(),
             enable_block_indexing: false,
+            unbonding_epochs: DEFAULT_UNBONDING_EPOCHS,
+            genesis_file: None,
+            sync_mode: SyncMode::Full,
         }
     }
 }
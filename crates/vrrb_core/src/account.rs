@@ -9,7 +9,7 @@ use primitives::{Address, SerializedPublicKey};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
-use crate::{txn::TransactionDigest, Error, Result};
+use crate::{txn::TransactionDigest, vesting::VestingSchedule, Error, Result};
 
 /// Enum containing options for updates - used to update value of single field
 /// in account struct.
@@ -174,6 +174,20 @@ impl Hash for UpdateArgs {
 
 pub type AccountNonce = u128;
 
+/// Produces the hex encoded SHA256 hash of the WASM module stored in an
+/// account's `code` field. Code is stored hex encoded, so the decoded module
+/// bytes are hashed whenever possible.
+pub fn hash_code(code: &str) -> String {
+    let mut hasher = Sha256::new();
+
+    match hex::decode(code) {
+        Ok(bytes) => hasher.update(bytes),
+        Err(_) => hasher.update(code.as_bytes()),
+    }
+
+    format!("{:x}", hasher.finalize())
+}
+
 #[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct Account {
     address: Address,
@@ -183,10 +197,16 @@ pub struct Account {
     debits: u128,
     storage: Option<String>,
     code: Option<String>,
+    #[serde(default)]
+    code_hash: Option<String>,
     pubkey: SerializedPublicKey,
     digests: AccountDigests,
     created_at: i64,
     updated_at: Option<i64>,
+    /// Schedule along which the funds granted to the account at genesis
+    /// unlock, if any
+    #[serde(default)]
+    vesting: Option<VestingSchedule>,
 }
 
 impl Account {
@@ -218,10 +238,12 @@ impl Account {
             debits,
             storage,
             code,
+            code_hash: None,
             pubkey,
             digests,
             created_at: Utc::now().timestamp(),
             updated_at: None,
+            vesting: None,
         }
     }
 
@@ -289,6 +311,7 @@ impl Account {
 
             // Should the code be impossible to delete?
             AccountField::Code(code) => {
+                self.code_hash = code.as_deref().map(hash_code);
                 self.code = code;
             },
 
@@ -398,6 +421,9 @@ impl Account {
     pub fn code(&self) -> &Option<String> {
         &self.code
     }
+    pub fn code_hash(&self) -> &Option<String> {
+        &self.code_hash
+    }
     pub fn pubkey(&self) -> &SerializedPublicKey {
         &self.pubkey
     }
@@ -410,6 +436,29 @@ impl Account {
     pub fn updated_at(&self) -> Option<i64> {
         self.updated_at
     }
+    pub fn vesting(&self) -> &Option<VestingSchedule> {
+        &self.vesting
+    }
+
+    pub fn set_vesting(&mut self, vesting: Option<VestingSchedule>) {
+        self.vesting = vesting;
+    }
+
+    /// Returns the part of the account's balance that is still locked by its
+    /// vesting schedule at the given time.
+    pub fn locked_balance(&self, timestamp: i64) -> u128 {
+        self.vesting
+            .as_ref()
+            .map_or(0, |vesting| vesting.locked_at(timestamp))
+    }
+
+    /// Returns the part of the account's balance that can be spent at the
+    /// given time.
+    pub fn spendable_balance(&self, timestamp: i64) -> u128 {
+        self.credits
+            .saturating_sub(self.debits)
+            .saturating_sub(self.locked_balance(timestamp))
+    }
 }
 
 #[cfg(test)]
@@ -426,4 +475,25 @@ mod tests {
 
         assert_eq!(account.nonce, 0);
     }
+
+    #[test]
+    fn locked_funds_should_not_be_spendable() {
+        let (_, pk) = generate_account_keypair();
+
+        let mut account = Account::new(pk);
+        account.update_field(AccountField::Credits(1_000)).unwrap();
+        account.set_vesting(Some(VestingSchedule {
+            amount: 800,
+            cliff_timestamp: 100,
+            cliff_amount: 400,
+            unlocks: 4,
+            unlock_interval: 10,
+        }));
+
+        assert_eq!(account.locked_balance(0), 800);
+        assert_eq!(account.spendable_balance(0), 200);
+        assert_eq!(account.spendable_balance(100), 600);
+        assert_eq!(account.spendable_balance(120), 800);
+        assert_eq!(account.spendable_balance(140), 1_000);
+    }
 }
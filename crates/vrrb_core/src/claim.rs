@@ -1,7 +1,7 @@
-use std::net::SocketAddr;
+use std::{collections::BTreeMap, net::SocketAddr};
 
 use ethereum_types::U256;
-use primitives::{Address, PublicKey, SerializedSecretKey};
+use primitives::{Address, Epoch, PublicKey, SerializedSecretKey};
 use serde::{Deserialize, Serialize};
 /// a Module for creating, maintaining, and using a claim in the fair,
 /// computationally inexpensive, collission proof, fully decentralized, fully
@@ -13,7 +13,7 @@ use thiserror::Error;
 use crate::{
     keypair::{KeyPairError, Keypair},
     ownable::Ownable,
-    staking::{Stake, StakeError, StakeUpdate},
+    staking::{Stake, StakeError, StakeUpdate, Unbonding},
 };
 
 pub type Result<T> = std::result::Result<T, ClaimError>;
@@ -52,6 +52,12 @@ pub struct Claim {
     pub signature: String,
     stake: u128,
     stake_txns: Vec<Stake>,
+    /// Stake other addresses delegated to the claim
+    #[serde(default)]
+    delegated_stake: u128,
+    /// Withdrawn stake waiting for its unbonding period to end
+    #[serde(default)]
+    unbonding: Vec<Unbonding>,
 }
 
 ///Node has privileges to be Miner/Validator,Farmer or None
@@ -105,6 +111,8 @@ impl Claim {
                 signature,
                 stake: 0,
                 stake_txns: vec![],
+                delegated_stake: 0,
+                unbonding: vec![],
             }),
             Err(e) => Err(e),
         };
@@ -232,58 +240,115 @@ impl Claim {
     /// the given claim's stake. This method is used within the
     /// state module to update a claim that has a transaction
     /// pointing to it, and has been included in a certified
-    /// convergence block.
+    /// convergence block. Withdrawn stake can be released right away.
     pub fn update_stake(&mut self, stake_txn: Stake) -> crate::staking::Result<()> {
-        if !self.depositing_claim(&stake_txn) {
+        self.update_stake_with_unbonding(stake_txn, 0)
+    }
+
+    /// Applies a stake transaction pointing to the claim, either staked by
+    /// the claim's own address or delegated to it by another one. Withdrawn
+    /// stake is queued until `release_epoch`, and slashes apply to the stake
+    /// delegated to the claim as well as the stake still unbonding from it.
+    pub fn update_stake_with_unbonding(
+        &mut self,
+        stake_txn: Stake,
+        release_epoch: Epoch,
+    ) -> crate::staking::Result<()> {
+        if !self.receiving_claim(&stake_txn) {
             return Err(StakeError::Other(
                 "This claim is not the intended receiver of the stake transaction".to_string(),
             ));
         }
 
-        if stake_txn.get_certificate().is_some() {
-            let prev_stake = self.stake;
-            self.stake_txns.push(stake_txn);
-            self.stake = self.check_stake_utxo();
+        if stake_txn.get_certificate().is_none() {
+            return Err(StakeError::UncertifiedStake);
+        }
 
-            if self.stake == prev_stake {
-                self.stake_txns.pop();
-            }
+        let staker = stake_txn.get_sender();
+        let amount = stake_txn.get_amount();
 
+        if matches!(amount, StakeUpdate::Slash(_)) && staker != self.address {
+            return Err(StakeError::Other(
+                "Only the claim's own address can be slashed".to_string(),
+            ));
+        }
+
+        let prev_stakes = self.check_stake_utxo();
+        let prev_unbonding = self.unbonding.clone();
+
+        self.stake_txns.push(stake_txn);
+        let stakes = self.check_stake_utxo();
+
+        if let StakeUpdate::Slash(pct) = amount {
+            self.unbonding.iter_mut().for_each(|unbonding| {
+                unbonding.amount = Self::slash_calculator(pct, unbonding.amount);
+            });
+            self.unbonding.retain(|unbonding| unbonding.amount > 0);
+        }
+
+        if stakes == prev_stakes && self.unbonding == prev_unbonding {
+            self.stake_txns.pop();
             return Ok(());
         }
 
-        Err(StakeError::UncertifiedStake)
+        if let StakeUpdate::Withdrawal(_) = amount {
+            let prev_stake = prev_stakes.get(&staker).copied().unwrap_or_default();
+            let stake = stakes.get(&staker).copied().unwrap_or_default();
+
+            self.unbonding.push(Unbonding {
+                staker,
+                amount: prev_stake.saturating_sub(stake),
+                release_epoch,
+            });
+        }
+
+        self.stake = stakes.get(&self.address).copied().unwrap_or_default();
+        self.delegated_stake = stakes
+            .iter()
+            .filter(|(staker, _)| *staker != &self.address)
+            .fold(0u128, |acc, (_, stake)| acc.saturating_add(*stake));
+
+        Ok(())
     }
 
-    fn depositing_claim(&self, stake_txn: &Stake) -> bool {
-        stake_txn.get_sender() == self.address
+    fn receiving_claim(&self, stake_txn: &Stake) -> bool {
+        stake_txn.get_receiver() == self.address
     }
 
-    /// Checks the cumulative value of a nodes stake by calculating
-    /// the UTXO of the stake transactions.
-    fn check_stake_utxo(&self) -> u128 {
-        self.stake_txns
+    /// Checks the cumulative value of the stake every address put in the
+    /// claim by calculating the UTXO of the stake transactions.
+    fn check_stake_utxo(&self) -> BTreeMap<Address, u128> {
+        let mut stakes = self
+            .stake_txns
             .iter()
-            .fold(0u128, |mut acc, val| match val.get_amount() {
-                StakeUpdate::Add(value) => {
-                    if let Some(v) = acc.checked_add(value) {
-                        acc = v;
-                    }
-                    acc
-                },
-                StakeUpdate::Withdrawal(value) => {
-                    if let Some(v) = acc.checked_sub(value) {
-                        acc = v;
-                    }
-                    acc
-                },
-                StakeUpdate::Slash(pct) => self.slash_calculator(pct, acc),
-            })
+            .fold(BTreeMap::new(), |mut stakes, val| {
+                match val.get_amount() {
+                    StakeUpdate::Add(value) => {
+                        let stake = stakes.entry(val.get_sender()).or_insert(0u128);
+                        if let Some(v) = stake.checked_add(value) {
+                            *stake = v;
+                        }
+                    },
+                    StakeUpdate::Withdrawal(value) => {
+                        let stake = stakes.entry(val.get_sender()).or_insert(0u128);
+                        if let Some(v) = stake.checked_sub(value) {
+                            *stake = v;
+                        }
+                    },
+                    StakeUpdate::Slash(pct) => stakes
+                        .values_mut()
+                        .for_each(|stake| *stake = Self::slash_calculator(pct, *stake)),
+                }
+                stakes
+            });
+
+        stakes.retain(|_, stake| *stake > 0);
+        stakes
     }
 
     /// Returns the slashed value of a nodes stake after a slashing
     /// event.
-    fn slash_calculator(&self, pct: u8, value: u128) -> u128 {
+    fn slash_calculator(pct: u8, value: u128) -> u128 {
         let slash = (value as f64) * (pct as f64 / 100f64);
         value - slash as u128
     }
@@ -296,6 +361,41 @@ impl Claim {
         self.stake_txns.clone()
     }
 
+    /// Returns the stake other addresses delegated to the claim.
+    pub fn get_delegated_stake(&self) -> u128 {
+        self.delegated_stake
+    }
+
+    /// Returns the claim's own stake along with the stake delegated to it,
+    /// which is what the claim weighs in elections.
+    pub fn get_total_stake(&self) -> u128 {
+        self.stake.saturating_add(self.delegated_stake)
+    }
+
+    /// Returns the stake delegated to the claim by each delegator.
+    pub fn get_delegations(&self) -> BTreeMap<Address, u128> {
+        let mut delegations = self.check_stake_utxo();
+        delegations.remove(&self.address);
+        delegations
+    }
+
+    /// Returns the withdrawn stake that was not released yet.
+    pub fn get_unbonding(&self) -> Vec<Unbonding> {
+        self.unbonding.clone()
+    }
+
+    /// Removes and returns the withdrawn stake whose unbonding period is over
+    /// at the given epoch.
+    pub fn release_unbonded(&mut self, epoch: Epoch) -> Vec<Unbonding> {
+        let (released, unbonding) = std::mem::take(&mut self.unbonding)
+            .into_iter()
+            .partition(|unbonding| unbonding.release_epoch <= epoch);
+
+        self.unbonding = unbonding;
+
+        released
+    }
+
     #[deprecated(note = "Please use get_election_result")]
     pub fn get_pointer(&self, block_seed: u128) -> Option<u128> {
         let block_seed_hex = format!("{block_seed:x}");
@@ -395,6 +495,8 @@ mod tests {
             signature: signature.clone(),
             stake: 0,
             stake_txns: vec![],
+            delegated_stake: 0,
+            unbonding: vec![],
         };
         let claim = Claim::new(public_key, address, ip_address, signature).unwrap();
         assert_eq!(test_claim, claim);
@@ -822,4 +924,107 @@ mod tests {
         assert_eq!(claim.get_stake(), 90_000u128);
         assert_eq!(claim.get_stake_txns().len(), 2);
     }
+
+    fn claim_for(kp: &KeyPair) -> Claim {
+        let public_key = kp.miner_kp.1;
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+
+        Claim::new(public_key, Address::new(public_key), ip_address, signature).unwrap()
+    }
+
+    fn certified_stake(kp: &KeyPair, amount: StakeUpdate, to: Option<Address>) -> Stake {
+        let from = Address::new(kp.miner_kp.1);
+        let mut stake = Stake::new(amount, kp.miner_kp.0, kp.miner_kp.1, from, to).unwrap();
+        stake.certify((vec![0; 96], vec![0; 96])).unwrap();
+
+        stake
+    }
+
+    #[test]
+    fn delegated_stake_should_count_toward_target_claim() {
+        let kp = KeyPair::random();
+        let delegator_kp = KeyPair::random();
+        let mut claim = claim_for(&kp);
+        let mut delegator_claim = claim_for(&delegator_kp);
+
+        let own_stake = certified_stake(&kp, StakeUpdate::Add(10_000), None);
+        let delegation = certified_stake(
+            &delegator_kp,
+            StakeUpdate::Add(20_000),
+            Some(claim.address.clone()),
+        );
+
+        assert!(delegator_claim.update_stake(delegation.clone()).is_err());
+        assert!(claim.update_stake(own_stake).is_ok());
+        assert!(claim.update_stake(delegation).is_ok());
+
+        assert_eq!(claim.get_stake(), 10_000u128);
+        assert_eq!(claim.get_delegated_stake(), 20_000u128);
+        assert_eq!(claim.get_total_stake(), 30_000u128);
+        assert_eq!(
+            claim.get_delegations().get(&delegator_claim.address),
+            Some(&20_000u128)
+        );
+
+        let slash = certified_stake(
+            &delegator_kp,
+            StakeUpdate::Slash(10),
+            Some(claim.address.clone()),
+        );
+        assert!(claim.update_stake(slash).is_err());
+
+        let slash = certified_stake(&kp, StakeUpdate::Slash(10), None);
+        assert!(claim.update_stake(slash).is_ok());
+        assert_eq!(claim.get_total_stake(), 27_000u128);
+    }
+
+    #[test]
+    fn withdrawn_stake_should_unbond_until_release_epoch() {
+        let kp = KeyPair::random();
+        let delegator_kp = KeyPair::random();
+        let mut claim = claim_for(&kp);
+        let delegator = Address::new(delegator_kp.miner_kp.1);
+
+        let delegation = certified_stake(
+            &delegator_kp,
+            StakeUpdate::Add(20_000),
+            Some(claim.address.clone()),
+        );
+        let withdrawal = certified_stake(
+            &delegator_kp,
+            StakeUpdate::Withdrawal(5_000),
+            Some(claim.address.clone()),
+        );
+
+        assert!(claim.update_stake(delegation).is_ok());
+        assert!(claim.update_stake_with_unbonding(withdrawal, 7).is_ok());
+
+        assert_eq!(claim.get_delegated_stake(), 15_000u128);
+        assert_eq!(
+            claim.get_unbonding(),
+            vec![Unbonding {
+                staker: delegator.clone(),
+                amount: 5_000,
+                release_epoch: 7,
+            }]
+        );
+
+        let slash = certified_stake(&kp, StakeUpdate::Slash(20), None);
+        assert!(claim.update_stake(slash).is_ok());
+        assert_eq!(claim.get_delegated_stake(), 12_000u128);
+
+        assert!(claim.release_unbonded(6).is_empty());
+
+        let released = claim.release_unbonded(7);
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].staker, delegator);
+        assert_eq!(released[0].amount, 4_000u128);
+        assert!(claim.get_unbonding().is_empty());
+    }
 }
@@ -9,13 +9,18 @@ pub mod helpers;
 pub mod keypair;
 pub mod nonceable;
 pub mod ownable;
+pub mod proof;
+pub mod receipt;
+pub mod reputation;
 pub mod result;
+pub mod reward_credit;
 pub mod serde_helpers;
 pub mod staking;
 pub mod storage_utils;
 pub mod txn;
 pub mod updateable;
 pub mod verifiable;
+pub mod vesting;
 pub mod node_health_report;
 
 pub use result::*;
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use patriecia::{db::MemoryDB, inner::InnerTrie, trie::Trie, H256};
+use primitives::ByteVec;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, ProofError>;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ProofError {
+    #[error("failed to encode proof key: {0}")]
+    InvalidKey(String),
+    #[error("proof does not match the root hash: {0}")]
+    InvalidProof(String),
+    #[error("failed to decode proven value: {0}")]
+    InvalidValue(String),
+}
+
+/// A Merkle inclusion proof for a single entry of one of the node's tries.
+///
+/// A proof carries everything needed to check it, so light clients can verify
+/// the inclusion of a transaction, account or claim against a root hash they
+/// trust without querying the node again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// The serialized key of the entry the proof is for.
+    pub key: ByteVec,
+    /// The encoded trie nodes on the path from the root to the entry.
+    pub nodes: Vec<ByteVec>,
+    /// The root hash of the trie the proof was generated from.
+    pub root_hash: [u8; 32],
+}
+
+impl MerkleProof {
+    pub fn new<K: Serialize>(key: &K, nodes: Vec<ByteVec>, root_hash: H256) -> Result<Self> {
+        let key = bincode::serialize(key).map_err(|err| ProofError::InvalidKey(err.to_string()))?;
+
+        Ok(Self {
+            key,
+            nodes,
+            root_hash: root_hash.0,
+        })
+    }
+
+    pub fn root_hash(&self) -> H256 {
+        H256::from(self.root_hash)
+    }
+
+    /// Verifies the proof against its own root hash and returns the encoded
+    /// value it proves, or `None` if it proves the key is absent.
+    pub fn verify(&self) -> Result<Option<ByteVec>> {
+        self.verify_against(self.root_hash())
+    }
+
+    /// Verifies the proof against a root hash obtained independently of the
+    /// node, such as from a block header.
+    pub fn verify_against(&self, root_hash: H256) -> Result<Option<ByteVec>> {
+        // Verification only walks the nodes contained in the proof, so an empty
+        // in-memory trie is enough to drive it.
+        let trie = InnerTrie::new(Arc::new(MemoryDB::new(true)));
+
+        trie.verify_proof(root_hash, &self.key, self.nodes.clone())
+            .map_err(|err| ProofError::InvalidProof(err.to_string()))
+    }
+
+    /// Verifies the proof against `root_hash` and decodes the proven value.
+    pub fn verify_value<V: DeserializeOwned>(&self, root_hash: H256) -> Result<Option<V>> {
+        self.verify_against(root_hash)?
+            .map(|value| {
+                bincode::deserialize(&value).map_err(|err| ProofError::InvalidValue(err.to_string()))
+            })
+            .transpose()
+    }
+
+    /// Returns true if the proof shows that `key` maps to `value` in the trie
+    /// with the given root hash.
+    pub fn proves<K, V>(&self, root_hash: H256, key: &K, value: &V) -> bool
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        let (key, value) = match (bincode::serialize(key), bincode::serialize(value)) {
+            (Ok(key), Ok(value)) => (key, value),
+            _ => return false,
+        };
+
+        key == self.key && matches!(self.verify_against(root_hash), Ok(Some(proven)) if proven == value)
+    }
+}
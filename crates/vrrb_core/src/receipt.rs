@@ -0,0 +1,101 @@
+use primitives::Epoch;
+use serde::{Deserialize, Serialize};
+
+use crate::txn::{QuorumCertifiedTxn, TransactionDigest, VoteReceipt};
+
+/// Where a transaction is within its lifecycle, from being accepted into the
+/// mempool to being included into a committed `ConvergenceBlock`.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxnReceiptStatus {
+    /// The transaction is waiting in the mempool to be validated.
+    #[default]
+    Pending,
+    /// A quorum validated the transaction but it has not been committed yet.
+    Validated,
+    /// The transaction was dropped, see `TxnReceipt::rejection_reason`.
+    Rejected,
+    /// The transaction was committed as part of a `ConvergenceBlock`.
+    Included,
+}
+
+/// Records the outcome of a transaction along with where it landed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxnReceipt {
+    pub txn_id: TransactionDigest,
+    pub status: TxnReceiptStatus,
+    /// Hash of the `ProposalBlock` that proposed the transaction.
+    pub proposal_block_hash: Option<String>,
+    /// Hash of the `ConvergenceBlock` the transaction was committed with.
+    pub convergence_block_hash: Option<String>,
+    pub round: Option<u128>,
+    pub epoch: Option<Epoch>,
+    /// Fee charged to the sender, 0 unless the transaction was included.
+    pub fee: u128,
+    /// Votes cast by the validators of the quorum that certified the
+    /// transaction.
+    pub votes: Vec<VoteReceipt>,
+    pub rejection_reason: Option<String>,
+}
+
+impl TxnReceipt {
+    /// Creates a receipt for a transaction that has not been proposed yet.
+    pub fn new(txn_id: TransactionDigest, status: TxnReceiptStatus) -> Self {
+        Self {
+            txn_id,
+            status,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a receipt for a transaction committed as part of the given
+    /// blocks.
+    pub fn included(
+        txn: &QuorumCertifiedTxn,
+        proposal_block_hash: String,
+        convergence_block_hash: String,
+        round: u128,
+        epoch: Epoch,
+    ) -> Self {
+        Self {
+            txn_id: txn.txn().id(),
+            status: TxnReceiptStatus::Included,
+            proposal_block_hash: Some(proposal_block_hash),
+            convergence_block_hash: Some(convergence_block_hash),
+            round: Some(round),
+            epoch: Some(epoch),
+            fee: txn.get_fee(),
+            votes: txn.votes().to_vec(),
+            rejection_reason: None,
+        }
+    }
+
+    /// Creates a receipt for a proposed transaction that was left out of the
+    /// given `ConvergenceBlock`.
+    pub fn rejected(
+        txn: &QuorumCertifiedTxn,
+        proposal_block_hash: String,
+        convergence_block_hash: String,
+        round: u128,
+        epoch: Epoch,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            txn_id: txn.txn().id(),
+            status: TxnReceiptStatus::Rejected,
+            proposal_block_hash: Some(proposal_block_hash),
+            convergence_block_hash: Some(convergence_block_hash),
+            round: Some(round),
+            epoch: Some(epoch),
+            fee: 0,
+            votes: txn.votes().to_vec(),
+            rejection_reason: Some(reason.into()),
+        }
+    }
+
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self.status,
+            TxnReceiptStatus::Included | TxnReceiptStatus::Rejected
+        )
+    }
+}
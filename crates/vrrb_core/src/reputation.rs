@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+/// Behaviour observed from a peer while it takes part in consensus.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PeerBehaviour {
+    /// The peer voted on a transaction, along with whether its vote matched
+    /// the one certified by the quorum.
+    VoteCast { with_majority: bool },
+    /// The peer delivered its partial signature of a block, along with
+    /// whether it arrived before the block was certified.
+    PartialSignatureDelivered { on_time: bool },
+    /// The peer's claim turned out to be abandoned.
+    ClaimAbandoned,
+    /// The peer was expected to take part in a round but never did.
+    RoundMissed,
+}
+
+/// Tallies the behaviour observed from a single peer.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerReputation {
+    pub votes_cast: u64,
+    pub votes_with_majority: u64,
+    pub partial_signatures_on_time: u64,
+    pub partial_signatures_late: u64,
+    pub claims_abandoned: u64,
+    pub rounds_missed: u64,
+}
+
+impl PeerReputation {
+    /// Peers scoring below this are considered untrusted.
+    pub const MIN_TRUSTED_SCORE: f64 = 0.25;
+
+    /// How many bad observations an abandoned claim counts for.
+    pub const CLAIM_ABANDONED_PENALTY: u64 = 5;
+
+    /// How many bad observations a missed round counts for.
+    pub const ROUND_MISSED_PENALTY: u64 = 2;
+
+    pub fn record(&mut self, behaviour: PeerBehaviour) {
+        match behaviour {
+            PeerBehaviour::VoteCast { with_majority } => {
+                self.votes_cast += 1;
+                if with_majority {
+                    self.votes_with_majority += 1;
+                }
+            },
+            PeerBehaviour::PartialSignatureDelivered { on_time: true } => {
+                self.partial_signatures_on_time += 1;
+            },
+            PeerBehaviour::PartialSignatureDelivered { on_time: false } => {
+                self.partial_signatures_late += 1;
+            },
+            PeerBehaviour::ClaimAbandoned => self.claims_abandoned += 1,
+            PeerBehaviour::RoundMissed => self.rounds_missed += 1,
+        }
+    }
+
+    fn good_observations(&self) -> u64 {
+        self.votes_with_majority + self.partial_signatures_on_time
+    }
+
+    fn bad_observations(&self) -> u64 {
+        (self.votes_cast - self.votes_with_majority)
+            + self.partial_signatures_late
+            + self.claims_abandoned * Self::CLAIM_ABANDONED_PENALTY
+            + self.rounds_missed * Self::ROUND_MISSED_PENALTY
+    }
+
+    /// Returns the peer's trust value, between 0 and 1, as the expected value
+    /// of a beta distribution over its good and bad observations. Peers that
+    /// have not been observed yet score 0.5.
+    pub fn score(&self) -> f64 {
+        let good = self.good_observations() as f64;
+        let bad = self.bad_observations() as f64;
+
+        (good + 1.0) / (good + bad + 2.0)
+    }
+
+    pub fn is_trusted(&self) -> bool {
+        self.score() >= Self::MIN_TRUSTED_SCORE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unobserved_peers_are_trusted() {
+        let reputation = PeerReputation::default();
+
+        assert_eq!(reputation.score(), 0.5);
+        assert!(reputation.is_trusted());
+    }
+
+    #[test]
+    fn misbehaving_peers_lose_trust() {
+        let mut reputation = PeerReputation::default();
+
+        for _ in 0..10 {
+            reputation.record(PeerBehaviour::VoteCast {
+                with_majority: true,
+            });
+        }
+        reputation.record(PeerBehaviour::PartialSignatureDelivered { on_time: true });
+
+        let trusted_score = reputation.score();
+        assert!(trusted_score > 0.5);
+
+        reputation.record(PeerBehaviour::VoteCast {
+            with_majority: false,
+        });
+        reputation.record(PeerBehaviour::RoundMissed);
+        assert!(reputation.score() < trusted_score);
+        assert!(reputation.is_trusted());
+
+        for _ in 0..10 {
+            reputation.record(PeerBehaviour::ClaimAbandoned);
+        }
+
+        assert_eq!(reputation.votes_cast, 11);
+        assert_eq!(reputation.votes_with_majority, 10);
+        assert!(!reputation.is_trusted());
+    }
+}
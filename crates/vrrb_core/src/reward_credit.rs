@@ -0,0 +1,31 @@
+use primitives::{Address, Epoch, Round};
+use serde::{Deserialize, Serialize};
+
+/// Why an account was credited when a `ConvergenceBlock` was committed.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RewardKind {
+    /// The block reward paid to the miner of the `ConvergenceBlock`.
+    BlockReward,
+    /// The share of transaction fees paid to the proposer of a
+    /// `ProposalBlock`.
+    ProposerFee,
+    /// The share of transaction fees paid to the farmers that voted on a
+    /// transaction.
+    ValidatorFee,
+    /// The cut of another reward paid to a delegator of the rewarded claim,
+    /// pro rata to its delegated stake.
+    Delegation,
+}
+
+/// Records a single credit of a block reward or fee share, so rewards can be
+/// audited per epoch.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardCredit {
+    pub epoch: Epoch,
+    pub round: Round,
+    /// Hash of the `ConvergenceBlock` whose commit paid the reward.
+    pub block_hash: String,
+    pub address: Address,
+    pub amount: u128,
+    pub kind: RewardKind,
+}
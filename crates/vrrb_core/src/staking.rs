@@ -1,4 +1,5 @@
-use primitives::{Address, PayloadHash, QuorumPublicKey, Signature};
+use hbbft::crypto::{PublicKey, Signature as ThresholdSignature, SIG_SIZE};
+use primitives::{Address, Epoch, PayloadHash, QuorumPublicKey, Signature};
 use secp256k1::Message;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -11,6 +12,9 @@ use crate::keypair::{MinerPk, MinerSk};
 pub type Certificate = (Vec<u8>, PayloadHash);
 pub const MIN_STAKE_FARMER: u128 = 10_000;
 pub const MIN_STAKE_VALIDATOR: u128 = 50_000;
+/// Number of epochs withdrawn stake stays locked before it is released, unless
+/// configured otherwise.
+pub const DEFAULT_UNBONDING_EPOCHS: Epoch = 7;
 
 pub type Result<T> = std::result::Result<T, StakeError>;
 
@@ -49,6 +53,15 @@ pub enum StakeUpdate {
     Slash(u8),
 }
 
+/// Stake withdrawn from a claim that is still locked. It can be slashed like
+/// bonded stake until it is released back to `staker` at `release_epoch`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Unbonding {
+    pub staker: Address,
+    pub amount: u128,
+    pub release_epoch: Epoch,
+}
+
 /// A struct thatt defines a stake, includes the public key (which
 /// can be converted into an address) an amount, which is an instance
 /// of the `StakeUpdate` enum, a timestamp to sequence it in the
@@ -235,11 +248,37 @@ impl Stake {
 
         Err(StakeError::InvalidPayload)
     }
+
+    /// Verifies that the certificate of the StakeTransaction is a threshold
+    /// signature over its payload by the quorum with the given group public
+    /// key.
+    pub fn verify_certificate(&self, quorum_public_key: &PublicKey) -> Result<()> {
+        let (signature, payload) = self
+            .certificate
+            .as_ref()
+            .ok_or(StakeError::UncertifiedStake)?;
+
+        if payload != &self.get_payload() {
+            return Err(StakeError::InvalidCertificate);
+        }
+
+        let signature = TryInto::<[u8; SIG_SIZE]>::try_into(signature.as_slice())
+            .ok()
+            .and_then(|bytes| ThresholdSignature::from_bytes(bytes).ok())
+            .ok_or(StakeError::InvalidCertificate)?;
+
+        if !quorum_public_key.verify(&signature, payload) {
+            return Err(StakeError::InvalidCertificate);
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use hbbft::crypto::SecretKey;
     use primitives::Address;
 
     use super::*;
@@ -273,6 +312,33 @@ mod tests {
         assert!(stake.get_certificate().is_some());
     }
 
+    #[test]
+    fn should_verify_certificate_against_quorum_key() {
+        let keypair = KeyPair::random();
+        let sk = keypair.miner_kp.0.clone();
+        let pk = keypair.miner_kp.1.clone();
+        let amount = StakeUpdate::Add(10_000u128);
+        let from = Address::new(pk.clone());
+
+        let mut stake = Stake::new(amount, sk, pk, from, None).unwrap();
+        assert_eq!(
+            stake.verify_certificate(&SecretKey::random().public_key()),
+            Err(StakeError::UncertifiedStake)
+        );
+
+        let quorum_key = SecretKey::random();
+        let payload = stake.get_payload();
+        let signature = quorum_key.sign(&payload).to_bytes().to_vec();
+
+        stake.certify((signature, payload)).unwrap();
+
+        assert!(stake.verify_certificate(&quorum_key.public_key()).is_ok());
+        assert_eq!(
+            stake.verify_certificate(&SecretKey::random().public_key()),
+            Err(StakeError::InvalidCertificate)
+        );
+    }
+
     #[test]
     fn should_verify_signature() {
         let keypair = KeyPair::random();
@@ -110,11 +110,41 @@ impl Default for Token {
     }
 }
 
+/// Distinguishes plain value transfers from transactions that carry a
+/// payload to be applied to the receiver's account.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Hash, PartialEq, Eq)]
+pub enum TxnKind {
+    #[default]
+    Transfer,
+    /// Deploys the contained WASM module to the (new) contract account
+    /// at the transaction's receiver address.
+    Deploy { code: ByteVec },
+}
+
+impl TxnKind {
+    /// Extends a transaction digest with the hash of the kind's payload, so
+    /// that two transactions which only differ in their payload never share a
+    /// digest. Plain transfers keep their original digest.
+    fn extend_digest(&self, digest: ByteVec) -> ByteVec {
+        match self {
+            TxnKind::Transfer => digest,
+            TxnKind::Deploy { code } => {
+                let mut hasher = Sha256::new();
+                hasher.update(digest);
+                hasher.update(Sha256::digest(code));
+                hasher.finalize().to_vec()
+            },
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Hash, Clone, PartialEq, Eq)]
 pub struct VoteReceipt {
     /// The identity of the voter.
     pub farmer_id: Vec<u8>,
     pub farmer_node_id: NodeIdx,
+    /// Address credited with the voter's share of the validator fees.
+    pub farmer_address: Address,
     /// Partial Signature
     pub signature: RawSignature,
 }
@@ -151,6 +181,10 @@ impl QuorumCertifiedTxn {
         self.txn.clone()
     }
 
+    pub fn votes(&self) -> &[VoteReceipt] {
+        &self.votes
+    }
+
     pub fn get_fee(&self) -> u128 {
         self.txn.get_fee()
     }
@@ -176,6 +210,8 @@ pub struct Txn {
     pub signature: Signature,
     pub validators: Option<HashMap<String, bool>>,
     pub nonce: TxNonce,
+    #[serde(default)]
+    pub kind: TxnKind,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -225,9 +261,21 @@ impl Txn {
             signature: args.signature,
             validators: args.validators,
             nonce: args.nonce,
+            kind: TxnKind::default(),
         }
     }
 
+    /// Creates a transaction deploying the given WASM module to the contract
+    /// account at `args.receiver_address`. The signature in `args` is
+    /// expected to cover the module's code, see [Txn::build_payload].
+    pub fn new_deploy(args: NewTxnArgs, code: ByteVec) -> Self {
+        let mut txn = Txn::new(args);
+        txn.kind = TxnKind::Deploy { code };
+        txn.id = txn.build_payload_digest();
+
+        txn
+    }
+
     pub fn null_txn() -> Txn {
         let timestamp = chrono::Utc::now().timestamp();
         let kp = Keypair::random();
@@ -272,6 +320,7 @@ impl Txn {
             signature,
             validators: None,
             nonce: 0,
+            kind: TxnKind::default(),
         }
     }
 
@@ -281,17 +330,7 @@ impl Txn {
     }
 
     pub fn build_payload_digest(&self) -> TransactionDigest {
-        let digest = generate_txn_digest_vec(
-            self.timestamp(),
-            self.sender_address().to_string(),
-            self.sender_public_key(),
-            self.receiver_address().to_string(),
-            self.token(),
-            self.amount(),
-            self.nonce(),
-        );
-
-        digest.into()
+        self.generate_txn_digest_vec().into()
     }
 
     #[deprecated]
@@ -359,8 +398,25 @@ impl Txn {
         self.validators.clone().unwrap_or_default()
     }
 
+    pub fn kind(&self) -> &TxnKind {
+        &self.kind
+    }
+
+    /// Returns the WASM code carried by the transaction if it is a
+    /// contract deployment
+    pub fn deploy_code(&self) -> Option<&ByteVec> {
+        match &self.kind {
+            TxnKind::Deploy { code } => Some(code),
+            TxnKind::Transfer => None,
+        }
+    }
+
+    pub fn is_deploy(&self) -> bool {
+        self.deploy_code().is_some()
+    }
+
     pub fn generate_txn_digest_vec(&self) -> ByteVec {
-        generate_txn_digest_vec(
+        let digest = generate_txn_digest_vec(
             self.timestamp(),
             self.sender_address().to_string(),
             self.sender_public_key(),
@@ -368,21 +424,37 @@ impl Txn {
             self.token(),
             self.amount(),
             self.nonce(),
-        )
+        );
+
+        self.kind.extend_digest(digest)
     }
 
     pub fn build_payload(&self) -> String {
-        format!(
-            "{:x}",
-            hash_data!(
-                self.sender_address.clone(),
-                self.sender_public_key.clone(),
-                self.receiver_address.clone(),
-                self.token.clone(),
-                self.amount.clone(),
-                self.nonce.clone()
-            )
-        )
+        match &self.kind {
+            TxnKind::Transfer => format!(
+                "{:x}",
+                hash_data!(
+                    self.sender_address.clone(),
+                    self.sender_public_key.clone(),
+                    self.receiver_address.clone(),
+                    self.token.clone(),
+                    self.amount.clone(),
+                    self.nonce.clone()
+                )
+            ),
+            TxnKind::Deploy { code } => format!(
+                "{:x}",
+                hash_data!(
+                    self.sender_address.clone(),
+                    self.sender_public_key.clone(),
+                    self.receiver_address.clone(),
+                    self.token.clone(),
+                    self.amount.clone(),
+                    self.nonce.clone(),
+                    code.clone()
+                )
+            ),
+        }
     }
 
     fn from_byte_slice(data: ByteSlice) -> Self {
@@ -486,6 +558,7 @@ impl Hash for Txn {
         self.amount.hash(state);
         self.signature.hash(state);
         self.nonce.hash(state);
+        self.kind.hash(state);
     }
 
     fn hash_slice<H: Hasher>(data: &[Self], state: &mut H)
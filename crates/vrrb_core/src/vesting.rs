@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+/// Number of seconds within a year, accounting for leap years.
+pub const SECONDS_PER_YEAR: i64 = 31_557_600;
+
+/// Schedule along which funds granted at genesis unlock: `cliff_amount`
+/// unlocks at `cliff_timestamp`, then the rest unlocks in `unlocks` equal
+/// parts, one every `unlock_interval` seconds after the cliff.
+///
+/// Timestamps are unix timestamps, in seconds.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct VestingSchedule {
+    /// Total amount granted, locked or not
+    pub amount: u128,
+    pub cliff_timestamp: i64,
+    pub cliff_amount: u128,
+    pub unlocks: u32,
+    pub unlock_interval: i64,
+}
+
+impl VestingSchedule {
+    /// Number of unlocks after the cliff that happened by the given time.
+    fn elapsed_unlocks(&self, timestamp: i64) -> u32 {
+        if self.unlock_interval <= 0 {
+            return self.unlocks;
+        }
+
+        let elapsed = (timestamp - self.cliff_timestamp) / self.unlock_interval;
+
+        elapsed.clamp(0, self.unlocks as i64) as u32
+    }
+
+    /// Returns the amount that unlocked by the given time.
+    pub fn vested_at(&self, timestamp: i64) -> u128 {
+        if timestamp < self.cliff_timestamp {
+            return 0;
+        }
+
+        if self.unlocks == 0 {
+            return self.amount;
+        }
+
+        let cliff_amount = self.cliff_amount.min(self.amount);
+        let remaining = self.amount - cliff_amount;
+        let unlocked = remaining.saturating_mul(self.elapsed_unlocks(timestamp) as u128)
+            / self.unlocks as u128;
+
+        cliff_amount + unlocked
+    }
+
+    /// Returns the amount that is still locked at the given time.
+    pub fn locked_at(&self, timestamp: i64) -> u128 {
+        self.amount - self.vested_at(timestamp)
+    }
+
+    /// Returns the time and amount of the first unlock after the given time,
+    /// if any amount is still locked.
+    pub fn next_unlock(&self, timestamp: i64) -> Option<(i64, u128)> {
+        if self.locked_at(timestamp) == 0 {
+            return None;
+        }
+
+        let unlock_timestamp = if timestamp < self.cliff_timestamp {
+            self.cliff_timestamp
+        } else {
+            let next_unlock = self.elapsed_unlocks(timestamp) as i64 + 1;
+            self.cliff_timestamp + next_unlock * self.unlock_interval
+        };
+
+        let amount = self.vested_at(unlock_timestamp) - self.vested_at(timestamp);
+
+        Some((unlock_timestamp, amount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn funds_unlock_at_the_cliff_then_linearly() {
+        let schedule = VestingSchedule {
+            amount: 1_200,
+            cliff_timestamp: 1_000,
+            cliff_amount: 600,
+            unlocks: 12,
+            unlock_interval: 10,
+        };
+
+        assert_eq!(schedule.vested_at(999), 0);
+        assert_eq!(schedule.locked_at(999), 1_200);
+        assert_eq!(schedule.next_unlock(0), Some((1_000, 600)));
+
+        assert_eq!(schedule.vested_at(1_000), 600);
+        assert_eq!(schedule.vested_at(1_009), 600);
+        assert_eq!(schedule.next_unlock(1_009), Some((1_010, 50)));
+
+        assert_eq!(schedule.vested_at(1_010), 650);
+        assert_eq!(schedule.vested_at(1_065), 900);
+        assert_eq!(schedule.locked_at(1_065), 300);
+
+        assert_eq!(schedule.vested_at(1_120), 1_200);
+        assert_eq!(schedule.vested_at(i64::MAX), 1_200);
+        assert_eq!(schedule.next_unlock(1_120), None);
+    }
+}
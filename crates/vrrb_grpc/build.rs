@@ -0,0 +1,11 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tonic_build::configure().compile(
+        &[
+            "../../infra/proto/node_read_service/v1/node_read_service.proto",
+            "../../infra/proto/node_write_service/v1/node_write_service.proto",
+        ],
+        &["../../infra/proto"],
+    )?;
+
+    Ok(())
+}
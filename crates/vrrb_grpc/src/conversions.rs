@@ -0,0 +1,151 @@
+use tonic::Status;
+
+/// Implements the conversions between core transactions and the
+/// `TransactionRecord` and `Token` messages of the given service module.
+/// Every service declares messages of its own, so they are implemented once
+/// per module.
+macro_rules! impl_txn_conversions {
+    ($($module:ident)::+) => {
+        impl From<vrrb_core::txn::Token> for $($module)::+::Token {
+            fn from(token: vrrb_core::txn::Token) -> Self {
+                Self {
+                    name: token.name,
+                    symbol: token.symbol,
+                    decimals: token.decimals.into(),
+                }
+            }
+        }
+
+        impl TryFrom<$($module)::+::Token> for vrrb_core::txn::Token {
+            type Error = Status;
+
+            fn try_from(token: $($module)::+::Token) -> Result<Self, Self::Error> {
+                let decimals = u8::try_from(token.decimals)
+                    .map_err(|_| Status::invalid_argument("token decimals must fit in 8 bits"))?;
+
+                Ok(Self {
+                    name: token.name,
+                    symbol: token.symbol,
+                    decimals,
+                })
+            }
+        }
+
+        impl TryFrom<vrrb_core::txn::Txn> for $($module)::+::TransactionRecord {
+            type Error = Status;
+
+            fn try_from(txn: vrrb_core::txn::Txn) -> Result<Self, Self::Error> {
+                // NOTE: amounts and nonces are 128 bits wide in the core but only 64 bits
+                // wide on the wire
+                let amount = u64::try_from(txn.amount())
+                    .map_err(|_| Status::out_of_range("transaction amount exceeds 64 bits"))?;
+
+                let nonce = u64::try_from(txn.nonce())
+                    .map_err(|_| Status::out_of_range("transaction nonce exceeds 64 bits"))?;
+
+                Ok(Self {
+                    id: txn.digest().to_string(),
+                    timestamp: txn.timestamp(),
+                    sender_address: txn.sender_address().to_string(),
+                    sender_public_key: txn.sender_public_key().to_string(),
+                    receiver_address: txn.receiver_address().to_string(),
+                    token: Some(txn.token().into()),
+                    amount,
+                    signature: txn.signature().to_string(),
+                    validators: txn.validators(),
+                    nonce,
+                })
+            }
+        }
+    };
+}
+
+impl_txn_conversions!(crate::node_read_service::v1);
+impl_txn_conversions!(crate::node_write_service::v1);
+
+impl TryFrom<vrrb_core::account::Account> for crate::node_read_service::v1::Account {
+    type Error = Status;
+
+    fn try_from(account: vrrb_core::account::Account) -> Result<Self, Self::Error> {
+        let nonce = u64::try_from(account.nonce())
+            .map_err(|_| Status::out_of_range("account nonce exceeds 64 bits"))?;
+
+        let credits = u64::try_from(account.credits())
+            .map_err(|_| Status::out_of_range("account credits exceed 64 bits"))?;
+
+        let debits = u64::try_from(account.debits())
+            .map_err(|_| Status::out_of_range("account debits exceed 64 bits"))?;
+
+        Ok(Self {
+            address: account.address().to_string(),
+            hash: account.hash().to_string(),
+            nonce,
+            credits,
+            debits,
+            storage: account.storage().clone(),
+            code: account.code().clone(),
+            pubkey: hex::encode(account.pubkey()),
+            created_at: account.created_at(),
+            updated_at: account.updated_at(),
+        })
+    }
+}
+
+impl TryFrom<vrrb_core::claim::Claim> for crate::node_read_service::v1::Claim {
+    type Error = Status;
+
+    fn try_from(claim: vrrb_core::claim::Claim) -> Result<Self, Self::Error> {
+        let stake = u64::try_from(claim.get_stake())
+            .map_err(|_| Status::out_of_range("claim stake exceeds 64 bits"))?;
+
+        let delegated_stake = u64::try_from(claim.get_delegated_stake())
+            .map_err(|_| Status::out_of_range("delegated stake exceeds 64 bits"))?;
+
+        Ok(Self {
+            hash: claim.hash.to_string(),
+            public_key: claim.public_key.to_string(),
+            address: claim.address.to_string(),
+            eligibility: claim.eligibility.to_string(),
+            ip_address: claim.ip_address.to_string(),
+            signature: claim.signature,
+            stake,
+            delegated_stake,
+        })
+    }
+}
+
+impl TryFrom<vrrb_core::reward_credit::RewardCredit>
+    for crate::node_read_service::v1::RewardCredit
+{
+    type Error = Status;
+
+    fn try_from(credit: vrrb_core::reward_credit::RewardCredit) -> Result<Self, Self::Error> {
+        let epoch = u64::try_from(credit.epoch)
+            .map_err(|_| Status::out_of_range("reward epoch exceeds 64 bits"))?;
+
+        let round = u64::try_from(credit.round)
+            .map_err(|_| Status::out_of_range("reward round exceeds 64 bits"))?;
+
+        let amount = u64::try_from(credit.amount)
+            .map_err(|_| Status::out_of_range("reward amount exceeds 64 bits"))?;
+
+        Ok(Self {
+            epoch,
+            round,
+            block_hash: credit.block_hash,
+            address: credit.address.to_string(),
+            amount,
+            kind: format!("{:?}", credit.kind),
+        })
+    }
+}
+
+impl From<vrrb_rpc::rpc::api::RpcMerkleProof> for crate::node_read_service::v1::MerkleProof {
+    fn from(proof: vrrb_rpc::rpc::api::RpcMerkleProof) -> Self {
+        Self {
+            key: proof.key,
+            nodes: proof.nodes,
+            root_hash: proof.root_hash,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+pub mod node_read_service {
+    pub mod v1 {
+        tonic::include_proto!("node_read_service.v1");
+    }
+}
+
+pub mod node_write_service {
+    pub mod v1 {
+        tonic::include_proto!("node_write_service.v1");
+    }
+}
+
+mod conversions;
+mod node_read_service_impl;
+mod node_write_service_impl;
+mod server;
+
+pub use node_read_service_impl::*;
+pub use node_write_service_impl::*;
+pub use server::*;
+
+pub type Result<T> = std::result::Result<T, GrpcError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum GrpcError {
+    #[error("gRPC transport error: {0}")]
+    Transport(#[from] tonic::transport::Error),
+
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Other(String),
+}
@@ -0,0 +1,517 @@
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ethereum_types::U256;
+use mempool::{MempoolReadHandleFactory, TxnFilter};
+use primitives::{Address, NodeId, NodeType};
+use storage::vrrbdb::{AccountFilter, VrrbDbReadHandle};
+use tonic::{Request, Response, Status};
+use vrrb_core::txn::{Token, TransactionDigest, Txn};
+use vrrb_rpc::rpc::api::{page_limit, RpcMerkleProof};
+
+use crate::node_read_service::v1::{
+    node_read_service_server::NodeReadService,
+    Account,
+    Claim,
+    Delegation,
+    GetAccountProofRequest,
+    GetAccountProofResponse,
+    GetAccountRequest,
+    GetAccountResponse,
+    GetClaimHashesRequest,
+    GetClaimHashesResponse,
+    GetClaimProofRequest,
+    GetClaimProofResponse,
+    GetClaimsByAccountIdRequest,
+    GetClaimsByAccountIdResponse,
+    GetClaimsRequest,
+    GetClaimsResponse,
+    GetDelegationsRequest,
+    GetDelegationsResponse,
+    GetEpochRewardsRequest,
+    GetEpochRewardsResponse,
+    GetFullMempoolRequest,
+    GetFullMempoolResponse,
+    GetFullStateRequest,
+    GetFullStateResponse,
+    GetNodeTypeRequest,
+    GetNodeTypeResponse,
+    GetPendingUnbondingsRequest,
+    GetPendingUnbondingsResponse,
+    GetTransactionCountRequest,
+    GetTransactionCountResponse,
+    GetTransactionProofRequest,
+    GetTransactionProofResponse,
+    GetTransactionRequest,
+    GetTransactionResponse,
+    GetVestingStatusRequest,
+    GetVestingStatusResponse,
+    ListTransactionsRequest,
+    ListTransactionsResponse,
+    MerkleProof,
+    RewardCredit,
+    TransactionRecord,
+    Unbonding,
+    VestingStatus,
+};
+
+#[derive(Debug, Clone)]
+pub struct NodeReadServiceImpl {
+    pub node_id: NodeId,
+    pub node_type: NodeType,
+    pub vrrbdb_read_handle: VrrbDbReadHandle,
+    pub mempool_read_handle_factory: MempoolReadHandleFactory,
+}
+
+#[tonic::async_trait]
+impl NodeReadService for NodeReadServiceImpl {
+    async fn get_node_type(
+        &self,
+        _request: Request<GetNodeTypeRequest>,
+    ) -> Result<Response<GetNodeTypeResponse>, Status> {
+        Ok(Response::new(GetNodeTypeResponse {
+            id: self.node_id.clone(),
+            result: self.node_type.to_string(),
+        }))
+    }
+
+    async fn get_full_mempool(
+        &self,
+        request: Request<GetFullMempoolRequest>,
+    ) -> Result<Response<GetFullMempoolResponse>, Status> {
+        let request = request.into_inner();
+
+        let filter = TxnFilter {
+            sender: request.sender_address.map(parse_address).transpose()?,
+            receiver: request.receiver_address.map(parse_address).transpose()?,
+            token: request.token.map(Token::try_from).transpose()?,
+        };
+
+        let after = request
+            .after
+            .map(|digest| digest.parse::<TransactionDigest>())
+            .transpose()
+            .map_err(|_err| Status::invalid_argument("unable to parse transaction digest"))?;
+
+        let limit = page_limit(request.limit.map(|limit| limit as usize));
+
+        let txns: Vec<Txn> = self
+            .mempool_read_handle_factory
+            .page(after.as_ref(), limit, &filter)
+            .into_iter()
+            .map(|record| record.txn)
+            .collect();
+
+        let next = match txns.last() {
+            Some(last) if txns.len() >= limit => Some(last.digest().to_string()),
+            _ => None,
+        };
+
+        let transaction_records = txns
+            .into_iter()
+            .map(TransactionRecord::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Response::new(GetFullMempoolResponse {
+            transaction_records,
+            next,
+        }))
+    }
+
+    async fn get_full_state(
+        &self,
+        request: Request<GetFullStateRequest>,
+    ) -> Result<Response<GetFullStateResponse>, Status> {
+        let request = request.into_inner();
+
+        let filter = AccountFilter {
+            min_balance: request.min_balance.map(u128::from),
+            has_code: request.has_code,
+        };
+
+        let after = request.after.map(parse_address).transpose()?;
+        let limit = page_limit(request.limit.map(|limit| limit as usize));
+
+        let accounts = match request.block_hash {
+            Some(block_hash) => self
+                .vrrbdb_read_handle
+                .state_page_at(&block_hash, after.as_ref(), limit, &filter)
+                .map_err(|err| {
+                    Status::not_found(format!("unable to read state at block: {err}"))
+                })?,
+            None => {
+                self.vrrbdb_read_handle
+                    .state_store_handle()
+                    .page(after.as_ref(), limit, &filter)
+            },
+        };
+
+        let next = match accounts.last() {
+            Some((address, _)) if accounts.len() >= limit => Some(address.to_string()),
+            _ => None,
+        };
+
+        let accounts = accounts
+            .into_iter()
+            .map(|(_, account)| Account::try_from(account))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Response::new(GetFullStateResponse { accounts, next }))
+    }
+
+    async fn get_account(
+        &self,
+        request: Request<GetAccountRequest>,
+    ) -> Result<Response<GetAccountResponse>, Status> {
+        let request = request.into_inner();
+        let address = parse_address(request.address)?;
+
+        let account = match request.block_hash {
+            Some(block_hash) => self
+                .vrrbdb_read_handle
+                .account_at(&block_hash, &address)
+                .map_err(|err| {
+                    Status::not_found(format!("unable to find account at block: {err}"))
+                })?,
+            None => self
+                .vrrbdb_read_handle
+                .state_store_handle()
+                .get(&address)
+                .map_err(|_err| Status::not_found("unable to find account"))?,
+        };
+
+        Ok(Response::new(GetAccountResponse {
+            account: Some(Account::try_from(account)?),
+        }))
+    }
+
+    async fn get_transaction(
+        &self,
+        request: Request<GetTransactionRequest>,
+    ) -> Result<Response<GetTransactionResponse>, Status> {
+        let digest = parse_digest(&request.into_inner().id)?;
+
+        let txn = self
+            .vrrbdb_read_handle
+            .transaction_store_handle()
+            .get(&digest)
+            .map_err(|_err| Status::not_found("unable to find transaction"))?;
+
+        Ok(Response::new(GetTransactionResponse {
+            transaction_record: Some(TransactionRecord::try_from(txn)?),
+        }))
+    }
+
+    async fn list_transactions(
+        &self,
+        request: Request<ListTransactionsRequest>,
+    ) -> Result<Response<ListTransactionsResponse>, Status> {
+        let transaction_store = self.vrrbdb_read_handle.transaction_store_handle();
+        let mut transaction_records = HashMap::new();
+
+        for id in request.into_inner().ids {
+            let digest = parse_digest(&id)?;
+
+            if let Ok(txn) = transaction_store.get(&digest) {
+                transaction_records.insert(id, TransactionRecord::try_from(txn)?);
+            }
+        }
+
+        Ok(Response::new(ListTransactionsResponse {
+            transaction_records,
+        }))
+    }
+
+    async fn get_transaction_count(
+        &self,
+        request: Request<GetTransactionCountRequest>,
+    ) -> Result<Response<GetTransactionCountResponse>, Status> {
+        let address = parse_address(request.into_inner().address)?;
+
+        let account = self
+            .vrrbdb_read_handle
+            .state_store_handle()
+            .get(&address)
+            .map_err(|_err| Status::not_found("unable to find account"))?;
+
+        let count = u64::try_from(account.nonce())
+            .map_err(|_| Status::out_of_range("account nonce exceeds 64 bits"))?;
+
+        Ok(Response::new(GetTransactionCountResponse { count }))
+    }
+
+    async fn get_claim_hashes(
+        &self,
+        request: Request<GetClaimHashesRequest>,
+    ) -> Result<Response<GetClaimHashesResponse>, Status> {
+        let request = request.into_inner();
+
+        let after = request
+            .after
+            .map(|claim_hash| parse_claim_hash(&claim_hash))
+            .transpose()?;
+
+        let limit = page_limit(request.limit.map(|limit| limit as usize));
+
+        let claim_hashes: Vec<U256> = self
+            .vrrbdb_read_handle
+            .claim_store_handle()
+            .page(after.as_ref(), limit)
+            .into_iter()
+            .map(|(claim_hash, _)| claim_hash)
+            .collect();
+
+        let next = match claim_hashes.last() {
+            Some(last) if claim_hashes.len() >= limit => Some(last.to_string()),
+            _ => None,
+        };
+
+        Ok(Response::new(GetClaimHashesResponse {
+            claim_hashes: claim_hashes.iter().map(U256::to_string).collect(),
+            next,
+        }))
+    }
+
+    async fn get_claims(
+        &self,
+        request: Request<GetClaimsRequest>,
+    ) -> Result<Response<GetClaimsResponse>, Status> {
+        let claim_store = self.vrrbdb_read_handle.claim_store_handle();
+        let mut claims = vec![];
+
+        for claim_hash in request.into_inner().claim_hashes {
+            let claim_hash = parse_claim_hash(&claim_hash)?;
+
+            if let Ok(claim) = claim_store.get_by_hash(&claim_hash) {
+                claims.push(Claim::try_from(claim)?);
+            }
+        }
+
+        Ok(Response::new(GetClaimsResponse { claims }))
+    }
+
+    async fn get_claims_by_account_id(
+        &self,
+        request: Request<GetClaimsByAccountIdRequest>,
+    ) -> Result<Response<GetClaimsByAccountIdResponse>, Status> {
+        let address = parse_address(request.into_inner().address)?;
+
+        let claims = self
+            .vrrbdb_read_handle
+            .claim_store_handle()
+            .get_by_address(&address)
+            .map_err(|err| Status::internal(format!("unable to read claims: {err}")))?
+            .into_iter()
+            .map(Claim::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Response::new(GetClaimsByAccountIdResponse { claims }))
+    }
+
+    async fn get_delegations(
+        &self,
+        request: Request<GetDelegationsRequest>,
+    ) -> Result<Response<GetDelegationsResponse>, Status> {
+        let address = parse_address(request.into_inner().address)?;
+
+        let claim = self
+            .vrrbdb_read_handle
+            .claim_store_handle()
+            .get_by_address(&address)
+            .map_err(|err| Status::internal(format!("unable to read claims: {err}")))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Status::not_found(format!("no claim found for {address}")))?;
+
+        let delegations = claim
+            .get_delegations()
+            .into_iter()
+            .map(|(delegator, amount)| {
+                Ok(Delegation {
+                    delegator: delegator.to_string(),
+                    amount: to_u64(amount, "delegated amount")?,
+                })
+            })
+            .collect::<Result<Vec<_>, Status>>()?;
+
+        Ok(Response::new(GetDelegationsResponse {
+            own_stake: to_u64(claim.get_stake(), "claim stake")?,
+            delegated_stake: to_u64(claim.get_delegated_stake(), "delegated stake")?,
+            total_stake: to_u64(claim.get_total_stake(), "total stake")?,
+            delegations,
+        }))
+    }
+
+    async fn get_pending_unbondings(
+        &self,
+        request: Request<GetPendingUnbondingsRequest>,
+    ) -> Result<Response<GetPendingUnbondingsResponse>, Status> {
+        let staker = parse_address(request.into_inner().staker)?;
+
+        let claims = self
+            .vrrbdb_read_handle
+            .claim_store_handle()
+            .get_by_unbonding_staker(&staker)
+            .map_err(|err| Status::internal(format!("unable to read claims: {err}")))?;
+
+        let mut unbondings = claims
+            .iter()
+            .flat_map(|claim| {
+                claim
+                    .get_unbonding()
+                    .into_iter()
+                    .filter(|unbonding| unbonding.staker == staker)
+                    .map(|unbonding| (claim.address.clone(), unbonding))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        unbondings.sort_by_key(|(_, unbonding)| unbonding.release_epoch);
+
+        let unbondings = unbondings
+            .into_iter()
+            .map(|(claim_address, unbonding)| {
+                Ok(Unbonding {
+                    claim_address: claim_address.to_string(),
+                    amount: to_u64(unbonding.amount, "unbonding amount")?,
+                    release_epoch: to_u64(unbonding.release_epoch, "release epoch")?,
+                })
+            })
+            .collect::<Result<Vec<_>, Status>>()?;
+
+        Ok(Response::new(GetPendingUnbondingsResponse { unbondings }))
+    }
+
+    async fn get_epoch_rewards(
+        &self,
+        request: Request<GetEpochRewardsRequest>,
+    ) -> Result<Response<GetEpochRewardsResponse>, Status> {
+        let epoch = request.into_inner().epoch;
+
+        let reward_credits = self
+            .vrrbdb_read_handle
+            .epoch_rewards(epoch.into())
+            .map_err(|err| Status::internal(format!("unable to read rewards: {err}")))?
+            .into_iter()
+            .map(RewardCredit::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Response::new(GetEpochRewardsResponse { reward_credits }))
+    }
+
+    async fn get_vesting_status(
+        &self,
+        request: Request<GetVestingStatusRequest>,
+    ) -> Result<Response<GetVestingStatusResponse>, Status> {
+        let request = request.into_inner();
+        let address = parse_address(request.address)?;
+
+        let timestamp = match request.timestamp {
+            Some(timestamp) => timestamp,
+            None => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|err| Status::internal(err.to_string()))?
+                .as_secs() as i64,
+        };
+
+        let account = self
+            .vrrbdb_read_handle
+            .state_store_handle()
+            .get(&address)
+            .map_err(|_err| Status::not_found("unable to find account"))?;
+
+        let vesting_status = account
+            .vesting()
+            .as_ref()
+            .map(|vesting| {
+                let next_unlock = vesting.next_unlock(timestamp);
+                let next_unlock_amount = next_unlock.map(|(_, amount)| amount).unwrap_or_default();
+
+                Ok::<_, Status>(VestingStatus {
+                    address: address.to_string(),
+                    vested: to_u64(vesting.vested_at(timestamp), "vested amount")?,
+                    locked: to_u64(vesting.locked_at(timestamp), "locked amount")?,
+                    next_unlock_timestamp: next_unlock.map(|(timestamp, _)| timestamp),
+                    next_unlock_amount: to_u64(next_unlock_amount, "next unlock amount")?,
+                })
+            })
+            .transpose()?;
+
+        Ok(Response::new(GetVestingStatusResponse { vesting_status }))
+    }
+
+    async fn get_transaction_proof(
+        &self,
+        request: Request<GetTransactionProofRequest>,
+    ) -> Result<Response<GetTransactionProofResponse>, Status> {
+        let digest = parse_digest(&request.into_inner().id)?;
+
+        let proof = self
+            .vrrbdb_read_handle
+            .transaction_store_handle()
+            .get_proof(&digest)
+            .map_err(|err| Status::not_found(format!("unable to create proof: {err}")))?;
+
+        Ok(Response::new(GetTransactionProofResponse {
+            proof: Some(MerkleProof::from(RpcMerkleProof::from(proof))),
+        }))
+    }
+
+    async fn get_account_proof(
+        &self,
+        request: Request<GetAccountProofRequest>,
+    ) -> Result<Response<GetAccountProofResponse>, Status> {
+        let address = parse_address(request.into_inner().address)?;
+
+        let proof = self
+            .vrrbdb_read_handle
+            .state_store_handle()
+            .get_proof(&address)
+            .map_err(|err| Status::not_found(format!("unable to create proof: {err}")))?;
+
+        Ok(Response::new(GetAccountProofResponse {
+            proof: Some(MerkleProof::from(RpcMerkleProof::from(proof))),
+        }))
+    }
+
+    async fn get_claim_proof(
+        &self,
+        request: Request<GetClaimProofRequest>,
+    ) -> Result<Response<GetClaimProofResponse>, Status> {
+        let claim_hash = parse_claim_hash(&request.into_inner().claim_hash)?;
+
+        let proof = self
+            .vrrbdb_read_handle
+            .claim_store_handle()
+            .get_proof(&claim_hash)
+            .map_err(|err| Status::not_found(format!("unable to create proof: {err}")))?;
+
+        Ok(Response::new(GetClaimProofResponse {
+            proof: Some(MerkleProof::from(RpcMerkleProof::from(proof))),
+        }))
+    }
+}
+
+fn parse_address(address: String) -> Result<Address, Status> {
+    address
+        .parse::<Address>()
+        .map_err(|_err| Status::invalid_argument(format!("invalid address: {address}")))
+}
+
+fn parse_digest(digest: &str) -> Result<TransactionDigest, Status> {
+    digest
+        .parse::<TransactionDigest>()
+        .map_err(|_err| Status::invalid_argument("unable to parse transaction digest"))
+}
+
+fn parse_claim_hash(claim_hash: &str) -> Result<U256, Status> {
+    U256::from_dec_str(claim_hash)
+        .map_err(|_err| Status::invalid_argument(format!("invalid claim hash: {claim_hash}")))
+}
+
+/// Narrows an amount to the 64 bits amounts are sent over the wire with.
+fn to_u64(amount: u128, what: &str) -> Result<u64, Status> {
+    u64::try_from(amount).map_err(|_| Status::out_of_range(format!("{what} exceeds 64 bits")))
+}
@@ -0,0 +1,81 @@
+use events::{Event, EventPublisher};
+use primitives::{Address, PublicKey, Signature};
+use telemetry::{debug, error};
+use tonic::{Request, Response, Status};
+use vrrb_core::txn::{NewTxnArgs, Token, Txn};
+
+use crate::node_write_service::v1::{
+    node_write_service_server::NodeWriteService,
+    CreateTransactionRequest,
+    TransactionRecord,
+};
+
+#[derive(Debug, Clone)]
+pub struct NodeWriteServiceImpl {
+    pub events_tx: EventPublisher,
+}
+
+#[tonic::async_trait]
+impl NodeWriteService for NodeWriteServiceImpl {
+    async fn create_transaction(
+        &self,
+        request: Request<CreateTransactionRequest>,
+    ) -> Result<Response<TransactionRecord>, Status> {
+        let args = NewTxnArgs::try_from(request.into_inner())?;
+        let txn = Txn::new(args);
+        let event = Event::NewTxnCreated(txn.clone());
+
+        debug!("{:?}", event);
+
+        self.events_tx.send(event.into()).await.map_err(|err| {
+            error!("could not queue transaction to mempool: {err}");
+            Status::internal(err.to_string())
+        })?;
+
+        Ok(Response::new(TransactionRecord::try_from(txn)?))
+    }
+}
+
+impl TryFrom<CreateTransactionRequest> for NewTxnArgs {
+    type Error = Status;
+
+    fn try_from(request: CreateTransactionRequest) -> Result<Self, Self::Error> {
+        let sender_address = request
+            .sender_address
+            .parse::<Address>()
+            .map_err(|_err| Status::invalid_argument("invalid sender address"))?;
+
+        let sender_public_key = request
+            .sender_public_key
+            .parse::<PublicKey>()
+            .map_err(|_err| Status::invalid_argument("invalid sender public key"))?;
+
+        let receiver_address = request
+            .receiver_address
+            .parse::<Address>()
+            .map_err(|_err| Status::invalid_argument("invalid receiver address"))?;
+
+        let signature = request
+            .signature
+            .parse::<Signature>()
+            .map_err(|_err| Status::invalid_argument("invalid signature"))?;
+
+        let validators = if request.validators.is_empty() {
+            None
+        } else {
+            Some(request.validators)
+        };
+
+        Ok(Self {
+            timestamp: request.timestamp,
+            sender_address,
+            sender_public_key,
+            receiver_address,
+            token: request.token.map(Token::try_from).transpose()?,
+            amount: request.amount.into(),
+            signature,
+            validators,
+            nonce: request.nonce.into(),
+        })
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    future::Future,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+};
+
+use events::{EventPublisher, DEFAULT_BUFFER};
+use mempool::{LeftRightMempool, MempoolReadHandleFactory};
+use primitives::{NodeId, NodeType};
+use storage::vrrbdb::{VrrbDb, VrrbDbConfig, VrrbDbReadHandle};
+use tokio::{net::TcpListener, sync::mpsc::channel, task::JoinHandle};
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+
+use crate::{
+    node_read_service::v1::node_read_service_server::NodeReadServiceServer,
+    node_write_service::v1::node_write_service_server::NodeWriteServiceServer,
+    NodeReadServiceImpl,
+    NodeWriteServiceImpl,
+    Result,
+};
+
+#[derive(Debug, Clone)]
+pub struct GrpcServerConfig {
+    pub address: SocketAddr,
+    pub node_id: NodeId,
+    pub node_type: NodeType,
+    pub vrrbdb_read_handle: VrrbDbReadHandle,
+    pub mempool_read_handle_factory: MempoolReadHandleFactory,
+    pub events_tx: EventPublisher,
+}
+
+#[derive(Debug)]
+pub struct GrpcServer;
+
+impl GrpcServer {
+    /// Serves the node read and write services until the given signal
+    /// resolves. Returns the handle of the server task along with the
+    /// address it's bound to.
+    pub async fn run<F>(
+        config: &GrpcServerConfig,
+        signal: F,
+    ) -> Result<(JoinHandle<Result<()>>, SocketAddr)>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let listener = TcpListener::bind(config.address).await?;
+        let addr = listener.local_addr()?;
+
+        let read_service = NodeReadServiceImpl {
+            node_id: config.node_id.clone(),
+            node_type: config.node_type,
+            vrrbdb_read_handle: config.vrrbdb_read_handle.clone(),
+            mempool_read_handle_factory: config.mempool_read_handle_factory.clone(),
+        };
+
+        let write_service = NodeWriteServiceImpl {
+            events_tx: config.events_tx.clone(),
+        };
+
+        let router = Server::builder()
+            .add_service(NodeReadServiceServer::new(read_service))
+            .add_service(NodeWriteServiceServer::new(write_service));
+
+        let handle = tokio::spawn(async move {
+            router
+                .serve_with_incoming_shutdown(TcpListenerStream::new(listener), signal)
+                .await?;
+
+            Ok(())
+        });
+
+        Ok((handle, addr))
+    }
+}
+
+impl Default for GrpcServerConfig {
+    fn default() -> GrpcServerConfig {
+        let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 50051);
+        let mut vrrbdb_config = VrrbDbConfig::default();
+
+        let temp_dir_path = std::env::temp_dir();
+        let db_path = temp_dir_path.join(vrrb_core::helpers::generate_random_string());
+
+        vrrbdb_config.path = db_path;
+
+        let vrrbdb = VrrbDb::new(vrrbdb_config).expect("failed to open the grpc server's database");
+        let vrrbdb_read_handle = vrrbdb.read_handle();
+
+        let mempool = LeftRightMempool::default();
+        let mempool_read_handle_factory = mempool.factory();
+
+        let node_type = NodeType::Full;
+        let (events_tx, _) = channel(DEFAULT_BUFFER);
+
+        GrpcServerConfig {
+            address,
+            node_id: NodeId::default(),
+            node_type,
+            vrrbdb_read_handle,
+            mempool_read_handle_factory,
+            events_tx,
+        }
+    }
+}
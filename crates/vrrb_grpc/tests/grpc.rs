@@ -0,0 +1,318 @@
+use events::{Event, EventMessage, DEFAULT_BUFFER};
+use mempool::LeftRightMempool;
+use primitives::{generate_mock_account_keypair, Address};
+use secp256k1::Message;
+use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
+use tokio::sync::{mpsc::channel, oneshot};
+use vrrb_core::{
+    account::Account,
+    helpers::generate_random_string,
+    txn::{generate_txn_digest_vec, NewTxnArgs, Token, Txn},
+};
+use vrrb_grpc::{
+    node_read_service::v1::{
+        node_read_service_client::NodeReadServiceClient,
+        GetAccountProofRequest,
+        GetAccountRequest,
+        GetFullMempoolRequest,
+        GetFullStateRequest,
+        GetNodeTypeRequest,
+        GetTransactionCountRequest,
+        GetTransactionProofRequest,
+        GetTransactionRequest,
+        GetVestingStatusRequest,
+        ListTransactionsRequest,
+    },
+    node_write_service::v1::{
+        node_write_service_client::NodeWriteServiceClient,
+        CreateTransactionRequest,
+    },
+    GrpcServer,
+    GrpcServerConfig,
+};
+
+fn test_server_config() -> GrpcServerConfig {
+    let mut grpc_server_config = GrpcServerConfig::default();
+    grpc_server_config.address = "127.0.0.1:0"
+        .parse()
+        .expect("Unable to create Socket Address");
+
+    grpc_server_config
+}
+
+#[tokio::test]
+async fn server_can_publish_transactions_to_be_created() {
+    let (events_tx, mut events_rx) = channel::<EventMessage>(DEFAULT_BUFFER);
+
+    let mut grpc_server_config = test_server_config();
+    grpc_server_config.events_tx = events_tx;
+
+    let (stop_tx, stop_rx) = oneshot::channel::<()>();
+    let (handle, grpc_server_address) = GrpcServer::run(&grpc_server_config, async {
+        stop_rx.await.ok();
+    })
+    .await
+    .unwrap();
+
+    let mut client = NodeWriteServiceClient::connect(format!("http://{grpc_server_address}"))
+        .await
+        .unwrap();
+
+    let (secret_key, public_key) = generate_mock_account_keypair();
+    let (_, recv_public_key) = generate_mock_account_keypair();
+
+    let address = Address::new(public_key);
+    let recv_address = Address::new(recv_public_key);
+
+    let digest = generate_txn_digest_vec(
+        0,
+        address.to_string(),
+        public_key,
+        recv_address.to_string(),
+        Token::default(),
+        10,
+        0,
+    );
+
+    type H = secp256k1::hashes::sha256::Hash;
+    let msg = Message::from_hashed_data::<H>(&digest);
+    let signature = secret_key.sign_ecdsa(msg);
+
+    let expected_txn = Txn::new(NewTxnArgs {
+        timestamp: 0,
+        sender_address: address.clone(),
+        sender_public_key: public_key,
+        receiver_address: recv_address.clone(),
+        token: None,
+        amount: 10,
+        signature,
+        validators: None,
+        nonce: 0,
+    });
+
+    let record = client
+        .create_transaction(CreateTransactionRequest {
+            timestamp: 0,
+            sender_address: address.to_string(),
+            sender_public_key: public_key.to_string(),
+            receiver_address: recv_address.to_string(),
+            token: None,
+            amount: 10,
+            signature: signature.to_string(),
+            validators: Default::default(),
+            nonce: 0,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(record.id, expected_txn.digest().to_string());
+    assert_eq!(record.sender_address, address.to_string());
+    assert_eq!(record.amount, 10);
+
+    let event: Event = events_rx.recv().await.unwrap().into();
+    assert_eq!(event, Event::NewTxnCreated(expected_txn));
+
+    let status = client
+        .create_transaction(CreateTransactionRequest {
+            sender_address: "not an address".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    stop_tx.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn server_pages_through_the_mempool() {
+    let mut mempool = LeftRightMempool::default();
+    for _ in 0..3 {
+        mempool.insert(Txn::null_txn()).unwrap();
+    }
+
+    let mut grpc_server_config = test_server_config();
+    grpc_server_config.mempool_read_handle_factory = mempool.factory();
+
+    let (stop_tx, stop_rx) = oneshot::channel::<()>();
+    let (handle, grpc_server_address) = GrpcServer::run(&grpc_server_config, async {
+        stop_rx.await.ok();
+    })
+    .await
+    .unwrap();
+
+    let mut client = NodeReadServiceClient::connect(format!("http://{grpc_server_address}"))
+        .await
+        .unwrap();
+
+    let node_type = client
+        .get_node_type(GetNodeTypeRequest {})
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(node_type.result, "Full");
+
+    let first_page = client
+        .get_full_mempool(GetFullMempoolRequest {
+            limit: Some(2),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(first_page.transaction_records.len(), 2);
+    assert!(first_page.next.is_some());
+
+    let last_page = client
+        .get_full_mempool(GetFullMempoolRequest {
+            after: first_page.next,
+            limit: Some(2),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(last_page.transaction_records.len(), 1);
+    assert!(last_page.next.is_none());
+
+    stop_tx.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn server_reads_accounts_and_transactions_from_state() {
+    let mut vrrbdb_config = VrrbDbConfig::default();
+    vrrbdb_config.path = std::env::temp_dir().join(generate_random_string());
+
+    let mut vrrbdb = VrrbDb::new(vrrbdb_config).unwrap();
+
+    let (_, public_key) = generate_mock_account_keypair();
+    let address = Address::new(public_key);
+    vrrbdb
+        .insert_account(address.clone(), Account::new(public_key))
+        .unwrap();
+
+    let txn = Txn::null_txn();
+    vrrbdb.insert_transaction_unchecked(txn.clone()).unwrap();
+
+    let mut grpc_server_config = test_server_config();
+    grpc_server_config.vrrbdb_read_handle = vrrbdb.read_handle();
+
+    let (stop_tx, stop_rx) = oneshot::channel::<()>();
+    let (handle, grpc_server_address) = GrpcServer::run(&grpc_server_config, async {
+        stop_rx.await.ok();
+    })
+    .await
+    .unwrap();
+
+    let mut client = NodeReadServiceClient::connect(format!("http://{grpc_server_address}"))
+        .await
+        .unwrap();
+
+    let account = client
+        .get_account(GetAccountRequest {
+            address: address.to_string(),
+            block_hash: None,
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .account
+        .unwrap();
+
+    assert_eq!(account.address, address.to_string());
+
+    let state = client
+        .get_full_state(GetFullStateRequest::default())
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(state.accounts.len(), 1);
+    assert!(state.next.is_none());
+
+    let txn_count = client
+        .get_transaction_count(GetTransactionCountRequest {
+            address: address.to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(txn_count.count, 0);
+
+    let txn_id = txn.digest().to_string();
+    let record = client
+        .get_transaction(GetTransactionRequest { id: txn_id.clone() })
+        .await
+        .unwrap()
+        .into_inner()
+        .transaction_record
+        .unwrap();
+
+    assert_eq!(record.id, txn_id);
+
+    let records = client
+        .list_transactions(ListTransactionsRequest {
+            ids: vec![txn_id.clone(), Txn::null_txn().digest().to_string()],
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .transaction_records;
+
+    assert_eq!(records.len(), 1);
+    assert!(records.contains_key(&txn_id));
+
+    let status = client
+        .get_transaction(GetTransactionRequest {
+            id: "not a digest".to_string(),
+        })
+        .await
+        .unwrap_err();
+
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    let account_proof = client
+        .get_account_proof(GetAccountProofRequest {
+            address: address.to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .proof
+        .unwrap();
+
+    assert!(!account_proof.nodes.is_empty());
+
+    let txn_proof = client
+        .get_transaction_proof(GetTransactionProofRequest { id: txn_id })
+        .await
+        .unwrap()
+        .into_inner()
+        .proof
+        .unwrap();
+
+    assert!(!txn_proof.nodes.is_empty());
+
+    let vesting_status = client
+        .get_vesting_status(GetVestingStatusRequest {
+            address: address.to_string(),
+            timestamp: Some(0),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .vesting_status;
+
+    assert!(vesting_status.is_none());
+
+    stop_tx.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
@@ -2,26 +2,73 @@ use std::collections::HashMap;
 
 use block::block::Block;
 use jsonrpsee::{core::Error, proc_macros::rpc};
-use primitives::{Address, NodeType, Round};
+use primitives::{Address, Epoch, NodeType, Round};
 use secp256k1::PublicKey;
 use serde::{Deserialize, Serialize};
-use block::ClaimHash;
+use block::{BlockHash, ClaimHash};
 use vrrb_core::node_health_report::NodeHealthReport;
-use storage::vrrbdb::Claims;
+use mempool::TxnFilter;
+use storage::vrrbdb::{AccountFilter, Claims};
 use vrrb_core::{
     account::Account,
+    proof::MerkleProof,
+    receipt::TxnReceipt,
+    reward_credit::RewardCredit,
+    staking::Stake,
     txn::{NewTxnArgs, Token, TxAmount, TxNonce, TxTimestamp, Txn},
 };
 use vrrb_core::claim::Claim;
 use vrrb_config::bootstrap_quorum::QuorumMembershipConfig;
-
+use wasm_runtime::wasm_runtime::ExecutionOutcome;
 
 use crate::rpc::SignOpts;
 
 pub type ExampleHash = [u8; 32];
 pub type ExampleStorageKey = Vec<u8>;
-pub type FullStateSnapshot = HashMap<Address, Account>;
-pub type FullMempoolSnapshot = Vec<RpcTransactionRecord>;
+pub type FullStateSnapshot = RpcPage<(Address, Account), Address>;
+pub type FullMempoolSnapshot = RpcPage<RpcTransactionRecord, RpcTransactionDigest>;
+pub type ClaimHashesPage = RpcPage<ClaimHash, ClaimHash>;
+
+/// Number of items returned per page when no limit is requested
+pub const DEFAULT_PAGE_LIMIT: usize = 100;
+/// Maximum number of items that can be returned per page
+pub const MAX_PAGE_LIMIT: usize = 1000;
+
+/// A single page of results ordered by key
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RpcPage<T, C> {
+    pub items: Vec<T>,
+    /// Key to pass as `after` to fetch the following page, `None` once the
+    /// last page was returned
+    pub next: Option<C>,
+}
+
+impl<T, C> RpcPage<T, C> {
+    /// Builds a page out of at most `limit` items, using `cursor` to produce
+    /// the key of the following page if the page is full.
+    pub fn new(items: Vec<T>, limit: usize, cursor: impl Fn(&T) -> C) -> Self {
+        let next = match items.last() {
+            Some(last) if items.len() >= limit => Some(cursor(last)),
+            _ => None,
+        };
+
+        Self { items, next }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// Clamps a requested page limit to `MAX_PAGE_LIMIT`, falling back to
+/// `DEFAULT_PAGE_LIMIT` if none was requested.
+pub fn page_limit(limit: Option<usize>) -> usize {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT)
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionRecord {
@@ -66,16 +113,142 @@ impl From<Txn> for RpcTransactionRecord {
     }
 }
 
+/// A deployed WASM program along with the metadata gathered by
+/// `wasm_loader` while parsing it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RpcProgramRecord {
+    pub address: Address,
+    /// Hex encoded WASM bytes of the program, as stored on the account
+    pub code: String,
+    pub imports: HashMap<String, Vec<String>>,
+    pub is_wasi: bool,
+    pub is_wasix: bool,
+    pub has_vrrb: bool,
+}
+
+/// The payload written to a program's stdin when it is called
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RpcProgramCallInput {
+    pub input: String,
+    pub storage: Option<String>,
+}
+
+/// The outcome of a read-only program call
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RpcProgramCallResult {
+    pub stdout: String,
+    pub stderr: String,
+    /// Exit status reported by the program, `None` if it was terminated
+    /// without reporting one (e.g. on a trap)
+    pub exit_status: Option<i32>,
+    pub outcome: ExecutionOutcome,
+    pub fuel_used: u64,
+}
+
+/// A Merkle inclusion proof along with the root hash it was generated
+/// against. Can be converted into a [MerkleProof] to be verified offline.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RpcMerkleProof {
+    /// Hex encoded key of the proven entry, as stored within the trie
+    pub key: String,
+    /// Hex encoded trie nodes on the path from the root to the entry
+    pub nodes: Vec<String>,
+    /// Hex encoded root hash of the trie
+    pub root_hash: String,
+}
+
+impl From<MerkleProof> for RpcMerkleProof {
+    fn from(proof: MerkleProof) -> Self {
+        Self {
+            key: hex::encode(&proof.key),
+            nodes: proof.nodes.iter().map(hex::encode).collect(),
+            root_hash: hex::encode(proof.root_hash),
+        }
+    }
+}
+
+impl TryFrom<RpcMerkleProof> for MerkleProof {
+    type Error = hex::FromHexError;
+
+    fn try_from(proof: RpcMerkleProof) -> Result<Self, Self::Error> {
+        let mut root_hash = [0u8; 32];
+        hex::decode_to_slice(&proof.root_hash, &mut root_hash)?;
+
+        Ok(Self {
+            key: hex::decode(&proof.key)?,
+            nodes: proof
+                .nodes
+                .iter()
+                .map(hex::decode)
+                .collect::<Result<Vec<_>, _>>()?,
+            root_hash,
+        })
+    }
+}
+
+/// Stake delegated to a claim by a single delegator
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RpcDelegation {
+    pub delegator: Address,
+    pub amount: u128,
+}
+
+/// The stake bonded to a claim, split between the stake of its owner and the
+/// stake delegated to it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RpcClaimStake {
+    pub own_stake: u128,
+    pub delegated_stake: u128,
+    /// Stake counted toward the claim's election weight
+    pub total_stake: u128,
+    pub delegations: Vec<RpcDelegation>,
+}
+
+/// Stake withdrawn from a claim that is still locked until `release_epoch`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RpcUnbonding {
+    pub claim_address: Address,
+    pub amount: u128,
+    pub release_epoch: Epoch,
+}
+
+/// How much of the funds an address was granted at genesis unlocked so far,
+/// and when the next part of them unlocks
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RpcVestingStatus {
+    pub address: Address,
+    pub vested: u128,
+    pub locked: u128,
+    /// Unix timestamp, in seconds, of the next unlock, if any funds are still
+    /// locked
+    pub next_unlock_timestamp: Option<i64>,
+    pub next_unlock_amount: u128,
+}
+
 #[rpc(server, client, namespace = "state")]
 #[async_trait]
 pub trait RpcApi {
-    /// Returns a full list of all accounts within state
+    /// Returns a page of the accounts within state matching `filter`, ordered
+    /// by address and starting after `after`, optionally as they were right
+    /// after the block with the given hash was committed
     #[method(name = "getFullState")]
-    async fn get_full_state(&self) -> Result<FullStateSnapshot, Error>;
+    async fn get_full_state(
+        &self,
+        block_hash: Option<BlockHash>,
+        filter: Option<AccountFilter>,
+        after: Option<Address>,
+        limit: Option<usize>,
+    ) -> Result<FullStateSnapshot, Error>;
 
-    /// Returns a full list of transactions pending to be confirmed
+    /// Returns a page of the transactions pending to be confirmed matching
+    /// `filter`, ordered by digest and starting after `after`
     #[method(name = "getFullMempool")]
-    async fn get_full_mempool(&self) -> Result<FullMempoolSnapshot, Error>;
+    async fn get_full_mempool(
+        &self,
+        filter: Option<TxnFilter>,
+        after: Option<RpcTransactionDigest>,
+        limit: Option<usize>,
+    ) -> Result<FullMempoolSnapshot, Error>;
 
     /// Returns the node type this client is connected to
     #[method(name = "getNodeType")]
@@ -92,6 +265,14 @@ pub trait RpcApi {
         transaction_digest: RpcTransactionDigest,
     ) -> Result<RpcTransactionRecord, Error>;
 
+    /// Returns the receipt of a transaction, telling whether it is still
+    /// pending, was validated, rejected or included and where it landed
+    #[method(name = "getTransactionReceipt")]
+    async fn get_transaction_receipt(
+        &self,
+        transaction_digest: RpcTransactionDigest,
+    ) -> Result<TxnReceipt, Error>;
+
     /// List a group of transactions
     #[method(name = "listTransactions")]
     async fn list_transactions(
@@ -105,8 +286,14 @@ pub trait RpcApi {
     #[method(name = "updateAccount")]
     async fn update_account(&self, account: Account) -> Result<(), Error>;
 
+    /// Returns an account, optionally as it was right after the block with
+    /// the given hash was committed
     #[method(name = "getAccount")]
-    async fn get_account(&self, address: Address) -> Result<Account, Error>;
+    async fn get_account(
+        &self,
+        address: Address,
+        block_hash: Option<BlockHash>,
+    ) -> Result<Account, Error>;
 
     #[method(name = "faucetDrip")]
     async fn faucet_drip(&self, address: Address) -> Result<(), Error>;
@@ -120,11 +307,22 @@ pub trait RpcApi {
     #[method(name = "getBlocks")]
     async fn get_blocks(&self) -> Result<Vec<Block>, Error>;
 
+    /// Returns the code of the program deployed at the given address
     #[method(name = "getProgram")]
-    async fn get_program(&self) -> Result<(), Error>;
+    async fn get_program(&self, address: Address) -> Result<RpcProgramRecord, Error>;
 
+    /// Executes the program deployed at the given address without modifying
+    /// state. The program is told it was called by `caller`, which is
+    /// usually the account the call is simulated on behalf of.
     #[method(name = "callProgram")]
-    async fn call_program(&self) -> Result<(), Error>;
+    async fn call_program(
+        &self,
+        address: Address,
+        caller: Address,
+        input: String,
+        args: Vec<String>,
+        env: HashMap<String, String>,
+    ) -> Result<RpcProgramCallResult, Error>;
 
     #[method(name = "getTransactionCount")]
     async fn get_transaction_count(&self, account: Address) -> Result<usize, Error>;
@@ -132,11 +330,45 @@ pub trait RpcApi {
     #[method(name = "getNodeHealth")]
     async fn get_node_health(&self) -> Result<NodeHealthReport, Error>;
 
+    /// Submits a signed stake to the harvester quorum for certification, and
+    /// returns the hex encoded payload identifying it
+    #[method(name = "createStake")]
+    async fn create_stake(&self, stake: Stake) -> Result<String, Error>;
+
+    /// Returns the stake bonded to the claim of the given address, including
+    /// the stake delegated to it
+    #[method(name = "getDelegations")]
+    async fn get_delegations(&self, address: Address) -> Result<RpcClaimStake, Error>;
+
+    /// Returns the stake the given address withdrew from claims that is still
+    /// unbonding
+    #[method(name = "getPendingUnbondings")]
+    async fn get_pending_unbondings(&self, staker: Address) -> Result<Vec<RpcUnbonding>, Error>;
+
+    /// Returns every reward credited within the given epoch
+    #[method(name = "getEpochRewards")]
+    async fn get_epoch_rewards(&self, epoch: Epoch) -> Result<Vec<RewardCredit>, Error>;
+
+    /// Returns the vesting status, at the given unix timestamp or now, of
+    /// the given address, or of every address with vesting funds
+    #[method(name = "getVestingStatus")]
+    async fn get_vesting_status(
+        &self,
+        address: Option<Address>,
+        timestamp: Option<i64>,
+    ) -> Result<Vec<RpcVestingStatus>, Error>;
+
     #[method(name = "getClaimsByAccountId")]
     async fn get_claims_by_account_id(&self, address: Address) -> Result<Claims, Error>;
 
+    /// Returns a page of claim hashes, ordered by hash and starting after
+    /// `after`
     #[method(name = "getClaimHashes")]
-    async fn get_claim_hashes(&self) -> Result<Vec<ClaimHash>, Error>;
+    async fn get_claim_hashes(
+        &self,
+        after: Option<ClaimHash>,
+        limit: Option<usize>,
+    ) -> Result<ClaimHashesPage, Error>;
 
     #[method(name = "getClaims")]
     async fn get_claims(&self, claim_hashes: Vec<ClaimHash>) -> Result<Claims, Error>;
@@ -146,4 +378,44 @@ pub trait RpcApi {
 
     #[method(name = "getLastBlock")]
     async fn get_last_block(&self) -> Result<Block, Error>;
+
+    /// Notifies the subscriber of every block created or confirmed by the node
+    #[subscription(
+        name = "subscribeNewBlocks" => "newBlock",
+        unsubscribe = "unsubscribeNewBlocks",
+        item = Block
+    )]
+    fn subscribe_new_blocks(&self);
+
+    /// Notifies the subscriber of every transaction added to the mempool
+    #[subscription(
+        name = "subscribePendingTransactions" => "pendingTransaction",
+        unsubscribe = "unsubscribePendingTransactions",
+        item = RpcTransactionRecord
+    )]
+    fn subscribe_pending_transactions(&self);
+
+    /// Notifies the subscriber of the account at the given address every time
+    /// a committed block changes it
+    #[subscription(
+        name = "subscribeAccount" => "account",
+        unsubscribe = "unsubscribeAccount",
+        item = Account
+    )]
+    fn subscribe_account(&self, address: Address);
+
+    /// Returns a Merkle proof of a transaction against the transaction root
+    #[method(name = "getTransactionProof")]
+    async fn get_transaction_proof(
+        &self,
+        transaction_digest: RpcTransactionDigest,
+    ) -> Result<RpcMerkleProof, Error>;
+
+    /// Returns a Merkle proof of an account against the state root
+    #[method(name = "getAccountProof")]
+    async fn get_account_proof(&self, address: Address) -> Result<RpcMerkleProof, Error>;
+
+    /// Returns a Merkle proof of a claim against the claim root
+    #[method(name = "getClaimProof")]
+    async fn get_claim_proof(&self, claim_hash: ClaimHash) -> Result<RpcMerkleProof, Error>;
 }
@@ -2,9 +2,11 @@ pub mod api;
 pub mod client;
 mod server;
 mod server_impl;
+mod subscriptions;
 use serde::{Deserialize, Serialize};
 pub use server::*;
 pub use server_impl::*;
+pub use subscriptions::*;
 use vrrb_core::txn::Token;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
@@ -7,7 +7,7 @@ use primitives::NodeType;
 use storage::vrrbdb::{VrrbDb, VrrbDbConfig, VrrbDbReadHandle};
 use tokio::sync::mpsc::channel;
 
-use crate::rpc::{api::RpcApiServer, server_impl::RpcServerImpl};
+use crate::rpc::{api::RpcApiServer, server_impl::RpcServerImpl, subscriptions::RpcSubscriptions};
 
 #[derive(Debug, Clone)]
 pub struct JsonRpcServerConfig {
@@ -16,6 +16,7 @@ pub struct JsonRpcServerConfig {
     pub mempool_read_handle_factory: MempoolReadHandleFactory,
     pub node_type: NodeType,
     pub events_tx: EventPublisher,
+    pub subscriptions: RpcSubscriptions,
 }
 
 #[derive(Debug)]
@@ -30,6 +31,7 @@ impl JsonRpcServer {
             events_tx: config.events_tx.clone(),
             vrrbdb_read_handle: config.vrrbdb_read_handle.clone(),
             mempool_read_handle_factory: config.mempool_read_handle_factory.clone(),
+            subscriptions: config.subscriptions.clone(),
         };
 
         let addr = server.local_addr()?;
@@ -52,12 +54,18 @@ impl Default for JsonRpcServerConfig {
 
         vrrbdb_config.path = db_path;
 
-        let vrrbdb = VrrbDb::new(vrrbdb_config);
+        let vrrbdb =
+            VrrbDb::new(vrrbdb_config).expect("failed to open the json rpc server's database");
         let vrrbdb_read_handle = vrrbdb.read_handle();
 
         let mempool = LeftRightMempool::default();
         let mempool_read_handle_factory = mempool.factory();
 
+        let subscriptions = RpcSubscriptions::new(
+            vrrbdb_read_handle.clone(),
+            mempool_read_handle_factory.clone(),
+        );
+
         let node_type = NodeType::Full;
         let (events_tx, _) = channel(DEFAULT_BUFFER);
 
@@ -67,6 +75,7 @@ impl Default for JsonRpcServerConfig {
             mempool_read_handle_factory,
             node_type,
             events_tx,
+            subscriptions,
         }
     }
 }
@@ -1,27 +1,49 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use async_trait::async_trait;
 use block::block::Block;
-use events::{Event, EventPublisher};
-use jsonrpsee::core::Error;
-use mempool::MempoolReadHandleFactory;
-use primitives::{Address, NodeType, Round};
+use events::{Event, EventMessage, EventPublisher};
+use jsonrpsee::{core::Error, server::SubscriptionSink, types::SubscriptionResult};
+use mempool::{MempoolReadHandleFactory, TxnFilter, TxnStatus};
+use primitives::{Address, Epoch, NodeType, Round};
 use secp256k1::{Message, SecretKey};
 use sha2::{Digest, Sha256};
-use block::ClaimHash;
-use storage::vrrbdb::{Claims, VrrbDbReadHandle};
+use block::{BlockHash, ClaimHash};
+use storage::vrrbdb::{AccountFilter, Claims, VrrbDbReadHandle};
 use telemetry::{debug, error};
 use vrrb_config::bootstrap_quorum::QuorumMembershipConfig;
 use vrrb_core::{
     account::Account,
+    receipt::{TxnReceipt, TxnReceiptStatus},
+    reward_credit::RewardCredit,
     serde_helpers::encode_to_binary,
+    staking::Stake,
     txn::{NewTxnArgs, TransactionDigest, Txn},
 };
-use vrrb_core::claim::Claim;
 use vrrb_core::node_health_report::NodeHealthReport;
+use wasm_loader::wasm_loader::WasmLoaderBuilder;
+use wasm_runtime::{host_functions::HostContext, wasm_runtime::WasmRuntime};
 
 use super::{
-    api::{FullMempoolSnapshot, RpcApiServer},
+    api::{
+        page_limit,
+        ClaimHashesPage,
+        FullMempoolSnapshot,
+        RpcApiServer,
+        RpcClaimStake,
+        RpcDelegation,
+        RpcMerkleProof,
+        RpcProgramCallInput,
+        RpcProgramCallResult,
+        RpcProgramRecord,
+        RpcUnbonding,
+        RpcVestingStatus,
+    },
+    subscriptions::{lagged_error, next_notification, Notification, RpcSubscriptions},
     SignOpts,
 };
 use crate::rpc::api::{FullStateSnapshot, RpcTransactionDigest, RpcTransactionRecord};
@@ -32,25 +54,84 @@ pub struct RpcServerImpl {
     pub vrrbdb_read_handle: VrrbDbReadHandle,
     pub mempool_read_handle_factory: MempoolReadHandleFactory,
     pub events_tx: EventPublisher,
+    pub subscriptions: RpcSubscriptions,
+}
+
+impl RpcServerImpl {
+    /// Retrieves the account of the program deployed at the given address
+    /// along with its hex encoded code and decoded WASM bytes.
+    fn get_program_account(&self, address: &Address) -> Result<(Account, String, Vec<u8>), Error> {
+        let account = self
+            .vrrbdb_read_handle
+            .state_store_handle()
+            .get(address)
+            .map_err(|_| Error::Custom("unable to find account".to_string()))?;
+
+        let code = account
+            .code()
+            .clone()
+            .ok_or_else(|| Error::Custom(format!("no program deployed at {address}")))?;
+
+        let wasm_bytes = hex::decode(&code)
+            .map_err(|err| Error::Custom(format!("invalid program code: {err}")))?;
+
+        Ok((account, code, wasm_bytes))
+    }
 }
 
 #[async_trait]
 impl RpcApiServer for RpcServerImpl {
-    async fn get_full_state(&self) -> Result<FullStateSnapshot, Error> {
-        let values = self.vrrbdb_read_handle.state_store_values();
+    async fn get_full_state(
+        &self,
+        block_hash: Option<BlockHash>,
+        filter: Option<AccountFilter>,
+        after: Option<Address>,
+        limit: Option<usize>,
+    ) -> Result<FullStateSnapshot, Error> {
+        let filter = filter.unwrap_or_default();
+        let limit = page_limit(limit);
+
+        let accounts = match block_hash {
+            Some(block_hash) => self
+                .vrrbdb_read_handle
+                .state_page_at(&block_hash, after.as_ref(), limit, &filter)
+                .map_err(|err| Error::Custom(format!("unable to read state at block: {err}")))?,
+            None => {
+                self.vrrbdb_read_handle
+                    .state_store_handle()
+                    .page(after.as_ref(), limit, &filter)
+            },
+        };
 
-        Ok(values)
+        Ok(FullStateSnapshot::new(accounts, limit, |(address, _)| {
+            address.clone()
+        }))
     }
 
-    async fn get_full_mempool(&self) -> Result<FullMempoolSnapshot, Error> {
-        let values = self
+    async fn get_full_mempool(
+        &self,
+        filter: Option<TxnFilter>,
+        after: Option<RpcTransactionDigest>,
+        limit: Option<usize>,
+    ) -> Result<FullMempoolSnapshot, Error> {
+        let filter = filter.unwrap_or_default();
+        let limit = page_limit(limit);
+
+        let after = after
+            .map(|digest| digest.parse::<TransactionDigest>())
+            .transpose()
+            .map_err(|_err| Error::Custom("unable to parse transaction digest".to_string()))?;
+
+        let records = self
             .mempool_read_handle_factory
-            .values()
-            .iter()
-            .map(|txn| RpcTransactionRecord::from(txn.clone()))
+            .page(after.as_ref(), limit, &filter)
+            .into_iter()
+            .map(|record| RpcTransactionRecord::from(record.txn))
             .collect();
 
-        Ok(values)
+        Ok(FullMempoolSnapshot::new(records, limit, |record| {
+            record.id.clone()
+        }))
     }
 
     async fn get_node_type(&self) -> Result<NodeType, Error> {
@@ -94,6 +175,45 @@ impl RpcApiServer for RpcServerImpl {
         }
     }
 
+    async fn get_transaction_receipt(
+        &self,
+        transaction_digest: RpcTransactionDigest,
+    ) -> Result<TxnReceipt, Error> {
+        debug!("Received a getTransactionReceipt RPC request");
+
+        let parsed_digest = transaction_digest
+            .parse::<TransactionDigest>()
+            .map_err(|_err| Error::Custom("unable to parse transaction digest".to_string()))?;
+
+        if let Ok(receipt) = self.vrrbdb_read_handle.transaction_receipt(&parsed_digest) {
+            return Ok(receipt);
+        }
+
+        // Transactions written without going through a convergence block have
+        // no receipt but are still part of the ledger
+        if self
+            .vrrbdb_read_handle
+            .transaction_store_handle()
+            .get(&parsed_digest)
+            .is_ok()
+        {
+            return Ok(TxnReceipt::new(parsed_digest, TxnReceiptStatus::Included));
+        }
+
+        let record = self
+            .mempool_read_handle_factory
+            .get(&parsed_digest)
+            .ok_or_else(|| Error::Custom("unable to find transaction".to_string()))?;
+
+        let status = match record.status {
+            TxnStatus::Pending | TxnStatus::Validating => TxnReceiptStatus::Pending,
+            TxnStatus::Validated => TxnReceiptStatus::Validated,
+            TxnStatus::Rejected => TxnReceiptStatus::Rejected,
+        };
+
+        Ok(TxnReceipt::new(parsed_digest, status))
+    }
+
     async fn list_transactions(
         &self,
         digests: Vec<RpcTransactionDigest>,
@@ -161,18 +281,25 @@ impl RpcApiServer for RpcServerImpl {
         Ok(())
     }
 
-    async fn get_account(&self, address: Address) -> Result<Account, Error> {
+    async fn get_account(
+        &self,
+        address: Address,
+        block_hash: Option<BlockHash>,
+    ) -> Result<Account, Error> {
         telemetry::info!("retrieving account {address}");
 
-        let values = self.vrrbdb_read_handle.state_store_values();
-        let value = values.get(&address);
+        if let Some(block_hash) = block_hash {
+            return self
+                .vrrbdb_read_handle
+                .account_at(&block_hash, &address)
+                .map_err(|err| Error::Custom(format!("unable to find account at block: {err}")));
+        }
+
+        let value = self.vrrbdb_read_handle.state_store_handle().get(&address);
 
         debug!("Received getAccount RPC Request: {value:?}");
 
-        match value {
-            Some(account) => return Ok(account.to_owned()),
-            None => return Err(Error::Custom("unable to find account".to_string())),
-        }
+        value.map_err(|_| Error::Custom("unable to find account".to_string()))
     }
 
     async fn faucet_drip(&self, address: Address) -> Result<(), Error> {
@@ -216,12 +343,73 @@ impl RpcApiServer for RpcServerImpl {
         todo!()
     }
 
-    async fn get_program(&self) -> Result<(), Error> {
-        todo!()
+    async fn get_program(&self, address: Address) -> Result<RpcProgramRecord, Error> {
+        debug!("Received getProgram RPC Request for {address}");
+
+        let (_, code, wasm_bytes) = self.get_program_account(&address)?;
+
+        let wasm_loader = WasmLoaderBuilder::default()
+            .wasm_bytes(wasm_bytes)
+            .parse()
+            .and_then(|builder| Ok(builder.build()?))
+            .map_err(|err| Error::Custom(format!("unable to parse program: {err}")))?;
+
+        Ok(RpcProgramRecord {
+            address,
+            code,
+            imports: wasm_loader.imports,
+            is_wasi: wasm_loader.is_wasi,
+            is_wasix: wasm_loader.is_wasix,
+            has_vrrb: wasm_loader.has_vrrb,
+        })
     }
 
-    async fn call_program(&self) -> Result<(), Error> {
-        todo!()
+    async fn call_program(
+        &self,
+        address: Address,
+        caller: Address,
+        input: String,
+        args: Vec<String>,
+        env: HashMap<String, String>,
+    ) -> Result<RpcProgramCallResult, Error> {
+        debug!("Received callProgram RPC Request for {address} from {caller}");
+
+        let (account, _, wasm_bytes) = self.get_program_account(&address)?;
+        let storage = account.storage().clone();
+
+        let stdin = serde_json::to_vec(&RpcProgramCallInput { input, storage })
+            .map_err(|err| Error::Custom(err.to_string()))?;
+
+        // Calls are read-only, any writes requested by the program through
+        // the host functions are discarded.
+        let host_context = HostContext {
+            contract_address: address.clone(),
+            caller_address: caller,
+            state: self.vrrbdb_read_handle.state_store_handle(),
+        };
+
+        // Executing a module is CPU bound so keep it off the async executor
+        tokio::task::spawn_blocking(move || {
+            let result = WasmRuntime::new(&wasm_bytes)?
+                .host(host_context)?
+                .stdin(&stdin)?
+                .args(&args)?
+                .env(&env)?
+                .execute()?;
+
+            debug!("program at {address} finished executing: {:?}", result.outcome);
+
+            Ok::<_, anyhow::Error>(RpcProgramCallResult {
+                stdout: result.stdout,
+                stderr: result.stderr,
+                exit_status: result.exit_code,
+                outcome: result.outcome,
+                fuel_used: result.fuel_used,
+            })
+        })
+        .await
+        .map_err(|err| Error::Custom(err.to_string()))?
+        .map_err(|err| Error::Custom(format!("unable to run program: {err}")))
     }
 
     async fn get_transaction_count(&self, account: Address) -> Result<usize, Error> {
@@ -232,27 +420,164 @@ impl RpcApiServer for RpcServerImpl {
         todo!()
     }
 
-    async fn get_claims_by_account_id(&self, address: Address) -> Result<Claims, Error> {
-        let claims = self.vrrbdb_read_handle.claim_store_values();
-        let claims = claims.iter().map(|(_, claim)| claim.clone()).filter(
-            |claim| claim.address == address
-        ).collect();
+    async fn create_stake(&self, stake: Stake) -> Result<String, Error> {
+        debug!("Received a createStake RPC request");
 
-        Ok(claims)
+        stake
+            .verify()
+            .map_err(|err| Error::Custom(err.to_string()))?;
+
+        let stake_id = hex::encode(stake.get_payload());
+        let em = EventMessage::new(
+            Some("consensus-events".into()),
+            Event::NewStakeCreated(stake),
+        );
+
+        self.events_tx.send(em).await.map_err(|err| {
+            error!("could not submit stake: {err}");
+            Error::Custom(err.to_string())
+        })?;
+
+        Ok(stake_id)
     }
 
-    async fn get_claim_hashes(&self) -> Result<Vec<ClaimHash>, Error> {
-        let claims = self.vrrbdb_read_handle.claim_store_values();
-        let claim_hashes = claims.iter().map(|(_, claim)| claim.hash.clone()).collect();
+    async fn get_delegations(&self, address: Address) -> Result<RpcClaimStake, Error> {
+        debug!("Received a getDelegations RPC request");
+
+        let claim = self
+            .vrrbdb_read_handle
+            .claim_store_handle()
+            .get_by_address(&address)
+            .map_err(|err| Error::Custom(format!("unable to read claims: {err}")))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::Custom(format!("no claim found for {address}")))?;
+
+        let delegations = claim
+            .get_delegations()
+            .into_iter()
+            .map(|(delegator, amount)| RpcDelegation { delegator, amount })
+            .collect();
 
-        Ok(claim_hashes)
+        Ok(RpcClaimStake {
+            own_stake: claim.get_stake(),
+            delegated_stake: claim.get_delegated_stake(),
+            total_stake: claim.get_total_stake(),
+            delegations,
+        })
+    }
+
+    async fn get_pending_unbondings(&self, staker: Address) -> Result<Vec<RpcUnbonding>, Error> {
+        debug!("Received a getPendingUnbondings RPC request");
+
+        let mut unbondings: Vec<RpcUnbonding> = self
+            .vrrbdb_read_handle
+            .claim_store_handle()
+            .get_by_unbonding_staker(&staker)
+            .map_err(|err| Error::Custom(format!("unable to read claims: {err}")))?
+            .into_iter()
+            .flat_map(|claim| {
+                claim
+                    .get_unbonding()
+                    .into_iter()
+                    .filter(|unbonding| unbonding.staker == staker)
+                    .map(|unbonding| RpcUnbonding {
+                        claim_address: claim.address.clone(),
+                        amount: unbonding.amount,
+                        release_epoch: unbonding.release_epoch,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        unbondings.sort_by_key(|unbonding| unbonding.release_epoch);
+
+        Ok(unbondings)
+    }
+
+    async fn get_epoch_rewards(&self, epoch: Epoch) -> Result<Vec<RewardCredit>, Error> {
+        debug!("Received a getEpochRewards RPC request");
+
+        self.vrrbdb_read_handle
+            .epoch_rewards(epoch)
+            .map_err(|err| Error::Custom(format!("unable to read rewards: {err}")))
+    }
+
+    async fn get_vesting_status(
+        &self,
+        address: Option<Address>,
+        timestamp: Option<i64>,
+    ) -> Result<Vec<RpcVestingStatus>, Error> {
+        debug!("Received a getVestingStatus RPC request");
+
+        let timestamp = match timestamp {
+            Some(timestamp) => timestamp,
+            None => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|err| Error::Custom(err.to_string()))?
+                .as_secs() as i64,
+        };
+
+        let mut statuses: Vec<RpcVestingStatus> = self
+            .vrrbdb_read_handle
+            .state_store_values()
+            .into_iter()
+            .filter(|(account_address, _)| {
+                address.is_none() || address.as_ref() == Some(account_address)
+            })
+            .filter_map(|(account_address, account)| {
+                let vesting = account.vesting().as_ref()?;
+                let next_unlock = vesting.next_unlock(timestamp);
+
+                Some(RpcVestingStatus {
+                    address: account_address,
+                    vested: vesting.vested_at(timestamp),
+                    locked: vesting.locked_at(timestamp),
+                    next_unlock_timestamp: next_unlock.map(|(timestamp, _)| timestamp),
+                    next_unlock_amount: next_unlock.map(|(_, amount)| amount).unwrap_or_default(),
+                })
+            })
+            .collect();
+
+        statuses.sort_by(|a, b| a.address.cmp(&b.address));
+
+        Ok(statuses)
+    }
+
+    async fn get_claims_by_account_id(&self, address: Address) -> Result<Claims, Error> {
+        self.vrrbdb_read_handle
+            .claim_store_handle()
+            .get_by_address(&address)
+            .map_err(|err| Error::Custom(format!("unable to read claims: {err}")))
+    }
+
+    async fn get_claim_hashes(
+        &self,
+        after: Option<ClaimHash>,
+        limit: Option<usize>,
+    ) -> Result<ClaimHashesPage, Error> {
+        let limit = page_limit(limit);
+
+        let claim_hashes = self
+            .vrrbdb_read_handle
+            .claim_store_handle()
+            .page(after.as_ref(), limit)
+            .into_iter()
+            .map(|(claim_hash, _)| claim_hash)
+            .collect();
+
+        Ok(ClaimHashesPage::new(claim_hashes, limit, |claim_hash| {
+            *claim_hash
+        }))
     }
 
     async fn get_claims(&self, claim_hashes: Vec<ClaimHash>) -> Result<Claims, Error> {
-        let claims = self.vrrbdb_read_handle.claim_store_values();
-        let claims = claims.iter().map(|(_, claim)| claim.clone()).filter(
-            |claim| claim_hashes.contains(&claim.hash)
-        ).collect();
+        let claim_store_handle = self.vrrbdb_read_handle.claim_store_handle();
+
+        let claims = claim_hashes
+            .iter()
+            .filter_map(|claim_hash| claim_store_handle.get_by_hash(claim_hash).ok())
+            .collect();
 
         Ok(claims)
     }
@@ -264,4 +589,127 @@ impl RpcApiServer for RpcServerImpl {
     async fn get_last_block(&self) -> Result<Block, Error> {
         todo!()
     }
+
+    fn subscribe_new_blocks(&self, mut sink: SubscriptionSink) -> SubscriptionResult {
+        sink.accept()?;
+
+        let mut new_blocks_rx = self.subscriptions.subscribe_new_blocks();
+
+        tokio::spawn(async move {
+            while let Some(notification) = next_notification(&mut new_blocks_rx).await {
+                let block = match notification {
+                    Notification::Value(block) => block,
+                    Notification::Lagged(missed) => {
+                        sink.close(lagged_error(missed));
+                        break;
+                    },
+                };
+
+                if !matches!(sink.send(&block), Ok(true)) {
+                    break;
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    fn subscribe_pending_transactions(&self, mut sink: SubscriptionSink) -> SubscriptionResult {
+        sink.accept()?;
+
+        let mut pending_transactions_rx = self.subscriptions.subscribe_pending_transactions();
+
+        tokio::spawn(async move {
+            while let Some(notification) = next_notification(&mut pending_transactions_rx).await {
+                let record = match notification {
+                    Notification::Value(record) => record,
+                    Notification::Lagged(missed) => {
+                        sink.close(lagged_error(missed));
+                        break;
+                    },
+                };
+
+                if !matches!(sink.send(&record), Ok(true)) {
+                    break;
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    fn subscribe_account(
+        &self,
+        mut sink: SubscriptionSink,
+        address: Address,
+    ) -> SubscriptionResult {
+        sink.accept()?;
+
+        let mut accounts_rx = self.subscriptions.subscribe_accounts();
+
+        tokio::spawn(async move {
+            while let Some(notification) = next_notification(&mut accounts_rx).await {
+                let (updated_address, account) = match notification {
+                    Notification::Value(update) => update,
+                    Notification::Lagged(missed) => {
+                        sink.close(lagged_error(missed));
+                        break;
+                    },
+                };
+
+                if updated_address != address {
+                    continue;
+                }
+
+                if !matches!(sink.send(&account), Ok(true)) {
+                    break;
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    async fn get_transaction_proof(
+        &self,
+        transaction_digest: RpcTransactionDigest,
+    ) -> Result<RpcMerkleProof, Error> {
+        debug!("Received a getTransactionProof RPC request");
+
+        let parsed_digest = transaction_digest
+            .parse::<TransactionDigest>()
+            .map_err(|_err| Error::Custom("unable to parse transaction digest".to_string()))?;
+
+        let proof = self
+            .vrrbdb_read_handle
+            .transaction_store_handle()
+            .get_proof(&parsed_digest)
+            .map_err(|err| Error::Custom(format!("unable to create proof: {err}")))?;
+
+        Ok(RpcMerkleProof::from(proof))
+    }
+
+    async fn get_account_proof(&self, address: Address) -> Result<RpcMerkleProof, Error> {
+        debug!("Received a getAccountProof RPC request");
+
+        let proof = self
+            .vrrbdb_read_handle
+            .state_store_handle()
+            .get_proof(&address)
+            .map_err(|err| Error::Custom(format!("unable to create proof: {err}")))?;
+
+        Ok(RpcMerkleProof::from(proof))
+    }
+
+    async fn get_claim_proof(&self, claim_hash: ClaimHash) -> Result<RpcMerkleProof, Error> {
+        debug!("Received a getClaimProof RPC request");
+
+        let proof = self
+            .vrrbdb_read_handle
+            .claim_store_handle()
+            .get_proof(&claim_hash)
+            .map_err(|err| Error::Custom(format!("unable to create proof: {err}")))?;
+
+        Ok(RpcMerkleProof::from(proof))
+    }
 }
@@ -0,0 +1,129 @@
+use block::block::Block;
+use events::{Event, DEFAULT_BUFFER};
+use jsonrpsee::types::{ErrorObject, ErrorObjectOwned};
+use mempool::MempoolReadHandleFactory;
+use primitives::Address;
+use storage::vrrbdb::VrrbDbReadHandle;
+use tokio::sync::broadcast::{self, error::RecvError, Receiver, Sender};
+use vrrb_core::{account::Account, serde_helpers::decode_from_binary_byte_slice};
+
+use crate::rpc::api::RpcTransactionRecord;
+
+/// Error code subscriptions are closed with once they missed notifications.
+pub const SUBSCRIPTION_LAGGED_CODE: i32 = -32001;
+
+/// A value broadcast to the clients of a subscription.
+#[derive(Debug, Clone)]
+pub enum Notification<T> {
+    Value(T),
+    /// The given number of values were missed, either by the subscription or
+    /// by the node feeding the subscriptions, so clients have to resync.
+    Lagged(u64),
+}
+
+/// Fans the events published through the node's router out to the clients
+/// subscribed over WebSocket.
+#[derive(Debug, Clone)]
+pub struct RpcSubscriptions {
+    vrrbdb_read_handle: VrrbDbReadHandle,
+    mempool_read_handle_factory: MempoolReadHandleFactory,
+    new_blocks: Sender<Notification<Block>>,
+    pending_transactions: Sender<Notification<RpcTransactionRecord>>,
+    accounts: Sender<Notification<(Address, Account)>>,
+}
+
+impl RpcSubscriptions {
+    pub fn new(
+        vrrbdb_read_handle: VrrbDbReadHandle,
+        mempool_read_handle_factory: MempoolReadHandleFactory,
+    ) -> Self {
+        let (new_blocks, _) = broadcast::channel(DEFAULT_BUFFER);
+        let (pending_transactions, _) = broadcast::channel(DEFAULT_BUFFER);
+        let (accounts, _) = broadcast::channel(DEFAULT_BUFFER);
+
+        Self {
+            vrrbdb_read_handle,
+            mempool_read_handle_factory,
+            new_blocks,
+            pending_transactions,
+            accounts,
+        }
+    }
+
+    pub fn subscribe_new_blocks(&self) -> Receiver<Notification<Block>> {
+        self.new_blocks.subscribe()
+    }
+
+    pub fn subscribe_pending_transactions(&self) -> Receiver<Notification<RpcTransactionRecord>> {
+        self.pending_transactions.subscribe()
+    }
+
+    pub fn subscribe_accounts(&self) -> Receiver<Notification<(Address, Account)>> {
+        self.accounts.subscribe()
+    }
+
+    /// Tells every subscriber that the given number of events were missed
+    /// before they could be published.
+    pub fn report_lag(&self, missed: u64) {
+        let _ = self.new_blocks.send(Notification::Lagged(missed));
+        let _ = self.pending_transactions.send(Notification::Lagged(missed));
+        let _ = self.accounts.send(Notification::Lagged(missed));
+    }
+
+    /// Notifies the subscribers interested in the given event. Events nobody
+    /// subscribed to are dropped.
+    pub fn publish(&self, event: &Event) {
+        // NOTE: sending only fails when there are no subscribers, which is fine
+        match event {
+            Event::BlockCreated(block) => {
+                let _ = self.new_blocks.send(Notification::Value(block.clone()));
+            },
+            Event::BlockConfirmed(block_bytes) => {
+                if let Ok(block) = decode_from_binary_byte_slice::<Block>(block_bytes) {
+                    let _ = self.new_blocks.send(Notification::Value(block));
+                }
+            },
+            Event::TxnAddedToMempool(digest) => {
+                if let Some(record) = self.mempool_read_handle_factory.get(digest) {
+                    let record = RpcTransactionRecord::from(record.txn);
+                    let _ = self.pending_transactions.send(Notification::Value(record));
+                }
+            },
+            Event::AccountsUpdated(addresses) => {
+                let state_store_handle = self.vrrbdb_read_handle.state_store_handle();
+
+                addresses.iter().for_each(|address| {
+                    if let Ok(account) = state_store_handle.get(address) {
+                        let _ = self
+                            .accounts
+                            .send(Notification::Value((address.clone(), account)));
+                    }
+                });
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Waits for the next notification broadcast to a subscription. Values the
+/// subscriber missed because it fell behind are reported as lag. Returns
+/// `None` once the channel is closed.
+pub(crate) async fn next_notification<T: Clone>(
+    rx: &mut Receiver<Notification<T>>,
+) -> Option<Notification<T>> {
+    match rx.recv().await {
+        Ok(notification) => Some(notification),
+        Err(RecvError::Lagged(missed)) => Some(Notification::Lagged(missed)),
+        Err(RecvError::Closed) => None,
+    }
+}
+
+/// The error subscriptions are closed with once they missed notifications,
+/// since their clients can no longer rely on having seen every change.
+pub(crate) fn lagged_error(missed: u64) -> ErrorObjectOwned {
+    ErrorObject::owned(
+        SUBSCRIPTION_LAGGED_CODE,
+        format!("subscription fell behind and missed {missed} notifications"),
+        None::<()>,
+    )
+}
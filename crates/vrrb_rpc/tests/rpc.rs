@@ -1,10 +1,11 @@
 use std::{collections::HashMap, net::SocketAddr};
 
-use events::{EventMessage, DEFAULT_BUFFER};
+use events::{Event, EventMessage, DEFAULT_BUFFER};
+use mempool::LeftRightMempool;
 use primitives::{generate_mock_account_keypair, Address};
 use secp256k1::Message;
 use tokio::sync::mpsc::channel;
-use vrrb_core::txn::{generate_txn_digest_vec, NewTxnArgs, Token};
+use vrrb_core::txn::{generate_txn_digest_vec, NewTxnArgs, Token, Txn};
 use vrrb_rpc::rpc::{
     api::{RpcApiClient, RpcTransactionRecord},
     client::create_client,
@@ -93,3 +94,36 @@ async fn server_can_publish_transactions_to_be_created() {
 
     handle.stop().unwrap();
 }
+
+#[tokio::test]
+async fn server_notifies_subscribers_of_pending_transactions() {
+    let mut mempool = LeftRightMempool::default();
+    let txn = Txn::default();
+    mempool.insert(txn.clone()).unwrap();
+
+    let mut json_rpc_server_config = JsonRpcServerConfig::default();
+    json_rpc_server_config.address = "127.0.0.1:0"
+        .parse()
+        .expect("Unable to create Socket Address");
+    json_rpc_server_config.mempool_read_handle_factory = mempool.factory();
+    json_rpc_server_config.subscriptions = RpcSubscriptions::new(
+        json_rpc_server_config.vrrbdb_read_handle.clone(),
+        mempool.factory(),
+    );
+
+    let (handle, rpc_server_address) = JsonRpcServer::run(&json_rpc_server_config).await.unwrap();
+
+    let client = create_client(rpc_server_address).await.unwrap();
+
+    let mut subscription = client.subscribe_pending_transactions().await.unwrap();
+
+    json_rpc_server_config
+        .subscriptions
+        .publish(&Event::TxnAddedToMempool(txn.id()));
+
+    let record = subscription.next().await.unwrap().unwrap();
+
+    assert_eq!(record.id, txn.id().to_string());
+
+    handle.stop().unwrap();
+}
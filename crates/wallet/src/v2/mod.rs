@@ -124,7 +124,18 @@ impl Wallet {
     }
 
     pub async fn get_mempool(&self) -> Result<Vec<RpcTransactionRecord>, WalletError> {
-        let mempool = self.client.get_full_mempool().await?;
+        let mut mempool = Vec::new();
+        let mut after = None;
+
+        loop {
+            let page = self.client.get_full_mempool(None, after, None).await?;
+            mempool.extend(page.items);
+
+            match page.next {
+                Some(next) => after = Some(next),
+                None => break,
+            }
+        }
 
         Ok(mempool)
     }
@@ -199,7 +210,7 @@ impl Wallet {
     }
 
     pub async fn get_account(&mut self, address: Address) -> WalletResult<Account> {
-        let account = self.client.get_account(address).await.map_err(|err| {
+        let account = self.client.get_account(address, None).await.map_err(|err| {
             error!("{:?}", err.to_string());
 
             WalletError::Custom(format!("API Error: {err}"))
@@ -30,6 +30,7 @@ pub fn run(opts: &DescribeOpts) -> Result<()> {
     println!("Start? {}", wasm_loader.has_start);
     println!("VRRB?  {}", wasm_loader.has_vrrb);
     println!("Namespaces: {:?}", wasm_loader.imports.keys());
+    println!("VRRB host functions: {:?}", wasm_loader.vrrb_host_functions());
 
     Ok(())
 }
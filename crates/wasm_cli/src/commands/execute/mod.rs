@@ -3,7 +3,13 @@ use std::{collections::HashMap, path::PathBuf};
 use anyhow::{anyhow, Result};
 use clap::Parser;
 use telemetry::info;
-use wasm_runtime::wasm_runtime::WasmRuntime;
+use wasm_runtime::wasm_runtime::{
+    ExecutionLimits,
+    WasmRuntime,
+    DEFAULT_FUEL_LIMIT,
+    DEFAULT_MAX_MEMORY_PAGES,
+    DEFAULT_MAX_OUTPUT_BYTES,
+};
 
 #[derive(Parser, Debug)]
 pub struct ExecuteOpts {
@@ -17,6 +23,18 @@ pub struct ExecuteOpts {
     /// multiple times.
     #[clap(short, long, value_parser, value_name = "KEY=VALUE")]
     pub env: Vec<String>,
+    /// The amount of fuel available to the WASM module. Every executed WASM
+    /// operator consumes one unit.
+    #[clap(long, value_parser, default_value_t = DEFAULT_FUEL_LIMIT)]
+    pub fuel: u64,
+    /// The maximum number of 64KiB pages the WASM module's memory may grow
+    /// to.
+    #[clap(long, value_parser, default_value_t = DEFAULT_MAX_MEMORY_PAGES)]
+    pub max_memory_pages: u32,
+    /// The maximum number of bytes kept from each of the WASM module's stdout
+    /// and stderr.
+    #[clap(long, value_parser, default_value_t = DEFAULT_MAX_OUTPUT_BYTES)]
+    pub max_output_bytes: usize,
     /// Remaining arguments (after '--') are passed to the WASM module command
     /// line.
     #[clap(last = true)]
@@ -56,17 +74,36 @@ pub fn run(opts: &ExecuteOpts) -> Result<()> {
         }
     }
 
+    let limits = ExecutionLimits {
+        fuel: opts.fuel,
+        max_memory_pages: opts.max_memory_pages,
+        max_output_bytes: opts.max_output_bytes,
+    };
+
     // Execute the WASM module.
     let mut wasm = WasmRuntime::new(&wasm_bytes)?
+        .limits(limits)?
         .stdin(&json_data)?
         .env(&env_vars)?
         .args(&opts.args)?;
-    wasm.execute()?;
+    let result = wasm.execute()?;
 
     // Temporary output for user -- will eventually be more structured and both
     // human and machine readable.
-    println!("{}", &wasm.stdout());
-    eprintln!("Contract errors: {}", &wasm.stderr());
+    println!("{}", &result.stdout);
+    eprintln!("Contract errors: {}", &result.stderr);
+    eprintln!(
+        "Outcome: {:?}, exit code: {:?}, fuel used: {}/{}{}",
+        result.outcome,
+        result.exit_code,
+        result.fuel_used,
+        limits.fuel,
+        if result.output_truncated {
+            " (output truncated)"
+        } else {
+            ""
+        }
+    );
 
     Ok(())
 }
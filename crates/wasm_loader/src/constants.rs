@@ -21,3 +21,5 @@ pub const WASI_ENTRY_POINT: &str = "_start";
 pub const VRRB_WASM_MAGIC: &str = "_vrrb_abi_magic";
 /// A VRRB-specific version number potentially exported by modules
 pub const VRRB_WASM_VERSION: &str = "_vrrb_abi_version";
+/// The namespace of the host functions VRRB provides to modules.
+pub const VRRB_HOST_NAMESPACE: &str = "vrrb";
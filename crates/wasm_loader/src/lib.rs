@@ -1,6 +1,8 @@
 mod constants;
 pub mod wasm_loader;
 
+pub use constants::VRRB_HOST_NAMESPACE;
+
 #[cfg(test)]
 mod loader_tests {
     use telemetry::log::debug;
@@ -69,6 +71,25 @@ mod loader_tests {
         }
     }
 
+    #[test]
+    fn builder_lists_vrrb_host_functions() {
+        let wat = r#"(module
+            (import "vrrb" "get_storage" (func (param i32 i32) (result i32)))
+            (import "vrrb" "emit_event" (func (param i32 i32) (result i32)))
+            (memory (export "memory") 1)
+            (func (export "_start")))"#;
+        let w = WasmLoaderBuilder::default()
+            .wat_text(wat.as_bytes().to_vec())
+            .parse()
+            .unwrap()
+            .build();
+        assert!(w.is_ok());
+        if let Ok(wasm) = w {
+            debug!("w: {:02x?}", wasm);
+            assert_eq!(wasm.vrrb_host_functions(), vec!["get_storage", "emit_event"]);
+        }
+    }
+
     //XXX: Test data to generate for additional test cases:
     //  - Binary with WASIX symbols (Rust?)
     //  - 64bit as well as 32bit (Rust?)
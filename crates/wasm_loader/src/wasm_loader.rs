@@ -71,6 +71,16 @@ pub struct WasmLoader {
     pub imports: HashMap<String, Vec<String>>,
 }
 
+impl WasmLoader {
+    /// Returns the names of the VRRB host functions this module imports.
+    pub fn vrrb_host_functions(&self) -> Vec<String> {
+        self.imports
+            .get(constants::VRRB_HOST_NAMESPACE)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
 impl WasmLoaderBuilder {
     /// Performs some validation on the built WasmLoader struct. Called
     /// automatically as part of [WasmLoaderBuilder::build].
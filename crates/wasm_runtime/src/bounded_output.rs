@@ -0,0 +1,165 @@
+//! Output limits for WASM execution
+//!
+//! Wraps the write end of an output pipe so that the bytes a module writes
+//! past the configured limit are discarded as they are written, rather than
+//! buffered until the execution ends.
+
+use std::{
+    future::Future,
+    io::{self, SeekFrom},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use tokio::io::{AsyncRead, AsyncSeek, AsyncWrite, ReadBuf};
+use virtual_fs::{Pipe, VirtualFile};
+
+/// The write end of an output stream that keeps at most `limit` bytes.
+/// Writes past the limit succeed, so the module keeps running, but their
+/// bytes are dropped and the truncation is recorded so that the caller can
+/// report it.
+#[derive(Debug)]
+pub struct BoundedOutput {
+    /// The pipe the kept bytes are written to.
+    inner: Pipe,
+    /// The number of bytes that can still be written to the pipe.
+    remaining: usize,
+    /// Set once a write went past the limit.
+    truncated: Arc<AtomicBool>,
+}
+
+impl BoundedOutput {
+    pub fn new(inner: Pipe, limit: usize, truncated: Arc<AtomicBool>) -> Self {
+        Self {
+            inner,
+            remaining: limit,
+            truncated,
+        }
+    }
+}
+
+impl AsyncWrite for BoundedOutput {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let kept = buf.len().min(self.remaining);
+        if kept < buf.len() {
+            self.truncated.store(true, Ordering::SeqCst);
+        }
+
+        if kept == 0 {
+            return Poll::Ready(Ok(buf.len()));
+        }
+
+        match Pin::new(&mut self.inner).poll_write(cx, &buf[..kept]) {
+            Poll::Ready(Ok(written)) => {
+                self.remaining -= written;
+
+                // NOTE: the dropped bytes are reported as written along with
+                // the last kept ones
+                if written == kept {
+                    Poll::Ready(Ok(buf.len()))
+                } else {
+                    Poll::Ready(Ok(written))
+                }
+            },
+            other => other,
+        }
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+impl AsyncRead for BoundedOutput {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl AsyncSeek for BoundedOutput {
+    fn start_seek(mut self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        Pin::new(&mut self.inner).start_seek(position)
+    }
+
+    fn poll_complete(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Pin::new(&mut self.inner).poll_complete(cx)
+    }
+}
+
+impl VirtualFile for BoundedOutput {
+    fn last_accessed(&self) -> u64 {
+        self.inner.last_accessed()
+    }
+
+    fn last_modified(&self) -> u64 {
+        self.inner.last_modified()
+    }
+
+    fn created_time(&self) -> u64 {
+        self.inner.created_time()
+    }
+
+    fn size(&self) -> u64 {
+        self.inner.size()
+    }
+
+    fn set_len(&mut self, new_size: u64) -> virtual_fs::Result<()> {
+        self.inner.set_len(new_size)
+    }
+
+    fn unlink(&mut self) -> Pin<Box<dyn Future<Output = virtual_fs::Result<()>> + Send + 'static>> {
+        self.inner.unlink()
+    }
+
+    fn poll_read_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_read_ready(cx)
+    }
+
+    fn poll_write_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_ready(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn writes_past_the_limit_are_dropped() {
+        let (out_wasm, mut stdout) = Pipe::channel();
+        let truncated = Arc::new(AtomicBool::new(false));
+        let mut output = BoundedOutput::new(out_wasm, 4, truncated.clone());
+
+        assert_eq!(output.write(b"abc").await.unwrap(), 3);
+        assert!(!truncated.load(Ordering::SeqCst));
+
+        assert_eq!(output.write(b"def").await.unwrap(), 3);
+        assert_eq!(output.write(b"ghi").await.unwrap(), 3);
+        assert!(truncated.load(Ordering::SeqCst));
+
+        drop(output);
+
+        let mut kept = vec![];
+        stdout.read_to_end(&mut kept).await.unwrap();
+
+        assert_eq!(kept, b"abcd");
+    }
+}
@@ -0,0 +1,362 @@
+//! VRRB host functions
+//!
+//! Contracts may import the functions below from the [VRRB_HOST_NAMESPACE]
+//! namespace to read chain state and record changes to it. State is read
+//! through a [StateStoreReadHandle] and writes are collected in a
+//! [HostWriteSet] instead of being applied, so that the caller decides
+//! whether to commit them.
+//!
+//! All data is exchanged through the module's linear memory as a pointer and
+//! a length. Functions that return data write at most `len` bytes to `ptr`
+//! and return the full length of the data, so a module can retry with a
+//! larger buffer. A negative return value signals an error.
+//!
+//! Every call consumes [HOST_CALL_FUEL] along with [HOST_BYTE_FUEL] per byte
+//! exchanged with the module, out of the same fuel as the module's own
+//! operators. A call the remaining fuel can't pay for traps.
+
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use primitives::Address;
+use serde_derive::{Deserialize, Serialize};
+use storage::vrrbdb::StateStoreReadHandle;
+use vrrb_core::account::UpdateArgs;
+pub use wasm_loader::VRRB_HOST_NAMESPACE;
+use wasmer::{
+    imports,
+    Function,
+    FunctionEnv,
+    FunctionEnvMut,
+    Imports,
+    Instance,
+    Memory,
+    MemoryView,
+    RuntimeError,
+    Store,
+};
+use wasmer_middlewares::metering::{get_remaining_points, set_remaining_points, MeteringPoints};
+
+/// The host functions provided within [VRRB_HOST_NAMESPACE].
+pub const VRRB_HOST_FUNCTIONS: &[&str] = &[
+    "get_storage",
+    "set_storage",
+    "get_balance",
+    "caller_address",
+    "emit_event",
+];
+
+/// Returned by host functions when the requested value does not exist.
+const HOST_NOT_FOUND: i32 = -1;
+/// Returned by host functions when the module passed invalid arguments.
+const HOST_INVALID_ARGS: i32 = -2;
+
+/// The fuel consumed by every call to a host function.
+pub const HOST_CALL_FUEL: u64 = 1_000;
+/// The fuel consumed by every byte a host function reads from or writes to
+/// the module's memory.
+pub const HOST_BYTE_FUEL: u64 = 1;
+
+/// The chain state a contract executes against.
+#[derive(Debug, Clone)]
+pub struct HostContext {
+    /// The address of the account the contract is deployed to.
+    pub contract_address: Address,
+    /// The address of the account calling the contract.
+    pub caller_address: Address,
+    pub state: StateStoreReadHandle,
+}
+
+/// An event emitted by a contract through `emit_event`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ContractEvent {
+    pub contract_address: Address,
+    pub data: Vec<u8>,
+}
+
+/// The changes a contract requested during its execution.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostWriteSet {
+    pub storage: Option<String>,
+    pub events: Vec<ContractEvent>,
+}
+
+impl HostWriteSet {
+    /// Converts the write-set into the `UpdateArgs` to be applied to the
+    /// contract's account. Returns `None` if the contract left its storage
+    /// untouched.
+    pub fn update_args(&self, contract_address: &Address) -> Option<UpdateArgs> {
+        self.storage.as_ref().map(|storage| UpdateArgs {
+            address: contract_address.clone(),
+            nonce: None,
+            credits: None,
+            debits: None,
+            storage: Some(Some(storage.clone())),
+            code: None,
+            digests: None,
+        })
+    }
+}
+
+/// The environment shared by all host functions of a single execution.
+pub(crate) struct HostEnv {
+    context: HostContext,
+    write_set: Arc<Mutex<HostWriteSet>>,
+    /// The instantiated module, whose fuel host calls consume.
+    instance: Option<Instance>,
+    memory: Option<Memory>,
+}
+
+impl HostEnv {
+    pub(crate) fn new(context: HostContext, write_set: Arc<Mutex<HostWriteSet>>) -> Self {
+        Self {
+            context,
+            write_set,
+            instance: None,
+            memory: None,
+        }
+    }
+
+    /// Sets the instantiated module along with the memory it exports, which
+    /// is used to exchange data with it.
+    pub(crate) fn set_instance(&mut self, instance: Instance, memory: Memory) {
+        self.instance = Some(instance);
+        self.memory = Some(memory);
+    }
+
+    /// Returns the contract's storage, taking into account any writes made
+    /// during the current execution.
+    fn storage(&self) -> Option<String> {
+        if let Ok(write_set) = self.write_set.lock() {
+            if let Some(storage) = &write_set.storage {
+                return Some(storage.clone());
+            }
+        }
+
+        self.context
+            .state
+            .get(&self.context.contract_address)
+            .ok()
+            .and_then(|account| account.storage().clone())
+    }
+}
+
+/// Builds the import object for [VRRB_HOST_NAMESPACE] bound to the given
+/// environment.
+pub(crate) fn host_imports(store: &mut Store, env: &FunctionEnv<HostEnv>) -> Imports {
+    imports! {
+        VRRB_HOST_NAMESPACE => {
+            "get_storage" => Function::new_typed_with_env(store, env, get_storage),
+            "set_storage" => Function::new_typed_with_env(store, env, set_storage),
+            "get_balance" => Function::new_typed_with_env(store, env, get_balance),
+            "caller_address" => Function::new_typed_with_env(store, env, caller_address),
+            "emit_event" => Function::new_typed_with_env(store, env, emit_event),
+        }
+    }
+}
+
+/// Consumes the fuel of a host call exchanging `bytes` bytes with the module.
+/// Traps once the module's remaining fuel can't pay for it.
+fn consume_fuel(env: &mut FunctionEnvMut<HostEnv>, bytes: usize) -> Result<(), RuntimeError> {
+    let instance = match env.data().instance.clone() {
+        Some(instance) => instance,
+        None => return Ok(()),
+    };
+
+    let fuel = HOST_BYTE_FUEL
+        .saturating_mul(bytes as u64)
+        .saturating_add(HOST_CALL_FUEL);
+
+    match get_remaining_points(env, &instance) {
+        MeteringPoints::Remaining(remaining) if remaining >= fuel => {
+            set_remaining_points(env, &instance, remaining - fuel);
+            Ok(())
+        },
+        _ => {
+            set_remaining_points(env, &instance, 0);
+            Err(RuntimeError::new("out of fuel"))
+        },
+    }
+}
+
+/// Clamps the length a module passed to a host function to a byte count.
+fn byte_count(len: i32) -> usize {
+    len.max(0) as usize
+}
+
+/// Reads `len` bytes at `ptr` from the module's memory. Ranges that are out of
+/// the memory's bounds are rejected before anything is allocated.
+fn read_bytes(view: &MemoryView, ptr: i32, len: i32) -> Option<Vec<u8>> {
+    if ptr < 0 || len < 0 {
+        return None;
+    }
+
+    let end = (ptr as u64).checked_add(len as u64)?;
+    if end > view.data_size() {
+        return None;
+    }
+
+    let mut buf = vec![0u8; len as usize];
+    view.read(ptr as u64, &mut buf).ok()?;
+
+    Some(buf)
+}
+
+/// Converts a length to the `i32` lengths are exchanged with the module as.
+/// Lengths that don't fit trap, since the module couldn't tell them apart
+/// from an error.
+fn host_len(len: usize) -> Result<i32, RuntimeError> {
+    i32::try_from(len).map_err(|_| RuntimeError::new(format!("length {len} exceeds i32")))
+}
+
+/// Writes as much of `data` as fits in `len` bytes at `ptr` into the module's
+/// memory and returns the full length of `data`.
+fn write_bytes(view: &MemoryView, ptr: i32, len: i32, data: &[u8]) -> Result<i32, RuntimeError> {
+    let data_len = host_len(data.len())?;
+
+    if ptr < 0 || len < 0 {
+        return Ok(HOST_INVALID_ARGS);
+    }
+
+    let written = data.len().min(len as usize);
+    if view.write(ptr as u64, &data[..written]).is_err() {
+        return Ok(HOST_INVALID_ARGS);
+    }
+
+    Ok(data_len)
+}
+
+/// `get_storage(ptr, len) -> i32`: copies the contract's storage into the
+/// buffer at `ptr`.
+fn get_storage(mut env: FunctionEnvMut<HostEnv>, ptr: i32, len: i32) -> Result<i32, RuntimeError> {
+    let storage = env.data().storage();
+    let written = storage
+        .as_ref()
+        .map_or(0, |storage| storage.len().min(byte_count(len)));
+
+    consume_fuel(&mut env, written)?;
+
+    let data = env.data();
+    let memory = match &data.memory {
+        Some(memory) => memory,
+        None => return Ok(HOST_INVALID_ARGS),
+    };
+
+    match storage {
+        Some(storage) => write_bytes(&memory.view(&env), ptr, len, storage.as_bytes()),
+        None => Ok(HOST_NOT_FOUND),
+    }
+}
+
+/// `set_storage(ptr, len) -> i32`: replaces the contract's storage with the
+/// UTF-8 string at `ptr`.
+fn set_storage(mut env: FunctionEnvMut<HostEnv>, ptr: i32, len: i32) -> Result<i32, RuntimeError> {
+    consume_fuel(&mut env, byte_count(len))?;
+
+    let data = env.data();
+    let memory = match &data.memory {
+        Some(memory) => memory,
+        None => return Ok(HOST_INVALID_ARGS),
+    };
+
+    let storage = match read_bytes(&memory.view(&env), ptr, len).map(String::from_utf8) {
+        Some(Ok(storage)) => storage,
+        _ => return Ok(HOST_INVALID_ARGS),
+    };
+
+    Ok(match data.write_set.lock() {
+        Ok(mut write_set) => {
+            write_set.storage = Some(storage);
+            0
+        },
+        Err(_) => HOST_INVALID_ARGS,
+    })
+}
+
+/// `get_balance(addr_ptr, addr_len, out_ptr) -> i32`: writes the balance of
+/// the account whose address is at `addr_ptr` to `out_ptr` as a 16 byte
+/// little endian integer.
+fn get_balance(
+    mut env: FunctionEnvMut<HostEnv>,
+    addr_ptr: i32,
+    addr_len: i32,
+    out_ptr: i32,
+) -> Result<i32, RuntimeError> {
+    consume_fuel(&mut env, byte_count(addr_len) + std::mem::size_of::<u128>())?;
+
+    let data = env.data();
+    let memory = match &data.memory {
+        Some(memory) => memory,
+        None => return Ok(HOST_INVALID_ARGS),
+    };
+    let view = memory.view(&env);
+
+    let address = match read_bytes(&view, addr_ptr, addr_len).map(String::from_utf8) {
+        Some(Ok(address)) => address,
+        _ => return Ok(HOST_INVALID_ARGS),
+    };
+
+    let address = match Address::from_str(&address) {
+        Ok(address) => address,
+        Err(_) => return Ok(HOST_INVALID_ARGS),
+    };
+
+    match data.context.state.get(&address) {
+        Ok(account) => {
+            let balance = account.credits().saturating_sub(account.debits());
+            let bytes = balance.to_le_bytes();
+            write_bytes(&view, out_ptr, host_len(bytes.len())?, &bytes)
+        },
+        Err(_) => Ok(HOST_NOT_FOUND),
+    }
+}
+
+/// `caller_address(ptr, len) -> i32`: copies the caller's address into the
+/// buffer at `ptr`.
+fn caller_address(
+    mut env: FunctionEnvMut<HostEnv>,
+    ptr: i32,
+    len: i32,
+) -> Result<i32, RuntimeError> {
+    let caller_address = env.data().context.caller_address.to_string();
+
+    consume_fuel(&mut env, caller_address.len().min(byte_count(len)))?;
+
+    let data = env.data();
+    let memory = match &data.memory {
+        Some(memory) => memory,
+        None => return Ok(HOST_INVALID_ARGS),
+    };
+
+    write_bytes(&memory.view(&env), ptr, len, caller_address.as_bytes())
+}
+
+/// `emit_event(ptr, len) -> i32`: records the bytes at `ptr` as an event of
+/// the contract.
+fn emit_event(mut env: FunctionEnvMut<HostEnv>, ptr: i32, len: i32) -> Result<i32, RuntimeError> {
+    consume_fuel(&mut env, byte_count(len))?;
+
+    let data = env.data();
+    let memory = match &data.memory {
+        Some(memory) => memory,
+        None => return Ok(HOST_INVALID_ARGS),
+    };
+
+    let event_data = match read_bytes(&memory.view(&env), ptr, len) {
+        Some(event_data) => event_data,
+        None => return Ok(HOST_INVALID_ARGS),
+    };
+
+    Ok(match data.write_set.lock() {
+        Ok(mut write_set) => {
+            write_set.events.push(ContractEvent {
+                contract_address: data.context.contract_address.clone(),
+                data: event_data,
+            });
+            0
+        },
+        Err(_) => HOST_INVALID_ARGS,
+    })
+}
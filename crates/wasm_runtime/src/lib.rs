@@ -1,3 +1,6 @@
+mod bounded_output;
+pub mod host_functions;
+mod limiting_tunables;
 pub mod wasm_runtime;
 
 #[cfg(test)]
@@ -0,0 +1,196 @@
+//! Memory limits for WASM execution
+//!
+//! Wraps the default Wasmer tunables so that every memory created for a
+//! module is capped at a maximum number of pages, no matter what the module
+//! itself declares. Allocations the cap refuses, at instantiation or through
+//! `memory.grow`, are recorded so they can be told apart from other traps.
+
+use std::{
+    ptr::NonNull,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use wasmer::{
+    vm::{
+        self,
+        LinearMemory,
+        MemoryError,
+        MemoryStyle,
+        TableStyle,
+        VMMemoryDefinition,
+        VMTableDefinition,
+    },
+    MemoryType,
+    Pages,
+    TableType,
+    Tunables,
+};
+
+/// A [Tunables] implementation that limits the number of pages any memory
+/// may grow to. Requests for more memory than the limit, at instantiation or
+/// when growing a memory, are rejected and recorded so that the caller can
+/// report them.
+pub struct LimitingTunables<T: Tunables> {
+    /// The maximum number of pages a memory may have.
+    limit: Pages,
+    /// The tunables used for everything other than enforcing the limit.
+    base: T,
+    /// Set once a memory requested more pages than the limit allows.
+    exceeded: Arc<AtomicBool>,
+}
+
+impl<T: Tunables> LimitingTunables<T> {
+    pub fn new(base: T, limit: Pages, exceeded: Arc<AtomicBool>) -> Self {
+        Self {
+            limit,
+            base,
+            exceeded,
+        }
+    }
+
+    /// Caps the maximum of the requested memory at the configured limit so
+    /// that `memory.grow` fails beyond it.
+    fn adjust_memory(&self, requested: &MemoryType) -> MemoryType {
+        let mut adjusted = *requested;
+        if requested.maximum.map_or(true, |maximum| maximum > self.limit) {
+            adjusted.maximum = Some(self.limit);
+        }
+        adjusted
+    }
+
+    /// Ensures the memory's initial size fits within the limit.
+    fn validate_memory(&self, ty: &MemoryType) -> Result<(), MemoryError> {
+        if ty.minimum > self.limit {
+            self.exceeded.store(true, Ordering::SeqCst);
+            return Err(MemoryError::Generic(format!(
+                "minimum of {} pages exceeds the memory limit of {} pages",
+                ty.minimum.0, self.limit.0
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Wraps a memory so that growing it past the limit is recorded.
+    fn limit_memory(&self, memory: vm::VMMemory) -> vm::VMMemory {
+        vm::VMMemory(Box::new(LimitedMemory {
+            inner: memory.0,
+            limit: self.limit,
+            exceeded: self.exceeded.clone(),
+        }))
+    }
+}
+
+impl<T: Tunables> Tunables for LimitingTunables<T> {
+    fn memory_style(&self, memory: &MemoryType) -> MemoryStyle {
+        let adjusted = self.adjust_memory(memory);
+        self.base.memory_style(&adjusted)
+    }
+
+    fn table_style(&self, table: &TableType) -> TableStyle {
+        self.base.table_style(table)
+    }
+
+    fn create_host_memory(
+        &self,
+        ty: &MemoryType,
+        style: &MemoryStyle,
+    ) -> Result<vm::VMMemory, MemoryError> {
+        let adjusted = self.adjust_memory(ty);
+        self.validate_memory(&adjusted)?;
+        self.base
+            .create_host_memory(&adjusted, style)
+            .map(|memory| self.limit_memory(memory))
+    }
+
+    unsafe fn create_vm_memory(
+        &self,
+        ty: &MemoryType,
+        style: &MemoryStyle,
+        vm_definition_location: NonNull<VMMemoryDefinition>,
+    ) -> Result<vm::VMMemory, MemoryError> {
+        let adjusted = self.adjust_memory(ty);
+        self.validate_memory(&adjusted)?;
+        self.base
+            .create_vm_memory(&adjusted, style, vm_definition_location)
+            .map(|memory| self.limit_memory(memory))
+    }
+
+    fn create_host_table(&self, ty: &TableType, style: &TableStyle) -> Result<vm::VMTable, String> {
+        self.base.create_host_table(ty, style)
+    }
+
+    unsafe fn create_vm_table(
+        &self,
+        ty: &TableType,
+        style: &TableStyle,
+        vm_definition_location: NonNull<VMTableDefinition>,
+    ) -> Result<vm::VMTable, String> {
+        self.base.create_vm_table(ty, style, vm_definition_location)
+    }
+}
+
+/// A memory created through [LimitingTunables]. Its maximum is already
+/// capped at the limit, so growing it past the limit fails; the failure is
+/// recorded before being returned to the module.
+#[derive(Debug)]
+struct LimitedMemory {
+    inner: Box<dyn LinearMemory + 'static>,
+    limit: Pages,
+    exceeded: Arc<AtomicBool>,
+}
+
+impl LimitedMemory {
+    fn with_inner(
+        &self,
+        inner: Box<dyn LinearMemory + 'static>,
+    ) -> Box<dyn LinearMemory + 'static> {
+        Box::new(Self {
+            inner,
+            limit: self.limit,
+            exceeded: self.exceeded.clone(),
+        })
+    }
+}
+
+impl LinearMemory for LimitedMemory {
+    fn ty(&self) -> MemoryType {
+        self.inner.ty()
+    }
+
+    fn size(&self) -> Pages {
+        self.inner.size()
+    }
+
+    fn style(&self) -> MemoryStyle {
+        self.inner.style()
+    }
+
+    fn grow(&mut self, delta: Pages) -> Result<Pages, MemoryError> {
+        let requested = self.inner.size().0.saturating_add(delta.0);
+        let grown = self.inner.grow(delta);
+
+        if grown.is_err() && requested > self.limit.0 {
+            self.exceeded.store(true, Ordering::SeqCst);
+        }
+
+        grown
+    }
+
+    fn vmmemory(&self) -> NonNull<VMMemoryDefinition> {
+        self.inner.vmmemory()
+    }
+
+    fn try_clone(&self) -> Option<Box<dyn LinearMemory + 'static>> {
+        let inner = self.inner.try_clone()?;
+        Some(self.with_inner(inner))
+    }
+
+    fn copy(&mut self) -> Result<Box<dyn LinearMemory + 'static>, MemoryError> {
+        let inner = self.inner.copy()?;
+        Ok(self.with_inner(inner))
+    }
+}
@@ -2,7 +2,13 @@ use std::collections::HashMap;
 
 use serde_derive::{Deserialize, Serialize};
 
-use crate::wasm_runtime::WasmRuntime;
+use primitives::{generate_account_keypair, Address};
+use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
+
+use crate::{
+    host_functions::HostContext,
+    wasm_runtime::{ExecutionLimits, ExecutionOutcome, WasmRuntime},
+};
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -130,7 +136,6 @@ fn test_environment_vars() {
 /// execute. This is done by setting a special variable that the WASM test
 /// module uses to trigger failure.
 #[test]
-#[should_panic]
 fn test_failed_execution() {
     let wasm_bytes = std::fs::read("test_data/wasm_test.wasm").unwrap();
     let json_data = std::fs::read("test_data/wasm_test_oneline.json").unwrap();
@@ -142,7 +147,207 @@ fn test_failed_execution() {
         .unwrap()
         .env(&wasm_env)
         .unwrap();
-    runtime.execute().unwrap();
+    let result = runtime.execute().unwrap();
+
+    assert!(!result.is_success());
+}
+
+/// This test checks that a module which runs out of fuel is stopped and
+/// reported as such, having consumed exactly its fuel budget.
+#[test]
+fn test_out_of_gas() {
+    let wasm_bytes = std::fs::read("test_data/wasm_test.wasm").unwrap();
+    let json_data = std::fs::read("test_data/wasm_test_oneline.json").unwrap();
+    let limits = ExecutionLimits {
+        fuel: 100,
+        ..ExecutionLimits::default()
+    };
+    let mut runtime = WasmRuntime::new(&wasm_bytes)
+        .unwrap()
+        .limits(limits)
+        .unwrap()
+        .stdin(&json_data)
+        .unwrap();
+    let result = runtime.execute().unwrap();
+
+    assert_eq!(result.outcome, ExecutionOutcome::OutOfGas);
+    assert_eq!(result.fuel_used, limits.fuel);
+}
+
+/// This test checks that output beyond the configured limit is truncated and
+/// that the truncation is reported.
+#[test]
+fn test_output_truncated() {
+    let wasm_bytes = std::fs::read("test_data/wasm_test.wasm").unwrap();
+    let json_data = std::fs::read("test_data/wasm_test_oneline.json").unwrap();
+    let limits = ExecutionLimits {
+        max_output_bytes: 8,
+        ..ExecutionLimits::default()
+    };
+    let mut runtime = WasmRuntime::new(&wasm_bytes)
+        .unwrap()
+        .limits(limits)
+        .unwrap()
+        .stdin(&json_data)
+        .unwrap();
+    let result = runtime.execute().unwrap();
+
+    assert!(result.is_success());
+    assert!(result.output_truncated);
+    assert_eq!(result.stdout.len(), limits.max_output_bytes);
+    assert!(result.fuel_used > 0);
+}
+
+/// This test checks that storage writes and events requested through the VRRB
+/// host functions are handed back to the caller instead of being applied.
+#[test]
+fn test_host_functions_write_set() {
+    let wat = r#"(module
+        (import "vrrb" "set_storage" (func $set_storage (param i32 i32) (result i32)))
+        (import "vrrb" "emit_event" (func $emit_event (param i32 i32) (result i32)))
+        (memory (export "memory") 1)
+        (data (i32.const 0) "hello")
+        (func (export "_start")
+            (drop (call $set_storage (i32.const 0) (i32.const 5)))
+            (drop (call $emit_event (i32.const 0) (i32.const 5)))))"#;
+    let wasm_bytes = wasmer::wat2wasm(wat.as_bytes()).unwrap().to_vec();
+
+    let db_path = std::env::temp_dir().join(format!("wasm_runtime_{}", std::process::id()));
+    let db = VrrbDb::new(VrrbDbConfig::default().with_path(db_path)).unwrap();
+
+    let (_, public_key) = generate_account_keypair();
+    let contract_address = Address::new(public_key);
+
+    let context = HostContext {
+        contract_address: contract_address.clone(),
+        caller_address: contract_address.clone(),
+        state: db.read_handle().state_store_handle(),
+    };
+
+    let mut runtime = WasmRuntime::new(&wasm_bytes)
+        .unwrap()
+        .host(context)
+        .unwrap();
+    let result = runtime.execute().unwrap();
+
+    assert!(result.is_success());
+    assert_eq!(result.events.len(), 1);
+    assert_eq!(result.events[0].data, b"hello".to_vec());
+
+    let update_args = result.update_args.unwrap();
+    assert_eq!(update_args.address, contract_address);
+    assert_eq!(update_args.storage, Some(Some("hello".to_string())));
+}
+
+/// Creates a host context for a random contract address backed by an empty
+/// database.
+fn test_host_context(name: &str) -> HostContext {
+    let db_path = std::env::temp_dir().join(format!("wasm_runtime_{name}_{}", std::process::id()));
+    let db = VrrbDb::new(VrrbDbConfig::default().with_path(db_path)).unwrap();
+
+    let (_, public_key) = generate_account_keypair();
+    let contract_address = Address::new(public_key);
+
+    HostContext {
+        contract_address: contract_address.clone(),
+        caller_address: contract_address,
+        state: db.read_handle().state_store_handle(),
+    }
+}
+
+/// This test checks that host functions reject ranges outside of the
+/// module's memory instead of reading them.
+#[test]
+fn test_host_functions_reject_out_of_bounds_ranges() {
+    let wat = r#"(module
+        (import "vrrb" "emit_event" (func $emit_event (param i32 i32) (result i32)))
+        (memory (export "memory") 1)
+        (func (export "_start")
+            (if (i32.ne (call $emit_event (i32.const 65000) (i32.const 1000)) (i32.const -2))
+                (then unreachable))))"#;
+    let wasm_bytes = wasmer::wat2wasm(wat.as_bytes()).unwrap().to_vec();
+
+    let mut runtime = WasmRuntime::new(&wasm_bytes)
+        .unwrap()
+        .host(test_host_context("out_of_bounds"))
+        .unwrap();
+    let result = runtime.execute().unwrap();
+
+    assert!(result.is_success());
+    assert!(result.events.is_empty());
+}
+
+/// This test checks that host calls consume the module's fuel.
+#[test]
+fn test_host_functions_consume_fuel() {
+    let wat = r#"(module
+        (import "vrrb" "emit_event" (func $emit_event (param i32 i32) (result i32)))
+        (memory (export "memory") 1)
+        (func (export "_start")
+            (drop (call $emit_event (i32.const 0) (i32.const 0)))
+            (drop (call $emit_event (i32.const 0) (i32.const 0)))
+            (drop (call $emit_event (i32.const 0) (i32.const 0)))))"#;
+    let wasm_bytes = wasmer::wat2wasm(wat.as_bytes()).unwrap().to_vec();
+    let limits = ExecutionLimits {
+        fuel: 2 * crate::host_functions::HOST_CALL_FUEL + 500,
+        ..ExecutionLimits::default()
+    };
+
+    let mut runtime = WasmRuntime::new(&wasm_bytes)
+        .unwrap()
+        .limits(limits)
+        .unwrap()
+        .host(test_host_context("host_fuel"))
+        .unwrap();
+    let result = runtime.execute().unwrap();
+
+    assert_eq!(result.outcome, ExecutionOutcome::OutOfGas);
+    assert_eq!(result.fuel_used, limits.fuel);
+}
+
+/// This test checks that a module trapping after it was refused memory beyond
+/// the limit is reported as having exceeded its memory.
+#[test]
+fn test_memory_exceeded() {
+    let wat = r#"(module
+        (memory (export "memory") 1)
+        (func (export "_start")
+            (if (i32.eq (memory.grow (i32.const 2)) (i32.const -1))
+                (then unreachable))))"#;
+    let wasm_bytes = wasmer::wat2wasm(wat.as_bytes()).unwrap().to_vec();
+    let limits = ExecutionLimits {
+        max_memory_pages: 2,
+        ..ExecutionLimits::default()
+    };
+
+    let mut runtime = WasmRuntime::new(&wasm_bytes)
+        .unwrap()
+        .limits(limits)
+        .unwrap();
+    let result = runtime.execute().unwrap();
+
+    assert_eq!(result.outcome, ExecutionOutcome::MemoryExceeded);
+}
+
+/// This test checks that a module trapping for another reason while its memory
+/// is at the limit is reported as a trap.
+#[test]
+fn test_trap_at_memory_limit() {
+    let wat = r#"(module
+        (memory (export "memory") 2)
+        (func (export "_start")
+            unreachable))"#;
+    let wasm_bytes = wasmer::wat2wasm(wat.as_bytes()).unwrap().to_vec();
+    let limits = ExecutionLimits {
+        max_memory_pages: 2,
+        ..ExecutionLimits::default()
+    };
+
+    let mut runtime = WasmRuntime::new(&wasm_bytes)
+        .unwrap()
+        .limits(limits)
+        .unwrap();
+    let result = runtime.execute().unwrap();
 
-    let _out: TestOutput = serde_json::from_str(&runtime.stdout()).unwrap();
+    assert!(matches!(result.outcome, ExecutionOutcome::Trap(_)));
 }
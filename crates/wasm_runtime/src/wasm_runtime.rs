@@ -4,24 +4,139 @@
 //! function calls and assumes that the WASM payload has a _start entry point,
 //! reads from STDIN and writes to STDOUT. It wraps around the Wasmer WASM
 //! runtime.
+//!
+//! Every execution is metered: each WASM operator consumes one unit of fuel,
+//! memories are capped at a maximum number of pages and captured output is
+//! truncated, so that executions are bounded and deterministic across nodes.
 
 use std::{
     collections::HashMap,
     io::{Read, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+        Mutex,
+    },
 };
 
 use anyhow::Result;
+use serde_derive::{Deserialize, Serialize};
 use telemetry::debug;
-use wasmer::{Module, Store};
-use wasmer_wasix::{Pipe, WasiEnv};
+use vrrb_core::account::UpdateArgs;
+use wasmer::{
+    wasmparser::Operator,
+    AsStoreMut,
+    BaseTunables,
+    CompilerConfig,
+    Cranelift,
+    Engine,
+    EngineBuilder,
+    FunctionEnv,
+    Instance,
+    Module,
+    Pages,
+    Store,
+    Target,
+};
+use wasmer_middlewares::{
+    metering::{get_remaining_points, MeteringPoints},
+    Metering,
+};
+use wasmer_wasix::{Pipe, WasiEnv, WasiEnvBuilder, WasiError, WasiFunctionEnv};
+use wasmer_wasix_types::wasi::ExitCode;
+
+use crate::{
+    bounded_output::BoundedOutput,
+    host_functions::{host_imports, ContractEvent, HostContext, HostEnv, HostWriteSet},
+    limiting_tunables::LimitingTunables,
+};
 
 /// This is the first command line argument, traditionally reserved for the
 /// program name (argv[0] in C and others).
 const MODULE_ARGV0: &str = "vrrb-contract";
+/// The name of the memory exported by WASI modules.
+const MODULE_MEMORY: &str = "memory";
+/// Default entry point for WASM modules.
+const MODULE_ENTRY_POINT: &str = "_start";
+
+/// The default amount of fuel an execution may consume.
+pub const DEFAULT_FUEL_LIMIT: u64 = 1_000_000_000;
+/// The default maximum number of 64KiB pages a module's memory may grow to.
+pub const DEFAULT_MAX_MEMORY_PAGES: u32 = 1024;
+/// The default maximum number of bytes captured from each of stdout and
+/// stderr.
+pub const DEFAULT_MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// The limits enforced on a single execution of a WASM module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionLimits {
+    /// The amount of fuel available to the module. Every executed WASM
+    /// operator consumes one unit.
+    pub fuel: u64,
+    /// The maximum number of 64KiB pages any memory of the module may have.
+    pub max_memory_pages: u32,
+    /// The maximum number of bytes kept from each output stream.
+    pub max_output_bytes: usize,
+}
+
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        Self {
+            fuel: DEFAULT_FUEL_LIMIT,
+            max_memory_pages: DEFAULT_MAX_MEMORY_PAGES,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+        }
+    }
+}
+
+/// How an execution of a WASM module ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status", content = "reason")]
+pub enum ExecutionOutcome {
+    /// The module ran to completion or exited on its own.
+    Ok,
+    /// The module consumed all of its fuel.
+    OutOfGas,
+    /// The module trapped, or could not be instantiated.
+    Trap(String),
+    /// The module requested more memory than permitted.
+    MemoryExceeded,
+}
+
+/// The structured result of executing a WASM module.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionResult {
+    pub outcome: ExecutionOutcome,
+    /// The amount of fuel consumed by the execution.
+    pub fuel_used: u64,
+    /// The exit status reported by the module, if any.
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    /// True if either output stream was cut at `max_output_bytes`.
+    pub output_truncated: bool,
+    /// Events emitted by the module through the VRRB host functions.
+    pub events: Vec<ContractEvent>,
+    /// The changes the module requested to its account's state, to be applied
+    /// by the caller. Only set for successful executions with a
+    /// [HostContext].
+    #[serde(skip)]
+    pub update_args: Option<UpdateArgs>,
+}
+
+impl ExecutionResult {
+    /// Returns true if the module ran to completion and exited with status
+    /// zero.
+    pub fn is_success(&self) -> bool {
+        self.outcome == ExecutionOutcome::Ok && self.exit_code == Some(0)
+    }
+}
 
 pub struct WasmRuntime {
-    store: Store,
-    module: Module,
+    wasm_bytes: Vec<u8>,
+    limits: ExecutionLimits,
+    host: Option<HostContext>,
     stdin: Vec<u8>,
     stdout: String,
     stderr: String,
@@ -31,19 +146,18 @@ pub struct WasmRuntime {
 
 impl WasmRuntime {
     /// Creates a new WasmRuntime environment to execute the WASM binary passed
-    /// in.
+    /// in, with the default [ExecutionLimits].
     pub fn new(wasm_bytes: &Vec<u8>) -> Result<Self> {
-        // Create an in-memory store for everything required to compile and run a WASM
-        // module
-        let store = Store::default();
+        debug!("Validating {} bytes of WASM", wasm_bytes.len());
 
-        debug!("Compiling {} bytes of WASM", wasm_bytes.len());
+        // The module is compiled on execution, once the limits are known, but
+        // is validated up front so that invalid payloads are rejected early.
+        Module::validate(&Engine::default(), wasm_bytes)?;
 
-        // Compile module into in-memory store
-        let module = Module::new(&store, wasm_bytes)?;
         Ok(Self {
-            store,
-            module,
+            wasm_bytes: wasm_bytes.to_vec(),
+            limits: ExecutionLimits::default(),
+            host: None,
             stdin: vec![],
             stdout: String::new(),
             stderr: String::new(),
@@ -52,6 +166,20 @@ impl WasmRuntime {
         })
     }
 
+    /// Sets the fuel, memory and output limits for the WASM module's
+    /// execution.
+    pub fn limits(mut self, limits: ExecutionLimits) -> Result<Self> {
+        self.limits = limits;
+        Ok(self)
+    }
+
+    /// Provides the VRRB host functions to the WASM module, executing it
+    /// against the given chain state.
+    pub fn host(mut self, context: HostContext) -> Result<Self> {
+        self.host = Some(context);
+        Ok(self)
+    }
+
     /// Adds a set of command line arguments to the WASM module's execution
     pub fn args(mut self, args: &[String]) -> Result<Self> {
         self.args = args.to_vec();
@@ -82,22 +210,209 @@ impl WasmRuntime {
         self.stderr.clone()
     }
 
-    /// Execute the compiled WASM module and retrieve the result.
-    pub fn execute(&mut self) -> Result<()> {
+    /// Creates a store whose engine meters every operator and caps memories
+    /// at the configured number of pages.
+    fn create_store(&self, memory_exceeded: Arc<AtomicBool>) -> Store {
+        let metering = Arc::new(Metering::new(self.limits.fuel, |_: &Operator| -> u64 {
+            1
+        }));
+
+        let mut compiler_config = Cranelift::default();
+        compiler_config.push_middleware(metering);
+
+        let tunables = LimitingTunables::new(
+            BaseTunables::for_target(&Target::default()),
+            Pages(self.limits.max_memory_pages),
+            memory_exceeded,
+        );
+
+        let mut engine: Engine = EngineBuilder::new(compiler_config).into();
+        engine.set_tunables(tunables);
+
+        Store::new(engine)
+    }
+
+    /// Execute the compiled WASM module and retrieve the result. Traps, fuel
+    /// exhaustion and memory limit violations are reported through the
+    /// returned [ExecutionResult]; an error is only returned if the module
+    /// could not be run at all.
+    pub fn execute(&mut self) -> Result<ExecutionResult> {
+        let memory_exceeded = Arc::new(AtomicBool::new(false));
+        let mut store = self.create_store(memory_exceeded.clone());
+
+        debug!("Compiling {} bytes of WASM", self.wasm_bytes.len());
+        let module = Module::new(&store, &self.wasm_bytes)?;
+
         let (mut stdin, in_wasm) = Pipe::channel();
-        let (out_wasm, mut stdout) = Pipe::channel();
-        let (err_wasm, mut stderr) = Pipe::channel();
+        let (out_wasm, stdout) = Pipe::channel();
+        let (err_wasm, stderr) = Pipe::channel();
         stdin.write_all(&self.stdin)?;
         stdin.flush()?;
-        WasiEnv::builder(MODULE_ARGV0)
+
+        // NOTE: output past the limit is dropped as it's written so a module
+        // can't make the node buffer an unbounded amount of it
+        let stdout_truncated = Arc::new(AtomicBool::new(false));
+        let stderr_truncated = Arc::new(AtomicBool::new(false));
+        let max_output_bytes = self.limits.max_output_bytes;
+
+        let builder = WasiEnv::builder(MODULE_ARGV0)
             .stdin(Box::new(in_wasm))
-            .stdout(Box::new(out_wasm))
-            .stderr(Box::new(err_wasm))
+            .stdout(Box::new(BoundedOutput::new(
+                out_wasm,
+                max_output_bytes,
+                stdout_truncated.clone(),
+            )))
+            .stderr(Box::new(BoundedOutput::new(
+                err_wasm,
+                max_output_bytes,
+                stderr_truncated.clone(),
+            )))
             .args(Box::new(self.args.iter()))
-            .envs(Box::new(self.env.iter()))
-            .run_with_store(self.module.to_owned(), &mut self.store)?;
-        stdout.read_to_string(&mut self.stdout)?;
-        stderr.read_to_string(&mut self.stderr)?;
-        Ok(())
+            .envs(Box::new(self.env.iter()));
+
+        let write_set = Arc::new(Mutex::new(HostWriteSet::default()));
+        let instantiated = self.instantiate(builder, module, &mut store, write_set.clone());
+
+        let (outcome, exit_code, fuel_used) = match instantiated {
+            Ok((instance, wasi_env)) => {
+                let (outcome, exit_code) = self.run_start(&instance, &mut store);
+                let fuel_used = self.fuel_used(&instance, &mut store);
+
+                let outcome = match outcome {
+                    ExecutionOutcome::Trap(_) if fuel_used == self.limits.fuel => {
+                        ExecutionOutcome::OutOfGas
+                    },
+                    ExecutionOutcome::Trap(_) if memory_exceeded.load(Ordering::SeqCst) => {
+                        ExecutionOutcome::MemoryExceeded
+                    },
+                    outcome => outcome,
+                };
+
+                // Closes the module's file descriptors, including the pipes
+                // read below.
+                wasi_env.cleanup(
+                    &mut store,
+                    Some(ExitCode::from(exit_code.unwrap_or(1))),
+                );
+
+                (outcome, exit_code, fuel_used)
+            },
+            Err(_) if memory_exceeded.load(Ordering::SeqCst) => {
+                (ExecutionOutcome::MemoryExceeded, None, 0)
+            },
+            Err(err) => (ExecutionOutcome::Trap(err.to_string()), None, 0),
+        };
+
+        let (stdout, stdout_truncated) = self.read_output(stdout, &stdout_truncated)?;
+        let (stderr, stderr_truncated) = self.read_output(stderr, &stderr_truncated)?;
+        self.stdout = stdout;
+        self.stderr = stderr;
+
+        let write_set = write_set
+            .lock()
+            .map(|write_set| write_set.clone())
+            .unwrap_or_default();
+
+        // Changes requested by a contract that did not complete successfully
+        // are discarded.
+        let succeeded = outcome == ExecutionOutcome::Ok && exit_code == Some(0);
+
+        let (update_args, events) = match &self.host {
+            Some(host) if succeeded => (
+                write_set.update_args(&host.contract_address),
+                write_set.events,
+            ),
+            _ => (None, vec![]),
+        };
+
+        Ok(ExecutionResult {
+            outcome,
+            fuel_used,
+            exit_code,
+            stdout: self.stdout.clone(),
+            stderr: self.stderr.clone(),
+            output_truncated: stdout_truncated || stderr_truncated,
+            events,
+            update_args,
+        })
+    }
+
+    /// Instantiates the module with the WASI imports and, if a [HostContext]
+    /// was provided, the VRRB host functions.
+    fn instantiate(
+        &self,
+        builder: WasiEnvBuilder,
+        module: Module,
+        store: &mut Store,
+        write_set: Arc<Mutex<HostWriteSet>>,
+    ) -> Result<(Instance, WasiFunctionEnv)> {
+        let mut wasi_env = builder.finalize(store)?;
+        let mut import_object = wasi_env.import_object(store, &module)?;
+
+        let host_env = self.host.as_ref().map(|context| {
+            let host_env = FunctionEnv::new(store, HostEnv::new(context.clone(), write_set));
+            let host_imports = host_imports(store, &host_env);
+            import_object.extend(&host_imports);
+            host_env
+        });
+
+        let instance = Instance::new(store, &module, &import_object)?;
+
+        if let Some(host_env) = host_env {
+            let memory = instance.exports.get_memory(MODULE_MEMORY)?.clone();
+            host_env
+                .as_mut(store)
+                .set_instance(instance.clone(), memory);
+        }
+
+        wasi_env.initialize(store, instance.clone())?;
+
+        Ok((instance, wasi_env))
+    }
+
+    /// Calls the module's entry point, translating a WASI exit into an exit
+    /// code rather than a trap.
+    fn run_start(
+        &self,
+        instance: &Instance,
+        store: &mut impl AsStoreMut,
+    ) -> (ExecutionOutcome, Option<i32>) {
+        let start = match instance.exports.get_function(MODULE_ENTRY_POINT) {
+            Ok(start) => start,
+            Err(err) => return (ExecutionOutcome::Trap(err.to_string()), None),
+        };
+
+        match start.call(store, &[]) {
+            Ok(_) => (ExecutionOutcome::Ok, Some(0)),
+            Err(err) => match err.downcast::<WasiError>() {
+                Ok(WasiError::Exit(code)) => (ExecutionOutcome::Ok, Some(code.raw())),
+                Ok(other) => (ExecutionOutcome::Trap(other.to_string()), None),
+                Err(err) => (ExecutionOutcome::Trap(err.message()), None),
+            },
+        }
+    }
+
+    /// Returns the amount of fuel consumed by the instance so far.
+    fn fuel_used(&self, instance: &Instance, store: &mut impl AsStoreMut) -> u64 {
+        match get_remaining_points(store, instance) {
+            MeteringPoints::Remaining(remaining) => self.limits.fuel.saturating_sub(remaining),
+            MeteringPoints::Exhausted => self.limits.fuel,
+        }
+    }
+
+    /// Reads an output stream of the module, which holds at most
+    /// `max_output_bytes`, returning the output and whether it was
+    /// truncated.
+    fn read_output(&self, pipe: Pipe, truncated: &AtomicBool) -> Result<(String, bool)> {
+        let max_output_bytes = self.limits.max_output_bytes;
+        let mut output = vec![];
+
+        pipe.take(max_output_bytes as u64)
+            .read_to_end(&mut output)?;
+
+        Ok((
+            String::from_utf8_lossy(&output).into_owned(),
+            truncated.load(Ordering::SeqCst),
+        ))
     }
 }